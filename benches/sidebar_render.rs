@@ -0,0 +1,53 @@
+// Sidebar render time for large workspaces.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ratatui::{backend::TestBackend, Terminal};
+
+use forge::state::app_state::AppState;
+use forge::state::collection::{Collection, CollectionItem, CollectionRequest, Folder};
+use forge::ui;
+
+/// A workspace of `n` requests: 10 collections of 10 folders each.
+fn large_workspace(n: usize) -> AppState {
+    let mut state = AppState::default();
+    for c in 0..10 {
+        let mut col = Collection::new(format!("collection {c}"));
+        for f in 0..10 {
+            let mut folder = Folder::new(format!("folder {c}.{f}"));
+            for i in 0..n / 100 {
+                folder.items.push(CollectionItem::Request(CollectionRequest::new(format!("req {c}.{f}.{i}"))));
+            }
+            col.items.push(CollectionItem::Folder(folder));
+        }
+        state.workspace.collections.push(col);
+    }
+    state
+}
+
+fn bench_sidebar_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sidebar_render");
+    for n in [1_000, 10_000, 50_000] {
+        let mut state = large_workspace(n);
+        // Somewhere in the middle, so the visible window has to be found
+        state.sidebar.cursor = n / 2;
+        let mut terminal = Terminal::new(TestBackend::new(40, 50)).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(n), &state, |b, state| {
+            b.iter(|| {
+                terminal.draw(|frame| ui::sidebar::render(frame, frame.area(), state)).unwrap();
+            });
+        });
+
+        state.sidebar.search_mode = true;
+        state.sidebar.search_query = "req 5.5".into();
+        state.sidebar.invalidate_nodes();
+        group.bench_with_input(BenchmarkId::new("search", n), &state, |b, state| {
+            b.iter(|| {
+                terminal.draw(|frame| ui::sidebar::render(frame, frame.area(), state)).unwrap();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sidebar_render);
+criterion_main!(benches);
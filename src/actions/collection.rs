@@ -1 +1,99 @@
 // Collection actions (create, rename, delete, select item)
+use crate::state::collection::{Collection, CollectionItem, CollectionRequest};
+
+/// Ids of every request under the node `id` (a collection, folder or request),
+/// in tree order. Empty if no node has that id.
+pub fn subtree_request_ids(collections: &[Collection], id: &str) -> Vec<String> {
+    fn collect(items: &[CollectionItem], out: &mut Vec<String>) {
+        for item in items {
+            match item {
+                CollectionItem::Folder(f) => collect(&f.items, out),
+                CollectionItem::Request(r) => out.push(r.id.clone()),
+            }
+        }
+    }
+    fn find(items: &[CollectionItem], id: &str, out: &mut Vec<String>) -> bool {
+        items.iter().any(|item| match item {
+            CollectionItem::Folder(f) if f.id == id => {
+                collect(&f.items, out);
+                true
+            }
+            CollectionItem::Folder(f) => find(&f.items, id, out),
+            CollectionItem::Request(r) if r.id == id => {
+                out.push(r.id.clone());
+                true
+            }
+            CollectionItem::Request(_) => false,
+        })
+    }
+
+    let mut out = Vec::new();
+    for col in collections {
+        if col.id == id {
+            collect(&col.items, &mut out);
+            break;
+        }
+        if find(&col.items, id, &mut out) {
+            break;
+        }
+    }
+    out
+}
+
+/// The saved request `id`, in whichever collection holds it.
+pub fn find_col_request_by_id<'a>(
+    collections: &'a [Collection],
+    id: &str,
+) -> Option<&'a CollectionRequest> {
+    for col in collections {
+        if let Some(r) = find_request_in_items(&col.items, id) {
+            return Some(r);
+        }
+    }
+    None
+}
+
+fn find_request_in_items<'a>(
+    items: &'a [CollectionItem],
+    id: &str,
+) -> Option<&'a CollectionRequest> {
+    for item in items {
+        match item {
+            CollectionItem::Request(r) if r.id == id => return Some(r),
+            CollectionItem::Folder(f) => {
+                if let Some(r) = find_request_in_items(&f.items, id) {
+                    return Some(r);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::collection::{CollectionRequest, Folder};
+
+    #[test]
+    fn test_subtree_request_ids() {
+        let a = CollectionRequest::new("a");
+        let b = CollectionRequest::new("b");
+        let c = CollectionRequest::new("c");
+        let mut folder = Folder::new("f");
+        folder.items.push(CollectionItem::Request(b.clone()));
+        folder.items.push(CollectionItem::Request(c.clone()));
+        let folder_id = folder.id.clone();
+        let mut col = Collection::new("col");
+        col.items.push(CollectionItem::Request(a.clone()));
+        col.items.push(CollectionItem::Folder(folder));
+        let col_id = col.id.clone();
+        let cols = vec![col];
+
+        assert_eq!(subtree_request_ids(&cols, &col_id), vec![a.id.clone(), b.id.clone(), c.id.clone()]);
+        assert_eq!(subtree_request_ids(&cols, &folder_id), vec![b.id, c.id.clone()]);
+        assert_eq!(subtree_request_ids(&cols, &c.id), vec![c.id]);
+        assert!(subtree_request_ids(&cols, "missing").is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+// Request dependencies ("run login first"): graph building, cycle checks and
+// resolution order
+use std::collections::{HashMap, HashSet};
+
+use crate::state::collection::{Collection, CollectionItem};
+
+/// request id → id of the request it depends on, across all collections.
+pub fn dependency_map(collections: &[Collection]) -> HashMap<String, String> {
+    fn walk(items: &[CollectionItem], out: &mut HashMap<String, String>) {
+        for item in items {
+            match item {
+                CollectionItem::Folder(f) => walk(&f.items, out),
+                CollectionItem::Request(r) => {
+                    if let Some(dep) = &r.depends_on {
+                        out.insert(r.id.clone(), dep.clone());
+                    }
+                }
+            }
+        }
+    }
+    let mut out = HashMap::new();
+    for col in collections {
+        walk(&col.items, &mut out);
+    }
+    out
+}
+
+/// Whether making `id` depend on `dep` would close a cycle (including `id`
+/// depending on itself).
+pub fn creates_cycle(deps: &HashMap<String, String>, id: &str, dep: &str) -> bool {
+    let mut cur = dep;
+    let mut seen = HashSet::new();
+    loop {
+        if cur == id {
+            return true;
+        }
+        if !seen.insert(cur) {
+            // An existing cycle that doesn't involve `id`
+            return false;
+        }
+        match deps.get(cur) {
+            Some(next) => cur = next,
+            None => return false,
+        }
+    }
+}
+
+/// Requests that must run before `id`, in execution order (the deepest
+/// dependency first). The chain stops at the first dependency already in
+/// `satisfied` — its own dependencies ran when it did. Cycles are cut at the
+/// first repeated request.
+pub fn resolution_order(
+    deps: &HashMap<String, String>,
+    id: &str,
+    satisfied: &HashSet<String>,
+) -> Vec<String> {
+    let mut order = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::from([id]);
+    let mut cur = id;
+    while let Some(dep) = deps.get(cur) {
+        if satisfied.contains(dep) || !seen.insert(dep) {
+            break;
+        }
+        order.push(dep.clone());
+        cur = dep;
+    }
+    order.reverse();
+    order
+}
+
+/// `ids` in run order, each preceded by its dependencies that haven't
+/// succeeded. A request runs once, at its first place, and counts as
+/// satisfied for the requests after it.
+pub fn run_order(
+    deps: &HashMap<String, String>,
+    ids: &[String],
+    satisfied: &HashSet<String>,
+) -> Vec<String> {
+    let mut ready = satisfied.clone();
+    let mut scheduled = HashSet::new();
+    let mut order = Vec::new();
+    for id in ids {
+        for next in resolution_order(deps, id, &ready).into_iter().chain([id.clone()]) {
+            if scheduled.insert(next.clone()) {
+                ready.insert(next.clone());
+                order.push(next);
+            }
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deps(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    fn set(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolution_order_deepest_first() {
+        // orders → token → login
+        let d = deps(&[("orders", "token"), ("token", "login")]);
+        assert_eq!(resolution_order(&d, "orders", &set(&[])), vec!["login", "token"]);
+        assert!(resolution_order(&d, "login", &set(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_resolution_order_stops_at_satisfied() {
+        let d = deps(&[("orders", "token"), ("token", "login")]);
+        assert_eq!(resolution_order(&d, "orders", &set(&["login"])), vec!["token"]);
+        assert!(resolution_order(&d, "orders", &set(&["token"])).is_empty());
+    }
+
+    #[test]
+    fn test_resolution_order_survives_cycles() {
+        let d = deps(&[("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_eq!(resolution_order(&d, "a", &set(&[])), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_run_order_adds_missing_dependencies() {
+        let d = deps(&[("orders", "token"), ("token", "login"), ("profile", "token")]);
+        let ids = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            run_order(&d, &ids(&["orders", "profile"]), &set(&[])),
+            vec!["login", "token", "orders", "profile"]
+        );
+        // Already in the list: moved up to run before its dependent
+        assert_eq!(run_order(&d, &ids(&["orders", "login"]), &set(&[])), vec!["login", "token", "orders"]);
+        // Succeeded earlier, so not added before "orders"; listed, it still runs
+        assert_eq!(run_order(&d, &ids(&["orders", "token"]), &set(&["token", "login"])), vec!["orders", "token"]);
+    }
+
+    #[test]
+    fn test_creates_cycle() {
+        let d = deps(&[("orders", "token"), ("token", "login")]);
+        assert!(creates_cycle(&d, "login", "orders"));
+        assert!(creates_cycle(&d, "login", "login"));
+        assert!(!creates_cycle(&d, "profile", "orders"));
+        assert!(!creates_cycle(&d, "orders", "login"));
+    }
+}
@@ -1,7 +1,10 @@
 pub mod navigation;
 pub mod request;
 pub mod response;
+pub mod dependency;
 pub mod collection;
 pub mod environment;
 pub mod history;
 pub mod workspace;
+pub mod runner;
+pub mod palette;
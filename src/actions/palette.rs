@@ -0,0 +1,201 @@
+// Command palette actions (everything the palette can run)
+use crate::util::fuzzy::fuzzy_match;
+
+/// An action offered by the command palette. Each runs the same `App`
+/// method as its keybinding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    SendRequest,
+    DryRun,
+    SaveTab,
+    CloseTab,
+    RenameTab,
+    GoToRequest,
+    NewCollection,
+    NewRequest,
+    NewFolder,
+    Rename,
+    Delete,
+    Duplicate,
+    RunInPlace,
+    RunCollection,
+    DefaultHeaders,
+    CollectionVariables,
+    CollectionEnvironment,
+    ClearRunnerResults,
+    CollapseAll,
+    ExpandAll,
+    SwitchEnvironment,
+    SwitchWorkspace,
+    Variables,
+    CookieJar,
+    SendQueue,
+    AuditLog,
+    ThemePicker,
+    Settings,
+    ToggleSidebar,
+    ToggleZen,
+    Help,
+    Quit,
+}
+
+impl Action {
+    /// Palette order when the query is empty.
+    pub const ALL: [Action; 32] = [
+        Action::SendRequest,
+        Action::DryRun,
+        Action::SaveTab,
+        Action::CloseTab,
+        Action::RenameTab,
+        Action::GoToRequest,
+        Action::NewCollection,
+        Action::NewRequest,
+        Action::NewFolder,
+        Action::Rename,
+        Action::Delete,
+        Action::Duplicate,
+        Action::RunInPlace,
+        Action::RunCollection,
+        Action::DefaultHeaders,
+        Action::CollectionVariables,
+        Action::CollectionEnvironment,
+        Action::ClearRunnerResults,
+        Action::CollapseAll,
+        Action::ExpandAll,
+        Action::SwitchEnvironment,
+        Action::SwitchWorkspace,
+        Action::Variables,
+        Action::CookieJar,
+        Action::SendQueue,
+        Action::AuditLog,
+        Action::ThemePicker,
+        Action::Settings,
+        Action::ToggleSidebar,
+        Action::ToggleZen,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::SendRequest => "Send request",
+            Action::DryRun => "Dry run request",
+            Action::SaveTab => "Save tab",
+            Action::CloseTab => "Close tab",
+            Action::RenameTab => "Rename tab",
+            Action::GoToRequest => "Go to request",
+            Action::NewCollection => "New collection",
+            Action::NewRequest => "New request",
+            Action::NewFolder => "New folder",
+            Action::Rename => "Rename sidebar item",
+            Action::Delete => "Delete sidebar item",
+            Action::Duplicate => "Duplicate sidebar item",
+            Action::RunInPlace => "Run sidebar request in place",
+            Action::RunCollection => "Run collection or folder",
+            Action::DefaultHeaders => "Edit default headers",
+            Action::CollectionVariables => "Edit collection variables",
+            Action::CollectionEnvironment => "Cycle collection default environment",
+            Action::ClearRunnerResults => "Clear runner results",
+            Action::CollapseAll => "Collapse all in sidebar",
+            Action::ExpandAll => "Expand all in sidebar",
+            Action::SwitchEnvironment => "Switch environment",
+            Action::SwitchWorkspace => "Switch workspace",
+            Action::Variables => "Show session variables",
+            Action::CookieJar => "Open cookie jar",
+            Action::SendQueue => "Open send queue",
+            Action::AuditLog => "Open audit log",
+            Action::ThemePicker => "Change theme",
+            Action::Settings => "Workspace settings: proxy",
+            Action::ToggleSidebar => "Toggle sidebar",
+            Action::ToggleZen => "Toggle zen mode",
+            Action::Help => "Show keybindings",
+            Action::Quit => "Quit",
+        }
+    }
+
+    /// The keybinding that does the same, shown beside the label.
+    pub fn keys(self) -> &'static str {
+        match self {
+            Action::SendRequest => "Ctrl+R",
+            Action::DryRun => "Ctrl+Shift+D",
+            Action::SaveTab => "Ctrl+S",
+            Action::CloseTab => "Alt+w",
+            Action::RenameTab => "r (open tabs)",
+            Action::GoToRequest => "Ctrl+P",
+            Action::NewCollection => "Ctrl+N (sidebar)",
+            Action::NewRequest => "n (sidebar)",
+            Action::NewFolder => "f (sidebar)",
+            Action::Rename => "r (sidebar)",
+            Action::Delete => "d (sidebar)",
+            Action::Duplicate => "D (sidebar)",
+            Action::RunInPlace => "s (sidebar)",
+            Action::RunCollection => "R (sidebar)",
+            Action::DefaultHeaders => "H (sidebar)",
+            Action::CollectionVariables => "v (sidebar)",
+            Action::CollectionEnvironment => "E (sidebar)",
+            Action::ClearRunnerResults => "c (sidebar)",
+            Action::CollapseAll => "Ctrl+- (sidebar)",
+            Action::ExpandAll => "Ctrl+= (sidebar)",
+            Action::SwitchEnvironment => "Ctrl+E",
+            Action::SwitchWorkspace => "Ctrl+W",
+            Action::Variables => "V",
+            Action::CookieJar => "C",
+            Action::SendQueue => "Q",
+            Action::AuditLog => "A",
+            Action::ThemePicker => "T",
+            Action::Settings => "W",
+            Action::ToggleSidebar => "Ctrl+B",
+            Action::ToggleZen => "Ctrl+F",
+            Action::Help => "?",
+            Action::Quit => "q",
+        }
+    }
+}
+
+/// Actions whose label matches `query`, best match first, with the matched
+/// char indices of each label. Ties keep `Action::ALL` order.
+pub fn palette_matches(query: &str) -> Vec<(Action, Vec<usize>)> {
+    let mut scored: Vec<(i64, Action, Vec<usize>)> = Action::ALL
+        .iter()
+        .filter_map(|&action| {
+            let m = fuzzy_match(query, action.label())?;
+            Some((m.score, action, m.indices))
+        })
+        .collect();
+    scored.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, action, indices)| (action, indices)).collect()
+}
+
+/// The name in a `theme <name>` palette query, if that's what was typed.
+pub fn theme_command(query: &str) -> Option<&str> {
+    let name = query.trim_start().strip_prefix("theme ")?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_matches_rank_word_starts_first() {
+        assert_eq!(palette_matches("").len(), Action::ALL.len());
+        let actions: Vec<Action> = palette_matches("sw env").into_iter().map(|(a, _)| a).collect();
+        assert_eq!(actions.first(), Some(&Action::SwitchEnvironment));
+        assert_eq!(palette_matches("tz")[0].0, Action::ToggleZen);
+        assert!(palette_matches("xyzzy").is_empty());
+    }
+
+    #[test]
+    fn test_theme_command() {
+        assert_eq!(theme_command("theme light"), Some("light"));
+        assert_eq!(theme_command(" theme  high-contrast "), Some("high-contrast"));
+        assert_eq!(theme_command("theme "), None);
+        assert_eq!(theme_command("change theme"), None);
+
+        use crate::state::config::ThemeKind;
+        assert_eq!(ThemeKind::from_name("Light"), Some(ThemeKind::Light));
+        assert_eq!(ThemeKind::from_name("tokyonight"), Some(ThemeKind::TokyoNight));
+        assert_eq!(ThemeKind::from_name("high-contrast"), Some(ThemeKind::HighContrast));
+        assert_eq!(ThemeKind::from_name("solarized"), None);
+    }
+}
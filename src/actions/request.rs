@@ -1 +1,223 @@
 // Request actions (send, cancel, update fields)
+use std::collections::HashMap;
+
+use crate::env::resolver::{active_env_vars, resolver_for};
+use crate::scripting::runner::{run_pre_request, ScriptResult};
+use crate::state::app_state::AppState;
+use crate::state::collection::{inherited_headers, CollectionRequest};
+use crate::state::request_state::{HttpMethod, KeyValuePair, RequestBody, RequestState};
+use crate::state::workspace::RequestTab;
+
+/// Request state for a saved collection request, as opened in a tab.
+pub fn saved_request_state(saved: &CollectionRequest) -> RequestState {
+    let mut request = RequestState {
+        name: saved.name.clone(),
+        method: HttpMethod::from_str_or_get(&saved.method),
+        url: saved.url.clone(),
+        description: saved.description.clone(),
+        checks: saved.checks.clone(),
+        scripts: saved.scripts.clone(),
+        auth: saved.auth.clone(),
+        auto_retry: saved.auto_retry,
+        ..RequestState::default()
+    };
+    if !saved.body_raw.is_empty() {
+        request.body = RequestBody::Json(saved.body_raw.clone());
+    }
+    request
+}
+
+/// Copy a tab's request onto its collection entry; the inverse of
+/// `saved_request_state`.
+pub fn store_request_state(saved: &mut CollectionRequest, request: &RequestState) {
+    saved.url = request.url.clone();
+    saved.method = request.method.as_str().to_string();
+    saved.body_raw = match &request.body {
+        RequestBody::Json(s) | RequestBody::Text(s) => s.clone(),
+        _ => String::new(),
+    };
+    saved.checks = request.checks.clone();
+    saved.description = request.description.clone();
+    saved.scripts = request.scripts.clone();
+    saved.auth = request.auth.for_storage();
+    saved.auto_retry = request.auto_retry;
+}
+
+/// Whether saving `request` would change its collection entry.
+pub fn differs_from_saved(saved: &CollectionRequest, request: &RequestState) -> bool {
+    let mut updated = saved.clone();
+    store_request_state(&mut updated, request);
+    updated != *saved
+}
+
+/// Run the pre-request script on a copy of the request (its changes apply
+/// to this send only), then resolve URL, headers and auth for sending. Script
+/// variables and environment values the script set shadow every environment
+/// layer. A request saved as `collection_id` also gets its collection's and
+/// folders' default headers and variables. Returns the request, or why it
+/// can't be sent, along with the script's outcome when there is one.
+pub fn prepare_request(
+    state: &AppState,
+    mut req: RequestState,
+    collection_id: Option<&str>,
+) -> (Result<RequestState, String>, Option<ScriptResult>) {
+    if let Some(id) = collection_id {
+        let defaults = inherited_headers(&state.workspace.collections, id);
+        req.headers = with_default_headers(defaults, std::mem::take(&mut req.headers));
+    }
+    let mut script_layer = HashMap::new();
+    let mut script_result = None;
+    if !req.scripts.pre_request.trim().is_empty() {
+        let script = req.scripts.pre_request.clone();
+        let result = run_pre_request(&script, &mut req, &active_env_vars(state));
+        if let Some(e) = &result.error {
+            return (Err(format!("Pre-request script: {e}")), Some(result));
+        }
+        script_layer.extend(result.env_updates.iter().cloned());
+        script_result = Some(result);
+    }
+    script_layer.extend(req.script_variables.clone());
+
+    let mut resolver = resolver_for(state, collection_id);
+    resolver.layers.insert(0, script_layer);
+    req.url = resolver.resolve_for_send(&req.url);
+    for header in &mut req.headers {
+        if header.enabled {
+            header.key = resolver.resolve_for_send(&header.key);
+            header.value = resolver.resolve_for_send(&header.value);
+        }
+    }
+    for field in req.auth.fields_mut() {
+        *field = resolver.resolve_for_send(field);
+    }
+    let max_mb = state.workspace.max_body_mb;
+    req.max_body_bytes = (max_mb > 0).then(|| max_mb as usize * 1_000_000);
+    (Ok(req), script_result)
+}
+
+/// `prepare_request` for a tab's request, with its collection's default
+/// headers. Sends and dry runs both go through here, so a dry run shows
+/// exactly what a send would put on the wire.
+pub fn prepare_tab(state: &AppState, tab: &RequestTab) -> (Result<RequestState, String>, Option<ScriptResult>) {
+    prepare_request(state, tab.request.clone(), tab.collection_id.as_deref())
+}
+
+/// `defaults` ahead of the request's own headers, less any whose key an
+/// enabled request header already sets.
+fn with_default_headers(defaults: Vec<KeyValuePair>, headers: Vec<KeyValuePair>) -> Vec<KeyValuePair> {
+    let own = |key: &str| headers.iter().any(|h| h.enabled && h.key.eq_ignore_ascii_case(key));
+    let mut merged: Vec<KeyValuePair> = defaults.into_iter().filter(|d| !own(&d.key)).collect();
+    merged.extend(headers);
+    merged
+}
+
+// ─── Trait extension for HttpMethod ──────────────────────────────────────────
+
+pub trait HttpMethodExt {
+    fn from_str_or_get(s: &str) -> HttpMethod;
+}
+
+impl HttpMethodExt for HttpMethod {
+    fn from_str_or_get(s: &str) -> Self {
+        match s {
+            "GET" => HttpMethod::Get,
+            "POST" => HttpMethod::Post,
+            "PUT" => HttpMethod::Put,
+            "PATCH" => HttpMethod::Patch,
+            "DELETE" => HttpMethod::Delete,
+            "HEAD" => HttpMethod::Head,
+            "OPTIONS" => HttpMethod::Options,
+            _ => HttpMethod::Get,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use crate::http::builder::build_request;
+    use crate::http::dry_run::dry_run;
+    use crate::state::collection::{Collection, CollectionItem};
+    use super::*;
+
+    #[test]
+    fn test_dry_run_prepares_the_request_a_send_would() {
+        let mut state = AppState::default();
+        let mut col = Collection::new("api");
+        col.default_headers = vec![KeyValuePair::new("Accept", "application/json")];
+        let mut saved = CollectionRequest::new("users");
+        saved.url = "https://{{host}}/users".into();
+        saved.method = "POST".into();
+        saved.body_raw = r#"{"name":"ada"}"#.into();
+        saved.scripts.pre_request = r#"
+            pm.variables.set("host", "api.test");
+            pm.request.headers.add("X-Trace", "abc");
+        "#
+        .into();
+        let tab = RequestTab {
+            request: saved_request_state(&saved),
+            collection_id: Some(saved.id.clone()),
+            ..RequestTab::default()
+        };
+        col.items.push(CollectionItem::Request(saved));
+        state.workspace.collections.push(col);
+
+        // A send executes the prepared request; a dry run previews its own
+        let client = Client::new();
+        let sent = prepare_tab(&state, &tab).0.unwrap();
+        let preview = dry_run(&client, &prepare_tab(&state, &tab).0.unwrap()).unwrap();
+        assert_eq!(dry_run(&client, &sent).unwrap(), preview);
+
+        let wire = build_request(&client, &sent).unwrap().build().unwrap();
+        assert_eq!(preview.method, "POST");
+        assert_eq!(preview.url, wire.url().as_str());
+        assert_eq!(preview.url, "https://api.test/users");
+        assert_eq!(preview.body, wire.body().and_then(|b| b.as_bytes()).unwrap());
+        let header = |key: &str| preview.headers.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+        assert_eq!(header("accept"), Some("application/json"));
+        assert_eq!(header("x-trace"), Some("abc"));
+        assert_eq!(header("content-type"), Some("application/json"));
+        assert_eq!(preview.headers.len(), wire.headers().len());
+    }
+
+    #[test]
+    fn test_description_round_trips_through_storage() {
+        let mut saved = CollectionRequest::new("Login");
+        saved.description = "Needs `user` and `pass` set.\n\nReturns a token.".into();
+        let mut col = Collection::new("api");
+        col.items.push(CollectionItem::Request(saved.clone()));
+        let loaded: Collection = toml::from_str(&toml::to_string_pretty(&col).unwrap()).unwrap();
+        let CollectionItem::Request(loaded) = &loaded.items[0] else {
+            panic!("request expected");
+        };
+        assert_eq!(loaded.description, saved.description);
+
+        let mut request = saved_request_state(loaded);
+        assert_eq!(request.description, saved.description);
+        request.description.push_str("\nRate limited.");
+        let mut stored = CollectionRequest::new("Login");
+        store_request_state(&mut stored, &request);
+        assert!(stored.description.ends_with("token.\nRate limited."));
+    }
+
+    #[test]
+    fn test_differs_from_saved_until_edit_is_undone() {
+        let mut saved = CollectionRequest::new("Users");
+        saved.url = "https://api.test/users".into();
+        saved.body_raw = "{}".into();
+        let mut request = saved_request_state(&saved);
+        assert!(!differs_from_saved(&saved, &request));
+
+        request.method = HttpMethod::Post;
+        assert!(differs_from_saved(&saved, &request));
+        request.method = HttpMethod::Get;
+        request.url.push_str("/1");
+        assert!(differs_from_saved(&saved, &request));
+        request.url = saved.url.clone();
+        request.body = RequestBody::Json("{\"a\": 1}".into());
+        assert!(differs_from_saved(&saved, &request));
+        request.body = RequestBody::Json("{}".into());
+        assert!(!differs_from_saved(&saved, &request));
+    }
+}
@@ -1 +1,188 @@
 // Response actions (scroll, copy, clear)
+use serde_json::Value;
+
+use crate::actions::runner::json_path;
+use crate::state::check::{Check, CheckCandidate, CheckKind};
+use crate::state::response_state::ResponseBody;
+use crate::util::json::child_path;
+
+/// Lists the fields a "copy as test" check can be generated for: every
+/// top-level key of the parsed body plus one level of nesting below it.
+/// Arrays contribute their first element. Returns `None` for non-JSON bodies.
+pub fn check_candidates(body: &ResponseBody) -> Option<Vec<CheckCandidate>> {
+    let ResponseBody::Text(text) = body else {
+        return None;
+    };
+    let root: Value = serde_json::from_str(text).ok()?;
+
+    let mut out = Vec::new();
+    for (path, value) in children("$", &root) {
+        out.push(CheckCandidate { path: path.clone(), value: value.to_string() });
+        for (nested_path, nested) in children(&path, value) {
+            out.push(CheckCandidate { path: nested_path, value: nested.to_string() });
+        }
+    }
+    Some(out)
+}
+
+/// Direct children of `value` as (JSONPath, value) pairs. Object keys are
+/// listed alphabetically regardless of the order the server sent them in.
+fn children<'a>(parent: &str, value: &'a Value) -> Vec<(String, &'a Value)> {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(k, _)| *k);
+            entries
+                .into_iter()
+                .map(|(k, v)| (child_path(parent, k), v))
+                .collect()
+        }
+        Value::Array(items) => items
+            .first()
+            .map(|v| vec![(format!("{parent}[0]"), v)])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Builds the checks for a response: a status check, followed by one JSONPath
+/// equality check per picked field.
+pub fn generate_checks(status: u16, picked: &[&CheckCandidate]) -> Vec<Check> {
+    let mut checks = vec![Check::status(status)];
+    checks.extend(picked.iter().map(|c| Check::json_path(c.path.clone(), c.value.clone())));
+    checks
+}
+
+/// Merges generated checks into an existing list. A check for the same
+/// target (status, or the same JSONPath) has its expected value replaced;
+/// anything else is appended.
+pub fn merge_checks(existing: &mut Vec<Check>, generated: Vec<Check>) {
+    for check in generated {
+        let same = existing.iter_mut().find(|c| {
+            c.kind == check.kind
+                && match c.kind {
+                    CheckKind::Status => true,
+                    CheckKind::JsonPath => c.path == check.path,
+                    CheckKind::BodyContains => c.expected == check.expected,
+                }
+        });
+        match same {
+            Some(c) => {
+                c.expected = check.expected;
+                c.enabled = true;
+            }
+            None => existing.push(check),
+        }
+    }
+}
+
+/// The value at `path` in a JSON body, as text for an environment variable:
+/// strings without quotes, anything else as JSON. `path` is a JSONPath
+/// (`$.data.token`) or a dot-path without the leading `$` (`data.token`).
+pub fn capture_value(body: &ResponseBody, path: &str) -> Result<String, String> {
+    let ResponseBody::Text(text) = body else {
+        return Err("The response body is not text".to_string());
+    };
+    let root: Value =
+        serde_json::from_str(text).map_err(|_| "The response body is not JSON".to_string())?;
+    let path = path.trim();
+    let path = match path.chars().next() {
+        Some('$') => path.to_string(),
+        Some('[') => format!("${path}"),
+        Some(_) => format!("$.{path}"),
+        None => return Err("Enter a path, e.g. $.data.token".to_string()),
+    };
+    match json_path(&root, &path) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(value) => Ok(value.to_string()),
+        None => Err(format!("{path} not found in the response")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> ResponseBody {
+        ResponseBody::Text(s.to_string())
+    }
+
+    fn paths(candidates: &[CheckCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_candidates_nested_object() {
+        let body = text(r#"{"id": 7, "user": {"name": "ada", "meta": {"deep": true}}}"#);
+        let c = check_candidates(&body).unwrap();
+        assert_eq!(paths(&c), vec!["$.id", "$.user", "$.user.meta", "$.user.name"]);
+        assert_eq!(c[0].value, "7");
+        assert_eq!(c[3].value, "\"ada\"");
+        // Only one level of nesting is offered
+        assert!(!paths(&c).contains(&"$.user.meta.deep"));
+    }
+
+    #[test]
+    fn test_candidates_arrays() {
+        let body = text(r#"[{"id": 1, "tags": ["a"]}, {"id": 2}]"#);
+        let c = check_candidates(&body).unwrap();
+        assert_eq!(paths(&c), vec!["$[0]", "$[0].id", "$[0].tags"]);
+        assert_eq!(c[1].value, "1");
+
+        let body = text(r#"{"items": [10, 20], "empty": []}"#);
+        let c = check_candidates(&body).unwrap();
+        assert_eq!(paths(&c), vec!["$.empty", "$.items", "$.items[0]"]);
+        assert_eq!(c[2].value, "10");
+    }
+
+    #[test]
+    fn test_candidates_quote_unusual_keys() {
+        let body = text(r#"{"content-type": "json", "2x": 1}"#);
+        let c = check_candidates(&body).unwrap();
+        assert_eq!(paths(&c), vec![r#"$["2x"]"#, r#"$["content-type"]"#]);
+    }
+
+    #[test]
+    fn test_non_json_falls_back_to_status_only() {
+        assert!(check_candidates(&text("<html></html>")).is_none());
+        assert!(check_candidates(&ResponseBody::Empty).is_none());
+        assert!(check_candidates(&ResponseBody::Binary(vec![0, 1])).is_none());
+
+        let checks = generate_checks(404, &[]);
+        assert_eq!(checks, vec![Check::status(404)]);
+    }
+
+    #[test]
+    fn test_generate_checks_from_picked_fields() {
+        let body = text(r#"{"id": 7, "ok": true}"#);
+        let c = check_candidates(&body).unwrap();
+        let picked: Vec<&CheckCandidate> = c.iter().filter(|c| c.path == "$.ok").collect();
+        let checks = generate_checks(200, &picked);
+        assert_eq!(checks, vec![Check::status(200), Check::json_path("$.ok", "true")]);
+    }
+
+    #[test]
+    fn test_merge_replaces_matching_checks() {
+        let mut existing = vec![Check::status(200), Check::json_path("$.id", "1")];
+        existing[1].enabled = false;
+        merge_checks(
+            &mut existing,
+            vec![Check::status(201), Check::json_path("$.id", "2"), Check::json_path("$.n", "3")],
+        );
+        assert_eq!(
+            existing,
+            vec![Check::status(201), Check::json_path("$.id", "2"), Check::json_path("$.n", "3")]
+        );
+    }
+
+    #[test]
+    fn test_capture_value_paths_and_errors() {
+        let body = ResponseBody::Text(r#"{"data":{"token":"t1","ids":[4,5]},"ok":true}"#.into());
+        assert_eq!(capture_value(&body, "$.data.token"), Ok("t1".to_string()));
+        assert_eq!(capture_value(&body, "data.ids[1]"), Ok("5".to_string()));
+        assert_eq!(capture_value(&body, " ok "), Ok("true".to_string()));
+        assert_eq!(capture_value(&body, "data"), Ok(r#"{"token":"t1","ids":[4,5]}"#.to_string()));
+        assert!(capture_value(&body, "data.missing").unwrap_err().contains("not found"));
+        assert!(capture_value(&ResponseBody::Text("<html>".into()), "a").unwrap_err().contains("not JSON"));
+    }
+}
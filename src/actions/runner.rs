@@ -0,0 +1,175 @@
+// Collection runner: check evaluation and the plain-text report for `forge run`
+use serde_json::Value;
+
+use crate::state::app_state::{NodeRunStatus, RunnerRow};
+use crate::state::check::{Check, CheckKind};
+use crate::state::response_state::{ResponseBody, ResponseState};
+
+/// Evaluate the enabled checks against a response and describe each failure.
+/// A request without enabled checks fails only on a 4xx/5xx status.
+pub fn check_failures(checks: &[Check], response: &ResponseState) -> Vec<String> {
+    let enabled: Vec<&Check> = checks.iter().filter(|c| c.enabled).collect();
+    if enabled.is_empty() {
+        return if response.status >= 400 {
+            vec![format!("status {}", response.status)]
+        } else {
+            Vec::new()
+        };
+    }
+    enabled.into_iter().filter_map(|c| check_failure(c, response)).collect()
+}
+
+fn check_failure(check: &Check, response: &ResponseState) -> Option<String> {
+    let body = match &response.body {
+        ResponseBody::Text(text) => Some(text.as_str()),
+        _ => None,
+    };
+    match check.kind {
+        CheckKind::Status => {
+            let expected = check.expected.trim();
+            (expected != response.status.to_string())
+                .then(|| format!("status {}, expected {}", response.status, expected))
+        }
+        CheckKind::BodyContains => {
+            let found = body.is_some_and(|b| b.contains(check.expected.as_str()));
+            (!found).then(|| format!("body does not contain {:?}", check.expected))
+        }
+        CheckKind::JsonPath => {
+            let Some(root) = body.and_then(|b| serde_json::from_str::<Value>(b).ok()) else {
+                return Some(format!("{}: body is not JSON", check.path));
+            };
+            let Some(actual) = json_path(&root, check.path.trim()) else {
+                return Some(format!("{} not found", check.path));
+            };
+            // `expected` is a JSON literal when possible, else a bare string
+            let expected = serde_json::from_str::<Value>(&check.expected)
+                .unwrap_or_else(|_| Value::String(check.expected.clone()));
+            (*actual != expected).then(|| format!("{} = {}, expected {}", check.path, actual, expected))
+        }
+    }
+}
+
+/// Resolve the JSONPath subset the check picker generates: `$`, `.key`,
+/// `["key"]` and `[index]` segments.
+pub fn json_path<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut rest = path.strip_prefix('$')?;
+    let mut current = root;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            current = current.get(&after[..end])?;
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = closing_bracket(after)?;
+            let inner = &after[..end];
+            current = match serde_json::from_str::<Value>(inner).ok()? {
+                Value::String(key) => current.get(key)?,
+                Value::Number(n) => current.get(n.as_u64()? as usize)?,
+                _ => return None,
+            };
+            rest = &after[end + 1..];
+        } else {
+            return None;
+        }
+    }
+    Some(current)
+}
+
+/// Index of the `]` closing a bracket segment, skipping over quoted keys.
+fn closing_bracket(s: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ']' if !in_string => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Plain-text results table with a summary line, as printed by `forge run`.
+pub fn format_report(title: &str, rows: &[RunnerRow]) -> String {
+    let name_w = rows.iter().map(|r| r.name.chars().count()).max().unwrap_or(0).max(4);
+    let mut out = format!("{title}\n\n");
+    out.push_str(&format!("{:<7} {:<name_w$}  {:<6} {:>8}  RESULT\n", "METHOD", "NAME", "STATUS", "TIME"));
+    let mut total_ms = 0;
+    for row in rows {
+        let (status, time) = match row.status {
+            NodeRunStatus::Done { status, total_ms: ms } => {
+                total_ms += ms;
+                (status.to_string(), format!("{ms}ms"))
+            }
+            _ => ("—".to_string(), "—".to_string()),
+        };
+        let result = match row.passed() {
+            Some(true) => "PASS".to_string(),
+            Some(false) => format!("FAIL  {}", row.failures.join("; ")),
+            None => "SKIPPED".to_string(),
+        };
+        out.push_str(&format!(
+            "{:<7} {:<name_w$}  {:<6} {:>8}  {}\n",
+            row.method, row.name, status, time, result
+        ));
+    }
+    let passed = rows.iter().filter(|r| r.passed() == Some(true)).count();
+    let failed = rows.len() - passed;
+    out.push_str(&format!(
+        "\n{passed} passed, {failed} failed ({} requests, {total_ms}ms)\n",
+        rows.len()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, body: &str) -> ResponseState {
+        ResponseState {
+            status,
+            body: ResponseBody::Text(body.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_json_path_segments() {
+        let root: Value =
+            serde_json::from_str(r#"{"data": {"items": [{"id": 7}], "content-type": "x"}}"#).unwrap();
+        assert_eq!(json_path(&root, "$.data.items[0].id"), Some(&Value::from(7)));
+        assert_eq!(json_path(&root, r#"$.data["content-type"]"#), Some(&Value::from("x")));
+        assert_eq!(json_path(&root, "$"), Some(&root));
+        assert!(json_path(&root, "$.data.missing").is_none());
+        assert!(json_path(&root, "data").is_none());
+    }
+
+    #[test]
+    fn test_check_failures() {
+        let resp = response(200, r#"{"id": 1, "name": "ada"}"#);
+        let checks = vec![
+            Check::status(200),
+            Check::json_path("$.id", "1"),
+            Check::json_path("$.name", "ada"),
+            Check { kind: CheckKind::BodyContains, expected: "ada".into(), ..Check::default() },
+        ];
+        assert!(check_failures(&checks, &resp).is_empty());
+
+        let failing = vec![
+            Check::status(201),
+            Check::json_path("$.id", "2"),
+            Check { kind: CheckKind::BodyContains, expected: "bob".into(), ..Check::default() },
+            Check { enabled: false, ..Check::status(500) },
+        ];
+        assert_eq!(check_failures(&failing, &resp).len(), 3);
+    }
+
+    #[test]
+    fn test_no_checks_fails_on_error_status() {
+        assert!(check_failures(&[], &response(204, "")).is_empty());
+        assert_eq!(check_failures(&[], &response(404, "")), vec!["status 404"]);
+    }
+}
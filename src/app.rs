@@ -1,39 +1,149 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
-use tokio::sync::mpsc::UnboundedSender;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::Position;
+use serde_json::Value;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio_util::sync::CancellationToken;
 
+use crate::actions::collection::{find_col_request_by_id, subtree_request_ids};
+use crate::actions::runner::check_failures;
+use crate::actions::palette::{palette_matches, theme_command, Action};
+use crate::actions::request::{differs_from_saved, prepare_request, prepare_tab, saved_request_state, store_request_state, HttpMethodExt};
+use crate::actions::dependency::{creates_cycle, dependency_map, resolution_order, run_order};
+use crate::actions::response::{capture_value, check_candidates, generate_checks, merge_checks};
 use crate::error::AppError;
 use crate::event::Event;
-use crate::http::{client::build_client, executor::execute};
+use crate::http::{
+    client::{build_client, check_proxy_url}, dry_run::dry_run, executor::{execute, execute_for, execute_queued}, json_filter::apply_filter,
+    retry::retry_after, websocket,
+};
 use crate::state::app_state::{
-    ActivePopup, ActiveTab, AppState, ConfirmDeleteState, NamingState, NamingTarget,
-    RequestStatus, WorkspaceSwitcherState,
+    ActivePopup, ActiveTab, AppState, AuditLogState, ResponseTab, CheckPickerState, CompletionState,
+    ConfirmCloseState, ConfirmDeleteState, ConfirmSendState, DependencyPickerState, DependencyPromptState, NamingState, NamingTarget,
+    NodeRunStatus, PendingSend, RequestFinderState, RequestStatus, RunHistoryState, RunnerRow, RunnerState,
+    CookieJarState, DiffState, JsonValueState, QueuedSend, SaveRequestState, ThemePickerState, VariablesState, DefaultHeadersState, SettingsState,
+    HelpState, CommandPaletteState, CollectionVarsState, EnvFilePrompt, WorkspaceSwitcherState,
 };
+use crate::state::audit::AuditEntry;
+use crate::state::check::{Check, CheckCandidate};
+use crate::state::config::ThemeKind;
 use crate::state::collection::{Collection, CollectionItem, CollectionRequest, Folder};
-use crate::state::environment::{EnvVariable, Environment, VarType};
+use crate::state::environment::{filter_variables, parse_hex_color, EnvVariable, Environment, VarOrigin, VarType};
 use crate::state::focus::Focus;
+use crate::state::history::{push_run, RunRecord};
 use crate::state::mode::Mode;
-use crate::state::request_state::KeyValuePair;
-use crate::state::response_state::{ResponseBody, ResponseState};
-use crate::state::workspace::RequestTab;
-use crate::env::resolver::resolver_from_state;
+use crate::state::request_state::{AuthConfig, KeyValuePair, OAuthToken, RequestBody, RequestState};
+use crate::state::response_state::{BodyFilter, ResponseBody, ResponseState};
+use crate::state::undo::{EditField, UndoEntry};
+use crate::state::sse::SseStream;
+use crate::state::websocket::{is_websocket_url, WsCommand, WsDirection, WsSession, WsStatus};
+use crate::state::workspace::{ProxySettings, RequestTab, SentEnvironment, TabGroup, WorkspaceState};
+use crate::env::completion::{filter_candidates, variable_candidates};
+use crate::env::chain::ChainSource;
+use crate::env::resolver::{active_env_vars, active_tab_resolver};
+use crate::scripting::runner::run_post_response;
+use crate::storage::audit as audit_storage;
+use crate::storage::config as config_storage;
+use crate::storage::state as state_storage;
+use crate::storage::theme as theme_storage;
+use crate::storage::cookies as cookie_storage;
+use crate::storage::history as history_storage;
 use crate::storage::environment as env_storage;
 use crate::storage::collection as col_storage;
 use crate::storage::workspace as ws_storage;
-use crate::ui::highlight::{detect_lang, highlight_text};
-use crate::ui::sidebar::flatten_tree;
+use crate::ui::highlight::{
+    HIGHLIGHT_SYNC_BYTES, LANGS, apply_syntax_theme, detect_lang, highlight_response, highlight_text,
+    response_lang, syntax_themes,
+};
+use crate::ui::dependency_picker::dependency_candidates;
+use crate::ui::diff::lcs_diff;
+use crate::ui::markdown::render_markdown;
+use crate::ui::request::body_editor::cursor_row_col;
+use crate::ui::response::body_viewer::body_text;
+use crate::ui::text_utils::{line_at_row, wrapped_rows};
+use crate::ui::request_finder::finder_matches;
+use crate::ui::sidebar::{flatten_tree, save_targets, NodeKind};
+use crate::ui::workspace_switcher::switcher_workspaces;
+use crate::ui::variables::session_variables;
+use crate::ui::help;
+use crate::util::text::{
+    line_end, line_start, line_with_break, url_word_end, url_word_next, url_word_prev, word_end, word_next, word_prev,
+};
+use crate::ui::theme::{theme, Theme};
+use crate::util::json::{sorted_pretty, tree_rows};
+use crate::util::redact::secret_values;
+
+/// Idle time after the last edit before a dirty tab is saved to its collection.
+const AUTO_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// Longest gap between the two clicks of a double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(300);
+
+/// How long a status bar toast stays up.
+const TOAST_TTL: Duration = Duration::from_secs(2);
+/// How long a sidebar node moved with `J` / `K` stays highlighted.
+const MOVE_FLASH: Duration = Duration::from_millis(250);
+/// `+` / `-` step and upper bound for the send queue's gap.
+const QUEUE_GAP_STEP_MS: u64 = 250;
+const MAX_QUEUE_GAP_MS: u64 = 60_000;
+/// Columns `←` / `→` scroll an unwrapped response body sideways.
+const H_SCROLL_STEP: usize = 4;
+
+/// A direct send in flight from an open tab.
+struct InFlight {
+    send_id: u64,
+    cancel: CancellationToken,
+    /// Audit entry, completed when the response returns
+    audit: Option<(AuditEntry, Instant)>,
+    /// For run history of failed sends
+    sent_at: Instant,
+    /// Collection request the tab was linked to when it sent, which the run
+    /// history and dependency successes are noted against
+    collection_id: Option<String>,
+}
 
 pub struct App {
     pub state: AppState,
     client: reqwest::Client,
     tx: UnboundedSender<Event>,
-    cancel: Option<CancellationToken>,
+    /// The in-flight direct send of each tab, by tab request id
+    in_flight: HashMap<String, InFlight>,
+    /// Last direct send id handed out (`Event::Response` carries it)
+    send_ids: u64,
+    /// Requests still to send after the in-flight one succeeds; set when the
+    /// user chooses to run a request's dependencies first.
+    dependency_queue: VecDeque<String>,
+    /// Requests still to run in place from the sidebar (`S`), in order.
+    sidebar_queue: VecDeque<String>,
+    /// Id of the in-flight sidebar run; results for any other id are stale.
+    sidebar_running: Option<String>,
+    sidebar_cancel: Option<CancellationToken>,
+    sidebar_sent_at: Option<Instant>,
+    sidebar_audit: Option<(AuditEntry, Instant)>,
+    /// The send queue's in-flight send; its tab id is in `state.send_queue`
+    queue_cancel: Option<CancellationToken>,
+    queue_audit: Option<(AuditEntry, Instant)>,
+    /// When the last queued send went out (after its gap), for the next gap
+    queue_last_sent: Option<Instant>,
+    /// Sends to a protected environment skip confirmation until then, by
+    /// environment id
+    protected_confirmed_until: HashMap<String, Instant>,
+    /// Last background highlight started (`Event::Highlighted` carries it)
+    highlight_jobs: u64,
+    /// Whether the last click on a sidebar request opened a tab for it, so
+    /// completing a double-click doesn't open a second one
+    click_opened_tab: bool,
 }
 
 impl App {
     pub fn new(tx: UnboundedSender<Event>) -> Self {
         let mut ws = ws_storage::load_workspace_full("default");
         let all_workspaces = ws_storage::list_workspaces();
+        Self::restore_pinned_tabs(&mut ws);
 
         if ws.open_tabs.is_empty() {
             ws.open_tabs.push(RequestTab::default());
@@ -45,22 +155,53 @@ impl App {
             ws.active_environment_idx.or(Some(0))
         };
         ws.active_environment_idx = active_env_idx;
+        let client = build_client(&ws.cookies, &ws.proxy);
 
-        Self {
+        let mut app = Self {
             state: AppState {
                 sidebar_visible: true,
                 dirty: true,
                 workspace: ws,
                 all_workspaces,
+                config: config_storage::load_config(),
                 ..Default::default()
             },
-            client: build_client(),
+            client,
             tx,
-            cancel: None,
-        }
+            in_flight: HashMap::new(),
+            send_ids: 0,
+            dependency_queue: VecDeque::new(),
+            sidebar_queue: VecDeque::new(),
+            sidebar_running: None,
+            sidebar_cancel: None,
+            sidebar_sent_at: None,
+            sidebar_audit: None,
+            queue_cancel: None,
+            queue_audit: None,
+            queue_last_sent: None,
+            protected_confirmed_until: HashMap::new(),
+            click_opened_tab: false,
+            highlight_jobs: 0,
+        };
+        app.reload_custom_theme();
+        apply_syntax_theme(&app.state.config.ui);
+        app.state.workspace_last_opened = state_storage::load_state().last_opened;
+        app.touch_workspace();
+        app
     }
 
     pub fn handle_event(&mut self, event: Event) {
+        let was_insert = self.state.mode == Mode::Insert;
+        self.dispatch_event(event);
+        // Leaving Insert mode closes the undo unit its edits were grouped in
+        if was_insert && self.state.mode != Mode::Insert {
+            for tab in &mut self.state.workspace.open_tabs {
+                tab.undo.end_session();
+            }
+        }
+    }
+
+    fn dispatch_event(&mut self, event: Event) {
         match event {
             Event::Key(key) if key.kind != KeyEventKind::Release => {
                 self.state.dirty = true;
@@ -73,16 +214,23 @@ impl App {
                     return;
                 }
 
+                // Ctrl+Shift+D: dry run, also global
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && (key.code == KeyCode::Char('D')
+                        || key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::SHIFT))
+                {
+                    self.dry_run_request();
+                    return;
+                }
+
                 // Ctrl+E: toggle environment switcher popup
                 if key.code == KeyCode::Char('e')
                     && key.modifiers.contains(KeyModifiers::CONTROL)
                 {
                     match self.state.active_popup {
                         ActivePopup::None => {
-                            self.state.active_popup = ActivePopup::EnvSwitcher;
-                            self.state.env_switcher.selected = 0;
-                            self.state.env_switcher.search.clear();
-                            self.state.env_switcher.search_cursor = 0;
+                            let for_tab = self.state.focus == Focus::UrlBar && self.state.active_tab().is_some();
+                            self.open_env_switcher(for_tab);
                         }
                         ActivePopup::EnvSwitcher | ActivePopup::EnvEditor => {
                             self.state.active_popup = ActivePopup::None;
@@ -94,15 +242,22 @@ impl App {
                     return;
                 }
 
-                // Ctrl+W: workspace switcher
+                // Ctrl+W: workspace switcher, except while typing, where it
+                // deletes the previous word
+                let typing = match self.state.active_popup {
+                    ActivePopup::None => {
+                        self.state.mode == Mode::Insert
+                            && matches!(self.state.focus, Focus::UrlBar | Focus::Editor)
+                    }
+                    ActivePopup::EnvEditor => self.state.env_editor.editing,
+                    _ => false,
+                };
                 if key.code == KeyCode::Char('w')
                     && key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !typing
                 {
                     match self.state.active_popup {
-                        ActivePopup::None => {
-                            self.state.active_popup = ActivePopup::WorkspaceSwitcher;
-                            self.state.ws_switcher = WorkspaceSwitcherState::default();
-                        }
+                        ActivePopup::None => self.open_workspace_switcher(),
                         ActivePopup::WorkspaceSwitcher => {
                             self.state.active_popup = ActivePopup::None;
                         }
@@ -113,21 +268,140 @@ impl App {
                     return;
                 }
 
+                // Ctrl+Shift+P: command palette (also `:` in Normal mode).
+                // Other popups ignore it, so none is left half-edited.
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && (key.code == KeyCode::Char('P')
+                        || key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::SHIFT))
+                {
+                    match self.state.active_popup {
+                        ActivePopup::None => self.open_command_palette(),
+                        ActivePopup::CommandPalette => self.state.active_popup = ActivePopup::None,
+                        _ => {}
+                    }
+                    return;
+                }
+
+                // Ctrl+P: go to any request in the workspace
+                if key.code == KeyCode::Char('p')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    match self.state.active_popup {
+                        ActivePopup::None => self.open_request_finder(),
+                        ActivePopup::RequestFinder => self.state.active_popup = ActivePopup::None,
+                        _ => {}
+                    }
+                    return;
+                }
+
                 // If a popup is open, route all keys to it
                 if self.state.active_popup != ActivePopup::None {
                     self.handle_popup_key(key);
                     return;
                 }
+                // Ctrl+Z / Ctrl+Y (or Ctrl+Shift+Z): undo and redo text edits,
+                // Ctrl+L: line numbers in the body and script editors, or the
+                // response body's highlight language in the viewer,
+                // Ctrl+S: save the tab to its collection
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && matches!(self.state.mode, Mode::Normal | Mode::Insert)
+                    && !self.state.sidebar.search_mode
+                {
+                    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                    match key.code {
+                        KeyCode::Char('z') if !shift => return self.undo_edit(false),
+                        KeyCode::Char('Z') | KeyCode::Char('z') | KeyCode::Char('y') => {
+                            return self.undo_edit(true);
+                        }
+                        KeyCode::Char('l') if self.state.focus == Focus::Editor => {
+                            return self.toggle_line_numbers();
+                        }
+                        KeyCode::Char('l') if self.state.focus == Focus::ResponseViewer => {
+                            return self.cycle_response_lang();
+                        }
+                        KeyCode::Char('s') => return self.save_active_tab(),
+                        _ => {}
+                    }
+                }
                 match self.state.mode {
                     Mode::Normal => self.handle_normal_key(key),
                     Mode::Insert => self.handle_insert_key(key),
                     Mode::Command | Mode::Visual => {}
                 }
+                self.scroll_body_to_cursor();
             }
             Event::Key(_) => {}
-            Event::Response(result) => {
+            Event::Response { tab_id, send_id, result } => {
+                self.state.dirty = true;
+                self.handle_response(&tab_id, send_id, result);
+            }
+            Event::SidebarRunResult { request_id, result } => {
+                self.state.dirty = true;
+                self.handle_sidebar_run_result(request_id, result);
+            }
+            Event::QueuedResponse { tab_id, result } => {
+                self.state.dirty = true;
+                self.handle_queued_response(tab_id, result);
+            }
+            Event::WsOpened { conn_id } => {
+                self.state.dirty = true;
+                if let Some(ws) = self.ws_session(&conn_id) {
+                    ws.status = WsStatus::Open;
+                }
+            }
+            Event::WsMessage { conn_id, text } => {
+                self.state.dirty = true;
+                if let Some(ws) = self.ws_session(&conn_id) {
+                    ws.push(WsDirection::Received, text);
+                }
+            }
+            Event::WsClosed { conn_id, code, reason } => {
+                self.state.dirty = true;
+                if let Some(ws) = self.ws_session(&conn_id) {
+                    ws.status = WsStatus::Closed { code, reason };
+                    ws.commands = None;
+                }
+            }
+            Event::OAuthToken { request_id, token } => {
+                self.state.dirty = true;
+                self.cache_oauth_token(&request_id, token);
+            }
+            Event::Highlighted { tab_id, job, text } => {
+                self.state.dirty = true;
+                let resp = self
+                    .tab_index(&tab_id)
+                    .and_then(|i| self.state.workspace.open_tabs[i].response.as_mut())
+                    .filter(|resp| resp.highlight_job == job);
+                if let Some(resp) = resp {
+                    resp.highlighted_body = Some(text);
+                    resp.highlight_job = 0;
+                }
+            }
+            Event::WorkspaceSummaries(summaries) => {
+                self.state.dirty = true;
+                self.state.workspace_summaries = summaries;
+            }
+            Event::SseOpened { tab_id, send_id, conn_id, response, cancel } => {
+                self.state.dirty = true;
+                let idx = self.tab_index(&tab_id).filter(|_| self.is_current_send(&tab_id, send_id));
+                self.handle_response(&tab_id, send_id, Ok(response));
+                match idx {
+                    Some(idx) => self.state.workspace.open_tabs[idx].sse = Some(SseStream::new(conn_id, cancel)),
+                    None => cancel.cancel(),
+                }
+            }
+            Event::SseMessage { conn_id, event } => {
+                self.state.dirty = true;
+                if let Some(sse) = self.sse_stream(&conn_id) {
+                    sse.push(event);
+                }
+            }
+            Event::SseClosed { conn_id, reason } => {
                 self.state.dirty = true;
-                self.handle_response(result);
+                if let Some(sse) = self.sse_stream(&conn_id) {
+                    sse.closed = Some(reason);
+                    sse.stop = None;
+                }
             }
             // Tick: only dirty when the spinner is visible; otherwise a no-op.
             Event::Tick => self.handle_tick(),
@@ -151,12 +425,43 @@ impl App {
             ActivePopup::WorkspaceSwitcher => self.handle_workspace_switcher_key(key),
             ActivePopup::CollectionNaming => self.handle_naming_key(key),
             ActivePopup::ConfirmDelete => self.handle_confirm_delete_key(key),
+            ActivePopup::CheckPicker => self.handle_check_picker_key(key),
+            ActivePopup::RequestFinder => self.handle_request_finder_key(key),
+            ActivePopup::AuditLog => self.handle_audit_log_key(key),
+            ActivePopup::DependencyPicker => self.handle_dependency_picker_key(key),
+            ActivePopup::DependencyPrompt => self.handle_dependency_prompt_key(key),
+            ActivePopup::ConfirmSend => self.handle_confirm_send_key(key),
+            ActivePopup::ConfirmClose => self.handle_confirm_close_key(key),
+            ActivePopup::RunHistory => self.handle_run_history_key(key),
+            ActivePopup::Runner => self.handle_runner_key(key),
+            ActivePopup::ThemePicker => self.handle_theme_picker_key(key),
+            ActivePopup::Cookies => self.handle_cookie_jar_key(key),
+            ActivePopup::Capture => self.handle_capture_key(key),
+            ActivePopup::SendQueue => self.handle_send_queue_key(key),
+            ActivePopup::SaveRequest => self.handle_save_request_key(key),
+            ActivePopup::JsonValue => self.handle_json_value_key(key),
+            ActivePopup::Diff => self.handle_diff_key(key),
+            ActivePopup::Variables => self.handle_variables_key(key),
+            ActivePopup::DefaultHeaders => self.handle_default_headers_key(key),
+            ActivePopup::Settings => self.handle_settings_key(key),
+            ActivePopup::Help => self.handle_help_key(key),
+            ActivePopup::CommandPalette => self.handle_command_palette_key(key),
+            ActivePopup::CollectionVariables => self.handle_collection_variables_key(key),
             ActivePopup::None => {}
         }
     }
 
     // ─── Env switcher ─────────────────────────────────────────────────────────
 
+    /// `for_tab` scopes the pick to the active tab (`Ctrl+E` on the URL bar).
+    fn open_env_switcher(&mut self, for_tab: bool) {
+        self.state.active_popup = ActivePopup::EnvSwitcher;
+        self.state.env_switcher.for_tab = for_tab;
+        self.state.env_switcher.selected = 0;
+        self.state.env_switcher.search.clear();
+        self.state.env_switcher.search_cursor = 0;
+    }
+
     fn handle_env_switcher_key(&mut self, key: KeyEvent) {
         if self.state.env_switcher.naming {
             self.handle_env_switcher_naming_key(key);
@@ -180,7 +485,7 @@ impl App {
                     .nth(selected)
                     .map(|(i, _)| i);
                 if let Some(i) = idx {
-                    self.state.workspace.active_environment_idx = Some(i);
+                    self.activate_environment(i);
                 }
                 self.state.active_popup = ActivePopup::None;
             }
@@ -204,6 +509,8 @@ impl App {
                     self.state.env_editor.cursor = 0;
                     self.state.env_editor.editing = false;
                     self.state.env_editor.show_secret = false;
+                    self.state.env_editor.search_mode = false;
+                    self.state.env_editor.search.clear();
                     self.state.active_popup = ActivePopup::EnvEditor;
                 } else if self.state.workspace.environments.is_empty() {
                     let new_env = Environment::default();
@@ -215,6 +522,8 @@ impl App {
                     self.state.env_editor.cursor = 0;
                     self.state.env_editor.editing = false;
                     self.state.env_editor.show_secret = false;
+                    self.state.env_editor.search_mode = false;
+                    self.state.env_editor.search.clear();
                     self.state.active_popup = ActivePopup::EnvEditor;
                 }
             }
@@ -223,6 +532,30 @@ impl App {
                 self.state.env_switcher.new_name = String::new();
                 self.state.env_switcher.new_name_cursor = 0;
             }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::ALT) => {
+                if let Some(env_idx) = self.selected_env_idx() {
+                    let slug = self.state.workspace.environments[env_idx].name.to_lowercase().replace(' ', "_");
+                    let switcher = &mut self.state.env_switcher;
+                    switcher.naming = true;
+                    switcher.file_prompt = Some(EnvFilePrompt::Export { env_idx, redact: true });
+                    switcher.new_name = format!("{slug}.toml");
+                    switcher.new_name_cursor = switcher.new_name.len();
+                }
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) && self.state.env_switcher.for_tab => {
+                // Back to the workspace's environment
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.env_override = None;
+                }
+                self.state.active_popup = ActivePopup::None;
+            }
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let switcher = &mut self.state.env_switcher;
+                switcher.naming = true;
+                switcher.file_prompt = Some(EnvFilePrompt::Import);
+                switcher.new_name = String::new();
+                switcher.new_name_cursor = 0;
+            }
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::ALT) => {
                 let filter = self.state.env_switcher.search.to_lowercase();
                 let selected = self.state.env_switcher.selected;
@@ -240,6 +573,11 @@ impl App {
                     let ws_name = self.state.workspace.name.clone();
                     let _ = env_storage::delete_ws(&ws_name, &env_id);
                     self.state.workspace.environments.remove(i);
+                    for tab in &mut self.state.workspace.open_tabs {
+                        if tab.env_override.as_deref() == Some(env_id.as_str()) {
+                            tab.env_override = None;
+                        }
+                    }
                     match self.state.workspace.active_environment_idx {
                         Some(ai) if ai == i => self.state.workspace.active_environment_idx = None,
                         Some(ai) if ai > i => {
@@ -283,6 +621,56 @@ impl App {
         }
     }
 
+    /// Index into `workspace.environments` of the switcher's selected row.
+    fn selected_env_idx(&self) -> Option<usize> {
+        let filter = self.state.env_switcher.search.to_lowercase();
+        self.state
+            .workspace
+            .environments
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| filter.is_empty() || e.name.to_lowercase().contains(&filter))
+            .nth(self.state.env_switcher.selected)
+            .map(|(i, _)| i)
+    }
+
+    /// Export or import the environment file named in the switcher's path
+    /// prompt. Failures keep the prompt open with the path to fix.
+    fn finish_env_file_prompt(&mut self, prompt: EnvFilePrompt) {
+        let path = env_storage::expand_home(self.state.env_switcher.new_name.trim());
+        let result = match prompt {
+            EnvFilePrompt::Export { env_idx, redact } => {
+                let Some(env) = self.state.workspace.environments.get(env_idx) else {
+                    return;
+                };
+                env_storage::export(env, &path, redact).map(|()| {
+                    let secrets = if redact { "secrets redacted" } else { "secrets included" };
+                    format!("Exported {} to {} ({secrets})", env.name, path.display())
+                })
+            }
+            EnvFilePrompt::Import => env_storage::import(&path).and_then(|env| {
+                env_storage::save_ws(&self.state.workspace.name, &env)?;
+                let message = format!("Imported {}", env.name);
+                self.state.workspace.environments.push(env);
+                self.state.env_switcher.search.clear();
+                self.state.env_switcher.search_cursor = 0;
+                self.state.env_switcher.selected = self.state.workspace.environments.len() - 1;
+                Ok(message)
+            }),
+        };
+        match result {
+            Ok(message) => {
+                let switcher = &mut self.state.env_switcher;
+                switcher.naming = false;
+                switcher.file_prompt = None;
+                switcher.new_name.clear();
+                switcher.new_name_cursor = 0;
+                self.show_toast(message);
+            }
+            Err(e) => self.show_toast(format!("{e:#}")),
+        }
+    }
+
     fn filtered_env_count(&self) -> usize {
         let filter = self.state.env_switcher.search.to_lowercase();
         self.state
@@ -297,8 +685,19 @@ impl App {
         match key.code {
             KeyCode::Esc => {
                 self.state.env_switcher.naming = false;
+                self.state.env_switcher.file_prompt = None;
                 self.state.env_switcher.new_name = String::new();
             }
+            KeyCode::Tab => {
+                if let Some(EnvFilePrompt::Export { redact, .. }) = &mut self.state.env_switcher.file_prompt {
+                    *redact = !*redact;
+                }
+            }
+            KeyCode::Enter if self.state.env_switcher.file_prompt.is_some() => {
+                if let Some(prompt) = self.state.env_switcher.file_prompt {
+                    self.finish_env_file_prompt(prompt);
+                }
+            }
             KeyCode::Enter => {
                 let name = if self.state.env_switcher.new_name.trim().is_empty() {
                     "New Environment".to_string()
@@ -312,7 +711,7 @@ impl App {
                 self.state.workspace.environments.push(new_env);
                 let i = self.state.workspace.environments.len() - 1;
                 self.state.env_switcher.selected = i;
-                self.state.workspace.active_environment_idx = Some(i);
+                self.activate_environment(i);
                 self.state.env_switcher.naming = false;
                 self.state.env_switcher.new_name = String::new();
                 self.state.env_switcher.new_name_cursor = 0;
@@ -371,11 +770,26 @@ impl App {
             self.handle_env_editor_insert_key(key);
             return;
         }
+        if self.state.env_editor.color_input.is_some() {
+            self.handle_env_color_key(key);
+            return;
+        }
+        if self.state.env_editor.search_mode {
+            self.handle_env_search_key(key);
+            return;
+        }
         match key.code {
+            KeyCode::Esc if !self.state.env_editor.search.is_empty() => {
+                self.state.env_editor.search.clear();
+                self.state.env_editor.row = 0;
+            }
             KeyCode::Esc => {
                 self.save_current_env();
                 self.state.active_popup = ActivePopup::None;
             }
+            KeyCode::Char('/') => {
+                self.state.env_editor.search_mode = true;
+            }
             KeyCode::Char('i') | KeyCode::Enter => {
                 let col = self.state.env_editor.col;
                 if col < 3 {
@@ -386,6 +800,8 @@ impl App {
             }
             KeyCode::Char('a') => {
                 let idx = self.state.env_editor.env_idx;
+                // A new, empty row would be hidden by the filter
+                self.state.env_editor.search.clear();
                 if let Some(env) = self.state.workspace.environments.get_mut(idx) {
                     env.variables.push(EnvVariable::default());
                     self.state.env_editor.row = env.variables.len() - 1;
@@ -396,28 +812,17 @@ impl App {
             }
             KeyCode::Char('d') => {
                 let idx = self.state.env_editor.env_idx;
-                if let Some(env) = self.state.workspace.environments.get_mut(idx) {
-                    let row = self.state.env_editor.row;
-                    if row < env.variables.len() {
-                        env.variables.remove(row);
-                        let new_len = env.variables.len();
-                        self.state.env_editor.row = if new_len > 0 {
-                            row.min(new_len - 1)
-                        } else {
-                            0
-                        };
-                    }
+                let editor = &mut self.state.env_editor;
+                if let Some(env) = self.state.workspace.environments.get_mut(idx)
+                    && let Some(var_idx) = editor.var_index(&env.variables)
+                {
+                    env.variables.remove(var_idx);
+                    let shown = filter_variables(&env.variables, &editor.search).len();
+                    editor.row = editor.row.min(shown.saturating_sub(1));
                 }
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                let idx = self.state.env_editor.env_idx;
-                let len = self
-                    .state
-                    .workspace
-                    .environments
-                    .get(idx)
-                    .map(|e| e.variables.len())
-                    .unwrap_or(0);
+                let len = self.env_editor_shown().len();
                 if len > 0 {
                     self.state.env_editor.row = (self.state.env_editor.row + 1).min(len - 1);
                 }
@@ -438,12 +843,42 @@ impl App {
                     self.state.env_editor.editing_name = true;
                 }
             }
+            KeyCode::Char('c') => {
+                let idx = self.state.env_editor.env_idx;
+                if let Some(env) = self.state.workspace.environments.get_mut(idx) {
+                    env.cycle_color();
+                }
+            }
+            KeyCode::Char('C') => {
+                let idx = self.state.env_editor.env_idx;
+                if let Some(env) = self.state.workspace.environments.get(idx) {
+                    self.state.env_editor.color_input = Some(env.color.clone());
+                }
+            }
+            KeyCode::Char('P') => {
+                let idx = self.state.env_editor.env_idx;
+                if let Some(env) = self.state.workspace.environments.get_mut(idx) {
+                    env.protected = !env.protected;
+                }
+            }
+            KeyCode::Char(' ') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                // Enable every shown row, or disable them if all are enabled
+                let shown = self.env_editor_shown();
+                let idx = self.state.env_editor.env_idx;
+                if let Some(env) = self.state.workspace.environments.get_mut(idx) {
+                    let enable = shown.iter().any(|&i| !env.variables[i].enabled);
+                    for i in shown {
+                        env.variables[i].enabled = enable;
+                    }
+                }
+            }
             KeyCode::Char(' ') => {
                 let idx = self.state.env_editor.env_idx;
-                let row = self.state.env_editor.row;
                 let col = self.state.env_editor.col;
+                let editor = &self.state.env_editor;
                 if let Some(env) = self.state.workspace.environments.get_mut(idx) {
-                    if let Some(var) = env.variables.get_mut(row) {
+                    let var_idx = editor.var_index(&env.variables);
+                    if let Some(var) = var_idx.and_then(|i| env.variables.get_mut(i)) {
                         match col {
                             0 => var.enabled = !var.enabled,
                             3 => {
@@ -468,6 +903,16 @@ impl App {
     }
 
     fn handle_env_editor_insert_key(&mut self, key: KeyEvent) {
+        if Self::is_word_edit_key(key) {
+            let mut cursor = self.state.env_editor.cursor;
+            // Values are often URLs; keys and descriptions are plain words
+            let url = self.state.env_editor.col == 1;
+            if let Some(field) = self.current_editor_field_mut() {
+                Self::word_edit_key(field, &mut cursor, key, url);
+                self.state.env_editor.cursor = cursor;
+            }
+            return;
+        }
         match key.code {
             KeyCode::Esc | KeyCode::Enter => {
                 self.state.env_editor.editing = false;
@@ -482,27 +927,17 @@ impl App {
                 } else {
                     self.state.env_editor.col = 0;
                     let idx = self.state.env_editor.env_idx;
-                    let len = self
-                        .state
-                        .workspace
-                        .environments
-                        .get(idx)
-                        .map(|e| e.variables.len())
-                        .unwrap_or(0);
-                    let next_row = self.state.env_editor.row + 1;
-                    if next_row >= len {
+                    let mut next_row = self.state.env_editor.row + 1;
+                    if next_row >= self.env_editor_shown().len() {
+                        // Add a row at the end, clearing the filter so it shows
+                        self.state.env_editor.search.clear();
                         if let Some(env) = self.state.workspace.environments.get_mut(idx) {
                             env.variables.push(EnvVariable::default());
+                            next_row = env.variables.len() - 1;
                         }
                     }
-                    let new_len = self
-                        .state
-                        .workspace
-                        .environments
-                        .get(idx)
-                        .map(|e| e.variables.len())
-                        .unwrap_or(0);
-                    self.state.env_editor.row = next_row.min(new_len.saturating_sub(1));
+                    let shown = self.env_editor_shown().len();
+                    self.state.env_editor.row = next_row.min(shown.saturating_sub(1));
                     self.state.env_editor.cursor = 0;
                     self.state.env_editor.editing = true;
                 }
@@ -555,6 +990,36 @@ impl App {
         }
     }
 
+    /// Typing a hex color for the environment: Enter applies it if it is
+    /// `#rrggbb`, Esc keeps the old one.
+    fn handle_env_color_key(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.state.env_editor.color_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.state.env_editor.color_input = None,
+            KeyCode::Enter => {
+                let hex = input.trim().to_lowercase();
+                if parse_hex_color(&hex).is_none() {
+                    self.show_toast(format!("\"{hex}\" isn't a color, use #rrggbb"));
+                    return;
+                }
+                let idx = self.state.env_editor.env_idx;
+                if let Some(env) = self.state.workspace.environments.get_mut(idx) {
+                    env.color = hex;
+                }
+                self.state.env_editor.color_input = None;
+            }
+            KeyCode::Char(c) if (c.is_ascii_hexdigit() || c == '#') && input.len() < 7 => {
+                input.push(c);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            _ => {}
+        }
+    }
+
     fn handle_env_name_edit_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc | KeyCode::Enter => {
@@ -623,15 +1088,47 @@ impl App {
         }
     }
 
+    /// Indices of the variables the env editor shows under its filter.
+    fn env_editor_shown(&self) -> Vec<usize> {
+        let editor = &self.state.env_editor;
+        self.state
+            .workspace
+            .environments
+            .get(editor.env_idx)
+            .map(|e| filter_variables(&e.variables, &editor.search))
+            .unwrap_or_default()
+    }
+
+    fn handle_env_search_key(&mut self, key: KeyEvent) {
+        let editor = &mut self.state.env_editor;
+        match key.code {
+            KeyCode::Esc => {
+                editor.search_mode = false;
+                editor.search.clear();
+            }
+            // Keep the filter and go back to moving between rows
+            KeyCode::Enter => editor.search_mode = false,
+            KeyCode::Char(c) => editor.search.push(c),
+            KeyCode::Backspace => {
+                editor.search.pop();
+                if editor.search.is_empty() {
+                    editor.search_mode = false;
+                }
+            }
+            _ => {}
+        }
+        self.state.env_editor.row = 0;
+    }
+
     fn current_editor_field_len(&self) -> usize {
         let idx = self.state.env_editor.env_idx;
-        let row = self.state.env_editor.row;
         let col = self.state.env_editor.col;
+        let editor = &self.state.env_editor;
         self.state
             .workspace
             .environments
             .get(idx)
-            .and_then(|e| e.variables.get(row))
+            .and_then(|e| e.variables.get(editor.var_index(&e.variables)?))
             .map(|v| match col {
                 0 => v.key.len(),
                 1 => v.value.len(),
@@ -643,15 +1140,11 @@ impl App {
 
     fn current_editor_field_mut(&mut self) -> Option<&mut String> {
         let idx = self.state.env_editor.env_idx;
-        let row = self.state.env_editor.row;
         let col = self.state.env_editor.col;
-        let var = self
-            .state
-            .workspace
-            .environments
-            .get_mut(idx)?
-            .variables
-            .get_mut(row)?;
+        let editor = &self.state.env_editor;
+        let env = self.state.workspace.environments.get_mut(idx)?;
+        let var_idx = editor.var_index(&env.variables)?;
+        let var = env.variables.get_mut(var_idx)?;
         match col {
             0 => Some(&mut var.key),
             1 => Some(&mut var.value),
@@ -660,6 +1153,25 @@ impl App {
         }
     }
 
+    /// Write values set with `forge.env.set` to the active environment and
+    /// save it, noting `request` and `via` as their origin for the variables
+    /// popup. Without an active environment they are dropped.
+    fn apply_env_updates(&mut self, updates: &[(String, String)], request: &str, via: &'static str) {
+        if updates.is_empty() {
+            return;
+        }
+        let env_idx = self.state.active_environment_idx();
+        let ws = &mut self.state.workspace;
+        let Some(env) = env_idx.and_then(|i| ws.environments.get_mut(i)) else {
+            return;
+        };
+        for (key, value) in updates {
+            env.set_var(key, value);
+            self.state.var_origins.insert(key.clone(), VarOrigin::now(request, via));
+        }
+        let _ = env_storage::save_ws(&ws.name, env);
+    }
+
     fn save_current_env(&self) {
         let idx = self.state.env_editor.env_idx;
         let ws_name = &self.state.workspace.name;
@@ -670,6 +1182,28 @@ impl App {
 
     // ─── Workspace switcher ───────────────────────────────────────────────────
 
+    /// Open the switcher with the cached workspace sizes and rescan them in
+    /// the background. The sort order is kept from the last time.
+    fn open_workspace_switcher(&mut self) {
+        self.state.active_popup = ActivePopup::WorkspaceSwitcher;
+        self.state.ws_switcher = WorkspaceSwitcherState {
+            alphabetical: self.state.ws_switcher.alphabetical,
+            ..WorkspaceSwitcherState::default()
+        };
+        let names = self.state.all_workspaces.clone();
+        let tx = self.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let summaries = names
+                .into_iter()
+                .map(|name| {
+                    let summary = col_storage::scan_collections(&name);
+                    (name, summary)
+                })
+                .collect();
+            let _ = tx.send(Event::WorkspaceSummaries(summaries));
+        });
+    }
+
     fn handle_workspace_switcher_key(&mut self, key: KeyEvent) {
         if self.state.ws_switcher.naming {
             self.handle_ws_naming_key(key);
@@ -680,22 +1214,13 @@ impl App {
                 self.state.active_popup = ActivePopup::None;
             }
             KeyCode::Enter => {
-                let filter = self.state.ws_switcher.search.to_lowercase();
                 let selected = self.state.ws_switcher.selected;
-                let chosen = self
-                    .state
-                    .all_workspaces
-                    .iter()
-                    .filter(|w| filter.is_empty() || w.to_lowercase().contains(&filter))
-                    .nth(selected)
-                    .cloned();
+                let chosen = switcher_workspaces(&self.state)
+                    .get(selected)
+                    .map(|name| name.to_string());
                 if let Some(name) = chosen {
                     if name != self.state.workspace.name {
-                        let mut ws = ws_storage::load_workspace_full(&name);
-                        if ws.open_tabs.is_empty() {
-                            ws.open_tabs.push(RequestTab::default());
-                        }
-                        self.state.workspace = ws;
+                        self.set_workspace(ws_storage::load_workspace_full(&name));
                     }
                 }
                 self.state.active_popup = ActivePopup::None;
@@ -705,14 +1230,48 @@ impl App {
                 self.state.ws_switcher.new_name = String::new();
                 self.state.ws_switcher.new_name_cursor = 0;
             }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let switcher = &mut self.state.ws_switcher;
+                switcher.alphabetical = !switcher.alphabetical;
+                switcher.selected = 0;
+            }
+            KeyCode::Char('r' | 'R') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let selected = self.state.ws_switcher.selected;
+                if let Some(name) = switcher_workspaces(&self.state).get(selected).map(|n| n.to_string()) {
+                    let switcher = &mut self.state.ws_switcher;
+                    switcher.naming = true;
+                    switcher.new_name_cursor = name.len();
+                    switcher.new_name = name.clone();
+                    switcher.renaming = Some(name);
+                }
+            }
+            KeyCode::Char('c' | 'C') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let selected = self.state.ws_switcher.selected;
+                if let Some(name) = switcher_workspaces(&self.state).get(selected).map(|n| n.to_string()) {
+                    let switcher = &mut self.state.ws_switcher;
+                    switcher.naming = true;
+                    switcher.new_name = format!("{name} copy");
+                    switcher.new_name_cursor = switcher.new_name.len();
+                    switcher.duplicating = Some(name);
+                }
+            }
+            KeyCode::Char('d' | 'D') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let selected = self.state.ws_switcher.selected;
+                if let Some(name) = switcher_workspaces(&self.state).get(selected).map(|n| n.to_string()) {
+                    if self.state.all_workspaces.len() <= 1 {
+                        self.show_toast("Can't delete the only workspace".to_string());
+                    } else {
+                        self.state.confirm_delete = ConfirmDeleteState {
+                            message: format!("Delete workspace '{name}' and all its data?"),
+                            target_id: name,
+                            workspace: true,
+                        };
+                        self.state.active_popup = ActivePopup::ConfirmDelete;
+                    }
+                }
+            }
             KeyCode::Char('j') | KeyCode::Down => {
-                let filter = self.state.ws_switcher.search.to_lowercase();
-                let count = self
-                    .state
-                    .all_workspaces
-                    .iter()
-                    .filter(|w| filter.is_empty() || w.to_lowercase().contains(&filter))
-                    .count();
+                let count = switcher_workspaces(&self.state).len();
                 if count > 0 {
                     self.state.ws_switcher.selected =
                         (self.state.ws_switcher.selected + 1).min(count - 1);
@@ -747,6 +1306,8 @@ impl App {
             KeyCode::Esc => {
                 self.state.ws_switcher.naming = false;
                 self.state.ws_switcher.new_name = String::new();
+                self.state.ws_switcher.renaming = None;
+                self.state.ws_switcher.duplicating = None;
             }
             KeyCode::Enter => {
                 let name = if self.state.ws_switcher.new_name.trim().is_empty() {
@@ -754,18 +1315,26 @@ impl App {
                 } else {
                     self.state.ws_switcher.new_name.trim().to_string()
                 };
-                let ws_file = crate::state::workspace::WorkspaceFile {
-                    name: name.clone(),
-                    active_environment_idx: None,
-                };
-                let _ = ws_storage::save_workspace(&ws_file);
+                if let Some(old) = self.state.ws_switcher.renaming.take() {
+                    self.rename_workspace(&old, &name);
+                    self.state.ws_switcher.naming = false;
+                    self.state.ws_switcher.new_name = String::new();
+                    self.state.ws_switcher.new_name_cursor = 0;
+                    return;
+                }
+                // Kept until the copy succeeds, so a corrected name retries it
+                if let Some(src) = self.state.ws_switcher.duplicating.clone() {
+                    if !self.duplicate_workspace(&src, &name) {
+                        return;
+                    }
+                    self.state.ws_switcher.duplicating = None;
+                } else {
+                    let ws_file = crate::state::workspace::WorkspaceFile::new(name.clone());
+                    let _ = ws_storage::save_workspace(&ws_file);
+                }
                 self.state.all_workspaces = ws_storage::list_workspaces();
                 // Switch to new workspace
-                let mut ws = ws_storage::load_workspace_full(&name);
-                if ws.open_tabs.is_empty() {
-                    ws.open_tabs.push(RequestTab::default());
-                }
-                self.state.workspace = ws;
+                self.set_workspace(ws_storage::load_workspace_full(&name));
                 self.state.ws_switcher.naming = false;
                 self.state.ws_switcher.new_name = String::new();
                 self.state.ws_switcher.new_name_cursor = 0;
@@ -805,6 +1374,93 @@ impl App {
         }
     }
 
+    /// Rename workspace `old` on disk, in `state.toml` and, if it is the
+    /// open one, in memory.
+    fn rename_workspace(&mut self, old: &str, new: &str) {
+        if old == new {
+            return;
+        }
+        if new.contains(['/', '\\']) || new == "." || new == ".." {
+            self.show_toast(format!("\"{new}\" can't be a workspace name"));
+            return;
+        }
+        if let Err(err) = ws_storage::rename_workspace(old, new) {
+            self.show_toast(format!("Rename failed: {err}"));
+            return;
+        }
+        if self.state.workspace.name == old {
+            self.state.workspace.name = new.to_string();
+        }
+        let mut shared = state_storage::load_state();
+        if let Some(at) = shared.last_opened.remove(old) {
+            shared.last_opened.insert(new.to_string(), at);
+        }
+        let _ = state_storage::save_state(&shared);
+        self.state.workspace_last_opened = shared.last_opened;
+        if let Some(summary) = self.state.workspace_summaries.remove(old) {
+            self.state.workspace_summaries.insert(new.to_string(), summary);
+        }
+        self.state.all_workspaces = ws_storage::list_workspaces();
+        // Keep the renamed workspace selected wherever it now sorts
+        if let Some(row) = switcher_workspaces(&self.state).iter().position(|w| *w == new) {
+            self.state.ws_switcher.selected = row;
+        }
+        self.show_toast(format!("Renamed workspace to \"{new}\""));
+    }
+
+    /// Copy workspace `src` to `new` on disk. Returns `false`, with a toast,
+    /// if the name is taken or the copy fails.
+    fn duplicate_workspace(&mut self, src: &str, new: &str) -> bool {
+        if new.contains(['/', '\\']) || new == "." || new == ".." {
+            self.show_toast(format!("\"{new}\" can't be a workspace name"));
+            return false;
+        }
+        // The copy is taken from disk, so write out the open workspace first
+        if self.state.workspace.name == src {
+            self.save_workspace_file();
+        }
+        if let Err(err) = ws_storage::duplicate_workspace(src, new) {
+            self.show_toast(format!("Duplicate failed: {err}"));
+            return false;
+        }
+        true
+    }
+
+    /// Delete workspace `name` from disk, switching to `default` (or another
+    /// workspace when deleting `default`) first if it is the open one. The
+    /// last workspace can't be deleted.
+    fn delete_workspace(&mut self, name: &str) {
+        let fallback = if name == "default" {
+            self.state.all_workspaces.iter().find(|w| *w != name).cloned()
+        } else {
+            Some("default".to_string())
+        };
+        let Some(fallback) = fallback.filter(|_| self.state.all_workspaces.len() > 1) else {
+            self.show_toast("Can't delete the only workspace".to_string());
+            return;
+        };
+        if self.state.workspace.name == name {
+            if !self.state.all_workspaces.contains(&fallback) {
+                let ws_file = crate::state::workspace::WorkspaceFile::new(fallback.clone());
+                let _ = ws_storage::save_workspace(&ws_file);
+            }
+            self.set_workspace(ws_storage::load_workspace_full(&fallback));
+        }
+        if let Err(err) = ws_storage::delete_workspace(name) {
+            self.show_toast(format!("Delete failed: {err}"));
+            return;
+        }
+        let mut shared = state_storage::load_state();
+        shared.last_opened.remove(name);
+        let _ = state_storage::save_state(&shared);
+        self.state.workspace_last_opened = shared.last_opened;
+        self.state.workspace_summaries.remove(name);
+        self.state.all_workspaces = ws_storage::list_workspaces();
+        let count = switcher_workspaces(&self.state).len();
+        self.state.ws_switcher.selected = self.state.ws_switcher.selected.min(count.saturating_sub(1));
+        self.show_toast(format!("Deleted workspace \"{name}\""));
+    }
+
     // ─── Collection naming popup ──────────────────────────────────────────────
 
     fn handle_naming_key(&mut self, key: KeyEvent) {
@@ -932,22 +1588,51 @@ impl App {
                     }
                 }
             }
+            NamingTarget::RenameTab { tab_id } => {
+                let Some(idx) = self.tab_index(&tab_id) else {
+                    self.state.naming = NamingState::default();
+                    return;
+                };
+                let tab = &mut self.state.workspace.open_tabs[idx];
+                tab.request.name = input.clone();
+                if let Some(id) = tab.collection_id.clone() {
+                    for col in &mut self.state.workspace.collections {
+                        if rename_item_in_list(&mut col.items, &id, &input) {
+                            let _ = col_storage::save_collection_meta(&ws_name, col);
+                            break;
+                        }
+                    }
+                }
+            }
         }
 
+        self.state.sidebar.invalidate_nodes();
         self.state.naming = NamingState::default();
     }
 
     // ─── Confirm delete popup ─────────────────────────────────────────────────
 
     fn handle_confirm_delete_key(&mut self, key: KeyEvent) {
+        // Workspace deletes return to the switcher they were started from
+        let back_to = if self.state.confirm_delete.workspace {
+            ActivePopup::WorkspaceSwitcher
+        } else {
+            ActivePopup::None
+        };
         match key.code {
             KeyCode::Char('y') | KeyCode::Enter => {
-                self.execute_delete();
-                self.state.active_popup = ActivePopup::None;
-                self.state.confirm_delete = ConfirmDeleteState::default();
+                self.state.active_popup = back_to;
+                let confirm = std::mem::take(&mut self.state.confirm_delete);
+                if confirm.workspace {
+                    self.delete_workspace(&confirm.target_id);
+                } else {
+                    self.state.confirm_delete = confirm;
+                    self.execute_delete();
+                    self.state.confirm_delete = ConfirmDeleteState::default();
+                }
             }
             KeyCode::Char('n') | KeyCode::Esc => {
-                self.state.active_popup = ActivePopup::None;
+                self.state.active_popup = back_to;
                 self.state.confirm_delete = ConfirmDeleteState::default();
             }
             _ => {}
@@ -969,6 +1654,7 @@ impl App {
             let col_name = self.state.workspace.collections[pos].name.clone();
             let _ = col_storage::delete_collection(&ws_name, &col_name);
             self.state.workspace.collections.remove(pos);
+            self.state.sidebar.invalidate_nodes();
             // Clamp cursor
             let len = self.state.workspace.collections.len();
             self.state.sidebar.cursor = self.state.sidebar.cursor.min(len.saturating_sub(1));
@@ -982,1154 +1668,5744 @@ impl App {
                 break;
             }
         }
+        self.state.sidebar.invalidate_nodes();
     }
 
-    // ─── Normal key handling ──────────────────────────────────────────────────
+    // ─── Check picker popup ("copy as test") ──────────────────────────────────
 
-    fn handle_normal_key(&mut self, key: KeyEvent) {
-        // Alt+1..Alt+9: jump to open tab by index
-        if key.modifiers.contains(KeyModifiers::ALT) {
-            match key.code {
-                KeyCode::Char(c @ '1'..='9') => {
-                    let idx = (c as usize) - ('1' as usize);
-                    if idx < self.state.workspace.open_tabs.len() {
-                        self.sync_active_tab_to_collection();
-                        self.state.workspace.active_tab_idx = idx;
-                    }
-                    return;
-                }
-                KeyCode::Char('w') => {
-                    self.sync_active_tab_to_collection();
-                    self.close_active_tab();
-                    return;
-                }
-                _ => {}
-            }
+    // ─── Capture to env popup (e) ─────────────────────────────────────────────
+
+    /// Open the capture popup. The last path and name are kept, since the
+    /// next capture is often the same field of a fresh response.
+    fn open_capture(&mut self) {
+        if self.state.active_tab().is_none_or(|t| t.response.is_none()) {
+            return;
         }
+        self.state.capture.error = None;
+        self.state.active_popup = ActivePopup::Capture;
+    }
 
+    fn handle_capture_key(&mut self, key: KeyEvent) {
+        let capture = &mut self.state.capture;
+        let (text, cursor) = if capture.on_name {
+            (&mut capture.name, &mut capture.name_cursor)
+        } else {
+            (&mut capture.path, &mut capture.path_cursor)
+        };
         match key.code {
-            KeyCode::Char('q') => self.state.should_quit = true,
-            KeyCode::Tab => self.state.focus = self.state.focus.next(),
-            KeyCode::BackTab => self.state.focus = self.state.focus.prev(),
-            KeyCode::Char('i') | KeyCode::Enter => {
-                if matches!(self.state.focus, Focus::UrlBar | Focus::Editor) {
-                    self.state.mode = Mode::Insert;
-                    if self.state.focus == Focus::Editor {
-                        let active_tab = self
-                            .state
-                            .active_tab()
-                            .map(|t| t.active_tab.clone());
-                        if active_tab == Some(ActiveTab::Headers) {
-                            let (row, col, len) = if let Some(tab) = self.state.active_tab() {
-                                let row = tab.request.headers_row;
-                                let col = tab.request.headers_col;
-                                let len = tab
-                                    .request
-                                    .headers
-                                    .get(row)
-                                    .map(|p| if col == 0 { p.key.len() } else { p.value.len() })
-                                    .unwrap_or(0);
-                                (row, col, len)
-                            } else {
-                                (0, 0, 0)
-                            };
-                            let _ = (row, col);
-                            if let Some(tab) = self.state.active_tab_mut() {
-                                tab.request.headers_cursor = len;
-                            }
-                        } else {
-                            if let Some(tab) = self.state.active_tab_mut() {
-                                if tab.request.body
-                                    == crate::state::request_state::RequestBody::None
-                                {
-                                    tab.request.body =
-                                        crate::state::request_state::RequestBody::Json(
-                                            String::new(),
-                                        );
-                                }
-                            }
-                        }
-                    }
-                } else if matches!(self.state.focus, Focus::Sidebar) {
-                    self.handle_sidebar_enter();
-                } else if matches!(self.state.focus, Focus::RequestTabs) {
-                    self.state.focus = Focus::UrlBar;
-                }
+            KeyCode::Esc => self.state.active_popup = ActivePopup::None,
+            KeyCode::Enter => self.capture_to_env(),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                capture.on_name = !capture.on_name;
             }
-            KeyCode::Char('[') => {
-                if self.state.focus == Focus::UrlBar {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.method = tab.request.method.prev();
-                    }
-                } else {
-                    self.sync_active_tab_to_collection();
-                    self.prev_open_tab();
-                }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                capture.secret = !capture.secret;
             }
-            KeyCode::Char(']') => {
-                if self.state.focus == Focus::UrlBar {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.method = tab.request.method.next();
-                    }
-                } else {
-                    self.sync_active_tab_to_collection();
-                    self.next_open_tab();
+            KeyCode::Char(c) => {
+                text.insert(*cursor, c);
+                *cursor += c.len_utf8();
+                capture.error = None;
+            }
+            KeyCode::Backspace if *cursor > 0 => {
+                let prev = Self::prev_char_boundary_of(text, *cursor);
+                text.drain(prev..*cursor);
+                *cursor = prev;
+                capture.error = None;
+            }
+            KeyCode::Left => *cursor = Self::prev_char_boundary_of(text, *cursor),
+            KeyCode::Right => *cursor = Self::next_char_boundary_of(text, *cursor),
+            KeyCode::Home => *cursor = 0,
+            KeyCode::End => *cursor = text.len(),
+            _ => {}
+        }
+    }
+
+    /// Write the value at the popup's path into the active environment and
+    /// close. Errors stay in the popup so the path can be fixed.
+    fn capture_to_env(&mut self) {
+        let capture = &self.state.capture;
+        let name = capture.name.trim().to_string();
+        let value = match self.state.active_tab().and_then(|t| t.response.as_ref()) {
+            None => Err("No response to capture from".to_string()),
+            Some(resp) => capture_value(&resp.body, &capture.path),
+        };
+        let env_idx = self.state.active_environment_idx();
+        let ws = &mut self.state.workspace;
+        let env = env_idx.and_then(|i| ws.environments.get_mut(i));
+        let error = match (value, env) {
+            (Err(e), _) => e,
+            _ if name.is_empty() => "Enter a variable name".to_string(),
+            (Ok(_), None) => "No active environment — pick one with Ctrl+E".to_string(),
+            (Ok(value), Some(env)) => {
+                let var = env.set_var(&name, &value);
+                if self.state.capture.secret {
+                    var.var_type = VarType::Secret;
                 }
+                let _ = env_storage::save_ws(&ws.name, env);
+                let request = self.state.active_tab().map(|t| t.request.name.clone()).unwrap_or_default();
+                self.state.var_origins.insert(name, VarOrigin::now(&request, "capture"));
+                self.state.active_popup = ActivePopup::None;
+                return;
             }
-            KeyCode::Esc => self.cancel_request(),
-            KeyCode::Char('j') | KeyCode::Down => {
-                if self.state.focus == Focus::Sidebar {
-                    self.sidebar_move_cursor(1);
-                } else if self.state.focus == Focus::Editor {
-                    let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                    if active_tab == Some(ActiveTab::Headers) {
-                        if let Some(tab) = self.state.active_tab_mut() {
-                            let len = tab.request.headers.len();
-                            if len > 0 {
-                                tab.request.headers_row =
-                                    (tab.request.headers_row + 1).min(len - 1);
-                            }
-                        }
-                    } else if let Some(tab) = self.state.active_tab_mut() {
-                        if let Some(resp) = &mut tab.response {
-                            resp.scroll_offset = resp.scroll_offset.saturating_add(1);
-                        }
-                    }
-                } else if let Some(tab) = self.state.active_tab_mut() {
-                    if let Some(resp) = &mut tab.response {
-                        resp.scroll_offset = resp.scroll_offset.saturating_add(1);
-                    }
-                }
+        };
+        self.state.capture.error = Some(error);
+    }
+
+    fn open_check_picker(&mut self) {
+        let Some(resp) = self.state.active_tab().and_then(|t| t.response.as_ref()) else {
+            return;
+        };
+        let candidates = check_candidates(&resp.body).unwrap_or_default();
+        self.state.check_picker = CheckPickerState {
+            status: resp.status,
+            checked: vec![false; candidates.len()],
+            candidates,
+            selected: 0,
+        };
+        self.state.active_popup = ActivePopup::CheckPicker;
+    }
+
+    fn handle_check_picker_key(&mut self, key: KeyEvent) {
+        let picker = &mut self.state.check_picker;
+        match key.code {
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
+                self.state.check_picker = CheckPickerState::default();
+            }
+            KeyCode::Char('j') | KeyCode::Down if picker.selected + 1 < picker.candidates.len() => {
+                picker.selected += 1;
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                if self.state.focus == Focus::Sidebar {
-                    self.sidebar_move_cursor_up();
-                } else if self.state.focus == Focus::Editor {
-                    let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                    if active_tab == Some(ActiveTab::Headers) {
-                        if let Some(tab) = self.state.active_tab_mut() {
-                            tab.request.headers_row =
-                                tab.request.headers_row.saturating_sub(1);
-                        }
-                    } else if let Some(tab) = self.state.active_tab_mut() {
-                        if let Some(resp) = &mut tab.response {
-                            resp.scroll_offset = resp.scroll_offset.saturating_sub(1);
-                        }
-                    }
-                } else if let Some(tab) = self.state.active_tab_mut() {
-                    if let Some(resp) = &mut tab.response {
-                        resp.scroll_offset = resp.scroll_offset.saturating_sub(1);
-                    }
-                }
+                picker.selected = picker.selected.saturating_sub(1);
             }
-            KeyCode::Left | KeyCode::Char('h')
-                if self.state.focus == Focus::TabBar =>
-            {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    tab.active_tab = tab.active_tab.prev();
+            KeyCode::Char(' ') => {
+                if let Some(checked) = picker.checked.get_mut(picker.selected) {
+                    *checked = !*checked;
                 }
             }
-            KeyCode::Right | KeyCode::Char('l')
-                if self.state.focus == Focus::TabBar =>
-            {
+            KeyCode::Char('a') => {
+                let all = picker.checked.iter().all(|c| *c);
+                picker.checked.iter_mut().for_each(|c| *c = !all);
+            }
+            KeyCode::Enter => {
+                let picked: Vec<&CheckCandidate> = picker
+                    .candidates
+                    .iter()
+                    .zip(&picker.checked)
+                    .filter(|(_, checked)| **checked)
+                    .map(|(c, _)| c)
+                    .collect();
+                let generated = generate_checks(picker.status, &picked);
+                self.state.active_popup = ActivePopup::None;
+                self.state.check_picker = CheckPickerState::default();
                 if let Some(tab) = self.state.active_tab_mut() {
-                    tab.active_tab = tab.active_tab.next();
+                    merge_checks(&mut tab.request.checks, generated);
+                    tab.request.checks_row = 0;
+                    tab.request.checks_col = 1;
+                    tab.active_tab = ActiveTab::Checks;
                 }
+                self.state.focus = Focus::Editor;
+                self.sync_active_tab_to_collection();
             }
-            KeyCode::Char('h') if self.state.focus == Focus::Sidebar => {
-                self.sidebar_collapse();
-            }
-            KeyCode::Char('l') if self.state.focus == Focus::Sidebar => {
-                self.sidebar_expand();
+            _ => {}
+        }
+    }
+
+    // ─── Request finder popup (Ctrl+P) ────────────────────────────────────────
+
+    fn open_request_finder(&mut self) {
+        self.state.active_popup = ActivePopup::RequestFinder;
+        self.state.request_finder = RequestFinderState::default();
+    }
+
+    fn handle_request_finder_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        // Bare j/k move only while the query is empty; otherwise they are typed
+        let query_empty = self.state.request_finder.query.is_empty();
+        match key.code {
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
             }
-            KeyCode::Left
-                if self.state.focus == Focus::Editor =>
-            {
-                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                if active_tab == Some(ActiveTab::Headers) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.headers_col = 0;
-                        let row = tab.request.headers_row;
-                        let len =
-                            tab.request.headers.get(row).map(|p| p.key.len()).unwrap_or(0);
-                        tab.request.headers_cursor = len;
-                    }
+            KeyCode::Enter => {
+                let selected = self.state.request_finder.selected;
+                if let Some(entry) = finder_matches(&self.state).into_iter().nth(selected) {
+                    self.sync_active_tab_to_collection();
+                    self.open_collection_request(&entry.id, &entry.name, &entry.method);
+                    self.state.focus = Focus::UrlBar;
                 }
+                self.state.active_popup = ActivePopup::None;
             }
-            KeyCode::Right
-                if self.state.focus == Focus::Editor =>
-            {
-                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                if active_tab == Some(ActiveTab::Headers) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.headers_col = 1;
-                        let row = tab.request.headers_row;
-                        let len =
-                            tab.request.headers.get(row).map(|p| p.value.len()).unwrap_or(0);
-                        tab.request.headers_cursor = len;
-                    }
+            KeyCode::Down => self.request_finder_move(1),
+            KeyCode::Up => self.request_finder_move(-1),
+            KeyCode::Char('j') if ctrl || query_empty => self.request_finder_move(1),
+            KeyCode::Char('k') if ctrl || query_empty => self.request_finder_move(-1),
+            KeyCode::Backspace => {
+                let finder = &mut self.state.request_finder;
+                if finder.cursor > 0 {
+                    let prev = Self::prev_char_boundary_of(&finder.query, finder.cursor);
+                    finder.query.drain(prev..finder.cursor);
+                    finder.cursor = prev;
+                    finder.selected = 0;
                 }
             }
-            KeyCode::Char('a')
-                if self.state.focus == Focus::Editor =>
-            {
-                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                if active_tab == Some(ActiveTab::Headers) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.headers.push(KeyValuePair::default());
-                        let new_row = tab.request.headers.len() - 1;
-                        tab.request.headers_row = new_row;
-                        tab.request.headers_col = 0;
-                        tab.request.headers_cursor = 0;
-                        self.state.mode = Mode::Insert;
+            KeyCode::Char(c) if !ctrl => {
+                let finder = &mut self.state.request_finder;
+                finder.query.insert(finder.cursor, c);
+                finder.cursor += c.len_utf8();
+                finder.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn request_finder_move(&mut self, delta: isize) {
+        let count = finder_matches(&self.state).len();
+        let finder = &mut self.state.request_finder;
+        if count == 0 {
+            finder.selected = 0;
+        } else {
+            finder.selected = finder.selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    // ─── Command palette (: / Ctrl+Shift+P) ───────────────────────────────────
+
+    fn open_command_palette(&mut self) {
+        self.state.command_palette = CommandPaletteState::default();
+        self.state.active_popup = ActivePopup::CommandPalette;
+    }
+
+    fn handle_command_palette_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let query_empty = self.state.command_palette.query.is_empty();
+        match key.code {
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
+            }
+            KeyCode::Enter => {
+                let palette = &self.state.command_palette;
+                if let Some(name) = theme_command(&palette.query) {
+                    let name = name.to_string();
+                    self.state.active_popup = ActivePopup::None;
+                    match ThemeKind::from_name(&name) {
+                        Some(kind) => self.switch_theme(kind),
+                        None => self.show_toast(format!("Unknown theme `{name}`")),
                     }
+                    return;
+                }
+                let action = palette_matches(&palette.query).into_iter().nth(palette.selected);
+                self.state.active_popup = ActivePopup::None;
+                if let Some((action, _)) = action {
+                    self.run_action(action);
                 }
             }
-            KeyCode::Char('x') | KeyCode::Char('d')
-                if self.state.focus == Focus::Editor =>
-            {
-                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                if active_tab == Some(ActiveTab::Headers) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let len = tab.request.headers.len();
-                        if len > 0 {
-                            tab.request.headers.remove(tab.request.headers_row);
-                            let new_len = tab.request.headers.len();
-                            tab.request.headers_row = if new_len > 0 {
-                                tab.request.headers_row.min(new_len - 1)
-                            } else {
-                                0
-                            };
-                        }
-                    }
+            KeyCode::Down => self.command_palette_move(1),
+            KeyCode::Up => self.command_palette_move(-1),
+            KeyCode::Char('j') if ctrl || query_empty => self.command_palette_move(1),
+            KeyCode::Char('k') if ctrl || query_empty => self.command_palette_move(-1),
+            KeyCode::Backspace => {
+                let palette = &mut self.state.command_palette;
+                if palette.cursor > 0 {
+                    let prev = Self::prev_char_boundary_of(&palette.query, palette.cursor);
+                    palette.query.drain(prev..palette.cursor);
+                    palette.cursor = prev;
+                    palette.selected = 0;
                 }
             }
-            KeyCode::Char(' ')
-                if self.state.focus == Focus::Editor =>
-            {
-                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-                if active_tab == Some(ActiveTab::Headers) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let row = tab.request.headers_row;
-                        if let Some(pair) = tab.request.headers.get_mut(row) {
-                            pair.enabled = !pair.enabled;
-                        }
-                    }
-                }
-            }
-            // Sidebar-specific keys
-            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar => {
-                self.state.naming = NamingState {
-                    target: NamingTarget::NewCollection,
-                    ..NamingState::default()
-                };
-                self.state.active_popup = ActivePopup::CollectionNaming;
-            }
-            KeyCode::Char('n') if self.state.focus == Focus::Sidebar => {
-                // New request at current cursor context
-                let target = self.sidebar_new_request_target();
-                self.state.naming = NamingState {
-                    target,
-                    method: "GET".to_string(),
-                    ..NamingState::default()
-                };
-                self.state.active_popup = ActivePopup::CollectionNaming;
-            }
-            KeyCode::Char('f') if self.state.focus == Focus::Sidebar => {
-                // New folder at current cursor context
-                let target = self.sidebar_new_folder_target();
-                self.state.naming = NamingState {
-                    target,
-                    ..NamingState::default()
-                };
-                self.state.active_popup = ActivePopup::CollectionNaming;
-            }
-            KeyCode::Char('r') if self.state.focus == Focus::Sidebar => {
-                self.sidebar_rename();
-            }
-            KeyCode::Char('d') if self.state.focus == Focus::Sidebar => {
-                self.sidebar_delete();
-            }
-            KeyCode::Char('D') if self.state.focus == Focus::Sidebar => {
-                self.sidebar_duplicate();
-            }
-            KeyCode::Char('/') if self.state.focus == Focus::Sidebar => {
-                self.state.sidebar.search_mode = true;
-                self.state.sidebar.search_query.clear();
-            }
-            // RequestTabs-specific keys
-            KeyCode::Left if self.state.focus == Focus::RequestTabs => {
-                self.sync_active_tab_to_collection();
-                self.prev_open_tab();
-            }
-            KeyCode::Right if self.state.focus == Focus::RequestTabs => {
-                self.sync_active_tab_to_collection();
-                self.next_open_tab();
-            }
-            KeyCode::Char('x') if self.state.focus == Focus::RequestTabs => {
-                self.sync_active_tab_to_collection();
-                self.close_active_tab();
+            KeyCode::Char(c) if !ctrl => {
+                let palette = &mut self.state.command_palette;
+                palette.query.insert(palette.cursor, c);
+                palette.cursor += c.len_utf8();
+                palette.selected = 0;
             }
-            KeyCode::Char('1') => self.state.focus = Focus::Sidebar,
-            KeyCode::Char('2') => self.state.focus = Focus::UrlBar,
-            KeyCode::Char('3') => self.state.focus = Focus::Editor,
-            KeyCode::Char('4') => self.state.focus = Focus::ResponseViewer,
             _ => {}
         }
     }
 
-    // ─── Sidebar helpers ──────────────────────────────────────────────────────
-
-    fn sidebar_move_cursor(&mut self, delta: usize) {
-        let nodes = flatten_tree(&self.state);
-        let max = nodes.len().saturating_sub(1);
-        let new_cursor = (self.state.sidebar.cursor + delta).min(max);
-        self.state.sidebar.cursor = new_cursor;
-        // Scroll down if needed
-        // (We'll implement simple scroll clamping — caller must know visible height)
-        // For now: no-op; layout scrolls based on cursor vs scroll_offset
-        self.clamp_sidebar_scroll();
+    fn command_palette_move(&mut self, delta: isize) {
+        let count = palette_matches(&self.state.command_palette.query).len();
+        let palette = &mut self.state.command_palette;
+        if count == 0 {
+            palette.selected = 0;
+        } else {
+            palette.selected = palette.selected.saturating_add_signed(delta).min(count - 1);
+        }
     }
 
-    fn sidebar_move_cursor_up(&mut self) {
-        self.state.sidebar.cursor = self.state.sidebar.cursor.saturating_sub(1);
-        self.clamp_sidebar_scroll();
+    /// Run a palette action through the same method as its keybinding.
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::SendRequest => self.send_request(),
+            Action::DryRun => self.dry_run_request(),
+            Action::SaveTab => self.save_active_tab(),
+            Action::CloseTab => self.request_close_active_tab(),
+            Action::RenameTab => self.rename_active_tab(),
+            Action::GoToRequest => self.open_request_finder(),
+            Action::NewCollection => self.prompt_new_collection(),
+            Action::NewRequest => self.prompt_new_request(),
+            Action::NewFolder => self.prompt_new_folder(),
+            Action::Rename => self.sidebar_rename(),
+            Action::Delete => self.sidebar_delete(),
+            Action::Duplicate => self.sidebar_duplicate(),
+            Action::RunInPlace => self.sidebar_run_in_place(false),
+            Action::RunCollection => self.open_runner(),
+            Action::DefaultHeaders => self.open_default_headers(),
+            Action::CollapseAll => self.sidebar_collapse_all(),
+            Action::ExpandAll => self.sidebar_expand_all(),
+            Action::CollectionVariables => self.open_collection_variables(),
+            Action::CollectionEnvironment => self.cycle_collection_environment(),
+            Action::ClearRunnerResults => self.clear_runner_results(),
+            Action::SwitchEnvironment => self.open_env_switcher(false),
+            Action::SwitchWorkspace => self.open_workspace_switcher(),
+            Action::Variables => self.open_variables(false),
+            Action::CookieJar => self.open_cookie_jar(),
+            Action::SendQueue => self.open_send_queue(),
+            Action::AuditLog => self.open_audit_log(),
+            Action::ThemePicker => self.open_theme_picker(),
+            Action::Settings => self.open_settings(),
+            Action::ToggleSidebar => self.toggle_sidebar(),
+            Action::ToggleZen => self.toggle_zen(),
+            Action::Help => self.open_help(),
+            Action::Quit => self.state.should_quit = true,
+        }
     }
 
-    fn clamp_sidebar_scroll(&mut self) {
-        // Keep cursor visible — conservative 20-line window
-        let visible = 20usize;
-        let cursor = self.state.sidebar.cursor;
-        let scroll = self.state.sidebar.scroll_offset;
-        if cursor < scroll {
-            self.state.sidebar.scroll_offset = cursor;
-        } else if cursor >= scroll + visible {
-            self.state.sidebar.scroll_offset = cursor.saturating_sub(visible - 1);
+    // ─── Request dependency popups ────────────────────────────────────────────
+
+    fn open_dependency_picker(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        if !matches!(node.kind, crate::ui::sidebar::NodeKind::Request { .. }) {
+            return;
+        }
+        self.state.dependency_picker = DependencyPickerState {
+            request_id: node.id.clone(),
+            request_name: node.label.clone(),
+            ..DependencyPickerState::default()
+        };
+        // Preselect the current dependency
+        let current = find_col_request_by_id(&self.state.workspace.collections, &node.id)
+            .and_then(|r| r.depends_on.clone());
+        if let Some(dep) = current
+            && let Some(pos) = dependency_candidates(&self.state).iter().position(|e| e.id == dep)
+        {
+            self.state.dependency_picker.selected = pos + 1;
         }
+        self.state.active_popup = ActivePopup::DependencyPicker;
     }
 
-    fn sidebar_collapse(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
-            match &node.kind {
-                crate::ui::sidebar::NodeKind::Collection { .. }
-                | crate::ui::sidebar::NodeKind::Folder { .. } => {
-                    self.state.sidebar.collapsed_ids.insert(node.id.clone());
+    fn handle_dependency_picker_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let query_empty = self.state.dependency_picker.query.is_empty();
+        match key.code {
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
+            }
+            KeyCode::Enter => self.apply_dependency_choice(),
+            KeyCode::Down => self.dependency_picker_move(1),
+            KeyCode::Up => self.dependency_picker_move(-1),
+            KeyCode::Char('j') if ctrl || query_empty => self.dependency_picker_move(1),
+            KeyCode::Char('k') if ctrl || query_empty => self.dependency_picker_move(-1),
+            KeyCode::Backspace => {
+                let picker = &mut self.state.dependency_picker;
+                if picker.cursor > 0 {
+                    let prev = Self::prev_char_boundary_of(&picker.query, picker.cursor);
+                    picker.query.drain(prev..picker.cursor);
+                    picker.cursor = prev;
+                    picker.selected = 0;
+                    picker.error = None;
                 }
-                _ => {}
             }
+            KeyCode::Char(c) if !ctrl => {
+                let picker = &mut self.state.dependency_picker;
+                picker.query.insert(picker.cursor, c);
+                picker.cursor += c.len_utf8();
+                // Jump to the best match rather than "(none)"
+                picker.selected = 1;
+                picker.error = None;
+            }
+            _ => {}
         }
     }
 
-    fn sidebar_expand(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
-            self.state.sidebar.collapsed_ids.remove(&node.id);
-        }
+    fn dependency_picker_move(&mut self, delta: isize) {
+        // Row 0 is "(none)"
+        let count = dependency_candidates(&self.state).len() + 1;
+        let picker = &mut self.state.dependency_picker;
+        picker.selected = picker.selected.saturating_add_signed(delta).min(count - 1);
+        picker.error = None;
     }
 
-    fn handle_sidebar_enter(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
-            match node.kind {
-                crate::ui::sidebar::NodeKind::Collection { collapsed }
-                | crate::ui::sidebar::NodeKind::Folder { collapsed } => {
-                    if collapsed {
-                        self.state.sidebar.collapsed_ids.remove(&node.id);
-                    } else {
-                        self.state.sidebar.collapsed_ids.insert(node.id.clone());
-                    }
-                }
-                crate::ui::sidebar::NodeKind::Request { method } => {
-                    // Dedup: if already open, just focus it
-                    if let Some(idx) = self.state.workspace.open_tabs.iter()
-                        .position(|t| t.collection_id.as_deref() == Some(&node.id))
-                    {
-                        self.state.workspace.active_tab_idx = idx;
-                        return;
-                    }
-                    // Load persisted state from collection
-                    let saved = find_col_request_by_id(&self.state.workspace.collections, &node.id).cloned();
-                    let mut tab = RequestTab::default();
-                    tab.request.name = node.label.clone();
-                    tab.request.method = crate::state::request_state::HttpMethod::from_str_or_get(&method);
-                    tab.collection_id = Some(node.id.clone());
-                    if let Some(saved) = saved {
-                        tab.request.url = saved.url.clone();
-                        if !saved.body_raw.is_empty() {
-                            tab.request.body = crate::state::request_state::RequestBody::Json(saved.body_raw.clone());
-                        }
-                    }
-                    self.state.workspace.open_tabs.push(tab);
-                    self.state.workspace.active_tab_idx = self.state.workspace.open_tabs.len() - 1;
-                }
-            }
+    /// Set (or clear) the picked request's dependency, refusing cycles.
+    fn apply_dependency_choice(&mut self) {
+        let picker = &self.state.dependency_picker;
+        let id = picker.request_id.clone();
+        let dep = match picker.selected {
+            0 => None,
+            n => match dependency_candidates(&self.state).into_iter().nth(n - 1) {
+                Some(entry) => Some(entry),
+                None => return,
+            },
+        };
+
+        if let Some(entry) = &dep
+            && creates_cycle(&dependency_map(&self.state.workspace.collections), &id, &entry.id)
+        {
+            self.state.dependency_picker.error = Some(format!(
+                "{} already depends on {} — that would be a cycle",
+                entry.name, self.state.dependency_picker.request_name
+            ));
+            return;
         }
-    }
 
-    fn sidebar_new_request_target(&self) -> NamingTarget {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
-            let col_id = self.find_collection_id_for_node(&node.id);
-            let folder_id = match &node.kind {
-                crate::ui::sidebar::NodeKind::Folder { .. } => Some(node.id.clone()),
-                _ => None,
-            };
-            if let Some(cid) = col_id {
-                return NamingTarget::NewRequest {
-                    collection_id: cid,
-                    folder_id,
-                };
+        let dep_id = dep.map(|e| e.id);
+        let ws_name = self.state.workspace.name.clone();
+        for col in &mut self.state.workspace.collections {
+            if set_request_dependency(&mut col.items, &id, dep_id.clone()) {
+                let _ = col_storage::save_collection_meta(&ws_name, col);
+                break;
             }
         }
-        NamingTarget::NewCollection
+        self.state.sidebar.invalidate_nodes();
+        self.state.active_popup = ActivePopup::None;
     }
 
-    fn sidebar_new_folder_target(&self) -> NamingTarget {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
-            let col_id = self.find_collection_id_for_node(&node.id);
-            if let Some(cid) = col_id {
-                return NamingTarget::NewFolder { collection_id: cid };
+    fn handle_dependency_prompt_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.state.active_popup = ActivePopup::None;
+                let order = std::mem::take(&mut self.state.dependency_prompt.order);
+                let Some(target) = self.state.active_tab().and_then(|t| t.collection_id.clone())
+                else {
+                    return;
+                };
+                self.dependency_queue = order.into_iter().chain([target]).collect();
+                self.send_next_queued();
             }
+            KeyCode::Char('n') => {
+                self.state.active_popup = ActivePopup::None;
+                self.dispatch_request();
+            }
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
+            }
+            _ => {}
         }
-        NamingTarget::NewCollection
     }
 
-    fn find_collection_id_for_node(&self, node_id: &str) -> Option<String> {
-        for col in &self.state.workspace.collections {
-            if col.id == node_id {
-                return Some(col.id.clone());
+    fn handle_confirm_send_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let confirm = std::mem::take(&mut self.state.confirm_send);
+                self.state.active_popup = confirm.back_to;
+                if confirm.skip_minute {
+                    let until = Instant::now() + Duration::from_secs(60);
+                    self.protected_confirmed_until.insert(confirm.environment_id, until);
+                }
+                self.send_pending(confirm.pending);
             }
-            if item_exists_in_list(&col.items, node_id) {
-                return Some(col.id.clone());
+            KeyCode::Char('m') => {
+                self.state.confirm_send.skip_minute = !self.state.confirm_send.skip_minute;
             }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                let confirm = std::mem::take(&mut self.state.confirm_send);
+                // A declined runner pass shows its rows as aborted
+                if matches!(confirm.pending, PendingSend::RunInPlace(_))
+                    && confirm.back_to == ActivePopup::Runner
+                {
+                    self.abort_sidebar_run();
+                }
+                self.state.active_popup = confirm.back_to;
+                // A declined dependency chain goes no further
+                self.dependency_queue.clear();
+            }
+            _ => {}
         }
-        None
     }
 
-    fn sidebar_rename(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
-            self.state.naming = NamingState {
-                target: NamingTarget::Rename {
-                    id: node.id.clone(),
-                    old_name: node.label.clone(),
-                },
-                input: node.label.clone(),
-                cursor: node.label.len(),
-                ..NamingState::default()
-            };
-            self.state.active_popup = ActivePopup::CollectionNaming;
+    // ─── Confirm close popup ──────────────────────────────────────────────────
+
+    fn handle_confirm_close_key(&mut self, key: KeyEvent) {
+        let save = match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => true,
+            KeyCode::Char('n') => false,
+            KeyCode::Esc => {
+                self.state.active_popup = ActivePopup::None;
+                self.state.confirm_close = ConfirmCloseState::default();
+                return;
+            }
+            _ => return,
+        };
+        self.state.active_popup = ActivePopup::None;
+        let confirm = std::mem::take(&mut self.state.confirm_close);
+        // A response may have switched tabs (dependency chains) meanwhile
+        let Some(idx) = self.tab_index(&confirm.request_id) else {
+            return;
+        };
+        self.state.workspace.active_tab_idx = idx;
+        if save {
+            self.sync_active_tab_to_collection();
         }
+        self.close_active_tab();
     }
 
-    fn sidebar_delete(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
-            let msg = format!("Delete \"{}\"?", node.label);
-            self.state.confirm_delete = ConfirmDeleteState {
-                message: msg,
-                target_id: node.id.clone(),
-            };
-            self.state.active_popup = ActivePopup::ConfirmDelete;
+    /// Open the next request of the dependency queue and send it.
+    fn send_next_queued(&mut self) {
+        let Some(id) = self.dependency_queue.pop_front() else {
+            return;
+        };
+        let Some(req) = find_col_request_by_id(&self.state.workspace.collections, &id).cloned()
+        else {
+            self.dependency_queue.clear();
+            return;
+        };
+        self.sync_active_tab_to_collection();
+        self.open_collection_request(&req.id, &req.name, &req.method);
+        if self.state.active_tab().is_some_and(|t| t.request.url.is_empty()) {
+            // Nothing to send; a response will never arrive to continue the chain
+            self.dependency_queue.clear();
+            return;
         }
+        self.dispatch_request();
     }
 
-    fn sidebar_duplicate(&mut self) {
-        let nodes = flatten_tree(&self.state);
-        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
-            if let crate::ui::sidebar::NodeKind::Request { method } = &node.kind {
-                let new_req = CollectionRequest {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    name: format!("{} (copy)", node.label),
-                    method: method.clone(),
-                    url: String::new(),
-                    body_raw: String::new(),
-                };
-                let ws_name = self.state.workspace.name.clone();
-                // Insert after cursor in the containing collection/folder
-                for col in &mut self.state.workspace.collections {
-                    if insert_after_in_list(
-                        &mut col.items,
-                        &node.id,
-                        CollectionItem::Request(new_req.clone()),
-                    ) {
-                        let _ = col_storage::save_collection_meta(&ws_name, col);
-                        break;
-                    }
-                    // Also check if the original is directly in the collection
-                    if col.items.iter().any(|item| match item {
-                        CollectionItem::Request(r) => r.id == node.id,
-                        _ => false,
-                    }) {
-                        col.items.push(CollectionItem::Request(new_req.clone()));
-                        let _ = col_storage::save_collection_meta(&ws_name, col);
-                        break;
-                    }
-                }
+    /// Highlight the response of open tab `idx`. Small bodies are done on the
+    /// spot; larger ones show as plain text until a blocking task sends
+    /// `Event::Highlighted`, so a big payload doesn't stall the UI.
+    fn highlight_tab_response(&mut self, idx: usize) {
+        let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) else {
+            return;
+        };
+        let tab_id = tab.request.id.clone();
+        let Some(resp) = tab.response.as_mut() else {
+            return;
+        };
+        let text = match &resp.body {
+            ResponseBody::Text(text) if text.len() > HIGHLIGHT_SYNC_BYTES => text.clone(),
+            _ => {
+                resp.highlight_job = 0;
+                highlight_response(resp);
+                return;
             }
-        }
+        };
+        self.highlight_jobs += 1;
+        let job = self.highlight_jobs;
+        resp.highlight_job = job;
+        resp.highlighted_body = None;
+        let lang = response_lang(resp).to_string();
+        let tx = self.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let text = highlight_text(&text, &lang);
+            let _ = tx.send(Event::Highlighted { tab_id, job, text });
+        });
     }
 
-    // ─── Open tab management ──────────────────────────────────────────────────
-
-    fn next_open_tab(&mut self) {
-        let len = self.state.workspace.open_tabs.len();
-        if len == 0 {
+    /// Toggle the sorted-keys view of a JSON response body. The sorted text is
+    /// highlighted once and cached on the response.
+    fn toggle_sorted_view(&mut self) {
+        let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) else {
             return;
+        };
+        if resp.sorted_body.is_none() {
+            let ResponseBody::Text(text) = &resp.body else {
+                return;
+            };
+            let Some(sorted) = sorted_pretty(text) else {
+                return;
+            };
+            resp.sorted_body = Some(highlight_text(&sorted, detect_lang(None, &sorted)));
         }
-        self.state.workspace.active_tab_idx =
-            (self.state.workspace.active_tab_idx + 1) % len;
+        resp.sorted_view = !resp.sorted_view;
     }
 
-    fn prev_open_tab(&mut self) {
-        let len = self.state.workspace.open_tabs.len();
-        if len == 0 {
+    /// Step the response body's highlight language through `LANGS`, then
+    /// back to the detected one (`Ctrl+L` in the viewer).
+    fn cycle_response_lang(&mut self) {
+        let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) else {
+            return;
+        };
+        if !matches!(resp.body, ResponseBody::Text(_)) {
             return;
         }
-        self.state.workspace.active_tab_idx =
-            (self.state.workspace.active_tab_idx + len - 1) % len;
+        let next = match &resp.force_lang {
+            None => Some(0),
+            Some(lang) => LANGS.iter().position(|l| l == lang).map(|i| i + 1).filter(|&i| i < LANGS.len()),
+        };
+        resp.force_lang = next.map(|i| LANGS[i].to_string());
+        self.highlight_tab_response(self.state.workspace.active_tab_idx);
     }
 
-    fn close_active_tab(&mut self) {
-        let idx = self.state.workspace.active_tab_idx;
-        let len = self.state.workspace.open_tabs.len();
-        if len == 0 {
+    /// Copy the selected response header as `Name: value` (Headers tab).
+    fn copy_response_header(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        if tab.response_tab != ResponseTab::Headers {
             return;
         }
-        self.state.workspace.open_tabs.remove(idx);
-        if self.state.workspace.open_tabs.is_empty() {
-            self.state.workspace.open_tabs.push(RequestTab::default());
-            self.state.workspace.active_tab_idx = 0;
-        } else {
-            self.state.workspace.active_tab_idx =
-                self.state.workspace.active_tab_idx.min(
-                    self.state.workspace.open_tabs.len() - 1,
-                );
+        let Some((name, value)) = tab.response.as_ref().and_then(|r| r.headers.get(r.headers_cursor)) else {
+            return;
+        };
+        if let Ok(mut clipboard) = arboard::Clipboard::new() {
+            let _ = clipboard.set_text(format!("{name}: {value}"));
         }
     }
 
-    // ─── Collection sync ──────────────────────────────────────────────────────
+    // ─── JSON tree view (t in the response viewer) ───────────────────────────
 
-    fn sync_active_tab_to_collection(&mut self) {
-        let idx = self.state.workspace.active_tab_idx;
-        if let Some(tab) = self.state.workspace.open_tabs.get(idx) {
-            let Some(req_id) = tab.collection_id.clone() else { return };
-            let url = tab.request.url.clone();
-            let method = tab.request.method.as_str().to_string();
-            let body_raw = match &tab.request.body {
-                crate::state::request_state::RequestBody::Json(s)
-                | crate::state::request_state::RequestBody::Text(s) => s.clone(),
-                _ => String::new(),
+    /// Toggle the collapsible tree view; bodies that aren't JSON stay in the
+    /// text view.
+    fn toggle_tree_view(&mut self) {
+        let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) else {
+            return;
+        };
+        if resp.tree_value.is_none() {
+            let ResponseBody::Text(text) = &resp.body else {
+                return;
             };
-            let ws_name = self.state.workspace.name.clone();
-            for col in &mut self.state.workspace.collections {
-                if update_col_request_state(&mut col.items, &req_id, &url, &method, &body_raw) {
-                    let _ = col_storage::save_collection_meta(&ws_name, col);
-                    break;
-                }
-            }
+            let Ok(value) = serde_json::from_str(text) else {
+                return;
+            };
+            resp.tree_value = Some(value);
         }
+        resp.tree_view = !resp.tree_view;
     }
 
-    // ─── Insert key handling ──────────────────────────────────────────────────
+    /// j/k move, Enter/h/l fold and unfold, Enter on a value shows it in
+    /// full, h on a leaf or folded node jumps to its parent. Returns `false`
+    /// when the tree view isn't showing or the key isn't one of these.
+    fn handle_tree_key(&mut self, key: KeyEvent) -> bool {
+        let page = (self.state.hit_areas.borrow().response_body.height as usize).max(1);
+        let Some(tab) = self.state.active_tab_mut() else {
+            return false;
+        };
+        if tab.response_tab != ResponseTab::Body {
+            return false;
+        }
+        let Some(resp) = tab.response.as_mut().filter(|r| r.tree_view) else {
+            return false;
+        };
+        let Some(root) = &resp.tree_value else {
+            return false;
+        };
+        let rows = tree_rows(root, &resp.tree_collapsed);
+        let cursor = resp.tree_cursor.min(rows.len().saturating_sub(1));
+        let Some(row) = rows.get(cursor) else {
+            return false;
+        };
+        let path = row.path.clone();
+        let container = matches!(row.value, Value::Object(_) | Value::Array(_));
+        let collapsed = row.collapsed;
+        let parent = rows[..cursor].iter().rposition(|r| r.depth < row.depth);
+        let full_value = match row.value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let last = rows.len() - 1;
 
-    fn handle_insert_key(&mut self, key: KeyEvent) {
-        // Check if we're in sidebar search mode
-        if self.state.focus == Focus::Sidebar && self.state.sidebar.search_mode {
-            match key.code {
-                KeyCode::Esc => {
-                    self.state.sidebar.search_mode = false;
-                    self.state.sidebar.search_query.clear();
-                    self.state.mode = Mode::Normal;
-                }
-                KeyCode::Char(c) => {
-                    self.state.sidebar.search_query.push(c);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => resp.tree_cursor = (cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => resp.tree_cursor = cursor.saturating_sub(1),
+            KeyCode::Char('G') => resp.tree_cursor = last,
+            KeyCode::PageDown => resp.tree_cursor = (cursor + page).min(last),
+            KeyCode::PageUp => resp.tree_cursor = cursor.saturating_sub(page),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                resp.tree_cursor = (cursor + (page / 2).max(1)).min(last);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                resp.tree_cursor = cursor.saturating_sub((page / 2).max(1));
+            }
+            KeyCode::Enter if container => {
+                if !resp.tree_collapsed.remove(&path) {
+                    resp.tree_collapsed.insert(path);
                 }
-                KeyCode::Backspace => {
-                    self.state.sidebar.search_query.pop();
-                    if self.state.sidebar.search_query.is_empty() {
-                        self.state.sidebar.search_mode = false;
-                        self.state.mode = Mode::Normal;
-                    }
+            }
+            KeyCode::Enter => {
+                self.state.json_value = JsonValueState { path, value: full_value, scroll: 0 };
+                self.state.active_popup = ActivePopup::JsonValue;
+            }
+            KeyCode::Char('l') => {
+                resp.tree_collapsed.remove(&path);
+            }
+            KeyCode::Char('h') if container && !collapsed => {
+                resp.tree_collapsed.insert(path);
+            }
+            KeyCode::Char('h') => {
+                if let Some(parent) = parent {
+                    resp.tree_cursor = parent;
                 }
-                _ => {}
             }
-            return;
+            _ => return false,
         }
+        true
+    }
 
-        let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
-        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Headers) {
-            self.handle_headers_insert_key(key);
+    // ─── Body filter bar (f in the response viewer) ──────────────────────────
+
+    fn filter_editing(&self) -> bool {
+        self.state.focus == Focus::ResponseViewer
+            && self
+                .state
+                .active_tab()
+                .and_then(|t| t.response.as_ref())
+                .is_some_and(|r| r.filter.editing)
+    }
+
+    /// Open the filter bar on the last expression, to refine it.
+    fn open_filter_bar(&mut self) {
+        let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) else {
             return;
+        };
+        if !matches!(resp.body, ResponseBody::Text(_)) {
+            return;
+        }
+        let filter = &mut resp.filter;
+        filter.editing = true;
+        filter.cursor = filter.input.len();
+    }
+
+    fn filter_in_use(&self) -> bool {
+        self.state
+            .active_tab()
+            .and_then(|t| t.response.as_ref())
+            .is_some_and(|r| r.filter.result.is_some() || r.filter.error.is_some())
+    }
+
+    /// Drop the filter and show the full body again.
+    fn clear_filter(&mut self) {
+        if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) {
+            resp.filter = BodyFilter::default();
         }
+    }
+
+    /// Edit the filter expression, re-evaluating it against the full body
+    /// on every change. An expression that fails keeps the last result.
+    fn handle_filter_key(&mut self, key: KeyEvent) {
+        let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) else {
+            return;
+        };
+        let filter = &mut resp.filter;
         match key.code {
-            KeyCode::Esc => self.state.mode = Mode::Normal,
+            KeyCode::Esc => {
+                resp.filter = BodyFilter::default();
+                return;
+            }
             KeyCode::Enter => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    self.state.mode = Mode::Normal;
-                    self.send_request();
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                            let cursor = tab.request.body_cursor;
-                            text.insert(cursor, '\n');
-                            tab.request.body_cursor = cursor + 1;
-                        }
-                    }
+                filter.editing = false;
+                if filter.input.trim().is_empty() {
+                    resp.filter = BodyFilter::default();
                 }
+                return;
             }
             KeyCode::Char(c) => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.url_cursor;
-                        tab.request.url.insert(cursor, c);
-                        tab.request.url_cursor += c.len_utf8();
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                            let cursor = tab.request.body_cursor;
-                            text.insert(cursor, c);
-                            tab.request.body_cursor = cursor + c.len_utf8();
-                        }
-                    }
-                }
+                filter.input.insert(filter.cursor, c);
+                filter.cursor += c.len_utf8();
             }
             KeyCode::Backspace => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.url_cursor;
-                        if cursor > 0 {
-                            let url = tab.request.url.clone();
-                            let prev = Self::prev_char_boundary_of(&url, cursor);
-                            tab.request.url.drain(prev..cursor);
-                            tab.request.url_cursor = prev;
-                        }
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        if cursor > 0 {
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                let prev = Self::prev_char_boundary_of(text, cursor);
-                                text.drain(prev..cursor);
-                                tab.request.body_cursor = prev;
-                            }
-                        }
-                    }
-                }
-            }
-            KeyCode::Delete => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.url_cursor;
-                        let url = tab.request.url.clone();
-                        if cursor < url.len() {
-                            let next = Self::next_char_boundary_of(&url, cursor);
-                            tab.request.url.drain(cursor..next);
-                        }
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let body_len = match &tab.request.body {
-                            crate::state::request_state::RequestBody::Json(s)
-                            | crate::state::request_state::RequestBody::Text(s) => s.len(),
-                            _ => 0,
-                        };
-                        if cursor < body_len {
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                let next = Self::next_char_boundary_of(text, cursor);
-                                text.drain(cursor..next);
-                            }
-                        }
-                    }
-                }
+                let Some(c) = filter.input[..filter.cursor].chars().next_back() else {
+                    return;
+                };
+                filter.cursor -= c.len_utf8();
+                filter.input.remove(filter.cursor);
             }
             KeyCode::Left => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.url_cursor;
-                        let url = tab.request.url.clone();
-                        tab.request.url_cursor = Self::prev_char_boundary_of(&url, cursor);
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let new_cursor =
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                Self::prev_char_boundary_of(text, cursor)
-                            } else {
-                                cursor
-                            };
-                        tab.request.body_cursor = new_cursor;
-                    }
+                if let Some(c) = filter.input[..filter.cursor].chars().next_back() {
+                    filter.cursor -= c.len_utf8();
                 }
             }
             KeyCode::Right => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.url_cursor;
-                        let url = tab.request.url.clone();
-                        tab.request.url_cursor = Self::next_char_boundary_of(&url, cursor);
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let new_cursor =
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                Self::next_char_boundary_of(text, cursor)
-                            } else {
-                                cursor
-                            };
-                        tab.request.body_cursor = new_cursor;
-                    }
+                if let Some(c) = filter.input[filter.cursor..].chars().next() {
+                    filter.cursor += c.len_utf8();
                 }
             }
-            KeyCode::Up => {
-                if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let body_snapshot = match &tab.request.body {
-                            crate::state::request_state::RequestBody::Json(s)
-                            | crate::state::request_state::RequestBody::Text(s) => s.clone(),
-                            _ => String::new(),
-                        };
-                        tab.request.body_cursor = Self::body_move_up(&body_snapshot, cursor);
-                    }
-                }
+            KeyCode::Home => filter.cursor = 0,
+            KeyCode::End => filter.cursor = filter.input.len(),
+            _ => return,
+        }
+
+        let ResponseBody::Text(body) = &resp.body else {
+            return;
+        };
+        let filter = &mut resp.filter;
+        if filter.input.trim().is_empty() {
+            filter.applied.clear();
+            filter.result = None;
+            filter.error = None;
+            return;
+        }
+        match apply_filter(body, &filter.input) {
+            Ok(out) => {
+                filter.result = Some(highlight_text(&out, "json"));
+                filter.applied = filter.input.trim().to_string();
+                filter.error = None;
+                resp.scroll_offset = 0;
+            }
+            Err(e) => filter.error = Some(e),
+        }
+    }
+
+    fn handle_json_value_key(&mut self, key: KeyEvent) {
+        let popup = &mut self.state.json_value;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                self.state.active_popup = ActivePopup::None;
             }
-            KeyCode::Down => {
-                if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let body_snapshot = match &tab.request.body {
-                            crate::state::request_state::RequestBody::Json(s)
-                            | crate::state::request_state::RequestBody::Text(s) => s.clone(),
-                            _ => String::new(),
-                        };
-                        tab.request.body_cursor = Self::body_move_down(&body_snapshot, cursor);
-                    }
-                }
+            KeyCode::Char('j') | KeyCode::Down => popup.scroll = popup.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => popup.scroll = popup.scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    // ─── Response diff (D) ────────────────────────────────────────────────────
+
+    /// `D` on the response viewer: pick another tab with a text response to
+    /// compare this tab's response with.
+    fn open_diff(&mut self) {
+        let Some(current) = self.state.active_tab() else {
+            return;
+        };
+        if response_text(current).is_none() {
+            self.show_toast("No text response to compare".to_string());
+            return;
+        }
+        let current_id = current.request.id.clone();
+        let current_name = tab_label(current);
+        let candidates: Vec<(String, String)> = self
+            .state
+            .workspace
+            .open_tabs
+            .iter()
+            .filter(|t| t.request.id != current_id && response_text(t).is_some())
+            .map(|t| (t.request.id.clone(), format!("{} {}", t.request.method.as_str(), tab_label(t))))
+            .collect();
+        if candidates.is_empty() {
+            self.show_toast("No other tab has a text response to compare with".to_string());
+            return;
+        }
+        self.state.diff = DiffState { candidates, current_name, ..DiffState::default() };
+        self.state.active_popup = ActivePopup::Diff;
+    }
+
+    fn handle_diff_key(&mut self, key: KeyEvent) {
+        let diff = &mut self.state.diff;
+        if let Some(lines) = &diff.lines {
+            let last = lines.len().saturating_sub(1);
+            match key.code {
+                // Back to the picker
+                KeyCode::Esc => diff.lines = None,
+                KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+                KeyCode::Char('j') | KeyCode::Down => diff.scroll = (diff.scroll + 1).min(last),
+                KeyCode::Char('k') | KeyCode::Up => diff.scroll = diff.scroll.saturating_sub(1),
+                KeyCode::PageDown => diff.scroll = (diff.scroll + 20).min(last),
+                KeyCode::PageUp => diff.scroll = diff.scroll.saturating_sub(20),
+                _ => {}
             }
-            KeyCode::Home => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.url_cursor = 0;
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let new_cursor =
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                let before = &text[..cursor.min(text.len())];
-                                match before.rfind('\n') {
-                                    Some(i) => i + 1,
-                                    None => 0,
-                                }
-                            } else {
-                                cursor
-                            };
-                        tab.request.body_cursor = new_cursor;
-                    }
-                }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Char('j') | KeyCode::Down => {
+                diff.selected = (diff.selected + 1).min(diff.candidates.len().saturating_sub(1));
             }
-            KeyCode::End => {
-                if matches!(self.state.focus, Focus::UrlBar) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        tab.request.url_cursor = tab.request.url.len();
-                    }
-                } else if matches!(self.state.focus, Focus::Editor) {
-                    if let Some(tab) = self.state.active_tab_mut() {
-                        let cursor = tab.request.body_cursor;
-                        let new_cursor =
-                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
-                                let after_start = cursor.min(text.len());
-                                let after = &text[after_start..];
-                                match after.find('\n') {
-                                    Some(i) => after_start + i,
-                                    None => text.len(),
-                                }
-                            } else {
-                                cursor
-                            };
-                        tab.request.body_cursor = new_cursor;
-                    }
-                }
+            KeyCode::Char('k') | KeyCode::Up => diff.selected = diff.selected.saturating_sub(1),
+            KeyCode::Enter => {
+                let Some((id, name)) = diff.candidates.get(diff.selected).cloned() else {
+                    return;
+                };
+                let ws = &self.state.workspace;
+                let other = ws.open_tabs.iter().find(|t| t.request.id == id).and_then(response_text);
+                let current = ws.open_tabs.get(ws.active_tab_idx).and_then(response_text);
+                let (Some(other), Some(current)) = (other, current) else {
+                    return;
+                };
+                let a: Vec<&str> = other.lines().collect();
+                let b: Vec<&str> = current.lines().collect();
+                let lines = lcs_diff(&a, &b);
+                let diff = &mut self.state.diff;
+                diff.other_name = name;
+                diff.lines = Some(lines);
+                diff.scroll = 0;
             }
             _ => {}
         }
     }
 
-    /// Get a mutable reference to the body text string.
-    fn body_text_mut(body: &mut crate::state::request_state::RequestBody) -> Option<&mut String> {
-        use crate::state::request_state::RequestBody;
-        match body {
-            RequestBody::Json(s) | RequestBody::Text(s) => Some(s),
-            RequestBody::None => {
-                *body = RequestBody::Json(String::new());
-                match body {
-                    RequestBody::Json(s) => Some(s),
-                    _ => None,
-                }
+    // ─── Run history popup (g h) ──────────────────────────────────────────────
+
+    fn open_run_history(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        let Some(id) = tab.collection_id.clone() else {
+            return;
+        };
+        self.state.run_history = RunHistoryState {
+            request_id: id,
+            request_name: tab.request.name.clone(),
+            selected: 0,
+        };
+        self.state.active_popup = ActivePopup::RunHistory;
+    }
+
+    fn handle_run_history_key(&mut self, key: KeyEvent) {
+        let count = self
+            .state
+            .workspace
+            .run_history
+            .get(&self.state.run_history.request_id)
+            .map_or(0, Vec::len);
+        let popup = &mut self.state.run_history;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(count.saturating_sub(1));
             }
-            RequestBody::Form(_) | RequestBody::Binary(_) => None,
+            KeyCode::Up | KeyCode::Char('k') => {
+                popup.selected = popup.selected.saturating_sub(1);
+            }
+            _ => {}
         }
     }
 
-    fn headers_active_text_mut(
-        headers: &mut Vec<KeyValuePair>,
-        row: usize,
-        col: u8,
-    ) -> Option<&mut String> {
-        let pair = headers.get_mut(row)?;
-        if col == 0 { Some(&mut pair.key) } else { Some(&mut pair.value) }
+    // ─── Help popup (?) ───────────────────────────────────────────────────────
+
+    fn open_help(&mut self) {
+        self.state.help = HelpState::default();
+        self.state.active_popup = ActivePopup::Help;
     }
 
-    fn handle_headers_insert_key(&mut self, key: KeyEvent) {
+    fn handle_help_key(&mut self, key: KeyEvent) {
+        // The renderer clamps to the last screenful; keep within the list
+        let last = help::line_count().saturating_sub(1);
+        let help = &mut self.state.help;
         match key.code {
-            KeyCode::Esc => {
-                self.state.mode = Mode::Normal;
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                self.state.active_popup = ActivePopup::None;
             }
-            KeyCode::Char(c) => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let cursor = tab.request.headers_cursor;
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    if let Some(text) =
-                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+            KeyCode::Char('j') | KeyCode::Down => help.scroll = (help.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => help.scroll = help.scroll.saturating_sub(1),
+            KeyCode::PageDown => help.scroll = (help.scroll + 10).min(last),
+            KeyCode::PageUp => help.scroll = help.scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => help.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => help.scroll = last,
+            _ => {}
+        }
+    }
+
+    // ─── Workspace settings popup (W) ─────────────────────────────────────────
+
+    fn open_settings(&mut self) {
+        self.state.settings = SettingsState::default();
+        self.state.active_popup = ActivePopup::Settings;
+    }
+
+    fn handle_settings_key(&mut self, key: KeyEvent) {
+        let popup = &mut self.state.settings;
+        if let Some((text, cursor)) = &mut popup.editing {
+            match key.code {
+                KeyCode::Esc => popup.editing = None,
+                KeyCode::Enter | KeyCode::Tab => {
+                    let value = text.trim().to_string();
+                    // Proxy URLs are checked before the client is rebuilt with them
+                    if popup.selected < 2
+                        && !value.is_empty()
+                        && let Err(e) = check_proxy_url(&value)
                     {
-                        text.insert(cursor, c);
-                        tab.request.headers_cursor = cursor + c.len_utf8();
+                        self.show_toast(format!("Invalid proxy URL: {e}"));
+                        return;
                     }
-                }
-            }
-            KeyCode::Backspace => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let cursor = tab.request.headers_cursor;
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    if cursor > 0 {
-                        if let Some(text) =
-                            Self::headers_active_text_mut(&mut tab.request.headers, row, col)
-                        {
-                            let prev = Self::prev_char_boundary_of(text, cursor);
-                            text.drain(prev..cursor);
-                            tab.request.headers_cursor = prev;
-                        }
+                    popup.editing = None;
+                    let selected = popup.selected;
+                    *self.state.workspace.proxy.field_mut(selected) = value;
+                    if key.code == KeyCode::Tab && selected + 1 < ProxySettings::FIELDS {
+                        let next = self.state.workspace.proxy.field(selected + 1).to_string();
+                        self.state.settings.selected = selected + 1;
+                        self.state.settings.editing = Some((next.clone(), next.len()));
                     }
+                    self.apply_proxy();
                 }
-            }
-            KeyCode::Delete => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let cursor = tab.request.headers_cursor;
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    if let Some(text) =
-                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
-                    {
-                        if cursor < text.len() {
-                            let next = Self::next_char_boundary_of(text, cursor);
-                            text.drain(cursor..next);
-                        }
-                    }
+                KeyCode::Char(c) => {
+                    text.insert(*cursor, c);
+                    *cursor += c.len_utf8();
                 }
-            }
-            KeyCode::Left => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let cursor = tab.request.headers_cursor;
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    let new_cursor = if let Some(text) =
-                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
-                    {
-                        Self::prev_char_boundary_of(text, cursor)
-                    } else {
-                        cursor
-                    };
-                    tab.request.headers_cursor = new_cursor;
+                KeyCode::Backspace if *cursor > 0 => {
+                    let prev = Self::prev_char_boundary_of(text, *cursor);
+                    text.drain(prev..*cursor);
+                    *cursor = prev;
                 }
+                KeyCode::Left => *cursor = Self::prev_char_boundary_of(text, *cursor),
+                KeyCode::Right => *cursor = Self::next_char_boundary_of(text, *cursor),
+                KeyCode::Home => *cursor = 0,
+                KeyCode::End => *cursor = text.len(),
+                _ => {}
             }
-            KeyCode::Right => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let cursor = tab.request.headers_cursor;
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    let new_cursor = if let Some(text) =
-                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
-                    {
-                        Self::next_char_boundary_of(text, cursor)
-                    } else {
-                        cursor
-                    };
-                    tab.request.headers_cursor = new_cursor;
-                }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => {
+                popup.selected = (popup.selected + 1).min(ProxySettings::FIELDS - 1);
             }
-            KeyCode::Home => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    tab.request.headers_cursor = 0;
-                }
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => {
+                popup.selected = popup.selected.saturating_sub(1);
             }
-            KeyCode::End => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let row = tab.request.headers_row;
-                    let col = tab.request.headers_col;
-                    let len = tab
-                        .request
-                        .headers
-                        .get(row)
-                        .map(|p| if col == 0 { p.key.len() } else { p.value.len() })
-                        .unwrap_or(0);
-                    tab.request.headers_cursor = len;
-                }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                let text = self.state.workspace.proxy.field(popup.selected).to_string();
+                popup.editing = Some((text.clone(), text.len()));
             }
-            KeyCode::Tab => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let col = tab.request.headers_col;
-                    if col == 0 {
-                        tab.request.headers_col = 1;
-                        let row = tab.request.headers_row;
-                        let val_len = tab
-                            .request
-                            .headers
-                            .get(row)
-                            .map(|p| p.value.len())
-                            .unwrap_or(0);
-                        tab.request.headers_cursor = val_len;
-                    } else {
-                        let next_row = tab.request.headers_row + 1;
-                        if next_row >= tab.request.headers.len() {
-                            tab.request.headers.push(KeyValuePair::default());
+            KeyCode::Char('d') | KeyCode::Char('x') => {
+                self.state.workspace.proxy.field_mut(popup.selected).clear();
+                self.apply_proxy();
+            }
+            _ => {}
+        }
+    }
+
+    /// Save the workspace's proxy settings and rebuild the client with them,
+    /// so they apply from the next send. Sends in flight keep the old one.
+    fn apply_proxy(&mut self) {
+        self.save_workspace_file();
+        let ws = &self.state.workspace;
+        self.client = build_client(&ws.cookies, &ws.proxy);
+    }
+
+    // ─── Default headers popup (H) ────────────────────────────────────────────
+
+    /// Edit the default headers of the collection or folder under the cursor.
+    fn open_default_headers(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        let headers = match node.kind {
+            NodeKind::Collection { .. } => self
+                .state
+                .workspace
+                .collections
+                .iter()
+                .find(|c| c.id == node.id)
+                .map(|c| c.default_headers.clone()),
+            NodeKind::Folder { .. } => self.state.workspace.collections.iter().find_map(|c| {
+                match find_item_in_list(&c.items, &node.id) {
+                    Some(CollectionItem::Folder(f)) => Some(f.default_headers.clone()),
+                    _ => None,
+                }
+            }),
+            NodeKind::Request { .. } => None,
+        };
+        let Some(headers) = headers else {
+            return;
+        };
+        self.state.default_headers = DefaultHeadersState {
+            target_id: node.id.clone(),
+            title: node.label.clone(),
+            headers,
+            ..DefaultHeadersState::default()
+        };
+        self.state.active_popup = ActivePopup::DefaultHeaders;
+    }
+
+    fn handle_default_headers_key(&mut self, key: KeyEvent) {
+        let popup = &mut self.state.default_headers;
+        if let Some((text, cursor)) = &mut popup.editing {
+            match key.code {
+                KeyCode::Esc => popup.editing = None,
+                KeyCode::Enter | KeyCode::Tab => {
+                    let (text, _) = popup.editing.take().unwrap_or_default();
+                    let next = key.code == KeyCode::Tab && popup.col == 0;
+                    if let Some(header) = popup.headers.get_mut(popup.selected) {
+                        match popup.col {
+                            0 => header.key = text.trim().to_string(),
+                            _ => header.value = text,
                         }
-                        tab.request.headers_row =
-                            next_row.min(tab.request.headers.len() - 1);
-                        tab.request.headers_col = 0;
-                        tab.request.headers_cursor = 0;
                     }
+                    // Tab from the key goes on to edit the value
+                    if next && let Some(header) = popup.headers.get(popup.selected) {
+                        popup.col = 1;
+                        popup.editing = Some((header.value.clone(), header.value.len()));
+                    }
+                    self.store_default_headers();
                 }
+                KeyCode::Char(c) => {
+                    text.insert(*cursor, c);
+                    *cursor += c.len_utf8();
+                }
+                KeyCode::Backspace if *cursor > 0 => {
+                    let prev = Self::prev_char_boundary_of(text, *cursor);
+                    text.drain(prev..*cursor);
+                    *cursor = prev;
+                }
+                KeyCode::Left => *cursor = Self::prev_char_boundary_of(text, *cursor),
+                KeyCode::Right => *cursor = Self::next_char_boundary_of(text, *cursor),
+                KeyCode::Home => *cursor = 0,
+                KeyCode::End => *cursor = text.len(),
+                _ => {}
             }
-            KeyCode::Enter => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    let next_row = tab.request.headers_row + 1;
-                    if next_row >= tab.request.headers.len() {
-                        tab.request.headers.push(KeyValuePair::default());
-                    }
-                    tab.request.headers_row = next_row.min(tab.request.headers.len() - 1);
-                    tab.request.headers_col = 0;
-                    tab.request.headers_cursor = 0;
+            return;
+        }
+
+        let count = popup.headers.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => popup.selected = popup.selected.saturating_sub(1),
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
+                popup.col = 1 - popup.col;
+            }
+            KeyCode::Char('a') | KeyCode::Char('o') => {
+                popup.headers.push(KeyValuePair::default());
+                popup.selected = popup.headers.len() - 1;
+                popup.col = 0;
+                popup.editing = Some((String::new(), 0));
+            }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                if let Some(header) = popup.headers.get(popup.selected) {
+                    let text = if popup.col == 0 { &header.key } else { &header.value };
+                    popup.editing = Some((text.clone(), text.len()));
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(header) = popup.headers.get_mut(popup.selected) {
+                    header.enabled = !header.enabled;
+                    self.store_default_headers();
                 }
             }
+            KeyCode::Char('d') | KeyCode::Char('x') if count > 0 => {
+                popup.headers.remove(popup.selected.min(count - 1));
+                popup.selected = popup.selected.min(count.saturating_sub(2));
+                self.store_default_headers();
+            }
             _ => {}
         }
     }
 
-    // ─── Char boundary helpers ────────────────────────────────────────────────
-
-    fn prev_char_boundary_of(text: &str, pos: usize) -> usize {
-        if pos == 0 {
-            return 0;
+    /// Write the popup's headers back to their collection or folder and save
+    /// it. Rows left without a key are kept while the popup is open.
+    fn store_default_headers(&mut self) {
+        let popup = &self.state.default_headers;
+        let ws_name = self.state.workspace.name.clone();
+        for col in &mut self.state.workspace.collections {
+            let slot = if col.id == popup.target_id {
+                &mut col.default_headers
+            } else if let Some(folder) = find_folder_in_list_mut(&mut col.items, &popup.target_id) {
+                &mut folder.default_headers
+            } else {
+                continue;
+            };
+            *slot = popup.headers.clone();
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+            return;
         }
-        let mut p = pos - 1;
-        while p > 0 && !text.is_char_boundary(p) {
+    }
+
+    // ─── Collection variables popup (v) ───────────────────────────────────────
+
+    /// Edit the variables of the collection or folder under the cursor.
+    fn open_collection_variables(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        let variables = match node.kind {
+            NodeKind::Collection { .. } => self
+                .state
+                .workspace
+                .collections
+                .iter()
+                .find(|c| c.id == node.id)
+                .map(|c| c.variables.clone()),
+            NodeKind::Folder { .. } => self.state.workspace.collections.iter().find_map(|c| {
+                match find_item_in_list(&c.items, &node.id) {
+                    Some(CollectionItem::Folder(f)) => Some(f.variables.clone()),
+                    _ => None,
+                }
+            }),
+            NodeKind::Request { .. } => None,
+        };
+        let Some(variables) = variables else {
+            return;
+        };
+        self.state.collection_vars = CollectionVarsState {
+            target_id: node.id.clone(),
+            title: node.label.clone(),
+            variables,
+            ..CollectionVarsState::default()
+        };
+        self.state.active_popup = ActivePopup::CollectionVariables;
+    }
+
+    fn handle_collection_variables_key(&mut self, key: KeyEvent) {
+        let popup = &mut self.state.collection_vars;
+        if let Some((text, cursor)) = &mut popup.editing {
+            match key.code {
+                KeyCode::Esc => popup.editing = None,
+                KeyCode::Enter | KeyCode::Tab => {
+                    let (text, _) = popup.editing.take().unwrap_or_default();
+                    let next = key.code == KeyCode::Tab && popup.col == 0;
+                    if let Some(var) = popup.variables.get_mut(popup.selected) {
+                        match popup.col {
+                            0 => var.key = text.trim().to_string(),
+                            _ => var.value = text,
+                        }
+                    }
+                    // Tab from the key goes on to edit the value
+                    if next && let Some(var) = popup.variables.get(popup.selected) {
+                        popup.col = 1;
+                        popup.editing = Some((var.value.clone(), var.value.len()));
+                    }
+                    self.store_collection_variables();
+                }
+                KeyCode::Char(c) => {
+                    text.insert(*cursor, c);
+                    *cursor += c.len_utf8();
+                }
+                KeyCode::Backspace if *cursor > 0 => {
+                    let prev = Self::prev_char_boundary_of(text, *cursor);
+                    text.drain(prev..*cursor);
+                    *cursor = prev;
+                }
+                KeyCode::Left => *cursor = Self::prev_char_boundary_of(text, *cursor),
+                KeyCode::Right => *cursor = Self::next_char_boundary_of(text, *cursor),
+                KeyCode::Home => *cursor = 0,
+                KeyCode::End => *cursor = text.len(),
+                _ => {}
+            }
+            return;
+        }
+
+        let count = popup.variables.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => popup.selected = popup.selected.saturating_sub(1),
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
+                popup.col = 1 - popup.col;
+            }
+            KeyCode::Char('a') | KeyCode::Char('o') => {
+                popup.variables.push(EnvVariable::default());
+                popup.selected = popup.variables.len() - 1;
+                popup.col = 0;
+                popup.editing = Some((String::new(), 0));
+            }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                if let Some(var) = popup.variables.get(popup.selected) {
+                    let text = if popup.col == 0 { &var.key } else { &var.value };
+                    popup.editing = Some((text.clone(), text.len()));
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(var) = popup.variables.get_mut(popup.selected) {
+                    var.enabled = !var.enabled;
+                    self.store_collection_variables();
+                }
+            }
+            KeyCode::Char('t') => {
+                if let Some(var) = popup.variables.get_mut(popup.selected) {
+                    var.var_type = match var.var_type {
+                        VarType::Secret => VarType::Text,
+                        VarType::Text => VarType::Secret,
+                    };
+                    self.store_collection_variables();
+                }
+            }
+            KeyCode::Char('s') => popup.show_secrets = !popup.show_secrets,
+            KeyCode::Char('d') | KeyCode::Char('x') if count > 0 => {
+                popup.variables.remove(popup.selected.min(count - 1));
+                popup.selected = popup.selected.min(count.saturating_sub(2));
+                self.store_collection_variables();
+            }
+            _ => {}
+        }
+    }
+
+    /// Write the popup's variables back to their collection or folder and
+    /// save it.
+    fn store_collection_variables(&mut self) {
+        let popup = &self.state.collection_vars;
+        let ws_name = self.state.workspace.name.clone();
+        for col in &mut self.state.workspace.collections {
+            let slot = if col.id == popup.target_id {
+                &mut col.variables
+            } else if let Some(folder) = find_folder_in_list_mut(&mut col.items, &popup.target_id) {
+                &mut folder.variables
+            } else {
+                continue;
+            };
+            *slot = popup.variables.clone();
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+            return;
+        }
+    }
+
+    // ─── Variables popup ──────────────────────────────────────────────────────
+
+    /// Show the variables set this session; `from_runner` makes Esc return to
+    /// the runner so a folder run can be followed from both.
+    fn open_variables(&mut self, from_runner: bool) {
+        self.state.variables = VariablesState { from_runner, ..VariablesState::default() };
+        self.state.active_popup = ActivePopup::Variables;
+    }
+
+    fn handle_variables_key(&mut self, key: KeyEvent) {
+        let count = session_variables(&self.state).len();
+        let popup = &mut self.state.variables;
+        popup.selected = popup.selected.min(count.saturating_sub(1));
+        if let Some((text, cursor)) = &mut popup.editing {
+            match key.code {
+                KeyCode::Esc => popup.editing = None,
+                KeyCode::Enter => self.save_variable_edit(),
+                KeyCode::Char(c) => {
+                    text.insert(*cursor, c);
+                    *cursor += c.len_utf8();
+                }
+                KeyCode::Backspace if *cursor > 0 => {
+                    let prev = Self::prev_char_boundary_of(text, *cursor);
+                    text.drain(prev..*cursor);
+                    *cursor = prev;
+                }
+                KeyCode::Left => *cursor = Self::prev_char_boundary_of(text, *cursor),
+                KeyCode::Right => *cursor = Self::next_char_boundary_of(text, *cursor),
+                KeyCode::Home => *cursor = 0,
+                KeyCode::End => *cursor = text.len(),
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state.active_popup =
+                    if popup.from_runner { ActivePopup::Runner } else { ActivePopup::None };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                popup.selected = popup.selected.saturating_sub(1);
+            }
+            KeyCode::Char('s') => popup.show_secrets = !popup.show_secrets,
+            KeyCode::Char('e') | KeyCode::Enter => {
+                let selected = popup.selected;
+                if let Some((var, _)) = session_variables(&self.state).get(selected) {
+                    let value = var.value.clone();
+                    let end = value.len();
+                    self.state.variables.editing = Some((value, end));
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') => {
+                let selected = popup.selected;
+                let Some(key) = session_variables(&self.state)
+                    .get(selected)
+                    .map(|(var, _)| var.key.clone())
+                else {
+                    return;
+                };
+                self.state.var_origins.remove(&key);
+                let env_idx = self.state.active_environment_idx();
+                let ws = &mut self.state.workspace;
+                if let Some(env) = env_idx.and_then(|i| ws.environments.get_mut(i)) {
+                    env.variables.retain(|v| v.key != key);
+                    let _ = env_storage::save_ws(&ws.name, env);
+                }
+                let popup = &mut self.state.variables;
+                popup.selected = popup.selected.min(count.saturating_sub(2));
+            }
+            _ => {}
+        }
+    }
+
+    /// Write the inline edit to the active environment; the variable stays
+    /// listed, now as edited by hand.
+    fn save_variable_edit(&mut self) {
+        let Some((value, _)) = self.state.variables.editing.take() else {
+            return;
+        };
+        let Some(key) = session_variables(&self.state)
+            .get(self.state.variables.selected)
+            .map(|(var, _)| var.key.clone())
+        else {
+            return;
+        };
+        self.apply_env_updates(&[(key, value)], "", "edited by hand");
+    }
+
+    // ─── Cookie jar popup ─────────────────────────────────────────────────────
+
+    fn open_cookie_jar(&mut self) {
+        self.state.workspace.cookies.write().purge_expired(chrono::Utc::now());
+        self.state.cookie_jar = CookieJarState::default();
+        self.state.active_popup = ActivePopup::Cookies;
+    }
+
+    fn handle_cookie_jar_key(&mut self, key: KeyEvent) {
+        let count = self.state.workspace.cookies.read().cookies.len();
+        let popup = &mut self.state.cookie_jar;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                popup.selected = (popup.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                popup.selected = popup.selected.saturating_sub(1);
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') => {
+                {
+                    let mut jar = self.state.workspace.cookies.write();
+                    let Some(cookie) = jar.sorted().get(popup.selected).map(|c| (*c).clone()) else {
+                        return;
+                    };
+                    jar.remove(&cookie);
+                }
+                popup.selected = popup.selected.min(count.saturating_sub(2));
+                self.save_cookies();
+            }
+            KeyCode::Char('D') => {
+                self.state.workspace.cookies.write().cookies.clear();
+                popup.selected = 0;
+                self.save_cookies();
+            }
+            KeyCode::Char('p') => {
+                let ws = &mut self.state.workspace;
+                ws.persist_cookies = !ws.persist_cookies;
+                if !ws.persist_cookies {
+                    let _ = cookie_storage::delete_cookies(&ws.name);
+                }
+                self.save_cookies();
+                self.save_workspace_file();
+            }
+            _ => {}
+        }
+    }
+
+    // ─── Theme picker popup ───────────────────────────────────────────────────
+
+    fn open_theme_picker(&mut self) {
+        let ui = &self.state.config.ui;
+        let syntax_selected = ui
+            .syntax_theme
+            .as_deref()
+            .and_then(|name| syntax_themes().position(|t| t == name))
+            .map_or(0, |i| i + 1);
+        self.state.theme_picker = ThemePickerState {
+            selected: ThemeKind::ALL.iter().position(|t| *t == ui.theme).unwrap_or(0),
+            original: ui.theme,
+            syntax_column: false,
+            syntax_selected,
+            original_syntax: ui.syntax_theme.clone(),
+        };
+        self.state.active_popup = ActivePopup::ThemePicker;
+    }
+
+    /// Moving the selection previews the theme; Enter keeps it and writes
+    /// `config.toml`, Esc restores the themes the picker opened with. Tab
+    /// switches between the UI and syntax theme columns.
+    fn handle_theme_picker_key(&mut self, key: KeyEvent) {
+        let picker = &mut self.state.theme_picker;
+        let syntax_count = syntax_themes().count() + 1;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state.config.ui.theme = picker.original;
+                self.state.config.ui.syntax_theme = picker.original_syntax.clone();
+                self.state.active_popup = ActivePopup::None;
+                self.apply_syntax_theme();
+                return;
+            }
+            KeyCode::Enter => {
+                self.state.active_popup = ActivePopup::None;
+                let _ = config_storage::save_config(&self.state.config);
+                return;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Char('l') => {
+                picker.syntax_column = !picker.syntax_column;
+                return;
+            }
+            KeyCode::Down | KeyCode::Char('j') if picker.syntax_column => {
+                picker.syntax_selected = (picker.syntax_selected + 1).min(syntax_count - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') if picker.syntax_column => {
+                picker.syntax_selected = picker.syntax_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                picker.selected = (picker.selected + 1).min(ThemeKind::ALL.len() - 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                picker.selected = picker.selected.saturating_sub(1);
+            }
+            _ => return,
+        }
+        let kind = ThemeKind::ALL[picker.selected];
+        let syntax = picker
+            .syntax_selected
+            .checked_sub(1)
+            .and_then(|i| syntax_themes().nth(i))
+            .map(str::to_string);
+        self.state.config.ui.theme = kind;
+        self.state.config.ui.syntax_theme = syntax;
+        self.apply_syntax_theme();
+        // Pick up edits to theme.toml each time it is previewed
+        if kind == ThemeKind::Custom && self.reload_custom_theme() {
+            self.show_toast(format!("No {} yet", theme_storage::theme_path().display()));
+        }
+    }
+
+    /// `theme <name>` from the command palette: switch and save right away.
+    fn switch_theme(&mut self, kind: ThemeKind) {
+        self.state.config.ui.theme = kind;
+        self.apply_syntax_theme();
+        if kind == ThemeKind::Custom && self.reload_custom_theme() {
+            self.show_toast(format!("No {} yet", theme_storage::theme_path().display()));
+        }
+        let _ = config_storage::save_config(&self.state.config);
+    }
+
+    /// Switch `highlight_text` to the configured syntax theme and re-highlight
+    /// the responses already shown with the old one.
+    fn apply_syntax_theme(&mut self) {
+        apply_syntax_theme(&self.state.config.ui);
+        for idx in 0..self.state.workspace.open_tabs.len() {
+            self.highlight_tab_response(idx);
+        }
+        for resp in self.state.workspace.open_tabs.iter_mut().filter_map(|t| t.response.as_mut()) {
+            if resp.sorted_body.is_some()
+                && let ResponseBody::Text(text) = &resp.body
+                && let Some(sorted) = sorted_pretty(text)
+            {
+                resp.sorted_body = Some(highlight_text(&sorted, detect_lang(None, &sorted)));
+            }
+        }
+    }
+
+    /// Re-read `theme.toml` for the Custom theme. A file that doesn't parse
+    /// keeps the previous palette and says why. Returns whether the file is
+    /// missing.
+    fn reload_custom_theme(&mut self) -> bool {
+        match theme_storage::load_theme() {
+            Ok(theme) => {
+                let missing = theme.is_none();
+                self.state.custom_theme = theme;
+                missing
+            }
+            Err(e) => {
+                self.show_toast(format!("theme.toml: {e}"));
+                false
+            }
+        }
+    }
+
+    // ─── Audit log popup ──────────────────────────────────────────────────────
+
+    fn open_audit_log(&mut self) {
+        self.state.audit_log = AuditLogState {
+            entries: audit_storage::load(&self.state.workspace.name),
+            ..AuditLogState::default()
+        };
+        self.state.active_popup = ActivePopup::AuditLog;
+    }
+
+    fn handle_audit_log_key(&mut self, key: KeyEvent) {
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if self.state.audit_log.editing_note {
+            let audit = &mut self.state.audit_log;
+            match key.code {
+                KeyCode::Esc => audit.editing_note = false,
+                KeyCode::Enter => {
+                    audit.editing_note = false;
+                    self.state.workspace.audit_note = audit.note_input.trim().to_string();
+                    self.save_workspace_file();
+                }
+                KeyCode::Backspace if audit.note_cursor > 0 => {
+                    let prev = Self::prev_char_boundary_of(&audit.note_input, audit.note_cursor);
+                    audit.note_input.drain(prev..audit.note_cursor);
+                    audit.note_cursor = prev;
+                }
+                KeyCode::Char(c) if !ctrl && !alt => {
+                    audit.note_input.insert(audit.note_cursor, c);
+                    audit.note_cursor += c.len_utf8();
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.state.active_popup = ActivePopup::None,
+            KeyCode::Char('e') if alt => {
+                self.state.workspace.audit_log = !self.state.workspace.audit_log;
+                self.save_workspace_file();
+            }
+            KeyCode::Char('n') if alt => {
+                let audit = &mut self.state.audit_log;
+                audit.editing_note = true;
+                audit.note_input = self.state.workspace.audit_note.clone();
+                audit.note_cursor = audit.note_input.len();
+            }
+            KeyCode::Down => {
+                let count = self.state.audit_log.visible().len();
+                let audit = &mut self.state.audit_log;
+                audit.selected = (audit.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up => {
+                let audit = &mut self.state.audit_log;
+                audit.selected = audit.selected.saturating_sub(1);
+            }
+            KeyCode::Backspace => {
+                let audit = &mut self.state.audit_log;
+                if audit.filter_cursor > 0 {
+                    let prev = Self::prev_char_boundary_of(&audit.filter, audit.filter_cursor);
+                    audit.filter.drain(prev..audit.filter_cursor);
+                    audit.filter_cursor = prev;
+                    audit.selected = 0;
+                }
+            }
+            KeyCode::Char(c) if !ctrl && !alt => {
+                let audit = &mut self.state.audit_log;
+                audit.filter.insert(audit.filter_cursor, c);
+                audit.filter_cursor += c.len_utf8();
+                audit.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    // ─── Normal key handling ──────────────────────────────────────────────────
+
+    fn handle_normal_key(&mut self, key: KeyEvent) {
+        if self.filter_editing() {
+            self.handle_filter_key(key);
+            return;
+        }
+
+        // Second key of a `g` sequence
+        if let Some(first) = self.state.pending_key.take() {
+            if first == 'g'
+                && key.code == KeyCode::Char('h')
+                && self.state.focus == Focus::ResponseViewer
+            {
+                self.open_run_history();
+            } else if first == 'g' && key.code == KeyCode::Char('g') {
+                if self.state.focus == Focus::ResponseViewer {
+                    self.scroll_response_by(isize::MIN);
+                    if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut())
+                    {
+                        resp.tree_cursor = 0;
+                    }
+                } else {
+                    self.jump_body(false);
+                }
+            } else if first == 'd' && key.code == KeyCode::Char('d') {
+                self.delete_text(true);
+            } else if first == 'y' && key.code == KeyCode::Char('y') {
+                self.yank_body_line();
+            }
+            return;
+        }
+
+        // Alt+1..Alt+9: jump to open tab by index
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            match key.code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    // Numbered within the active tab's pane
+                    let ws = &self.state.workspace;
+                    let pane = ws.pane_tabs(ws.active_tab_idx);
+                    if let Some(&idx) = pane.get((c as usize) - ('1' as usize)) {
+                        self.sync_active_tab_to_collection();
+                        self.state.workspace.active_tab_idx = idx;
+                    }
+                    return;
+                }
+                KeyCode::Char('w') => {
+                    self.request_close_active_tab();
+                    return;
+                }
+                KeyCode::Char('b') => {
+                    self.toggle_workspace_auto_retry();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if self.state.focus == Focus::ResponseViewer && self.handle_tree_key(key) {
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => self.state.should_quit = true,
+            KeyCode::Char('?') => self.open_help(),
+            KeyCode::Char(':') => self.open_command_palette(),
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => self.toggle_sidebar(),
+            KeyCode::Char('\\') => self.toggle_sidebar(),
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => self.toggle_zen(),
+            KeyCode::Char('z') if self.state.focus == Focus::ResponseViewer => self.toggle_zen(),
+            KeyCode::Char(c @ ('{' | '}')) => self.cycle_environment(c == '}'),
+            KeyCode::Left | KeyCode::Right
+                if key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+            {
+                self.move_tab_to_pane(key.code == KeyCode::Right);
+            }
+            KeyCode::Left | KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.focus_pane(key.code == KeyCode::Right);
+            }
+            KeyCode::Char('A') => self.open_audit_log(),
+            KeyCode::Char('T') => self.open_theme_picker(),
+            KeyCode::Char('W') => self.open_settings(),
+            KeyCode::Char('C') => self.open_cookie_jar(),
+            KeyCode::Char('V') => self.open_variables(false),
+            KeyCode::Char('Q') => self.open_send_queue(),
+            KeyCode::Tab => self.cycle_focus(true),
+            KeyCode::BackTab => self.cycle_focus(false),
+            KeyCode::PageDown | KeyCode::PageUp if self.normal_text_field() == Some(EditField::Body) => {
+                self.page_body(key.code == KeyCode::PageDown, false);
+            }
+            KeyCode::Char(c @ ('d' | 'u'))
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.normal_text_field() == Some(EditField::Body) =>
+            {
+                self.page_body(c == 'd', true);
+            }
+            KeyCode::Char('g') if self.normal_text_field() == Some(EditField::Body) => {
+                self.state.pending_key = Some('g');
+            }
+            KeyCode::Char('G') if self.normal_text_field() == Some(EditField::Body) => self.jump_body(true),
+            KeyCode::PageDown | KeyCode::PageUp if self.state.focus == Focus::ResponseViewer => {
+                let page = self.response_page();
+                self.scroll_response_by(if key.code == KeyCode::PageDown { page } else { -page });
+            }
+            KeyCode::Char(c @ ('d' | 'u'))
+                if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::ResponseViewer =>
+            {
+                let half = (self.response_page() / 2).max(1);
+                self.scroll_response_by(if c == 'd' { half } else { -half });
+            }
+            KeyCode::Char('G') if self.state.focus == Focus::ResponseViewer => self.scroll_response_by(isize::MAX),
+            KeyCode::Char('i') | KeyCode::Enter => {
+                if matches!(self.state.focus, Focus::UrlBar | Focus::Editor) {
+                    self.state.mode = Mode::Insert;
+                    if self.state.focus == Focus::Editor {
+                        let active_tab = self
+                            .state
+                            .active_tab()
+                            .map(|t| t.active_tab.clone());
+                        if active_tab == Some(ActiveTab::Headers) {
+                            let (row, col, len) = if let Some(tab) = self.state.active_tab() {
+                                let row = tab.request.headers_row;
+                                let col = tab.request.headers_col;
+                                let len = tab
+                                    .request
+                                    .headers
+                                    .get(row)
+                                    .map(|p| if col == 0 { p.key.len() } else { p.value.len() })
+                                    .unwrap_or(0);
+                                (row, col, len)
+                            } else {
+                                (0, 0, 0)
+                            };
+                            let _ = (row, col);
+                            if let Some(tab) = self.state.active_tab_mut() {
+                                tab.request.headers_cursor = len;
+                            }
+                        } else if active_tab == Some(ActiveTab::Checks) {
+                            if let Some(tab) = self.state.active_tab_mut() {
+                                let row = tab.request.checks_row;
+                                let col = tab.request.checks_col;
+                                tab.request.checks_cursor =
+                                    Self::checks_active_text_mut(&mut tab.request.checks, row, col)
+                                        .map(|t| t.len())
+                                        .unwrap_or(0);
+                            }
+                        } else if active_tab == Some(ActiveTab::Auth) {
+                            if let Some(tab) = self.state.active_tab_mut() {
+                                let req = &mut tab.request;
+                                match req.auth.field_mut(req.auth_field) {
+                                    Some(text) => req.auth_cursor = text.len(),
+                                    // Nothing to type into without an auth type
+                                    None => self.state.mode = Mode::Normal,
+                                }
+                            }
+                        } else if active_tab == Some(ActiveTab::Docs) {
+                            if let Some(tab) = self.state.active_tab_mut() {
+                                let req = &mut tab.request;
+                                req.description_cursor = req.description_cursor.min(req.description.len());
+                            }
+                        } else {
+                            if let Some(tab) = self.state.active_tab_mut() {
+                                if tab.request.body
+                                    == crate::state::request_state::RequestBody::None
+                                {
+                                    tab.request.body =
+                                        crate::state::request_state::RequestBody::Json(
+                                            String::new(),
+                                        );
+                                }
+                            }
+                        }
+                    }
+                } else if matches!(self.state.focus, Focus::Sidebar) {
+                    self.handle_sidebar_enter();
+                } else if matches!(self.state.focus, Focus::RequestTabs) {
+                    self.state.focus = Focus::UrlBar;
+                }
+            }
+            KeyCode::Char('u') if matches!(self.state.focus, Focus::UrlBar | Focus::Editor) => {
+                self.undo_edit(false);
+            }
+            KeyCode::Char('U') if matches!(self.state.focus, Focus::UrlBar | Focus::Editor) => {
+                self.undo_edit(true);
+            }
+            KeyCode::Char('[') => {
+                if self.state.focus == Focus::UrlBar {
+                    let before = self.field_entry(EditField::Method);
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.method = tab.request.method.prev();
+                    }
+                    self.record_undo(before, false);
+                    self.mark_active_tab_dirty();
+                } else {
+                    self.sync_active_tab_to_collection();
+                    self.prev_open_tab();
+                }
+            }
+            KeyCode::Char(']') => {
+                if self.state.focus == Focus::UrlBar {
+                    let before = self.field_entry(EditField::Method);
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.method = tab.request.method.next();
+                    }
+                    self.record_undo(before, false);
+                    self.mark_active_tab_dirty();
+                } else {
+                    self.sync_active_tab_to_collection();
+                    self.next_open_tab();
+                }
+            }
+            KeyCode::Esc if self.state.focus == Focus::Sidebar && self.sidebar_running.is_some() => {
+                self.abort_sidebar_run();
+            }
+            KeyCode::Esc if self.state.focus == Focus::Sidebar && self.state.sidebar.cut.is_some() => {
+                self.state.sidebar.cut = None;
+            }
+            KeyCode::Esc if self.state.focus == Focus::ResponseViewer && self.filter_in_use() => {
+                self.clear_filter();
+            }
+            KeyCode::Esc => self.cancel_request(),
+            KeyCode::Char('j')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar =>
+            {
+                self.sidebar_reorder(true);
+            }
+            KeyCode::Char('k')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar =>
+            {
+                self.sidebar_reorder(false);
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                let last = self.response_scroll_limit();
+                if self.state.focus == Focus::Sidebar {
+                    self.sidebar_move_cursor(1);
+                } else if self.state.focus == Focus::Editor {
+                    let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                    if active_tab == Some(ActiveTab::Headers) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            let len = tab.request.headers.len();
+                            if len > 0 {
+                                tab.request.headers_row =
+                                    (tab.request.headers_row + 1).min(len - 1);
+                            }
+                        }
+                    } else if active_tab == Some(ActiveTab::Checks) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            let len = tab.request.checks.len();
+                            if len > 0 {
+                                tab.request.checks_row =
+                                    (tab.request.checks_row + 1).min(len - 1);
+                            }
+                        }
+                    } else if active_tab == Some(ActiveTab::Auth) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            let len = tab.request.auth.fields().len();
+                            tab.request.auth_field =
+                                (tab.request.auth_field + 1).min(len.saturating_sub(1));
+                        }
+                    } else if let Some(tab) = self.state.active_tab_mut() {
+                        if let Some(resp) = &mut tab.response {
+                            resp.scroll_offset = resp.scroll_offset.saturating_add(1).min(last);
+                        }
+                    }
+                } else if let Some(tab) = self.state.active_tab_mut() {
+                    let response_tab = tab.response_tab.clone();
+                    if let Some(ws) = &mut tab.ws {
+                        // The log scrolls back from its newest message
+                        ws.scroll = ws.scroll.saturating_sub(1);
+                    } else if let Some(sse) = &mut tab.sse
+                        && response_tab == ResponseTab::Body
+                    {
+                        sse.scroll = sse.scroll.saturating_sub(1);
+                    } else if let Some(resp) = &mut tab.response {
+                        match response_tab {
+                            ResponseTab::Cookies => {
+                                let last = resp.cookies.len().saturating_sub(1) as u16;
+                                resp.cookies_scroll = (resp.cookies_scroll + 1).min(last);
+                            }
+                            ResponseTab::Headers => {
+                                let last = resp.headers.len().saturating_sub(1);
+                                resp.headers_cursor = (resp.headers_cursor + 1).min(last);
+                            }
+                            _ => resp.scroll_offset = resp.scroll_offset.saturating_add(1).min(last),
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                // An offset past a body that has since shrunk steps back from its end
+                let last = self.response_scroll_limit();
+                if self.state.focus == Focus::Sidebar {
+                    self.sidebar_move_cursor_up();
+                } else if self.state.focus == Focus::Editor {
+                    let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                    if active_tab == Some(ActiveTab::Headers) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            tab.request.headers_row =
+                                tab.request.headers_row.saturating_sub(1);
+                        }
+                    } else if active_tab == Some(ActiveTab::Checks) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            tab.request.checks_row =
+                                tab.request.checks_row.saturating_sub(1);
+                        }
+                    } else if active_tab == Some(ActiveTab::Auth) {
+                        if let Some(tab) = self.state.active_tab_mut() {
+                            tab.request.auth_field = tab.request.auth_field.saturating_sub(1);
+                        }
+                    } else if let Some(tab) = self.state.active_tab_mut() {
+                        if let Some(resp) = &mut tab.response {
+                            resp.scroll_offset = resp.scroll_offset.min(last).saturating_sub(1);
+                        }
+                    }
+                } else if let Some(tab) = self.state.active_tab_mut() {
+                    let response_tab = tab.response_tab.clone();
+                    if let Some(ws) = &mut tab.ws {
+                        ws.scroll = (ws.scroll + 1).min(ws.line_count().saturating_sub(1) as u16);
+                    } else if let Some(sse) = &mut tab.sse
+                        && response_tab == ResponseTab::Body
+                    {
+                        sse.scroll = (sse.scroll + 1).min(sse.line_count().saturating_sub(1) as u16);
+                    } else if let Some(resp) = &mut tab.response {
+                        match response_tab {
+                            ResponseTab::Cookies => {
+                                resp.cookies_scroll = resp.cookies_scroll.saturating_sub(1);
+                            }
+                            ResponseTab::Headers => {
+                                resp.headers_cursor = resp.headers_cursor.saturating_sub(1);
+                            }
+                            _ => resp.scroll_offset = resp.scroll_offset.min(last).saturating_sub(1),
+                        }
+                    }
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h')
+                if self.state.focus == Focus::TabBar =>
+            {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.active_tab = tab.active_tab.prev();
+                }
+            }
+            KeyCode::Right | KeyCode::Char('l')
+                if self.state.focus == Focus::TabBar =>
+            {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.active_tab = tab.active_tab.next();
+                }
+            }
+            KeyCode::Char('h') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_collapse();
+            }
+            KeyCode::Char('l') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_expand();
+            }
+            KeyCode::Left
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Headers) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.headers_col = 0;
+                        let row = tab.request.headers_row;
+                        let len =
+                            tab.request.headers.get(row).map(|p| p.key.len()).unwrap_or(0);
+                        tab.request.headers_cursor = len;
+                    }
+                } else if active_tab == Some(ActiveTab::Checks) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.checks_col = 0;
+                    }
+                } else if active_tab == Some(ActiveTab::Auth) {
+                    self.cycle_auth_type(false);
+                }
+            }
+            KeyCode::Right
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Headers) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.headers_col = 1;
+                        let row = tab.request.headers_row;
+                        let len =
+                            tab.request.headers.get(row).map(|p| p.value.len()).unwrap_or(0);
+                        tab.request.headers_cursor = len;
+                    }
+                } else if active_tab == Some(ActiveTab::Checks) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.checks_col = 1;
+                    }
+                } else if active_tab == Some(ActiveTab::Auth) {
+                    self.cycle_auth_type(true);
+                }
+            }
+            KeyCode::Char('a')
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Headers) {
+                    let before = self.field_entry(EditField::HeaderRows);
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.headers.push(KeyValuePair::default());
+                        let new_row = tab.request.headers.len() - 1;
+                        tab.request.headers_row = new_row;
+                        tab.request.headers_col = 0;
+                        tab.request.headers_cursor = 0;
+                    }
+                    self.record_undo(before, false);
+                    if self.state.active_tab().is_some() {
+                        self.state.mode = Mode::Insert;
+                    }
+                } else if active_tab == Some(ActiveTab::Checks)
+                    && let Some(tab) = self.state.active_tab_mut()
+                {
+                    tab.request.checks.push(Check::default());
+                    tab.request.checks_row = tab.request.checks.len() - 1;
+                    tab.request.checks_col = 0;
+                    tab.request.checks_cursor = 0;
+                    self.state.mode = Mode::Insert;
+                }
+            }
+            KeyCode::Char(c @ ('w' | 'b' | 'e' | '0' | '$')) if self.normal_text_field().is_some() => {
+                self.move_text_cursor(c);
+            }
+            KeyCode::Char('x') if self.normal_text_field().is_some() => self.delete_text(false),
+            KeyCode::Char(c @ ('d' | 'y')) if self.normal_text_field() == Some(EditField::Body) => {
+                self.state.pending_key = Some(c);
+            }
+            KeyCode::Char('p') if self.normal_text_field() == Some(EditField::Body) => self.put_body_line(),
+            KeyCode::Char('x')
+                if self.state.focus == Focus::Editor
+                    && self.state.active_tab().is_some_and(|t| {
+                        t.active_tab == ActiveTab::Auth && matches!(t.request.auth, AuthConfig::OAuth2 { .. })
+                    }) =>
+            {
+                // Forget the cached token so the next send fetches a new one
+                if let Some(tab) = self.state.active_tab_mut()
+                    && let AuthConfig::OAuth2 { token, .. } = &mut tab.request.auth
+                {
+                    *token = None;
+                    self.mark_active_tab_dirty();
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Char('d')
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Headers) {
+                    self.mark_active_tab_dirty();
+                    let before = self.field_entry(EditField::HeaderRows);
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let len = tab.request.headers.len();
+                        if len > 0 {
+                            tab.request.headers.remove(tab.request.headers_row);
+                            let new_len = tab.request.headers.len();
+                            tab.request.headers_row = if new_len > 0 {
+                                tab.request.headers_row.min(new_len - 1)
+                            } else {
+                                0
+                            };
+                        }
+                    }
+                    self.record_undo(before, false);
+                } else if active_tab == Some(ActiveTab::Checks) {
+                    if let Some(tab) = self.state.active_tab_mut()
+                        && tab.request.checks_row < tab.request.checks.len()
+                    {
+                        tab.request.checks.remove(tab.request.checks_row);
+                        tab.request.checks_row = tab
+                            .request
+                            .checks_row
+                            .min(tab.request.checks.len().saturating_sub(1));
+                    }
+                    self.sync_active_tab_to_collection();
+                }
+            }
+            KeyCode::Char(' ')
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Headers) {
+                    self.mark_active_tab_dirty();
+                    let before = self.field_entry(EditField::HeaderRows);
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let row = tab.request.headers_row;
+                        if let Some(pair) = tab.request.headers.get_mut(row) {
+                            pair.enabled = !pair.enabled;
+                        }
+                    }
+                    self.record_undo(before, false);
+                } else if active_tab == Some(ActiveTab::Checks) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let row = tab.request.checks_row;
+                        if let Some(check) = tab.request.checks.get_mut(row) {
+                            check.enabled = !check.enabled;
+                        }
+                    }
+                    self.sync_active_tab_to_collection();
+                }
+            }
+            KeyCode::Char('t')
+                if self.state.focus == Focus::Editor =>
+            {
+                let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+                if active_tab == Some(ActiveTab::Checks) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let row = tab.request.checks_row;
+                        if let Some(check) = tab.request.checks.get_mut(row) {
+                            check.kind = check.kind.next();
+                        }
+                    }
+                    self.sync_active_tab_to_collection();
+                }
+                if active_tab == Some(ActiveTab::Scripts)
+                    && let Some(tab) = self.state.active_tab_mut()
+                {
+                    let req = &mut tab.request;
+                    req.scripts_kind = req.scripts_kind.toggle();
+                    req.scripts_cursor = req.scripts.get(&req.scripts_kind).len();
+                }
+                if active_tab == Some(ActiveTab::Auth)
+                    && let Some(tab) = self.state.active_tab_mut()
+                {
+                    match &mut tab.request.auth {
+                        AuthConfig::ApiKey { in_header, .. } => *in_header = !*in_header,
+                        AuthConfig::OAuth2 { grant, token, .. } => {
+                            *grant = grant.toggle();
+                            *token = None;
+                            tab.request.auth_field = 0;
+                        }
+                        _ => return,
+                    }
+                    self.mark_active_tab_dirty();
+                }
+            }
+            KeyCode::Char('p')
+                if self.state.focus == Focus::Editor
+                    && self.state.active_tab().is_some_and(|t| {
+                        t.active_tab == ActiveTab::Auth && matches!(t.request.auth, AuthConfig::OAuth2 { .. })
+                    }) =>
+            {
+                if let Some(tab) = self.state.active_tab_mut()
+                    && let AuthConfig::OAuth2 { persist_token, .. } = &mut tab.request.auth
+                {
+                    *persist_token = !*persist_token;
+                    self.mark_active_tab_dirty();
+                }
+            }
+            KeyCode::Char('v')
+                if self.state.focus == Focus::Editor
+                    && self.state.active_tab().is_some_and(|t| t.active_tab == ActiveTab::Auth) =>
+            {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.request.auth_reveal = !tab.request.auth_reveal;
+                }
+            }
+            KeyCode::Char('#')
+                if matches!(self.state.focus, Focus::Editor | Focus::ResponseViewer) =>
+            {
+                self.toggle_line_numbers();
+            }
+            KeyCode::Char('c') if self.state.focus == Focus::ResponseViewer => {
+                self.open_check_picker();
+            }
+            KeyCode::Char('e') if self.state.focus == Focus::ResponseViewer => {
+                self.open_capture();
+            }
+            KeyCode::Char('g') if self.state.focus == Focus::ResponseViewer => {
+                self.state.pending_key = Some('g');
+            }
+            KeyCode::Char('o') if self.state.focus == Focus::ResponseViewer => {
+                self.toggle_sorted_view();
+            }
+            KeyCode::Char('t') if self.state.focus == Focus::ResponseViewer => {
+                self.toggle_tree_view();
+            }
+            KeyCode::Char('f') if self.state.focus == Focus::ResponseViewer => {
+                self.open_filter_bar();
+            }
+            KeyCode::Char('m') if self.state.focus == Focus::ResponseViewer => {
+                let theme = *theme(&self.state);
+                if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) {
+                    toggle_markdown(resp, &theme);
+                }
+            }
+            KeyCode::Char('w') if self.state.focus == Focus::ResponseViewer => self.toggle_word_wrap(),
+            KeyCode::Char('D') if self.state.focus == Focus::ResponseViewer => self.open_diff(),
+            KeyCode::Char('y') if self.state.focus == Focus::ResponseViewer => {
+                self.copy_response_header();
+            }
+            KeyCode::Char('b') if self.state.focus == Focus::ResponseViewer => {
+                self.toggle_request_auto_retry();
+            }
+            KeyCode::Left | KeyCode::Right if self.state.focus == Focus::ResponseViewer && self.body_scrolls_sideways() => {
+                let overflow = self.state.hit_areas.borrow().response_body_overflow;
+                if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) {
+                    resp.h_scroll = if key.code == KeyCode::Right {
+                        (resp.h_scroll as usize + H_SCROLL_STEP).min(overflow) as u16
+                    } else {
+                        resp.h_scroll.saturating_sub(H_SCROLL_STEP as u16)
+                    };
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h')
+                if self.state.focus == Focus::ResponseViewer =>
+            {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.response_tab = tab.response_tab.prev();
+                }
+            }
+            KeyCode::Right | KeyCode::Char('l')
+                if self.state.focus == Focus::ResponseViewer =>
+            {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.response_tab = tab.response_tab.next();
+                }
+            }
+            // Sidebar-specific keys
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar => {
+                self.prompt_new_collection();
+            }
+            KeyCode::Char('n') if self.state.focus == Focus::Sidebar => {
+                self.prompt_new_request();
+            }
+            KeyCode::Char('f') if self.state.focus == Focus::Sidebar => {
+                self.prompt_new_folder();
+            }
+            KeyCode::Char('r') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_rename();
+            }
+            KeyCode::Char('d') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_delete();
+            }
+            KeyCode::Char('D') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_duplicate();
+            }
+            KeyCode::Char('x') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_cut();
+            }
+            KeyCode::Char('p') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_paste();
+            }
+            KeyCode::Char('-')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar =>
+            {
+                self.sidebar_collapse_all();
+            }
+            KeyCode::Char('=')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && self.state.focus == Focus::Sidebar =>
+            {
+                self.sidebar_expand_all();
+            }
+            KeyCode::Char('J') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_reorder(true);
+            }
+            KeyCode::Char('K') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_reorder(false);
+            }
+            KeyCode::Char('L') if self.state.focus == Focus::Sidebar => {
+                self.open_dependency_picker();
+            }
+            KeyCode::Char('s') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_run_in_place(false);
+            }
+            KeyCode::Char('S') if self.state.focus == Focus::Sidebar => {
+                self.sidebar_run_in_place(true);
+            }
+            KeyCode::Char('H') if self.state.focus == Focus::Sidebar => {
+                self.open_default_headers();
+            }
+            KeyCode::Char('v') if self.state.focus == Focus::Sidebar => {
+                self.open_collection_variables();
+            }
+            KeyCode::Char('E') if self.state.focus == Focus::Sidebar => {
+                self.cycle_collection_environment();
+            }
+            KeyCode::Char('c') if self.state.focus == Focus::Sidebar => {
+                self.clear_runner_results();
+            }
+            KeyCode::Char('R') if self.state.focus == Focus::Sidebar => {
+                self.open_runner();
+            }
+            KeyCode::Char('w') if self.state.focus == Focus::Sidebar => {
+                // Cycle sidebar width mode: auto → compact → full
+                let ui = &mut self.state.config.ui;
+                ui.sidebar_mode = ui.sidebar_mode.next();
+                let _ = config_storage::save_config(&self.state.config);
+            }
+            KeyCode::Char('/') if self.state.focus == Focus::Sidebar => {
+                self.state.sidebar.search_mode = true;
+                self.state.sidebar.search_query.clear();
+                self.state.sidebar.invalidate_nodes();
+            }
+            // RequestTabs-specific keys
+            KeyCode::Left if self.state.focus == Focus::RequestTabs => {
+                self.sync_active_tab_to_collection();
+                self.prev_open_tab();
+            }
+            KeyCode::Right if self.state.focus == Focus::RequestTabs => {
+                self.sync_active_tab_to_collection();
+                self.next_open_tab();
+            }
+            KeyCode::Char('x') if self.state.focus == Focus::RequestTabs => {
+                self.request_close_active_tab();
+            }
+            KeyCode::Char('r') if self.state.focus == Focus::RequestTabs => {
+                self.rename_active_tab();
+            }
+            KeyCode::Char('p') if self.state.focus == Focus::RequestTabs => self.toggle_tab_pin(),
+            KeyCode::Char('1') => {
+                self.state.sidebar_visible = true;
+                self.state.focus = Focus::Sidebar;
+            }
+            KeyCode::Char('2') => {
+                self.state.zen = false;
+                self.state.focus = Focus::UrlBar;
+            }
+            KeyCode::Char('3') => {
+                self.state.zen = false;
+                self.state.focus = Focus::Editor;
+            }
+            KeyCode::Char('4') => self.state.focus = Focus::ResponseViewer,
+            _ => {}
+        }
+    }
+
+    // ─── Layout toggles ───────────────────────────────────────────────────────
+
+    /// `Ctrl+B` / `\`: show or hide the sidebar. Hiding it moves focus off it.
+    fn toggle_sidebar(&mut self) {
+        self.state.sidebar_visible = !self.state.sidebar_visible;
+        if !self.state.sidebar_visible && self.state.focus == Focus::Sidebar {
+            self.state.focus = if self.state.zen { Focus::ResponseViewer } else { Focus::UrlBar };
+        }
+    }
+
+    /// `z` (response) / `Ctrl+F`: let the response viewer fill the right
+    /// panel, or bring the request rows back.
+    fn toggle_zen(&mut self) {
+        self.state.zen = !self.state.zen;
+        if self.state.zen && self.state.focus != Focus::Sidebar {
+            self.state.focus = Focus::ResponseViewer;
+        }
+    }
+
+    /// `Tab` / `Shift+Tab`, skipping panels the layout hides.
+    fn cycle_focus(&mut self, forward: bool) {
+        let shown = |focus: &Focus| match focus {
+            Focus::Sidebar => self.state.sidebar_visible,
+            Focus::ResponseViewer => true,
+            _ => !self.state.zen,
+        };
+        let mut focus = self.state.focus.clone();
+        loop {
+            focus = if forward { focus.next() } else { focus.prev() };
+            if shown(&focus) {
+                break;
+            }
+        }
+        self.state.focus = focus;
+    }
+
+    // ─── Sidebar helpers ──────────────────────────────────────────────────────
+
+    fn sidebar_move_cursor(&mut self, delta: usize) {
+        let nodes = flatten_tree(&self.state);
+        let max = nodes.len().saturating_sub(1);
+        let new_cursor = (self.state.sidebar.cursor + delta).min(max);
+        self.state.sidebar.cursor = new_cursor;
+        // Scroll down if needed
+        // (We'll implement simple scroll clamping — caller must know visible height)
+        // For now: no-op; layout scrolls based on cursor vs scroll_offset
+        self.clamp_sidebar_scroll();
+    }
+
+    fn sidebar_move_cursor_up(&mut self) {
+        self.state.sidebar.cursor = self.state.sidebar.cursor.saturating_sub(1);
+        self.clamp_sidebar_scroll();
+    }
+
+    fn clamp_sidebar_scroll(&mut self) {
+        // Keep cursor visible — conservative 20-line window
+        let visible = 20usize;
+        let cursor = self.state.sidebar.cursor;
+        let scroll = self.state.sidebar.scroll_offset;
+        if cursor < scroll {
+            self.state.sidebar.scroll_offset = cursor;
+        } else if cursor >= scroll + visible {
+            self.state.sidebar.scroll_offset = cursor.saturating_sub(visible - 1);
+        }
+    }
+
+    fn sidebar_collapse(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
+            match &node.kind {
+                crate::ui::sidebar::NodeKind::Collection { .. }
+                | crate::ui::sidebar::NodeKind::Folder { .. } => {
+                    self.state.sidebar.collapsed_ids.insert(node.id.clone());
+                    self.state.sidebar.invalidate_nodes();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Fold every collection and folder (`Ctrl+-`). The cursor moves up to
+    /// the collection it was in.
+    fn sidebar_collapse_all(&mut self) {
+        self.state.sidebar.collapsed_ids.clear();
+        self.state.sidebar.invalidate_nodes();
+        let nodes = flatten_tree(&self.state);
+        let cursor = self.state.sidebar.cursor.min(nodes.len().saturating_sub(1));
+        let collection = nodes[..nodes.len().min(cursor + 1)]
+            .iter()
+            .rev()
+            .find(|n| n.depth == 0)
+            .map(|n| n.id.clone());
+        for node in nodes.iter() {
+            if matches!(node.kind, NodeKind::Collection { .. } | NodeKind::Folder { .. }) {
+                self.state.sidebar.collapsed_ids.insert(node.id.clone());
+            }
+        }
+        self.state.sidebar.invalidate_nodes();
+        self.sidebar_cursor_to(collection);
+    }
+
+    /// Unfold every collection and folder (`Ctrl+=`), keeping the cursor on
+    /// its node.
+    fn sidebar_expand_all(&mut self) {
+        let current = flatten_tree(&self.state).get(self.state.sidebar.cursor).map(|n| n.id.clone());
+        self.state.sidebar.collapsed_ids.clear();
+        self.state.sidebar.invalidate_nodes();
+        self.sidebar_cursor_to(current);
+    }
+
+    /// Put the sidebar cursor on node `id` if it is shown, else on the first
+    /// node.
+    fn sidebar_cursor_to(&mut self, id: Option<String>) {
+        let nodes = flatten_tree(&self.state);
+        self.state.sidebar.cursor = id.and_then(|id| nodes.iter().position(|n| n.id == id)).unwrap_or(0);
+        self.clamp_sidebar_scroll();
+    }
+
+    fn sidebar_expand(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
+            self.state.sidebar.collapsed_ids.remove(&node.id);
+            self.state.sidebar.invalidate_nodes();
+        }
+    }
+
+    fn handle_sidebar_enter(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
+            match node.kind {
+                crate::ui::sidebar::NodeKind::Collection { collapsed, .. }
+                | crate::ui::sidebar::NodeKind::Folder { collapsed } => {
+                    if collapsed {
+                        self.state.sidebar.collapsed_ids.remove(&node.id);
+                    } else {
+                        self.state.sidebar.collapsed_ids.insert(node.id.clone());
+                    }
+                    self.state.sidebar.invalidate_nodes();
+                }
+                crate::ui::sidebar::NodeKind::Request { method, .. } => {
+                    self.open_collection_request(&node.id, &node.label, &method);
+                }
+            }
+        }
+    }
+
+    /// Opens a collection request in a tab, or focuses its tab if it is
+    /// already open.
+    fn open_collection_request(&mut self, id: &str, name: &str, method: &str) {
+        // Dedup: if already open, just focus it
+        if let Some(idx) = self.state.workspace.open_tabs.iter()
+            .position(|t| t.collection_id.as_deref() == Some(id))
+        {
+            self.state.workspace.focus_tab(idx);
+            self.apply_collection_environment(id);
+            return;
+        }
+        self.open_collection_request_tab(id, name, method);
+    }
+
+    /// Opens a collection request in a new tab, even if it is already open.
+    fn open_collection_request_tab(&mut self, id: &str, name: &str, method: &str) {
+        // Load persisted state from collection
+        let saved = find_col_request_by_id(&self.state.workspace.collections, id);
+        let mut tab = RequestTab::default();
+        if let Some(saved) = saved {
+            tab.request = saved_request_state(saved);
+        }
+        tab.request.name = name.to_string();
+        tab.request.method = crate::state::request_state::HttpMethod::from_str_or_get(method);
+        tab.collection_id = Some(id.to_string());
+        // New tabs open in the pane with focus
+        let ws = &mut self.state.workspace;
+        if ws.in_right_pane(ws.active_tab_idx)
+            && let Some(split) = &mut ws.split
+        {
+            split.tab_ids.push(tab.request.id.clone());
+        }
+        ws.open_tabs.push(tab);
+        ws.active_tab_idx = ws.open_tabs.len() - 1;
+        self.apply_collection_environment(id);
+    }
+
+    /// Switch to the default environment of the collection holding request
+    /// `id`, unless the active tab has an environment of its own.
+    fn apply_collection_environment(&mut self, id: &str) {
+        let ws = &mut self.state.workspace;
+        if ws.open_tabs.get(ws.active_tab_idx).is_none_or(|t| t.env_override.is_some()) {
+            return;
+        }
+        let env_idx = ws
+            .collections
+            .iter()
+            .find(|c| item_exists_in_list(&c.items, id))
+            .and_then(|c| c.default_env_id.as_deref())
+            .and_then(|env_id| ws.environments.iter().position(|e| e.id == env_id));
+        if let Some(i) = env_idx {
+            ws.active_environment_idx = Some(i);
+        }
+    }
+
+    /// Make environment `i` active: for the active tab alone when the
+    /// switcher was opened for it, otherwise for the workspace.
+    fn activate_environment(&mut self, i: usize) {
+        let ws = &mut self.state.workspace;
+        if self.state.env_switcher.for_tab {
+            let id = ws.environments.get(i).map(|e| e.id.clone());
+            if let Some(tab) = ws.open_tabs.get_mut(ws.active_tab_idx) {
+                tab.env_override = id;
+            }
+        } else {
+            ws.active_environment_idx = Some(i);
+        }
+    }
+
+    /// `}` / `{`: make the next / previous environment active without the
+    /// switcher — the tab's own one if it has one, else the workspace's,
+    /// which is saved straight away.
+    fn cycle_environment(&mut self, forward: bool) {
+        let count = self.state.workspace.environments.len();
+        if count == 0 {
+            self.show_toast("No environments".to_string());
+            return;
+        }
+        let next = match self.state.active_environment_idx() {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        let ws = &mut self.state.workspace;
+        let name = ws.environments[next].name.clone();
+        match ws.open_tabs.get_mut(ws.active_tab_idx).filter(|t| t.env_override.is_some()) {
+            Some(tab) => tab.env_override = Some(ws.environments[next].id.clone()),
+            None => {
+                ws.active_environment_idx = Some(next);
+                self.save_workspace_file();
+            }
+        }
+        self.show_toast(format!("Environment: {name}"));
+    }
+
+    /// Cycle the default environment of the collection at the sidebar
+    /// cursor through every environment and none.
+    fn cycle_collection_environment(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        let Some(col_id) = self.find_collection_id_for_node(&node.id) else {
+            return;
+        };
+        let ws = &mut self.state.workspace;
+        if ws.environments.is_empty() {
+            self.show_toast("No environments — Ctrl+E creates one".to_string());
+            return;
+        }
+        let Some(col) = ws.collections.iter_mut().find(|c| c.id == col_id) else {
+            return;
+        };
+        let current = col
+            .default_env_id
+            .as_deref()
+            .and_then(|env_id| ws.environments.iter().position(|e| e.id == env_id));
+        let next = match current {
+            None => Some(0),
+            Some(i) => Some(i + 1).filter(|&n| n < ws.environments.len()),
+        };
+        col.default_env_id = next.map(|i| ws.environments[i].id.clone());
+        let message = match next {
+            Some(i) => format!("{} opens with {}", col.name, ws.environments[i].name),
+            None => format!("{} has no default environment", col.name),
+        };
+        let _ = col_storage::save_collection_meta(&ws.name, col);
+        self.state.sidebar.invalidate_nodes();
+        self.show_toast(message);
+    }
+
+    fn prompt_new_collection(&mut self) {
+        self.state.naming = NamingState {
+            target: NamingTarget::NewCollection,
+            ..NamingState::default()
+        };
+        self.state.active_popup = ActivePopup::CollectionNaming;
+    }
+
+    /// New request at the sidebar cursor's collection or folder.
+    fn prompt_new_request(&mut self) {
+        let target = self.sidebar_new_request_target();
+        self.state.naming = NamingState {
+            target,
+            method: "GET".to_string(),
+            ..NamingState::default()
+        };
+        self.state.active_popup = ActivePopup::CollectionNaming;
+    }
+
+    /// New folder in the sidebar cursor's collection.
+    fn prompt_new_folder(&mut self) {
+        let target = self.sidebar_new_folder_target();
+        self.state.naming = NamingState {
+            target,
+            ..NamingState::default()
+        };
+        self.state.active_popup = ActivePopup::CollectionNaming;
+    }
+
+    fn sidebar_new_request_target(&self) -> NamingTarget {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
+            let col_id = self.find_collection_id_for_node(&node.id);
+            let folder_id = match &node.kind {
+                crate::ui::sidebar::NodeKind::Folder { .. } => Some(node.id.clone()),
+                _ => None,
+            };
+            if let Some(cid) = col_id {
+                return NamingTarget::NewRequest {
+                    collection_id: cid,
+                    folder_id,
+                };
+            }
+        }
+        NamingTarget::NewCollection
+    }
+
+    fn sidebar_new_folder_target(&self) -> NamingTarget {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor) {
+            let col_id = self.find_collection_id_for_node(&node.id);
+            if let Some(cid) = col_id {
+                return NamingTarget::NewFolder { collection_id: cid };
+            }
+        }
+        NamingTarget::NewCollection
+    }
+
+    fn find_collection_id_for_node(&self, node_id: &str) -> Option<String> {
+        for col in &self.state.workspace.collections {
+            if col.id == node_id {
+                return Some(col.id.clone());
+            }
+            if item_exists_in_list(&col.items, node_id) {
+                return Some(col.id.clone());
+            }
+        }
+        None
+    }
+
+    fn sidebar_rename(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
+            self.state.naming = NamingState {
+                target: NamingTarget::Rename {
+                    id: node.id.clone(),
+                    old_name: node.label.clone(),
+                },
+                input: node.label.clone(),
+                cursor: node.label.len(),
+                ..NamingState::default()
+            };
+            self.state.active_popup = ActivePopup::CollectionNaming;
+        }
+    }
+
+    /// Name the active tab; a tab saved to a collection renames the saved
+    /// request too.
+    fn rename_active_tab(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        let name = tab.request.name.clone();
+        self.state.naming = NamingState {
+            target: NamingTarget::RenameTab { tab_id: tab.request.id.clone() },
+            cursor: name.len(),
+            input: name,
+            ..NamingState::default()
+        };
+        self.state.active_popup = ActivePopup::CollectionNaming;
+    }
+
+    fn sidebar_delete(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
+            let msg = format!("Delete \"{}\"?", node.label);
+            self.state.confirm_delete = ConfirmDeleteState {
+                message: msg,
+                target_id: node.id.clone(),
+                workspace: false,
+            };
+            self.state.active_popup = ActivePopup::ConfirmDelete;
+        }
+    }
+
+    fn sidebar_duplicate(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        if let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() {
+            if let crate::ui::sidebar::NodeKind::Request { method, .. } = &node.kind {
+                let new_req = CollectionRequest {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: format!("{} (copy)", node.label),
+                    method: method.clone(),
+                    url: String::new(),
+                    body_raw: String::new(),
+                    checks: Vec::new(),
+                    scripts: Default::default(),
+                    auth: Default::default(),
+                    depends_on: None,
+                    auto_retry: false,
+                    description: String::new(),
+                };
+                let ws_name = self.state.workspace.name.clone();
+                // Insert after cursor in the containing collection/folder
+                for col in &mut self.state.workspace.collections {
+                    if insert_after_in_list(
+                        &mut col.items,
+                        &node.id,
+                        CollectionItem::Request(new_req.clone()),
+                    ) {
+                        let _ = col_storage::save_collection_meta(&ws_name, col);
+                        break;
+                    }
+                    // Also check if the original is directly in the collection
+                    if col.items.iter().any(|item| match item {
+                        CollectionItem::Request(r) => r.id == node.id,
+                        _ => false,
+                    }) {
+                        col.items.push(CollectionItem::Request(new_req.clone()));
+                        let _ = col_storage::save_collection_meta(&ws_name, col);
+                        break;
+                    }
+                }
+                self.state.sidebar.invalidate_nodes();
+            } else if let crate::ui::sidebar::NodeKind::Folder { .. } = node.kind {
+                self.duplicate_folder(&node.id);
+            } else if let crate::ui::sidebar::NodeKind::Collection { .. } = node.kind {
+                self.duplicate_collection(&node.id);
+            }
+        }
+    }
+
+    /// Deep-copy a folder next to itself, with fresh ids throughout.
+    fn duplicate_folder(&mut self, folder_id: &str) {
+        let ws_name = self.state.workspace.name.clone();
+        for col in &mut self.state.workspace.collections {
+            let Some(CollectionItem::Folder(folder)) = find_item_in_list(&col.items, folder_id)
+            else {
+                continue;
+            };
+            let mut copy = folder.clone();
+            copy.name = format!("{} (copy)", copy.name);
+            let mut copy = CollectionItem::Folder(copy);
+            rekey_items(std::slice::from_mut(&mut copy));
+            insert_after_in_list(&mut col.items, folder_id, copy);
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+            self.state.sidebar.invalidate_nodes();
+            return;
+        }
+    }
+
+    /// Copy a whole collection under a new id and name, placed right after
+    /// the original.
+    fn duplicate_collection(&mut self, col_id: &str) {
+        let collections = &self.state.workspace.collections;
+        let Some(idx) = collections.iter().position(|c| c.id == col_id) else {
+            return;
+        };
+        // Collections are stored by name, so the copy needs one of its own
+        let taken = |name: &str| {
+            let slug = name.to_lowercase().replace(' ', "_");
+            collections.iter().any(|c| c.name.to_lowercase().replace(' ', "_") == slug)
+        };
+        let mut name = format!("{} (copy)", collections[idx].name);
+        let mut n = 2;
+        while taken(&name) {
+            name = format!("{} (copy {n})", collections[idx].name);
+            n += 1;
+        }
+
+        let mut copy = collections[idx].clone();
+        copy.id = uuid::Uuid::new_v4().to_string();
+        copy.name = name;
+        rekey_items(&mut copy.items);
+        let _ = col_storage::save_collection_meta(&self.state.workspace.name, &copy);
+        self.state.workspace.collections.insert(idx + 1, copy);
+        self.state.sidebar.invalidate_nodes();
+        self.save_workspace_file();
+    }
+
+    /// Mark the request or folder under the cursor for moving (`x` again
+    /// unmarks it).
+    fn sidebar_cut(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        if matches!(node.kind, NodeKind::Collection { .. }) {
+            return;
+        }
+        let sidebar = &mut self.state.sidebar;
+        sidebar.cut = if sidebar.cut.as_deref() == Some(node.id.as_str()) {
+            None
+        } else {
+            Some(node.id.clone())
+        };
+    }
+
+    /// Move the marked node into the collection or folder under the cursor,
+    /// or right after the request under it. A folder can't move into itself
+    /// or one of its descendants.
+    fn sidebar_paste(&mut self) {
+        let Some(cut_id) = self.state.sidebar.cut.clone() else {
+            return;
+        };
+        let nodes = flatten_tree(&self.state);
+        let Some(target) = nodes.get(self.state.sidebar.cursor).cloned() else {
+            return;
+        };
+        if target.id == cut_id {
+            return;
+        }
+        let collections = &self.state.workspace.collections;
+        let moved = collections.iter().find_map(|c| find_item_in_list(&c.items, &cut_id));
+        let Some(moved) = moved else {
+            self.state.sidebar.cut = None;
+            return;
+        };
+        if let CollectionItem::Folder(f) = moved
+            && (f.id == target.id || item_exists_in_list(&f.items, &target.id))
+        {
+            self.show_toast("Can't move a folder into itself".to_string());
+            return;
+        }
+        let Some(to_col) = self.find_collection_id_for_node(&target.id) else {
+            return;
+        };
+        let Some(from_col) = self.find_collection_id_for_node(&cut_id) else {
+            return;
+        };
+
+        let ws_name = self.state.workspace.name.clone();
+        let collections = &mut self.state.workspace.collections;
+        let Some(item) = collections
+            .iter_mut()
+            .find(|c| c.id == from_col)
+            .and_then(|c| take_item_from_list(&mut c.items, &cut_id))
+        else {
+            return;
+        };
+        if let Some(col) = collections.iter_mut().find(|c| c.id == to_col) {
+            match target.kind {
+                NodeKind::Collection { .. } => col.items.push(item),
+                NodeKind::Folder { .. } => {
+                    add_request_to_folder(&mut col.items, &target.id, item);
+                }
+                NodeKind::Request { .. } => {
+                    insert_after_in_list(&mut col.items, &target.id, item);
+                }
+            }
+        }
+        for col in collections.iter().filter(|c| c.id == from_col || c.id == to_col) {
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+        }
+        self.state.sidebar.cut = None;
+        // Show the moved node where it landed
+        if !matches!(target.kind, NodeKind::Request { .. }) {
+            self.state.sidebar.collapsed_ids.remove(&target.id);
+        }
+        self.state.sidebar.invalidate_nodes();
+    }
+
+    /// Swap the node under the cursor with its next (`down`) or previous
+    /// sibling. Requests and folders stay within their parent; collections
+    /// reorder in the workspace.
+    fn sidebar_reorder(&mut self, down: bool) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor).cloned() else {
+            return;
+        };
+        let ws_name = self.state.workspace.name.clone();
+        if matches!(node.kind, NodeKind::Collection { .. }) {
+            let collections = &mut self.state.workspace.collections;
+            let Some(idx) = collections.iter().position(|c| c.id == node.id) else {
+                return;
+            };
+            if !swap_sibling(collections, idx, down) {
+                return;
+            }
+            self.save_workspace_file();
+        } else {
+            let collections = &mut self.state.workspace.collections;
+            let Some(col) = collections.iter_mut().find(|c| item_exists_in_list(&c.items, &node.id))
+            else {
+                return;
+            };
+            if !swap_item_in_list(&mut col.items, &node.id, down) {
+                return;
+            }
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+        }
+        self.state.sidebar.invalidate_nodes();
+        // Keep the cursor on the moved node
+        if let Some(pos) = flatten_tree(&self.state).iter().position(|n| n.id == node.id) {
+            self.state.sidebar.cursor = pos;
+            self.clamp_sidebar_scroll();
+        }
+        self.state.sidebar.moved = Some((node.id, Instant::now()));
+    }
+
+    // ─── Open tab management ──────────────────────────────────────────────────
+
+    fn next_open_tab(&mut self) {
+        self.step_open_tab(1);
+    }
+
+    fn prev_open_tab(&mut self) {
+        self.step_open_tab(-1);
+    }
+
+    /// Move `step` tabs along the active tab's pane, wrapping around.
+    fn step_open_tab(&mut self, step: isize) {
+        let ws = &mut self.state.workspace;
+        let pane = ws.pane_tabs(ws.active_tab_idx);
+        let Some(pos) = pane.iter().position(|&i| i == ws.active_tab_idx) else {
+            return;
+        };
+        let len = pane.len() as isize;
+        ws.active_tab_idx = pane[(pos as isize + step).rem_euclid(len) as usize];
+    }
+
+    /// `Ctrl+Shift+Right` / `Ctrl+Shift+Left`: move the active tab into the
+    /// right-hand pane, splitting the request area, or back into the left
+    /// one. The tab keeps focus.
+    fn move_tab_to_pane(&mut self, right: bool) {
+        let ws = &mut self.state.workspace;
+        let idx = ws.active_tab_idx;
+        if ws.in_right_pane(idx) == right {
+            if right && ws.split.is_some() {
+                self.show_toast("Only one split pane is supported".to_string());
+            }
+            return;
+        }
+        let pane = ws.pane_tabs(idx);
+        if pane.len() < 2 && ws.split.is_none() {
+            self.show_toast("Open another tab to split the request area".to_string());
+            return;
+        }
+        // The pane being left goes on showing a neighbour of the tab
+        let pos = pane.iter().position(|&i| i == idx).unwrap_or(0);
+        let neighbour = pane.get(pos + 1).or(pos.checked_sub(1).and_then(|p| pane.get(p)));
+        let neighbour = neighbour.map(|&i| ws.open_tabs[i].request.id.clone()).unwrap_or_default();
+        let id = ws.open_tabs[idx].request.id.clone();
+        let split = ws.split.get_or_insert_with(TabGroup::default);
+        if right {
+            split.tab_ids.push(id);
+        } else {
+            split.tab_ids.retain(|t| *t != id);
+        }
+        split.other_shown = neighbour;
+        ws.normalize_split();
+    }
+
+    /// `Ctrl+Left` / `Ctrl+Right`: give focus to the left or right pane of
+    /// a split request area.
+    fn focus_pane(&mut self, right: bool) {
+        let ws = &self.state.workspace;
+        if ws.in_right_pane(ws.active_tab_idx) == right {
+            return;
+        }
+        let Some(other) = ws.other_pane_tab() else {
+            return;
+        };
+        self.sync_active_tab_to_collection();
+        self.state.workspace.focus_tab(other);
+    }
+
+    /// Alt+w, `x` on the tabs bar, "Close tab": close the active tab, asking
+    /// first when it has edits its saved request doesn't.
+    fn request_close_active_tab(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        let saved = tab
+            .collection_id
+            .as_deref()
+            .and_then(|id| find_col_request_by_id(&self.state.workspace.collections, id));
+        let unsaved = tab.is_dirty && !tab.pinned && saved.is_some_and(|saved| differs_from_saved(saved, &tab.request));
+        if !unsaved {
+            self.sync_active_tab_to_collection();
+            self.close_active_tab();
+            return;
+        }
+        self.state.confirm_close = ConfirmCloseState {
+            request_id: tab.request.id.clone(),
+            name: tab_label(tab),
+        };
+        self.state.active_popup = ActivePopup::ConfirmClose;
+    }
+
+    fn close_active_tab(&mut self) {
+        let idx = self.state.workspace.active_tab_idx;
+        let len = self.state.workspace.open_tabs.len();
+        if len == 0 {
+            return;
+        }
+        if self.state.workspace.open_tabs[idx].pinned {
+            self.show_toast("Tab is pinned, p on the tabs bar unpins it".to_string());
+            return;
+        }
+        // Focus the next tab of the same pane, else the other pane's
+        let ws = &self.state.workspace;
+        let pane = ws.pane_tabs(idx);
+        let pos = pane.iter().position(|&i| i == idx).unwrap_or(0);
+        let next = pane.get(pos + 1).or(pos.checked_sub(1).and_then(|p| pane.get(p))).copied();
+        let next = next.or_else(|| ws.other_pane_tab()).map(|i| ws.open_tabs[i].request.id.clone());
+        self.state.workspace.open_tabs.remove(idx);
+        self.state.workspace.normalize_split();
+        if self.state.workspace.open_tabs.is_empty() {
+            self.state.workspace.open_tabs.push(RequestTab::default());
+            self.state.workspace.active_tab_idx = 0;
+        } else {
+            self.state.workspace.active_tab_idx = next
+                .and_then(|id| self.tab_index(&id))
+                .unwrap_or(idx.min(self.state.workspace.open_tabs.len() - 1));
+        }
+    }
+
+    /// `p` on the open-tabs bar: pin or unpin the active tab. Pins are saved
+    /// with the workspace right away.
+    fn toggle_tab_pin(&mut self) {
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        tab.pinned = !tab.pinned;
+        let message = match (tab.pinned, tab.collection_id.is_some()) {
+            (false, _) => "Tab unpinned",
+            (true, true) => "Tab pinned",
+            (true, false) => "Tab pinned; save it (Ctrl+S) to keep it pinned next time",
+        };
+        self.save_workspace_file();
+        self.show_toast(message.to_string());
+    }
+
+    /// Reopen the saved requests whose tabs were pinned when the workspace
+    /// was last saved.
+    fn restore_pinned_tabs(ws: &mut WorkspaceState) {
+        for id in ws_storage::load_workspace(&ws.name).pinned_tabs {
+            let Some(saved) = find_col_request_by_id(&ws.collections, &id) else {
+                continue;
+            };
+            ws.open_tabs.push(RequestTab {
+                request: saved_request_state(saved),
+                collection_id: Some(id),
+                pinned: true,
+                ..RequestTab::default()
+            });
+        }
+    }
+
+    // ─── Workspace persistence ────────────────────────────────────────────────
+
+    /// Make `ws` the current workspace, with a client that uses its cookie jar.
+    fn set_workspace(&mut self, mut ws: WorkspaceState) {
+        Self::restore_pinned_tabs(&mut ws);
+        if ws.open_tabs.is_empty() {
+            ws.open_tabs.push(RequestTab::default());
+        }
+        self.client = build_client(&ws.cookies, &ws.proxy);
+        self.state.workspace = ws;
+        self.state.sidebar.invalidate_nodes();
+        self.touch_workspace();
+    }
+
+    /// Record that the active workspace was opened now, in `state.toml`.
+    fn touch_workspace(&mut self) {
+        let mut shared = state_storage::load_state();
+        shared.last_opened.insert(self.state.workspace.name.clone(), chrono::Utc::now());
+        let _ = state_storage::save_state(&shared);
+        self.state.workspace_last_opened = shared.last_opened;
+    }
+
+    /// Write the cookie jar to disk unless the workspace keeps cookies for
+    /// the session only.
+    fn save_cookies(&self) {
+        let ws = &self.state.workspace;
+        if ws.persist_cookies {
+            let _ = cookie_storage::save_cookies(&ws.name, &ws.cookies.read());
+        }
+    }
+
+    /// Write the active workspace's `workspace.toml` from in-memory state.
+    fn save_workspace_file(&self) {
+        let ws = &self.state.workspace;
+        let ws_file = crate::state::workspace::WorkspaceFile {
+            name: ws.name.clone(),
+            active_environment_idx: ws.active_environment_idx,
+            show_line_numbers: ws.show_line_numbers,
+            audit_log: ws.audit_log,
+            audit_note: ws.audit_note.clone(),
+            persist_cookies: ws.persist_cookies,
+            send_queue: ws.send_queue,
+            queue_gap_ms: ws.queue_gap_ms,
+            auto_retry: ws.auto_retry,
+            retry_max_secs: ws.retry_max_secs,
+            max_body_mb: ws.max_body_mb,
+            collection_order: ws.collections.iter().map(|c| c.id.clone()).collect(),
+            pinned_tabs: ws.open_tabs.iter().filter(|t| t.pinned).filter_map(|t| t.collection_id.clone()).collect(),
+            proxy: ws.proxy.clone(),
+        };
+        let _ = ws_storage::save_workspace(&ws_file);
+    }
+
+    // ─── Collection sync ──────────────────────────────────────────────────────
+
+    /// `Ctrl+S`: sync a linked tab to its collection, or pick where to save
+    /// a scratch tab.
+    fn save_active_tab(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        if tab.collection_id.is_some() {
+            let name = tab.request.name.clone();
+            self.sync_active_tab_to_collection();
+            self.show_toast(format!("✓ Saved {name}"));
+            return;
+        }
+        if self.state.workspace.collections.is_empty() {
+            self.show_toast("Create a collection to save requests into".to_string());
+            return;
+        }
+        let name = tab.request.name.clone();
+        // Collections start open, folders closed
+        let expanded = self.state.workspace.collections.iter().map(|c| c.id.clone()).collect();
+        self.state.save_request = SaveRequestState {
+            cursor: name.len(),
+            name,
+            expanded,
+            ..SaveRequestState::default()
+        };
+        self.state.mode = Mode::Normal;
+        self.state.active_popup = ActivePopup::SaveRequest;
+    }
+
+    /// First pick the target collection or folder, then confirm the name.
+    fn handle_save_request_key(&mut self, key: KeyEvent) {
+        let targets = save_targets(&self.state);
+        let save = &mut self.state.save_request;
+        if !save.naming {
+            let target = targets.get(save.selected);
+            match key.code {
+                KeyCode::Esc => self.state.active_popup = ActivePopup::None,
+                KeyCode::Enter if target.is_some() => save.naming = true,
+                KeyCode::Up | KeyCode::Char('k') => save.selected = save.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    save.selected = (save.selected + 1).min(targets.len().saturating_sub(1));
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    if let Some(target) = target {
+                        save.expanded.insert(target.id.clone());
+                    }
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    if let Some(target) = target {
+                        save.expanded.remove(&target.id);
+                    }
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(target) = target
+                        && !save.expanded.remove(&target.id)
+                    {
+                        save.expanded.insert(target.id.clone());
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => save.naming = false,
+            KeyCode::Enter => self.confirm_save_request(),
+            KeyCode::Char(c) => {
+                save.name.insert(save.cursor, c);
+                save.cursor += c.len_utf8();
+            }
+            KeyCode::Backspace if save.cursor > 0 => {
+                let prev = Self::prev_char_boundary_of(&save.name, save.cursor);
+                save.name.drain(prev..save.cursor);
+                save.cursor = prev;
+            }
+            KeyCode::Left => save.cursor = Self::prev_char_boundary_of(&save.name, save.cursor),
+            KeyCode::Right => save.cursor = Self::next_char_boundary_of(&save.name, save.cursor),
+            KeyCode::Home => save.cursor = 0,
+            KeyCode::End => save.cursor = save.name.len(),
+            _ => {}
+        }
+    }
+
+    /// Add the scratch tab's request to the selected collection or folder and
+    /// link the tab to it.
+    fn confirm_save_request(&mut self) {
+        let name = self.state.save_request.name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let targets = save_targets(&self.state);
+        let Some(target) = targets.get(self.state.save_request.selected) else {
+            return;
+        };
+        let Some(collection_id) = self.find_collection_id_for_node(&target.id) else {
+            return;
+        };
+        let folder_id = matches!(target.kind, NodeKind::Folder { .. }).then(|| target.id.clone());
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let mut req = CollectionRequest::new(&name);
+        store_request_state(&mut req, &tab.request);
+        tab.request.name = name.clone();
+        tab.collection_id = Some(req.id.clone());
+        tab.is_dirty = false;
+
+        let ws_name = self.state.workspace.name.clone();
+        if let Some(col) = self
+            .state
+            .workspace
+            .collections
+            .iter_mut()
+            .find(|c| c.id == collection_id)
+        {
+            match folder_id {
+                Some(fid) => {
+                    add_request_to_folder(&mut col.items, &fid, CollectionItem::Request(req));
+                }
+                None => col.items.push(CollectionItem::Request(req)),
+            }
+            let _ = col_storage::save_collection_meta(&ws_name, col);
+        }
+        self.state.sidebar.invalidate_nodes();
+        self.state.active_popup = ActivePopup::None;
+        self.show_toast(format!("✓ Saved {name}"));
+    }
+
+    /// Flash a short message in the status bar.
+    fn show_toast(&mut self, message: String) {
+        self.state.toast = Some((message, Instant::now()));
+    }
+
+    fn sync_active_tab_to_collection(&mut self) {
+        self.sync_tab_to_collection(self.state.workspace.active_tab_idx);
+    }
+
+    fn sync_tab_to_collection(&mut self, idx: usize) {
+        if let Some(tab) = self.state.workspace.open_tabs.get(idx) {
+            let Some(req_id) = tab.collection_id.clone() else { return };
+            let request = tab.request.clone();
+            let ws_name = self.state.workspace.name.clone();
+            for col in &mut self.state.workspace.collections {
+                if update_col_request_state(&mut col.items, &req_id, &request) {
+                    let _ = col_storage::save_collection_meta(&ws_name, col);
+                    break;
+                }
+            }
+            // The name or method may have changed
+            self.state.sidebar.invalidate_nodes();
+            if let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) {
+                tab.is_dirty = false;
+            }
+        }
+    }
+
+    // ─── Insert key handling ──────────────────────────────────────────────────
+
+    fn handle_insert_key(&mut self, key: KeyEvent) {
+        // Keys that change request text (Enter in the URL bar sends instead)
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let edits = match self.state.focus {
+            Focus::UrlBar => {
+                matches!(key.code, KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete) && !ctrl
+                    || matches!(key.code, KeyCode::Char('w' | 'u')) && ctrl
+            }
+            Focus::Editor => matches!(
+                key.code,
+                KeyCode::Char(_)
+                    | KeyCode::Backspace
+                    | KeyCode::Delete
+                    | KeyCode::Enter
+                    | KeyCode::Tab
+            ),
+            _ => false,
+        };
+        if edits {
+            self.mark_active_tab_dirty();
+        }
+        let before = self.undo_snapshot();
+        if self.state.completion.active && self.handle_completion_key(key) {
+            self.record_undo(before, false);
+            return;
+        }
+        self.handle_insert_edit_key(key);
+        let typed = matches!(key.code, KeyCode::Char(_))
+            && !key.modifiers.contains(KeyModifiers::CONTROL);
+        self.record_undo(before, typed);
+        self.scroll_url_to_cursor();
+        self.update_completion();
+    }
+
+    /// `Ctrl+A` / `Ctrl+C` in the URL bar, and typing over its selection.
+    /// Returns `true` if the key was fully handled.
+    fn handle_url_selection_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let Some(tab) = self.state.active_tab_mut() else {
+            return false;
+        };
+        let req = &mut tab.request;
+        match key.code {
+            KeyCode::Char('a') if ctrl => {
+                req.select_all_url();
+                true
+            }
+            KeyCode::Char('c') if ctrl => {
+                if let Some(range) = req.url_selection()
+                    && let Ok(mut clipboard) = arboard::Clipboard::new()
+                {
+                    let _ = clipboard.set_text(&req.url[range]);
+                }
+                true
+            }
+            // A typed character replaces the selection
+            KeyCode::Char(_) => {
+                req.delete_url_selection();
+                false
+            }
+            KeyCode::Backspace | KeyCode::Delete => req.delete_url_selection(),
+            _ => {
+                req.clear_url_selection();
+                false
+            }
+        }
+    }
+
+    /// The text Normal-mode `w b e 0 $ x dd` act on: the URL, or the body
+    /// when it has text. Form and binary bodies have none.
+    fn normal_text_field(&self) -> Option<EditField> {
+        use crate::state::request_state::RequestBody;
+        let field = self.edit_field()?;
+        let body = &self.state.active_tab()?.request.body;
+        match field {
+            EditField::Url => Some(field),
+            EditField::Body if matches!(body, RequestBody::Json(_) | RequestBody::Text(_)) => Some(field),
+            _ => None,
+        }
+    }
+
+    /// `w` / `b` / `e` word motions and `0` / `$` line start / end.
+    fn move_text_cursor(&mut self, motion: char) {
+        let Some(field) = self.normal_text_field() else {
+            return;
+        };
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let Some((text, cursor)) = Self::edit_field_mut(&mut tab.request, field) else {
+            return;
+        };
+        let url = field == EditField::Url;
+        let pos = (*cursor).min(text.len());
+        *cursor = match motion {
+            'w' if url => url_word_next(text, pos),
+            'w' => word_next(text, pos),
+            'b' if url => url_word_prev(text, pos),
+            'b' => word_prev(text, pos),
+            'e' if url => url_word_end(text, pos),
+            'e' => word_end(text, pos),
+            '0' => line_start(text, pos),
+            _ => line_end(text, pos),
+        };
+        self.scroll_url_to_cursor();
+    }
+
+    /// `x` deletes the char under the cursor, `dd` the whole body line.
+    fn delete_text(&mut self, line: bool) {
+        let Some(field) = self.normal_text_field() else {
+            return;
+        };
+        let before = self.field_entry(field);
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let Some((text, cursor)) = Self::edit_field_mut(&mut tab.request, field) else {
+            return;
+        };
+        let pos = (*cursor).min(text.len());
+        let range = if line { line_with_break(text, pos) } else { pos..Self::next_char_boundary_of(text, pos) };
+        if range.is_empty() {
+            return;
+        }
+        let taken = line.then(|| text[line_start(text, pos)..line_end(text, pos)].to_string());
+        text.drain(range.clone());
+        *cursor = if line { line_start(text, range.start) } else { pos.min(text.len()) };
+        if taken.is_some() {
+            self.state.line_register = taken;
+        }
+        self.mark_active_tab_dirty();
+        self.record_undo(before, false);
+        self.scroll_url_to_cursor();
+    }
+
+    /// `yy`: copy the body's cursor line into the line register.
+    fn yank_body_line(&mut self) {
+        let Some(req) = self.state.active_tab().map(|t| &t.request) else {
+            return;
+        };
+        if let RequestBody::Json(text) | RequestBody::Text(text) = &req.body {
+            let pos = req.body_cursor.min(text.len());
+            self.state.line_register = Some(text[line_start(text, pos)..line_end(text, pos)].to_string());
+        }
+    }
+
+    /// `p`: put the line register on a new line below the body's cursor
+    /// line, the cursor on its first non-blank char.
+    fn put_body_line(&mut self) {
+        let Some(line) = self.state.line_register.clone() else {
+            return;
+        };
+        let before = self.field_entry(EditField::Body);
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        let (RequestBody::Json(text) | RequestBody::Text(text)) = &mut req.body else {
+            return;
+        };
+        let end = line_end(text, req.body_cursor.min(text.len()));
+        text.insert_str(end, &format!("\n{line}"));
+        let indent = line.len() - line.trim_start().len();
+        req.body_cursor = end + 1 + indent;
+        self.mark_active_tab_dirty();
+        self.record_undo(before, false);
+    }
+
+    /// Ctrl+W, Ctrl+U, Alt+b / Alt+f and Ctrl+← / Ctrl+→: the word-wise
+    /// keys every text input shares.
+    fn is_word_edit_key(key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('w' | 'u') => ctrl,
+            KeyCode::Char('b' | 'f') => alt,
+            KeyCode::Left | KeyCode::Right => ctrl,
+            _ => false,
+        }
+    }
+
+    /// Apply a word-wise key to `text`: Ctrl+W deletes the word before the
+    /// cursor, Ctrl+U everything back to the line start, the others move by
+    /// word. With `url`, `/ . ? & =` also end words.
+    fn word_edit_key(text: &mut String, cursor: &mut usize, key: KeyEvent, url: bool) {
+        let pos = (*cursor).min(text.len());
+        let prev = if url { url_word_prev(text, pos) } else { word_prev(text, pos) };
+        *cursor = match key.code {
+            KeyCode::Char('w' | 'u') => {
+                let start = if key.code == KeyCode::Char('w') { prev } else { line_start(text, pos) };
+                text.drain(start..pos);
+                start
+            }
+            KeyCode::Char('b') | KeyCode::Left => prev,
+            _ if url => url_word_next(text, pos),
+            _ => word_next(text, pos),
+        };
+    }
+
+    /// Scroll the URL bar so its cursor stays on screen.
+    fn scroll_url_to_cursor(&mut self) {
+        let width = self.state.hit_areas.borrow().url_input.width as usize;
+        if let Some(tab) = self.state.active_tab_mut() {
+            let req = &mut tab.request;
+            let column = crate::ui::request::url_bar::cursor_column(&req.url, req.url_cursor);
+            req.url_scroll_offset =
+                crate::ui::request::url_bar::scroll_to_cursor(req.url_scroll_offset, column, width);
+        }
+    }
+
+    /// Scroll the body editor so its cursor stays within the rows last drawn.
+    fn scroll_body_to_cursor(&mut self) {
+        let height = self.state.hit_areas.borrow().body_editor.height as usize;
+        if let Some(tab) = self.state.active_tab_mut() {
+            let req = &mut tab.request;
+            let (RequestBody::Json(text) | RequestBody::Text(text)) = &req.body else {
+                return;
+            };
+            let (row, _) = cursor_row_col(text, req.body_cursor);
+            let offset = req.body_scroll_offset as usize;
+            req.body_scroll_offset = crate::ui::request::url_bar::scroll_to_cursor(offset, row, height) as u16;
+        }
+    }
+
+    /// Move the body cursor and view down (or up) a page, or half a page for
+    /// `Ctrl+d` / `Ctrl+u`.
+    fn page_body(&mut self, down: bool, half: bool) {
+        let height = self.state.hit_areas.borrow().body_editor.height as usize;
+        let rows = if half { height / 2 } else { height }.max(1);
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        let (RequestBody::Json(text) | RequestBody::Text(text)) = &req.body else {
+            return;
+        };
+        for _ in 0..rows {
+            req.body_cursor = if down {
+                Self::body_move_down(text, req.body_cursor)
+            } else {
+                Self::body_move_up(text, req.body_cursor)
+            };
+        }
+        let last = text.split('\n').count().saturating_sub(height);
+        let offset = req.body_scroll_offset as usize;
+        let offset = if down { (offset + rows).min(last) } else { offset.saturating_sub(rows) };
+        req.body_scroll_offset = offset as u16;
+    }
+
+    /// `gg` / `G` in the body: the start of its first or last line.
+    fn jump_body(&mut self, end: bool) {
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        if let RequestBody::Json(text) | RequestBody::Text(text) = &req.body {
+            req.body_cursor = if end { text.rfind('\n').map_or(0, |i| i + 1) } else { 0 };
+        }
+    }
+
+    /// Furthest the response body scrolls: its last line at the bottom of
+    /// the view.
+    fn response_scroll_limit(&self) -> u16 {
+        let hits = self.state.hit_areas.borrow();
+        hits.response_body_lines.saturating_sub(hits.response_body.height as usize) as u16
+    }
+
+    /// Scroll the response body by `delta` lines, or to its top or bottom
+    /// with `isize::MIN` / `isize::MAX`.
+    fn scroll_response_by(&mut self, delta: isize) {
+        let last = self.response_scroll_limit();
+        if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) {
+            let offset = (resp.scroll_offset as usize).saturating_add_signed(delta);
+            resp.scroll_offset = offset.min(last as usize) as u16;
+        }
+    }
+
+    /// Turn word wrap on or off, keeping the same body line at the top: the
+    /// scroll offset counts wrapped rows while wrapping and lines otherwise.
+    fn toggle_word_wrap(&mut self) {
+        let wrap = !self.state.word_wrap;
+        self.state.word_wrap = wrap;
+        let width = self.state.hit_areas.borrow().response_body.width as usize;
+        if let Some(resp) = self.state.active_tab_mut().and_then(|t| t.response.as_mut()) {
+            let rows = wrapped_rows(&body_text(resp), width);
+            let offset = resp.scroll_offset as usize;
+            let offset = if wrap { rows.iter().take(offset).sum() } else { line_at_row(&rows, offset) };
+            resp.scroll_offset = offset as u16;
+            resp.h_scroll = 0;
+        }
+    }
+
+    /// Whether `←` / `→` scroll the response body sideways rather than switch
+    /// response tabs: an unwrapped body on the Body tab.
+    fn body_scrolls_sideways(&self) -> bool {
+        !self.state.word_wrap
+            && self.state.active_tab().is_some_and(|t| {
+                t.response_tab == ResponseTab::Body
+                    && t.ws.is_none()
+                    && t.sse.is_none()
+                    && t.response.as_ref().is_some_and(|r| !r.tree_view || r.filter.result.is_some())
+            })
+    }
+
+    /// Response body view height, for paging.
+    fn response_page(&self) -> isize {
+        (self.state.hit_areas.borrow().response_body.height as isize).max(1)
+    }
+
+    // ─── Undo / redo ──────────────────────────────────────────────────────────
+
+    /// The undoable field under the cursor: the URL, the selected header
+    /// cell or the body.
+    fn edit_field(&self) -> Option<EditField> {
+        let tab = self.state.active_tab()?;
+        match (&self.state.focus, &tab.active_tab) {
+            (Focus::UrlBar, _) => Some(EditField::Url),
+            (Focus::Editor, ActiveTab::Body) => Some(EditField::Body),
+            (Focus::Editor, ActiveTab::Headers) => Some(EditField::Header {
+                row: tab.request.headers_row,
+                col: tab.request.headers_col,
+            }),
+            (Focus::Editor, ActiveTab::Auth) => Some(EditField::Auth {
+                field: tab.request.auth_field,
+            }),
+            _ => None,
+        }
+    }
+
+    fn edit_field_mut(req: &mut RequestState, field: EditField) -> Option<(&mut String, &mut usize)> {
+        match field {
+            EditField::Url => Some((&mut req.url, &mut req.url_cursor)),
+            EditField::Body => Some((Self::body_text_mut(&mut req.body)?, &mut req.body_cursor)),
+            EditField::Header { row, col } => {
+                let text = Self::headers_active_text_mut(&mut req.headers, row, col)?;
+                Some((text, &mut req.headers_cursor))
+            }
+            EditField::Auth { field } => Some((req.auth.field_mut(field)?, &mut req.auth_cursor)),
+            EditField::Method | EditField::HeaderRows => None,
+        }
+    }
+
+    /// Current state of `field` in the active tab.
+    fn field_entry(&mut self, field: EditField) -> Option<UndoEntry> {
+        let req = &mut self.state.active_tab_mut()?.request;
+        let entry = match field {
+            EditField::Method => {
+                UndoEntry { field, text: req.method.as_str().to_string(), cursor: 0, rows: Vec::new() }
+            }
+            EditField::HeaderRows => {
+                UndoEntry { field, text: String::new(), cursor: req.headers_row, rows: req.headers.clone() }
+            }
+            _ => {
+                let (text, cursor) = Self::edit_field_mut(req, field)?;
+                UndoEntry { field, text: text.clone(), cursor: *cursor, rows: Vec::new() }
+            }
+        };
+        Some(entry)
+    }
+
+    fn undo_snapshot(&mut self) -> Option<UndoEntry> {
+        use crate::state::request_state::RequestBody;
+        let field = self.edit_field()?;
+        // Don't turn an empty body into JSON just by looking at it
+        if field == EditField::Body && self.state.active_tab()?.request.body == RequestBody::None {
+            return Some(UndoEntry { field, text: String::new(), cursor: 0, rows: Vec::new() });
+        }
+        self.field_entry(field)
+    }
+
+    /// Push `before` onto the active tab's undo stack if the edit changed
+    /// that field. Edits in Insert mode join the session's undo unit.
+    fn record_undo(&mut self, before: Option<UndoEntry>, typed: bool) {
+        let Some(before) = before else {
+            return;
+        };
+        let changed = self
+            .field_entry(before.field)
+            .is_some_and(|after| after.text != before.text || after.rows != before.rows);
+        let insert = self.state.mode == Mode::Insert;
+        if changed && let Some(tab) = self.state.active_tab_mut() {
+            if insert {
+                tab.undo.record_insert(before);
+            } else {
+                tab.undo.record(before, typed, Instant::now());
+            }
+        }
+    }
+
+    /// Restore the last entry of the undo (or redo) history, moving the
+    /// text it replaces to the opposite history.
+    fn undo_edit(&mut self, redo: bool) {
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let popped = if redo { tab.undo.redo() } else { tab.undo.undo() };
+        let Some(entry) = popped else {
+            return;
+        };
+        let Some(current) = self.field_entry(entry.field) else {
+            // The header row it belonged to is gone
+            return;
+        };
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        if redo {
+            tab.undo.push(current);
+        } else {
+            tab.undo.push_redo(current);
+        }
+        match entry.field {
+            EditField::Header { row, col } => {
+                tab.request.headers_row = row;
+                tab.request.headers_col = col;
+            }
+            EditField::Auth { field } => tab.request.auth_field = field,
+            EditField::Method => {
+                tab.request.method = crate::state::request_state::HttpMethod::from_str_or_get(&entry.text);
+            }
+            EditField::HeaderRows => {
+                tab.request.headers = entry.rows;
+                tab.request.headers_row = entry.cursor.min(tab.request.headers.len().saturating_sub(1));
+            }
+            EditField::Url | EditField::Body => {}
+        }
+        if let Some((text, cursor)) = Self::edit_field_mut(&mut tab.request, entry.field) {
+            *text = entry.text;
+            *cursor = entry.cursor.min(text.len());
+        }
+        self.state.completion = CompletionState::default();
+        self.mark_active_tab_dirty();
+        self.scroll_url_to_cursor();
+        self.scroll_body_to_cursor();
+    }
+
+    fn handle_insert_edit_key(&mut self, key: KeyEvent) {
+        // Check if we're in sidebar search mode
+        if self.state.focus == Focus::Sidebar && self.state.sidebar.search_mode {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.sidebar.search_mode = false;
+                    self.state.sidebar.search_query.clear();
+                    self.state.mode = Mode::Normal;
+                }
+                KeyCode::Char(c) => {
+                    self.state.sidebar.search_query.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.state.sidebar.search_query.pop();
+                    if self.state.sidebar.search_query.is_empty() {
+                        self.state.sidebar.search_mode = false;
+                        self.state.mode = Mode::Normal;
+                    }
+                }
+                _ => {}
+            }
+            self.state.sidebar.invalidate_nodes();
+            return;
+        }
+        if self.state.focus == Focus::UrlBar && self.handle_url_selection_key(key) {
+            return;
+        }
+        if Self::is_word_edit_key(key)
+            && let Some(field) = self.edit_field()
+            && let Some(tab) = self.state.active_tab_mut()
+            && let Some((text, cursor)) = Self::edit_field_mut(&mut tab.request, field)
+        {
+            Self::word_edit_key(text, cursor, key, field == EditField::Url);
+            return;
+        }
+
+        let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Headers) {
+            self.handle_headers_insert_key(key);
+            return;
+        }
+        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Checks) {
+            self.handle_checks_insert_key(key);
+            return;
+        }
+        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Scripts) {
+            self.handle_scripts_insert_key(key);
+            return;
+        }
+        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Docs) {
+            self.handle_docs_insert_key(key);
+            return;
+        }
+        if self.state.focus == Focus::Editor && active_tab == Some(ActiveTab::Auth) {
+            self.handle_auth_insert_key(key);
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => self.state.mode = Mode::Normal,
+            KeyCode::Enter => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    self.state.mode = Mode::Normal;
+                    self.send_request();
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                            let cursor = tab.request.body_cursor;
+                            text.insert(cursor, '\n');
+                            tab.request.body_cursor = cursor + 1;
+                        }
+                    }
+                }
+            }
+            KeyCode::Char(c) => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.url_cursor;
+                        tab.request.url.insert(cursor, c);
+                        tab.request.url_cursor += c.len_utf8();
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                            let cursor = tab.request.body_cursor;
+                            text.insert(cursor, c);
+                            tab.request.body_cursor = cursor + c.len_utf8();
+                        }
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.url_cursor;
+                        if cursor > 0 {
+                            let url = tab.request.url.clone();
+                            let prev = Self::prev_char_boundary_of(&url, cursor);
+                            tab.request.url.drain(prev..cursor);
+                            tab.request.url_cursor = prev;
+                        }
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        if cursor > 0 {
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                let prev = Self::prev_char_boundary_of(text, cursor);
+                                text.drain(prev..cursor);
+                                tab.request.body_cursor = prev;
+                            }
+                        }
+                    }
+                }
+            }
+            KeyCode::Delete => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.url_cursor;
+                        let url = tab.request.url.clone();
+                        if cursor < url.len() {
+                            let next = Self::next_char_boundary_of(&url, cursor);
+                            tab.request.url.drain(cursor..next);
+                        }
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let body_len = match &tab.request.body {
+                            crate::state::request_state::RequestBody::Json(s)
+                            | crate::state::request_state::RequestBody::Text(s) => s.len(),
+                            _ => 0,
+                        };
+                        if cursor < body_len {
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                let next = Self::next_char_boundary_of(text, cursor);
+                                text.drain(cursor..next);
+                            }
+                        }
+                    }
+                }
+            }
+            KeyCode::Left => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.url_cursor;
+                        let url = tab.request.url.clone();
+                        tab.request.url_cursor = Self::prev_char_boundary_of(&url, cursor);
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let new_cursor =
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                Self::prev_char_boundary_of(text, cursor)
+                            } else {
+                                cursor
+                            };
+                        tab.request.body_cursor = new_cursor;
+                    }
+                }
+            }
+            KeyCode::Right => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.url_cursor;
+                        let url = tab.request.url.clone();
+                        tab.request.url_cursor = Self::next_char_boundary_of(&url, cursor);
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let new_cursor =
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                Self::next_char_boundary_of(text, cursor)
+                            } else {
+                                cursor
+                            };
+                        tab.request.body_cursor = new_cursor;
+                    }
+                }
+            }
+            KeyCode::Up => {
+                if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let body_snapshot = match &tab.request.body {
+                            crate::state::request_state::RequestBody::Json(s)
+                            | crate::state::request_state::RequestBody::Text(s) => s.clone(),
+                            _ => String::new(),
+                        };
+                        tab.request.body_cursor = Self::body_move_up(&body_snapshot, cursor);
+                    }
+                }
+            }
+            KeyCode::Down => {
+                if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let body_snapshot = match &tab.request.body {
+                            crate::state::request_state::RequestBody::Json(s)
+                            | crate::state::request_state::RequestBody::Text(s) => s.clone(),
+                            _ => String::new(),
+                        };
+                        tab.request.body_cursor = Self::body_move_down(&body_snapshot, cursor);
+                    }
+                }
+            }
+            KeyCode::PageDown | KeyCode::PageUp if matches!(self.state.focus, Focus::Editor) => {
+                self.page_body(key.code == KeyCode::PageDown, false);
+            }
+            KeyCode::Home => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.url_cursor = 0;
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let new_cursor =
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                let before = &text[..cursor.min(text.len())];
+                                match before.rfind('\n') {
+                                    Some(i) => i + 1,
+                                    None => 0,
+                                }
+                            } else {
+                                cursor
+                            };
+                        tab.request.body_cursor = new_cursor;
+                    }
+                }
+            }
+            KeyCode::End => {
+                if matches!(self.state.focus, Focus::UrlBar) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        tab.request.url_cursor = tab.request.url.len();
+                    }
+                } else if matches!(self.state.focus, Focus::Editor) {
+                    if let Some(tab) = self.state.active_tab_mut() {
+                        let cursor = tab.request.body_cursor;
+                        let new_cursor =
+                            if let Some(text) = Self::body_text_mut(&mut tab.request.body) {
+                                let after_start = cursor.min(text.len());
+                                let after = &text[after_start..];
+                                match after.find('\n') {
+                                    Some(i) => after_start + i,
+                                    None => text.len(),
+                                }
+                            } else {
+                                cursor
+                            };
+                        tab.request.body_cursor = new_cursor;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // ─── {{variable}} completion ──────────────────────────────────────────────
+
+    /// Text and cursor of the field being edited, if it supports completion
+    /// (URL bar, header cells, body editor).
+    fn completion_field_mut(&mut self) -> Option<(&mut String, &mut usize)> {
+        let focus = self.state.focus.clone();
+        let tab = self.state.active_tab_mut()?;
+        let req = &mut tab.request;
+        match (focus, &tab.active_tab) {
+            (Focus::UrlBar, _) => Some((&mut req.url, &mut req.url_cursor)),
+            (Focus::Editor, ActiveTab::Headers) => {
+                let text = Self::headers_active_text_mut(&mut req.headers, req.headers_row, req.headers_col)?;
+                Some((text, &mut req.headers_cursor))
+            }
+            (Focus::Editor, ActiveTab::Auth) => {
+                Some((req.auth.field_mut(req.auth_field)?, &mut req.auth_cursor))
+            }
+            (Focus::Editor, ActiveTab::Body) => {
+                use crate::state::request_state::RequestBody;
+                match &mut req.body {
+                    RequestBody::Json(text) | RequestBody::Text(text) => {
+                        Some((text, &mut req.body_cursor))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Open, refilter or close the completion popup after an edit.
+    fn update_completion(&mut self) {
+        let was_active = self.state.completion.active;
+        let anchor = self.state.completion.anchor;
+        let prefix = if self.state.mode != Mode::Insert {
+            None
+        } else {
+            match self.completion_field_mut() {
+                None => None,
+                Some((text, cursor)) => {
+                    let cursor = (*cursor).min(text.len());
+                    if was_active {
+                        // Still inside the `{{…` we opened on?
+                        text.get(anchor..cursor)
+                            .filter(|p| !p.contains(['{', '}', ' ', '\n']))
+                            .map(str::to_string)
+                    } else if text[..cursor].ends_with("{{") {
+                        self.state.completion.anchor = cursor;
+                        Some(String::new())
+                    } else {
+                        None
+                    }
+                }
+            }
+        };
+
+        let Some(prefix) = prefix else {
+            self.state.completion = CompletionState::default();
+            return;
+        };
+        let items = filter_candidates(variable_candidates(&self.state), &prefix);
+        let completion = &mut self.state.completion;
+        completion.active = true;
+        completion.selected = completion.selected.min(items.len().saturating_sub(1));
+        if !was_active {
+            completion.selected = 0;
+        }
+        completion.items = items;
+    }
+
+    /// Keys handled by the open completion popup. Returns `false` for keys that
+    /// should fall through to normal editing.
+    fn handle_completion_key(&mut self, key: KeyEvent) -> bool {
+        let completion = &mut self.state.completion;
+        match key.code {
+            KeyCode::Esc => {
+                self.state.completion = CompletionState::default();
+                true
+            }
+            KeyCode::Down if !completion.items.is_empty() => {
+                completion.selected = (completion.selected + 1) % completion.items.len();
+                true
+            }
+            KeyCode::Up if !completion.items.is_empty() => {
+                let len = completion.items.len();
+                completion.selected = (completion.selected + len - 1) % len;
+                true
+            }
+            KeyCode::Enter | KeyCode::Tab if !completion.items.is_empty() => {
+                let key_name = completion.items[completion.selected].key.clone();
+                let anchor = completion.anchor;
+                if let Some((text, cursor)) = self.completion_field_mut() {
+                    let end = (*cursor).min(text.len());
+                    let closing = if text[end..].starts_with("}}") { "" } else { "}}" };
+                    let insert = format!("{key_name}{closing}");
+                    text.replace_range(anchor..end, &insert);
+                    *cursor = anchor + insert.len() + if closing.is_empty() { 2 } else { 0 };
+                }
+                self.state.completion = CompletionState::default();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get a mutable reference to the body text string.
+    fn body_text_mut(body: &mut crate::state::request_state::RequestBody) -> Option<&mut String> {
+        use crate::state::request_state::RequestBody;
+        match body {
+            RequestBody::Json(s) | RequestBody::Text(s) => Some(s),
+            RequestBody::None => {
+                *body = RequestBody::Json(String::new());
+                match body {
+                    RequestBody::Json(s) => Some(s),
+                    _ => None,
+                }
+            }
+            RequestBody::Form(_) | RequestBody::Binary(_) => None,
+        }
+    }
+
+    fn headers_active_text_mut(
+        headers: &mut Vec<KeyValuePair>,
+        row: usize,
+        col: u8,
+    ) -> Option<&mut String> {
+        let pair = headers.get_mut(row)?;
+        if col == 0 { Some(&mut pair.key) } else { Some(&mut pair.value) }
+    }
+
+    fn handle_headers_insert_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.mode = Mode::Normal;
+            }
+            KeyCode::Char(c) => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let cursor = tab.request.headers_cursor;
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    if let Some(text) =
+                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+                    {
+                        text.insert(cursor, c);
+                        tab.request.headers_cursor = cursor + c.len_utf8();
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let cursor = tab.request.headers_cursor;
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    if cursor > 0 {
+                        if let Some(text) =
+                            Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+                        {
+                            let prev = Self::prev_char_boundary_of(text, cursor);
+                            text.drain(prev..cursor);
+                            tab.request.headers_cursor = prev;
+                        }
+                    }
+                }
+            }
+            KeyCode::Delete => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let cursor = tab.request.headers_cursor;
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    if let Some(text) =
+                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+                    {
+                        if cursor < text.len() {
+                            let next = Self::next_char_boundary_of(text, cursor);
+                            text.drain(cursor..next);
+                        }
+                    }
+                }
+            }
+            KeyCode::Left => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let cursor = tab.request.headers_cursor;
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    let new_cursor = if let Some(text) =
+                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+                    {
+                        Self::prev_char_boundary_of(text, cursor)
+                    } else {
+                        cursor
+                    };
+                    tab.request.headers_cursor = new_cursor;
+                }
+            }
+            KeyCode::Right => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let cursor = tab.request.headers_cursor;
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    let new_cursor = if let Some(text) =
+                        Self::headers_active_text_mut(&mut tab.request.headers, row, col)
+                    {
+                        Self::next_char_boundary_of(text, cursor)
+                    } else {
+                        cursor
+                    };
+                    tab.request.headers_cursor = new_cursor;
+                }
+            }
+            KeyCode::Home => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.request.headers_cursor = 0;
+                }
+            }
+            KeyCode::End => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let row = tab.request.headers_row;
+                    let col = tab.request.headers_col;
+                    let len = tab
+                        .request
+                        .headers
+                        .get(row)
+                        .map(|p| if col == 0 { p.key.len() } else { p.value.len() })
+                        .unwrap_or(0);
+                    tab.request.headers_cursor = len;
+                }
+            }
+            KeyCode::Tab => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let col = tab.request.headers_col;
+                    if col == 0 {
+                        tab.request.headers_col = 1;
+                        let row = tab.request.headers_row;
+                        let val_len = tab
+                            .request
+                            .headers
+                            .get(row)
+                            .map(|p| p.value.len())
+                            .unwrap_or(0);
+                        tab.request.headers_cursor = val_len;
+                    } else {
+                        let next_row = tab.request.headers_row + 1;
+                        if next_row >= tab.request.headers.len() {
+                            tab.request.headers.push(KeyValuePair::default());
+                        }
+                        tab.request.headers_row =
+                            next_row.min(tab.request.headers.len() - 1);
+                        tab.request.headers_col = 0;
+                        tab.request.headers_cursor = 0;
+                    }
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let next_row = tab.request.headers_row + 1;
+                    if next_row >= tab.request.headers.len() {
+                        tab.request.headers.push(KeyValuePair::default());
+                    }
+                    tab.request.headers_row = next_row.min(tab.request.headers.len() - 1);
+                    tab.request.headers_col = 0;
+                    tab.request.headers_cursor = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Editable text of the selected checks cell. Only JSONPath checks have a path.
+    fn checks_active_text_mut(checks: &mut [Check], row: usize, col: u8) -> Option<&mut String> {
+        let check = checks.get_mut(row)?;
+        match col {
+            0 if !check.kind.has_path() => None,
+            0 => Some(&mut check.path),
+            _ => Some(&mut check.expected),
+        }
+    }
+
+    fn handle_checks_insert_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.state.mode = Mode::Normal;
+                self.sync_active_tab_to_collection();
+                return;
+            }
+            KeyCode::Tab => {
+                if let Some(tab) = self.state.active_tab_mut() {
+                    let row = tab.request.checks_row;
+                    let col = if tab.request.checks_col == 0 { 1 } else { 0 };
+                    tab.request.checks_col = col;
+                    tab.request.checks_cursor =
+                        Self::checks_active_text_mut(&mut tab.request.checks, row, col)
+                            .map(|t| t.len())
+                            .unwrap_or(0);
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let row = tab.request.checks_row;
+        let col = tab.request.checks_col;
+        let cursor = tab.request.checks_cursor;
+        let Some(text) = Self::checks_active_text_mut(&mut tab.request.checks, row, col) else {
+            return;
+        };
+        let cursor = cursor.min(text.len());
+        let new_cursor = match key.code {
+            KeyCode::Char(c) => {
+                text.insert(cursor, c);
+                cursor + c.len_utf8()
+            }
+            KeyCode::Backspace if cursor > 0 => {
+                let prev = Self::prev_char_boundary_of(text, cursor);
+                text.drain(prev..cursor);
+                prev
+            }
+            KeyCode::Delete if cursor < text.len() => {
+                let next = Self::next_char_boundary_of(text, cursor);
+                text.drain(cursor..next);
+                cursor
+            }
+            KeyCode::Left => Self::prev_char_boundary_of(text, cursor),
+            KeyCode::Right => Self::next_char_boundary_of(text, cursor),
+            KeyCode::Home => 0,
+            KeyCode::End => text.len(),
+            _ => cursor,
+        };
+        tab.request.checks_cursor = new_cursor;
+    }
+
+    // ─── Auth editing ─────────────────────────────────────────────────────────
+
+    fn cycle_auth_type(&mut self, forward: bool) {
+        if let Some(tab) = self.state.active_tab_mut() {
+            let req = &mut tab.request;
+            req.auth = if forward { req.auth.next() } else { req.auth.prev() };
+            req.auth_field = 0;
+            req.auth_cursor = 0;
+        }
+        self.mark_active_tab_dirty();
+    }
+
+    fn handle_auth_insert_key(&mut self, key: KeyEvent) {
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.state.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Tab => {
+                let count = req.auth.fields().len();
+                if count > 0 {
+                    req.auth_field = (req.auth_field + 1) % count;
+                    req.auth_cursor = req.auth.field_mut(req.auth_field).map_or(0, |t| t.len());
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let cursor = req.auth_cursor;
+        let Some(text) = req.auth.field_mut(req.auth_field) else {
+            return;
+        };
+        let cursor = cursor.min(text.len());
+        req.auth_cursor = match key.code {
+            KeyCode::Char(c) => {
+                text.insert(cursor, c);
+                cursor + c.len_utf8()
+            }
+            KeyCode::Backspace if cursor > 0 => {
+                let prev = Self::prev_char_boundary_of(text, cursor);
+                text.drain(prev..cursor);
+                prev
+            }
+            KeyCode::Delete if cursor < text.len() => {
+                let next = Self::next_char_boundary_of(text, cursor);
+                text.drain(cursor..next);
+                cursor
+            }
+            KeyCode::Left => Self::prev_char_boundary_of(text, cursor),
+            KeyCode::Right => Self::next_char_boundary_of(text, cursor),
+            KeyCode::Home => 0,
+            KeyCode::End => text.len(),
+            _ => cursor,
+        };
+    }
+
+    // ─── Scripts insert handling ──────────────────────────────────────────────
+
+    fn handle_scripts_insert_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Esc {
+            self.state.mode = Mode::Normal;
+            self.sync_active_tab_to_collection();
+            return;
+        }
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        let text = req.scripts.get_mut(&req.scripts_kind);
+        req.scripts_cursor = Self::edit_multiline(text, req.scripts_cursor, key);
+    }
+
+    // ─── Docs insert handling ─────────────────────────────────────────────────
+
+    fn handle_docs_insert_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Esc {
+            self.state.mode = Mode::Normal;
+            self.sync_active_tab_to_collection();
+            return;
+        }
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        let req = &mut tab.request;
+        req.description_cursor = Self::edit_multiline(&mut req.description, req.description_cursor, key);
+    }
+
+    /// Apply an Insert-mode key to a multi-line `text` edited at `cursor`,
+    /// returning the new cursor.
+    fn edit_multiline(text: &mut String, cursor: usize, key: KeyEvent) -> usize {
+        let cursor = cursor.min(text.len());
+        match key.code {
+            KeyCode::Enter => {
+                text.insert(cursor, '\n');
+                cursor + 1
+            }
+            KeyCode::Tab => {
+                text.insert_str(cursor, "    ");
+                cursor + 4
+            }
+            KeyCode::Char(c) => {
+                text.insert(cursor, c);
+                cursor + c.len_utf8()
+            }
+            KeyCode::Backspace if cursor > 0 => {
+                let prev = Self::prev_char_boundary_of(text, cursor);
+                text.drain(prev..cursor);
+                prev
+            }
+            KeyCode::Delete if cursor < text.len() => {
+                let next = Self::next_char_boundary_of(text, cursor);
+                text.drain(cursor..next);
+                cursor
+            }
+            KeyCode::Left => Self::prev_char_boundary_of(text, cursor),
+            KeyCode::Right => Self::next_char_boundary_of(text, cursor),
+            KeyCode::Up => Self::body_move_up(text, cursor),
+            KeyCode::Down => Self::body_move_down(text, cursor),
+            KeyCode::Home => text[..cursor].rfind('\n').map(|i| i + 1).unwrap_or(0),
+            KeyCode::End => text[cursor..]
+                .find('\n')
+                .map(|i| cursor + i)
+                .unwrap_or(text.len()),
+            _ => cursor,
+        }
+    }
+
+    // ─── Char boundary helpers ────────────────────────────────────────────────
+
+    fn prev_char_boundary_of(text: &str, pos: usize) -> usize {
+        if pos == 0 {
+            return 0;
+        }
+        let mut p = pos - 1;
+        while p > 0 && !text.is_char_boundary(p) {
             p -= 1;
         }
-        p
+        p
+    }
+
+    fn next_char_boundary_of(text: &str, pos: usize) -> usize {
+        if pos >= text.len() {
+            return text.len();
+        }
+        let mut p = pos + 1;
+        while p < text.len() && !text.is_char_boundary(p) {
+            p += 1;
+        }
+        p
+    }
+
+    fn body_move_up(text: &str, cursor: usize) -> usize {
+        let clamped = cursor.min(text.len());
+        let before = &text[..clamped];
+        let lines: Vec<&str> = before.split('\n').collect();
+        let current_row = lines.len().saturating_sub(1);
+        let current_col = lines.last().map(|l| l.chars().count()).unwrap_or(0);
+        if current_row == 0 {
+            return 0;
+        }
+        let target_row = current_row - 1;
+        let rows: Vec<&str> = text.split('\n').collect();
+        let target_line = rows.get(target_row).copied().unwrap_or("");
+        let target_col = current_col.min(target_line.chars().count());
+        let row_start: usize = rows[..target_row].iter().map(|l| l.len() + 1).sum();
+        let col_bytes: usize = target_line
+            .char_indices()
+            .nth(target_col)
+            .map(|(i, _)| i)
+            .unwrap_or(target_line.len());
+        row_start + col_bytes
+    }
+
+    fn body_move_down(text: &str, cursor: usize) -> usize {
+        let clamped = cursor.min(text.len());
+        let before = &text[..clamped];
+        let lines_before: Vec<&str> = before.split('\n').collect();
+        let current_row = lines_before.len().saturating_sub(1);
+        let current_col = lines_before.last().map(|l| l.chars().count()).unwrap_or(0);
+        let rows: Vec<&str> = text.split('\n').collect();
+        let target_row = current_row + 1;
+        if target_row >= rows.len() {
+            return text.len();
+        }
+        let target_line = rows[target_row];
+        let target_col = current_col.min(target_line.chars().count());
+        let row_start: usize = rows[..target_row].iter().map(|l| l.len() + 1).sum();
+        let col_bytes: usize = target_line
+            .char_indices()
+            .nth(target_col)
+            .map(|(i, _)| i)
+            .unwrap_or(target_line.len());
+        row_start + col_bytes
+    }
+
+    // ─── Mouse handling ───────────────────────────────────────────────────────
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let pos = Position { x: mouse.column, y: mouse.row };
+        let over_sidebar = self.state.active_popup == ActivePopup::None
+            && self.state.hit_areas.borrow().sidebar_list.contains(pos);
+        match mouse.kind {
+            // The wheel over the sidebar walks the tree instead
+            MouseEventKind::ScrollDown if over_sidebar => self.sidebar_move_cursor(3),
+            MouseEventKind::ScrollUp if over_sidebar => {
+                for _ in 0..3 {
+                    self.sidebar_move_cursor_up();
+                }
+            }
+            MouseEventKind::ScrollDown => self.scroll_response_by(3),
+            MouseEventKind::ScrollUp => self.scroll_response_by(-3),
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(mouse.column, mouse.row),
+            _ => {}
+        }
     }
 
-    fn next_char_boundary_of(text: &str, pos: usize) -> usize {
-        if pos >= text.len() {
-            return text.len();
+    /// Switch request/response sub-tabs by clicking their labels, select
+    /// sidebar nodes, or focus the panel clicked in.
+    fn handle_click(&mut self, column: u16, row: u16) {
+        if self.state.active_popup != ActivePopup::None {
+            return;
         }
-        let mut p = pos + 1;
-        while p < text.len() && !text.is_char_boundary(p) {
-            p += 1;
+        let now = Instant::now();
+        let double = self.state.last_click.is_some_and(|(x, y, at)| {
+            (x, y) == (column, row) && now.duration_since(at) < DOUBLE_CLICK
+        });
+        // A double-click's second press doesn't start another one
+        self.state.last_click = if double { None } else { Some((column, row, now)) };
+
+        let pos = Position { x: column, y: row };
+        let hits = self.state.hit_areas.borrow().clone();
+        if hits.sidebar_list.contains(pos) {
+            self.click_sidebar((row - hits.sidebar_list.y) as usize, double);
+        } else if let Some((_, clicked)) = hits.request_tabs.iter().find(|(r, _)| r.contains(pos)) {
+            if let Some(tab) = self.state.active_tab_mut() {
+                tab.active_tab = clicked.clone();
+            }
+            self.state.mode = Mode::Normal;
+            self.state.focus = Focus::Editor;
+        } else if let Some((_, clicked)) = hits.response_tabs.iter().find(|(r, _)| r.contains(pos)) {
+            if let Some(tab) = self.state.active_tab_mut() {
+                tab.response_tab = clicked.clone();
+            }
+            self.state.mode = Mode::Normal;
+            self.state.focus = Focus::ResponseViewer;
+        } else if hits.url_bar.contains(pos) {
+            self.click_focus(Focus::UrlBar);
+        } else if hits.editor.contains(pos) {
+            self.click_focus(Focus::Editor);
+        } else if hits.response.contains(pos) {
+            self.click_focus(Focus::ResponseViewer);
         }
-        p
     }
 
-    fn body_move_up(text: &str, cursor: usize) -> usize {
-        let clamped = cursor.min(text.len());
-        let before = &text[..clamped];
-        let lines: Vec<&str> = before.split('\n').collect();
-        let current_row = lines.len().saturating_sub(1);
-        let current_col = lines.last().map(|l| l.chars().count()).unwrap_or(0);
-        if current_row == 0 {
-            return 0;
-        }
-        let target_row = current_row - 1;
-        let rows: Vec<&str> = text.split('\n').collect();
-        let target_line = rows.get(target_row).copied().unwrap_or("");
-        let target_col = current_col.min(target_line.chars().count());
-        let row_start: usize = rows[..target_row].iter().map(|l| l.len() + 1).sum();
-        let col_bytes: usize = target_line
-            .char_indices()
-            .nth(target_col)
-            .map(|(i, _)| i)
-            .unwrap_or(target_line.len());
-        row_start + col_bytes
+    /// Focus the clicked panel. A click inside the panel already being edited
+    /// leaves Insert mode alone.
+    fn click_focus(&mut self, focus: Focus) {
+        if self.state.focus != focus {
+            self.state.focus = focus;
+            self.state.mode = Mode::Normal;
+        }
+    }
+
+    /// A click on the sidebar's `row`-th visible node selects it. Folders and
+    /// collections fold or unfold on the first click; a request opens on a
+    /// double-click.
+    fn click_sidebar(&mut self, row: usize, double: bool) {
+        let idx = self.state.sidebar.scroll_offset + row;
+        let Some(node) = flatten_tree(&self.state).get(idx).cloned() else {
+            return;
+        };
+        self.state.sidebar.cursor = idx;
+        self.state.focus = Focus::Sidebar;
+        self.state.mode = Mode::Normal;
+        match node.kind {
+            // The first click opened or focused the request; a double-click
+            // on one that was already open opens it again in a new tab
+            NodeKind::Request { method, .. } if double => {
+                if !self.click_opened_tab {
+                    self.sync_active_tab_to_collection();
+                    self.open_collection_request_tab(&node.id, &node.label, &method);
+                }
+            }
+            NodeKind::Request { .. } => {
+                let tabs = self.state.workspace.open_tabs.len();
+                self.sync_active_tab_to_collection();
+                self.handle_sidebar_enter();
+                self.click_opened_tab = self.state.workspace.open_tabs.len() > tabs;
+            }
+            // The second click of a double-click would fold it straight back
+            _ if double => {}
+            _ => self.handle_sidebar_enter(),
+        }
+    }
+
+    // ─── Response handling ────────────────────────────────────────────────────
+
+    /// Show the result of a direct send in the tab that sent it, wherever
+    /// the focus has moved since. Results of sends that were replaced are
+    /// dropped.
+    fn handle_response(&mut self, tab_id: &str, send_id: u64, result: Result<ResponseState, AppError>) {
+        if !self.is_current_send(tab_id, send_id) {
+            return;
+        }
+        let Some(flight) = self.in_flight.remove(tab_id) else {
+            return;
+        };
+        write_audit_entry(&self.state.workspace.name, flight.audit, &result);
+        let started = Some(flight.sent_at);
+        let Some(idx) = self.tab_index(tab_id) else {
+            // The tab was closed meanwhile; the send still counts
+            self.record_send(flight.collection_id, started, &result);
+            self.dependency_queue.clear();
+            return;
+        };
+        let succeeded = self.apply_response(idx, flight.collection_id, started, result);
+
+        // Continue a "run dependencies first" chain, or abandon it on failure
+        if succeeded {
+            self.send_next_queued();
+        } else {
+            self.dependency_queue.clear();
+        }
+    }
+
+    /// Record a finished send of open tab `idx` and show the result there:
+    /// run history, cookies, chained values and the post-response script.
+    /// Returns whether the response was a 2xx.
+    fn apply_response(
+        &mut self,
+        idx: usize,
+        collection_id: Option<String>,
+        started: Option<Instant>,
+        result: Result<ResponseState, AppError>,
+    ) -> bool {
+        let succeeded = self.record_send(collection_id.clone(), started, &result);
+        match result {
+            Ok(mut response) => {
+                if response.is_markdown() {
+                    toggle_markdown(&mut response, theme(&self.state));
+                }
+                if let Some(id) = collection_id {
+                    let source = Arc::new(ChainSource::from_response(&response));
+                    self.state.workspace.last_responses.insert(id, source);
+                }
+                let env = active_env_vars(&self.state);
+                let script_result = self
+                    .state
+                    .workspace
+                    .open_tabs
+                    .get(idx)
+                    .map(|t| t.request.scripts.post_response.clone())
+                    .filter(|script| !script.trim().is_empty())
+                    .map(|script| run_post_response(&script, &response, &env));
+                if let Some(result) = &script_result {
+                    let tab = self.state.workspace.open_tabs.get(idx);
+                    let name = tab.map(|t| t.request.name.clone()).unwrap_or_default();
+                    self.apply_env_updates(&result.env_updates, &name, "post-response script");
+                }
+                let retry_in = self.retry_delay(idx, &response);
+                if let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) {
+                    tab.retry_at = retry_in.map(|delay| Instant::now() + delay);
+                    response.environment = tab.sent_environment.take().map(|e| e.name);
+                    tab.response = Some(response);
+                    tab.script_result = script_result;
+                    tab.request_status = RequestStatus::Idle;
+                }
+                self.highlight_tab_response(idx);
+                self.sync_tab_to_collection(idx);
+            }
+            Err(AppError::Cancelled) => {
+                if let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) {
+                    tab.request_status = RequestStatus::Idle;
+                }
+            }
+            Err(e) => {
+                if let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) {
+                    tab.request_status = RequestStatus::Error(e.to_string());
+                }
+            }
+        }
+        succeeded
+    }
+
+    /// Note a finished send of collection request `collection_id` in its run
+    /// history and, for a 2xx, the session's successes; save the cookies it
+    /// set. Returns whether it was a 2xx.
+    fn record_send(
+        &mut self,
+        collection_id: Option<String>,
+        started: Option<Instant>,
+        result: &Result<ResponseState, AppError>,
+    ) -> bool {
+        if let Some(id) = collection_id.clone() {
+            self.record_run(id, started, result);
+        }
+        self.save_cookies();
+        let succeeded = matches!(result, Ok(r) if (200..300).contains(&r.status));
+        if succeeded && let Some(id) = collection_id {
+            self.state.succeeded_requests.insert(id);
+        }
+        succeeded
+    }
+
+    /// Append a send of collection request `id` to its run history.
+    /// Cancelled sends are not recorded.
+    fn record_run(
+        &mut self,
+        id: String,
+        started: Option<Instant>,
+        result: &Result<ResponseState, AppError>,
+    ) {
+        let run = match result {
+            Ok(response) => RunRecord {
+                status: Some(response.status),
+                total_ms: response.timing.total_ms,
+                timestamp: response.received_at,
+            },
+            Err(AppError::Cancelled) => return,
+            Err(_) => RunRecord {
+                status: None,
+                total_ms: started.map(|t| t.elapsed().as_millis() as u64).unwrap_or(0),
+                timestamp: chrono::Utc::now(),
+            },
+        };
+        let ws = &mut self.state.workspace;
+        push_run(ws.run_history.entry(id).or_default(), run);
+        let _ = history_storage::save_run_history(&ws.name, &ws.run_history);
+    }
+
+    // ─── Tick handling ────────────────────────────────────────────────────────
+
+    fn handle_tick(&mut self) {
+        if let Some(tab) = self.state.active_tab_mut() {
+            if let RequestStatus::Loading { spinner_tick } = &mut tab.request_status {
+                *spinner_tick = spinner_tick.wrapping_add(1);
+                self.state.dirty = true;
+            }
+        }
+
+        // Retry-After countdown of the tab in front; redraw it every tick
+        if let Some(at) = self.state.active_tab().and_then(|t| t.retry_at) {
+            self.state.dirty = true;
+            if Instant::now() >= at {
+                self.dispatch_request();
+            }
+        }
+
+        if self.state.toast.as_ref().is_some_and(|(_, at)| at.elapsed() >= TOAST_TTL) {
+            self.state.toast = None;
+            self.state.dirty = true;
+        }
+        if self.state.sidebar.moved.as_ref().is_some_and(|(_, at)| at.elapsed() >= MOVE_FLASH) {
+            self.state.sidebar.moved = None;
+            self.state.dirty = true;
+        }
+
+        // Debounced auto-save of the tab being edited
+        if self
+            .state
+            .last_edit_instant
+            .is_some_and(|t| t.elapsed() >= AUTO_SAVE_DELAY)
+        {
+            self.state.last_edit_instant = None;
+            if self.state.active_tab().is_some_and(|t| t.is_dirty) {
+                self.sync_active_tab_to_collection();
+                self.state.dirty = true;
+            }
+        }
+    }
+
+    /// Show or hide the line-number gutter of the body and script editors and
+    /// the response body (`#`, or `Ctrl+L` in the editor); saved with the
+    /// workspace.
+    fn toggle_line_numbers(&mut self) {
+        let active_tab = self.state.active_tab().map(|t| t.active_tab.clone());
+        if self.state.focus == Focus::ResponseViewer || matches!(active_tab, Some(ActiveTab::Body | ActiveTab::Scripts)) {
+            self.state.workspace.show_line_numbers = !self.state.workspace.show_line_numbers;
+            self.save_workspace_file();
+        }
+    }
+
+    fn mark_active_tab_dirty(&mut self) {
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.is_dirty = true;
+            self.state.last_edit_instant = Some(Instant::now());
+        }
+    }
+
+    // ─── HTTP request ─────────────────────────────────────────────────────────
+
+    /// Send the active request, first offering to run any dependencies that
+    /// haven't succeeded this session.
+    fn send_request(&mut self) {
+        // A manual send abandons any unfinished dependency chain
+        self.dependency_queue.clear();
+        let Some(id) = self.state.active_tab().and_then(|t| t.collection_id.clone()) else {
+            self.dispatch_request();
+            return;
+        };
+        let collections = &self.state.workspace.collections;
+        let names: Vec<(String, String)> =
+            resolution_order(&dependency_map(collections), &id, &self.state.succeeded_requests)
+                .into_iter()
+                // Dependencies on deleted requests are ignored
+                .filter_map(|dep| {
+                    find_col_request_by_id(collections, &dep).map(|r| (dep, r.name.clone()))
+                })
+                .collect();
+        if names.is_empty() {
+            self.dispatch_request();
+            return;
+        }
+        let chain = names.iter().map(|(_, n)| n.as_str()).collect::<Vec<_>>().join(" → ");
+        self.state.dependency_prompt = DependencyPromptState {
+            message: format!("This request depends on {chain}, which hasn't succeeded this session."),
+            order: names.into_iter().map(|(id, _)| id).collect(),
+        };
+        self.state.active_popup = ActivePopup::DependencyPrompt;
+    }
+
+    fn dispatch_request(&mut self) {
+        let url_empty = self
+            .state
+            .active_tab()
+            .map(|t| t.request.url.is_empty())
+            .unwrap_or(true);
+        if url_empty {
+            return;
+        }
+        // A send replaces any pending Retry-After re-send, open event stream
+        // and dry-run preview
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.retry_at = None;
+            tab.sse = None;
+            tab.dry_run = None;
+        }
+        if self.state.active_tab().is_some_and(|t| is_websocket_url(&t.request.url)) {
+            self.send_guarded(PendingSend::WebSocket);
+            return;
+        }
+        // An HTTP send from a former WebSocket tab drops the connection
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.ws = None;
+        }
+        // Dependency chains continue from direct sends, so they bypass the queue
+        if self.state.workspace.send_queue && self.dependency_queue.is_empty() {
+            self.send_guarded(PendingSend::Enqueue);
+            return;
+        }
+
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        let name = tab.request.name.clone();
+        let (prepared, pre_script) = prepare_tab(&self.state, tab);
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.pre_script_result = pre_script.clone();
+        }
+        match prepared {
+            Ok(request) => {
+                self.send_guarded(PendingSend::Prepared { request: Box::new(request), pre_script, name });
+            }
+            Err(e) => {
+                if let Some(result) = &pre_script {
+                    self.apply_env_updates(&result.env_updates, &name, "pre-request script");
+                }
+                if let Some(tab) = self.state.active_tab_mut() {
+                    tab.request_status = RequestStatus::Error(e);
+                }
+            }
+        }
+    }
+
+    /// Go ahead with `pending`, or hold it behind the confirmation prompt
+    /// while the active environment is protected and sends to it haven't
+    /// been confirmed for the minute.
+    fn send_guarded(&mut self, pending: PendingSend) {
+        let Some(env) = self.state.active_environment().filter(|env| env.protected) else {
+            self.send_pending(pending);
+            return;
+        };
+        if self.protected_confirmed_until.get(&env.id).is_some_and(|until| Instant::now() < *until) {
+            self.send_pending(pending);
+            return;
+        }
+        let (environment_id, environment) = (env.id.clone(), env.name.clone());
+        let (method, target) = match &pending {
+            PendingSend::Prepared { request, .. } => (request.method.as_str().to_string(), request.url.clone()),
+            PendingSend::RunInPlace(ids) if ids.len() == 1 => (String::new(), "1 request".to_string()),
+            PendingSend::RunInPlace(ids) => (String::new(), format!("{} requests", ids.len())),
+            PendingSend::WebSocket | PendingSend::Enqueue | PendingSend::None => {
+                let Some(tab) = self.state.active_tab() else {
+                    return;
+                };
+                let url = active_tab_resolver(&self.state).resolve_for_send(&tab.request.url);
+                let method = if is_websocket_url(&url) { String::new() } else { tab.request.method.as_str().to_string() };
+                (method, url)
+            }
+        };
+        self.state.confirm_send = ConfirmSendState {
+            pending,
+            environment_id,
+            environment,
+            method,
+            target,
+            skip_minute: false,
+            back_to: self.state.active_popup.clone(),
+        };
+        self.state.active_popup = ActivePopup::ConfirmSend;
+    }
+
+    fn send_pending(&mut self, pending: PendingSend) {
+        match pending {
+            PendingSend::None => {}
+            PendingSend::Prepared { request, pre_script, name } => {
+                if let Some(result) = &pre_script {
+                    self.apply_env_updates(&result.env_updates, &name, "pre-request script");
+                }
+                self.execute_prepared(*request);
+            }
+            PendingSend::WebSocket => self.dispatch_websocket(),
+            PendingSend::Enqueue => self.enqueue_send(),
+            PendingSend::RunInPlace(ids) => self.run_in_place(ids),
+        }
+    }
+
+    /// Send a resolved request from the active tab. A send still in flight
+    /// from the same tab is cancelled; other tabs' keep going.
+    fn execute_prepared(&mut self, request: RequestState) {
+        let Some(tab_id) = self.state.active_tab().map(|t| t.request.id.clone()) else {
+            return;
+        };
+        if let Some(flight) = self.in_flight.remove(&tab_id) {
+            flight.cancel.cancel();
+        }
+        let token = CancellationToken::new();
+        self.send_ids += 1;
+        let send_id = self.send_ids;
+
+        let environment = self.state.active_environment().map(SentEnvironment::of);
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.request_status = RequestStatus::Loading { spinner_tick: 0 };
+            tab.response = None;
+            tab.script_result = None;
+            tab.sent_environment = environment;
+        }
+
+        let flight = InFlight {
+            send_id,
+            cancel: token.clone(),
+            audit: self.audit_entry_for(&request),
+            sent_at: Instant::now(),
+            collection_id: self.state.active_tab().and_then(|t| t.collection_id.clone()),
+        };
+        self.in_flight.insert(tab_id.clone(), flight);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+
+        tokio::spawn(async move {
+            execute(client, request, tab_id, send_id, tx, token).await;
+        });
+    }
+
+    /// `Ctrl+Shift+D`: prepare the active tab exactly as a send would —
+    /// default headers, pre-request script, variables, auth and body — and
+    /// show the result in the response area instead of sending it. The
+    /// script's environment changes are not kept.
+    fn dry_run_request(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        if tab.request.url.is_empty() {
+            return;
+        }
+        if is_websocket_url(&tab.request.url) {
+            self.show_toast("Dry run covers HTTP requests".to_string());
+            return;
+        }
+        let (prepared, pre_script) = prepare_tab(&self.state, tab);
+        let preview = prepared.and_then(|request| dry_run(&self.client, &request).map_err(|e| e.to_string()));
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        tab.pre_script_result = pre_script;
+        match preview {
+            Ok(preview) => tab.dry_run = Some(preview),
+            Err(e) => {
+                tab.dry_run = None;
+                tab.request_status = RequestStatus::Error(e);
+            }
+        }
+        tab.response_tab = ResponseTab::Body;
+    }
+
+    // ─── WebSocket requests ───────────────────────────────────────────────────
+
+    /// `Ctrl+R` on a `ws://` / `wss://` tab: connect, or once connected send
+    /// the Body tab's text as a frame. `{{var}}`s resolve in both the URL and
+    /// the message.
+    fn dispatch_websocket(&mut self) {
+        let Some(tab) = self.state.active_tab() else {
+            return;
+        };
+        if let Some(ws) = &tab.ws
+            && ws.is_live()
+        {
+            let text = match &tab.request.body {
+                RequestBody::Json(s) | RequestBody::Text(s) => s.clone(),
+                _ => String::new(),
+            };
+            if ws.status != WsStatus::Open || text.is_empty() {
+                return;
+            }
+            let text = active_tab_resolver(&self.state).resolve_for_send(&text);
+            let Some(ws) = self.state.active_tab_mut().and_then(|t| t.ws.as_mut()) else {
+                return;
+            };
+            if ws.commands.as_ref().is_some_and(|c| c.send(WsCommand::Send(text.clone())).is_ok()) {
+                ws.push(WsDirection::Sent, text);
+                ws.scroll = 0;
+            }
+            return;
+        }
+
+        let req = tab.request.clone();
+        let collection_id = tab.collection_id.clone();
+        let name = req.name.clone();
+        let (prepared, pre_script) = prepare_request(&self.state, req, collection_id.as_deref());
+        if let Some(result) = &pre_script {
+            self.apply_env_updates(&result.env_updates, &name, "pre-request script");
+        }
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        tab.pre_script_result = pre_script;
+        tab.script_result = None;
+        tab.response = None;
+        let request = match prepared {
+            Ok(request) => request,
+            Err(e) => {
+                tab.request_status = RequestStatus::Error(e);
+                return;
+            }
+        };
+        tab.request_status = RequestStatus::Idle;
+        tab.response_tab = ResponseTab::Body;
+        let (commands, rx) = unbounded_channel();
+        let session = WsSession::new(commands);
+        let conn_id = session.id.clone();
+        tab.ws = Some(session);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        tokio::spawn(websocket::connect(client, request, conn_id, tx, rx));
+    }
+
+    /// `Esc` on a tab with a live WebSocket: close it. Returns `false` when
+    /// there is none.
+    fn close_websocket(&mut self) -> bool {
+        let Some(ws) = self.state.active_tab_mut().and_then(|t| t.ws.as_mut()) else {
+            return false;
+        };
+        if !ws.is_live() {
+            return false;
+        }
+        match ws.status {
+            WsStatus::Open => {
+                if let Some(commands) = &ws.commands {
+                    let _ = commands.send(WsCommand::Close);
+                }
+            }
+            // Still handshaking: drop the connection without waiting for it
+            _ => {
+                ws.commands = None;
+                ws.status = WsStatus::Closed { code: None, reason: "Cancelled".to_string() };
+            }
+        }
+        true
+    }
+
+    /// `Esc` on a tab with an open event stream: stop it. Returns `false`
+    /// when there is none.
+    fn close_event_stream(&mut self) -> bool {
+        let Some(sse) = self.state.active_tab_mut().and_then(|t| t.sse.as_mut()) else {
+            return false;
+        };
+        if !sse.is_live() {
+            return false;
+        }
+        // The stream task reports the close
+        sse.stop = None;
+        true
+    }
+
+    /// The tab event stream `conn_id` tags, if it is still the tab's current
+    /// one.
+    fn sse_stream(&mut self, conn_id: &str) -> Option<&mut SseStream> {
+        self.state
+            .workspace
+            .open_tabs
+            .iter_mut()
+            .filter_map(|t| t.sse.as_mut())
+            .find(|sse| sse.id == conn_id)
+    }
+
+    /// The tab session that connection `conn_id` belongs to, if it is still
+    /// the tab's current one.
+    fn ws_session(&mut self, conn_id: &str) -> Option<&mut WsSession> {
+        self.state
+            .workspace
+            .open_tabs
+            .iter_mut()
+            .filter_map(|t| t.ws.as_mut())
+            .find(|ws| ws.id == conn_id)
+    }
+
+    /// Keep an OAuth2 token a send fetched on the tabs holding the request, and
+    /// on the saved request when it keeps its token on disk, then write it to
+    /// the request's token variable if it names one.
+    fn cache_oauth_token(&mut self, request_id: &str, token: OAuthToken) {
+        let mut token_var = None;
+        let mut name = String::new();
+        for tab in &mut self.state.workspace.open_tabs {
+            let matches = tab.request.id == request_id || tab.collection_id.as_deref() == Some(request_id);
+            if matches && let AuthConfig::OAuth2 { token: cached, token_var: var, .. } = &mut tab.request.auth {
+                *cached = Some(token.clone());
+                token_var = Some(var.clone());
+                name = tab.request.name.clone();
+            }
+        }
+        let ws_name = self.state.workspace.name.clone();
+        for col in &mut self.state.workspace.collections {
+            let Some(saved) = find_request_in_items_mut(&mut col.items, request_id) else {
+                continue;
+            };
+            if name.is_empty() {
+                name = saved.name.clone();
+            }
+            if let AuthConfig::OAuth2 { token: cached, token_var: var, persist_token, .. } = &mut saved.auth {
+                token_var.get_or_insert_with(|| var.clone());
+                if *persist_token {
+                    *cached = Some(token.clone());
+                    let _ = col_storage::save_collection_meta(&ws_name, col);
+                }
+            }
+            break;
+        }
+        if let Some(var) = token_var.filter(|v| !v.is_empty()) {
+            self.apply_env_updates(&[(var, token.access_token)], &name, "OAuth token");
+        }
+    }
+
+    /// Start an audit entry for a resolved request, if audit logging is on.
+    fn audit_entry_for(&self, request: &RequestState) -> Option<(AuditEntry, Instant)> {
+        self.state.workspace.audit_log.then(|| {
+            let env = self
+                .state
+                .active_environment_idx()
+                .and_then(|i| self.state.workspace.environments.get(i));
+            let secrets = secret_values(env, &request.auth);
+            let entry = AuditEntry::new(
+                &self.state.workspace.name,
+                env.map(|e| e.name.as_str()),
+                request.method.as_str(),
+                &request.url,
+                &self.state.workspace.audit_note,
+                &secrets,
+            );
+            (entry, Instant::now())
+        })
+    }
+
+    // ─── Sidebar run-in-place (s / S) ─────────────────────────────────────────
+
+    /// Run the request under the sidebar cursor in the background from its
+    /// saved definition, or with `subtree` every request below the cursor
+    /// node, one after another. Open tabs are left untouched.
+    fn sidebar_run_in_place(&mut self, subtree: bool) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        if !subtree && !matches!(node.kind, crate::ui::sidebar::NodeKind::Request { .. }) {
+            return;
+        }
+        let ids = self.with_dependencies(subtree_request_ids(&self.state.workspace.collections, &node.id));
+        self.start_run_in_place(ids);
+    }
+
+    /// `ids` with the dependencies that haven't succeeded this session added
+    /// before the requests needing them, as a send would offer. Dependencies
+    /// on deleted requests are ignored.
+    fn with_dependencies(&self, ids: Vec<String>) -> Vec<String> {
+        let collections = &self.state.workspace.collections;
+        run_order(&dependency_map(collections), &ids, &self.state.succeeded_requests)
+            .into_iter()
+            .filter(|id| find_col_request_by_id(collections, id).is_some())
+            .collect()
     }
 
-    fn body_move_down(text: &str, cursor: usize) -> usize {
-        let clamped = cursor.min(text.len());
-        let before = &text[..clamped];
-        let lines_before: Vec<&str> = before.split('\n').collect();
-        let current_row = lines_before.len().saturating_sub(1);
-        let current_col = lines_before.last().map(|l| l.chars().count()).unwrap_or(0);
-        let rows: Vec<&str> = text.split('\n').collect();
-        let target_row = current_row + 1;
-        if target_row >= rows.len() {
-            return text.len();
+    /// Run the requests `ids` in place, once confirmed for a protected
+    /// environment.
+    fn start_run_in_place(&mut self, ids: Vec<String>) {
+        if ids.is_empty() {
+            return;
         }
-        let target_line = rows[target_row];
-        let target_col = current_col.min(target_line.chars().count());
-        let row_start: usize = rows[..target_row].iter().map(|l| l.len() + 1).sum();
-        let col_bytes: usize = target_line
-            .char_indices()
-            .nth(target_col)
-            .map(|(i, _)| i)
-            .unwrap_or(target_line.len());
-        row_start + col_bytes
+        self.send_guarded(PendingSend::RunInPlace(ids));
     }
 
-    // ─── Mouse handling ───────────────────────────────────────────────────────
+    fn run_in_place(&mut self, ids: Vec<String>) {
+        // Saved definitions are what runs; flush pending edits first
+        self.sync_active_tab_to_collection();
+        self.abort_sidebar_run();
+        for id in &ids {
+            self.state.sidebar.run_status.insert(id.clone(), NodeRunStatus::Queued);
+        }
+        self.sidebar_queue = ids.into();
+        self.run_next_in_place();
+    }
 
-    fn handle_mouse(&mut self, mouse: MouseEvent) {
-        match mouse.kind {
-            MouseEventKind::ScrollDown => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    if let Some(resp) = &mut tab.response {
-                        resp.scroll_offset = resp.scroll_offset.saturating_add(3);
-                    }
-                }
-            }
-            MouseEventKind::ScrollUp => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    if let Some(resp) = &mut tab.response {
-                        resp.scroll_offset = resp.scroll_offset.saturating_sub(3);
-                    }
+    /// Send the next request of the sidebar queue. Requests that can't be sent
+    /// (no URL, failing pre-request script) are marked failed and skipped.
+    fn run_next_in_place(&mut self) {
+        while let Some(id) = self.sidebar_queue.pop_front() {
+            let Some(saved) = find_col_request_by_id(&self.state.workspace.collections, &id)
+            else {
+                self.state.sidebar.run_status.remove(&id);
+                continue;
+            };
+            let req = saved_request_state(saved);
+            let name = req.name.clone();
+            let (prepared, pre_script) = prepare_request(&self.state, req, Some(&id));
+            // Values set by scripts are seen by the requests after this one
+            if let Some(result) = pre_script {
+                self.apply_env_updates(&result.env_updates, &name, "pre-request script");
+            }
+            let request = match prepared {
+                Ok(request) if !request.url.is_empty() => request,
+                result => {
+                    let reason = result.err().unwrap_or_else(|| "no URL".to_string());
+                    self.finish_runner_row(&id, NodeRunStatus::Failed, vec![reason], None);
+                    self.state.sidebar.run_status.insert(id, NodeRunStatus::Failed);
+                    continue;
                 }
+            };
+
+            let token = CancellationToken::new();
+            self.sidebar_cancel = Some(token.clone());
+            self.sidebar_running = Some(id.clone());
+            self.sidebar_sent_at = Some(Instant::now());
+            self.sidebar_audit = self.audit_entry_for(&request);
+            self.state.sidebar.run_status.insert(id.clone(), NodeRunStatus::Running);
+            if let Some(row) = self.pending_runner_row(&id) {
+                row.status = NodeRunStatus::Running;
             }
-            _ => {}
+
+            let client = self.client.clone();
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                execute_for(client, request, id, tx, token).await;
+            });
+            return;
         }
     }
 
-    // ─── Response handling ────────────────────────────────────────────────────
+    fn handle_sidebar_run_result(&mut self, id: String, result: Result<ResponseState, AppError>) {
+        if self.sidebar_running.as_deref() != Some(id.as_str()) {
+            return;
+        }
+        self.sidebar_running = None;
+        self.sidebar_cancel = None;
+        let audit = self.sidebar_audit.take();
+        write_audit_entry(&self.state.workspace.name, audit, &result);
+        let started = self.sidebar_sent_at.take();
+        self.record_run(id.clone(), started, &result);
+        self.save_cookies();
 
-    fn handle_response(&mut self, result: Result<ResponseState, AppError>) {
-        self.cancel = None;
-        match result {
-            Ok(mut response) => {
-                if let ResponseBody::Text(text) = &response.body {
-                    let lang = detect_lang(text);
-                    response.highlighted_body = Some(highlight_text(text, lang));
+        let saved = find_col_request_by_id(&self.state.workspace.collections, &id);
+        let checks = saved.map(|r| r.checks.clone()).unwrap_or_default();
+        let post_script = saved
+            .map(|r| r.scripts.post_response.clone())
+            .filter(|script| !script.trim().is_empty());
+        let (status, failures, response) = match result {
+            Ok(response) => {
+                let source = Arc::new(ChainSource::from_response(&response));
+                self.state.workspace.last_responses.insert(id.clone(), source);
+                if let Some(script) = post_script {
+                    let env = active_env_vars(&self.state);
+                    let result = run_post_response(&script, &response, &env);
+                    let name = saved.map(|r| r.name.clone()).unwrap_or_default();
+                    self.apply_env_updates(&result.env_updates, &name, "post-response script");
                 }
-                if let Some(tab) = self.state.active_tab_mut() {
-                    tab.response = Some(response);
-                    tab.request_status = RequestStatus::Idle;
+                if (200..300).contains(&response.status) {
+                    self.state.succeeded_requests.insert(id.clone());
                 }
-                self.sync_active_tab_to_collection();
+                let status = NodeRunStatus::Done {
+                    status: response.status,
+                    total_ms: response.timing.total_ms,
+                };
+                (status, check_failures(&checks, &response), Some(response))
             }
-            Err(AppError::Cancelled) => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    tab.request_status = RequestStatus::Idle;
-                }
+            Err(e) => (NodeRunStatus::Failed, vec![e.to_string()], None),
+        };
+        self.finish_runner_row(&id, status.clone(), failures, response);
+        self.state.sidebar.run_status.insert(id, status);
+        self.run_next_in_place();
+    }
+
+    /// The collection runner's unfinished row for `id`, if a run includes it.
+    fn pending_runner_row(&mut self, id: &str) -> Option<&mut RunnerRow> {
+        self.state
+            .runner
+            .rows
+            .iter_mut()
+            .find(|r| r.request_id == id && r.passed().is_none())
+    }
+
+    fn finish_runner_row(
+        &mut self,
+        id: &str,
+        status: NodeRunStatus,
+        failures: Vec<String>,
+        response: Option<ResponseState>,
+    ) {
+        let Some(row) = self.pending_runner_row(id) else {
+            return;
+        };
+        row.status = status;
+        row.failures = failures;
+        row.response = response;
+        let passed = row.passed().unwrap_or(false);
+        let ws = &mut self.state.workspace;
+        ws.runner_results.insert(id.to_string(), passed);
+        let _ = history_storage::save_runner_results(&ws.name, &ws.runner_results);
+    }
+
+    /// Forget the last runner pass, clearing the sidebar health marks.
+    fn clear_runner_results(&mut self) {
+        let ws = &mut self.state.workspace;
+        ws.runner_results.clear();
+        let _ = history_storage::save_runner_results(&ws.name, &ws.runner_results);
+    }
+
+    /// Cancel the in-flight sidebar run and drop the rest of the queue.
+    /// Finished nodes keep their results.
+    fn abort_sidebar_run(&mut self) {
+        if let Some(token) = self.sidebar_cancel.take() {
+            token.cancel();
+        }
+        self.sidebar_running = None;
+        self.sidebar_sent_at = None;
+        self.sidebar_audit = None;
+        self.sidebar_queue.clear();
+        self.state
+            .sidebar
+            .run_status
+            .retain(|_, s| !matches!(s, NodeRunStatus::Queued | NodeRunStatus::Running));
+        for row in &mut self.state.runner.rows {
+            if row.passed().is_none() {
+                row.status = NodeRunStatus::Failed;
+                row.failures = vec!["aborted".to_string()];
             }
-            Err(e) => {
-                if let Some(tab) = self.state.active_tab_mut() {
-                    tab.request_status = RequestStatus::Error(e.to_string());
+        }
+    }
+
+    // ─── Collection runner popup (R) ──────────────────────────────────────────
+
+    /// Run every request under the sidebar cursor top to bottom, each after
+    /// its unsatisfied dependencies, showing the results in the runner popup.
+    fn open_runner(&mut self) {
+        let nodes = flatten_tree(&self.state);
+        let Some(node) = nodes.get(self.state.sidebar.cursor) else {
+            return;
+        };
+        let ids = self.with_dependencies(subtree_request_ids(&self.state.workspace.collections, &node.id));
+        let collections = &self.state.workspace.collections;
+        let rows = ids
+            .iter()
+            .filter_map(|id| find_col_request_by_id(collections, id))
+            .map(|r| RunnerRow {
+                request_id: r.id.clone(),
+                name: r.name.clone(),
+                method: r.method.clone(),
+                status: NodeRunStatus::Queued,
+                failures: Vec::new(),
+                response: None,
+            })
+            .collect();
+        // Stop any earlier run before its rows are replaced
+        self.abort_sidebar_run();
+        self.clear_runner_results();
+        self.state.runner = RunnerState {
+            title: node.label.clone(),
+            rows,
+            selected: 0,
+        };
+        self.state.active_popup = ActivePopup::Runner;
+        self.start_run_in_place(ids);
+    }
+
+    fn handle_runner_key(&mut self, key: KeyEvent) {
+        let count = self.state.runner.rows.len();
+        let runner = &mut self.state.runner;
+        match key.code {
+            KeyCode::Esc if self.sidebar_running.is_some() => self.abort_sidebar_run(),
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                runner.selected = (runner.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                runner.selected = runner.selected.saturating_sub(1);
+            }
+            KeyCode::Char('r') if runner.finished() => {
+                let ids: Vec<String> = runner.rows.iter().map(|r| r.request_id.clone()).collect();
+                for row in &mut runner.rows {
+                    row.status = NodeRunStatus::Queued;
+                    row.failures.clear();
+                    row.response = None;
                 }
+                self.clear_runner_results();
+                self.start_run_in_place(ids);
             }
+            KeyCode::Enter => self.open_runner_row(),
+            KeyCode::Char('v') => self.open_variables(true),
+            _ => {}
         }
     }
 
-    // ─── Tick handling ────────────────────────────────────────────────────────
+    /// Open the selected row's request in a tab with the captured response.
+    fn open_runner_row(&mut self) {
+        let Some(row) = self.state.runner.rows.get(self.state.runner.selected).cloned() else {
+            return;
+        };
+        if row.passed().is_none() {
+            return;
+        }
+        self.sync_active_tab_to_collection();
+        self.open_collection_request(&row.request_id, &row.name, &row.method);
+        let response = row.response;
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.request_status = match &response {
+                Some(_) => RequestStatus::Idle,
+                None => RequestStatus::Error(row.failures.join("; ")),
+            };
+            tab.response = response;
+            tab.pre_script_result = None;
+            tab.script_result = None;
+        }
+        self.highlight_tab_response(self.state.workspace.active_tab_idx);
+        self.state.active_popup = ActivePopup::None;
+        self.state.mode = Mode::Normal;
+        self.state.focus = Focus::ResponseViewer;
+    }
 
-    fn handle_tick(&mut self) {
+    pub fn cancel_request(&mut self) {
+        if self.close_websocket() || self.close_event_stream() {
+            return;
+        }
+        if let Some(flight) = self.state.active_tab().and_then(|t| self.in_flight.get(&t.request.id)) {
+            flight.cancel.cancel();
+        }
         if let Some(tab) = self.state.active_tab_mut() {
-            if let RequestStatus::Loading { spinner_tick } = &mut tab.request_status {
-                *spinner_tick = spinner_tick.wrapping_add(1);
-                self.state.dirty = true;
-            }
+            tab.retry_at = None;
+        }
+        if let Some(tab_id) = self.state.active_tab().map(|t| t.request.id.clone()) {
+            self.cancel_queued_sends(&tab_id);
+        }
+        if let Some(tab) = self.state.active_tab_mut() {
+            tab.request_status = RequestStatus::Idle;
         }
     }
 
-    // ─── HTTP request ─────────────────────────────────────────────────────────
+    // ─── Retry-After backoff ──────────────────────────────────────────────────
 
-    fn send_request(&mut self) {
-        let url_empty = self
-            .state
-            .active_tab()
-            .map(|t| t.request.url.is_empty())
-            .unwrap_or(true);
-        if url_empty {
+    /// How long to wait before re-sending open tab `idx` after `response`,
+    /// when auto-retry is on for the workspace or the request and the
+    /// response is a 429/503 with a `Retry-After` header.
+    fn retry_delay(&self, idx: usize, response: &ResponseState) -> Option<Duration> {
+        let ws = &self.state.workspace;
+        let enabled = ws.auto_retry || ws.open_tabs.get(idx).is_some_and(|t| t.request.auto_retry);
+        if !enabled {
+            return None;
+        }
+        let max = Duration::from_secs(ws.retry_max_secs);
+        // At least a second apart, even when the server says "now"
+        retry_after(response.status, &response.headers, chrono::Utc::now(), max)
+            .map(|delay| delay.max(Duration::from_secs(1)))
+    }
+
+    /// Toggle auto-retry for the active request (`b`). Turning it on right
+    /// after a rate-limited response schedules that response's retry.
+    fn toggle_request_auto_retry(&mut self) {
+        let idx = self.state.workspace.active_tab_idx;
+        let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) else {
             return;
+        };
+        tab.request.auto_retry = !tab.request.auto_retry;
+        self.mark_active_tab_dirty();
+        self.reschedule_retry(idx);
+    }
+
+    /// Toggle auto-retry for every request of the workspace (`Alt+b`).
+    fn toggle_workspace_auto_retry(&mut self) {
+        let ws = &mut self.state.workspace;
+        ws.auto_retry = !ws.auto_retry;
+        self.save_workspace_file();
+        self.reschedule_retry(self.state.workspace.active_tab_idx);
+    }
+
+    fn reschedule_retry(&mut self, idx: usize) {
+        let retry_in = self
+            .state
+            .workspace
+            .open_tabs
+            .get(idx)
+            .and_then(|t| t.response.as_ref())
+            .and_then(|response| self.retry_delay(idx, response));
+        if let Some(tab) = self.state.workspace.open_tabs.get_mut(idx) {
+            tab.retry_at = retry_in.map(|delay| Instant::now() + delay);
         }
+    }
 
-        if let Some(token) = self.cancel.take() {
-            token.cancel();
+    // ─── Send queue (Q) ───────────────────────────────────────────────────────
+
+    /// Queue the active tab's request; it is sent after the sends before it,
+    /// at least the workspace's gap after the previous one.
+    fn enqueue_send(&mut self) {
+        let Some(tab) = self.state.active_tab_mut() else {
+            return;
+        };
+        if !matches!(tab.request_status, RequestStatus::Loading { .. }) {
+            tab.request_status = RequestStatus::Queued;
         }
-        let token = CancellationToken::new();
-        self.cancel = Some(token.clone());
+        let entry = QueuedSend { tab_id: tab.request.id.clone(), request: tab.request.clone() };
+        self.state.send_queue.entries.push(entry);
+        self.dispatch_send_queue();
+    }
 
-        if let Some(tab) = self.state.active_tab_mut() {
+    /// Send the next queued request unless one is in flight. Entries whose
+    /// tab was closed are dropped; a failing pre-request script marks its tab
+    /// and moves on.
+    fn dispatch_send_queue(&mut self) {
+        if self.state.send_queue.running.is_some() {
+            return;
+        }
+        while !self.state.send_queue.entries.is_empty() {
+            let entry = self.state.send_queue.entries.remove(0);
+            let queue = &mut self.state.send_queue;
+            queue.selected = queue.selected.min(queue.entries.len().saturating_sub(1));
+            let Some(idx) = self.tab_index(&entry.tab_id) else {
+                continue;
+            };
+            let collection_id = self.state.workspace.open_tabs[idx].collection_id.clone();
+            let (prepared, pre_script) =
+                prepare_request(&self.state, entry.request.clone(), collection_id.as_deref());
+            if let Some(result) = &pre_script {
+                self.apply_env_updates(&result.env_updates, &entry.request.name, "pre-request script");
+            }
+            let queued_again = self.state.send_queue.entries.iter().any(|e| e.tab_id == entry.tab_id);
+            let environment = self.state.active_environment().map(SentEnvironment::of);
+            let tab = &mut self.state.workspace.open_tabs[idx];
+            tab.pre_script_result = pre_script;
+            tab.script_result = None;
+            let request = match prepared {
+                Ok(request) => request,
+                Err(e) => {
+                    tab.request_status = if queued_again {
+                        RequestStatus::Queued
+                    } else {
+                        RequestStatus::Error(e)
+                    };
+                    continue;
+                }
+            };
             tab.request_status = RequestStatus::Loading { spinner_tick: 0 };
             tab.response = None;
-        }
+            tab.sent_environment = environment;
 
-        // Build resolver and resolve URL + headers before cloning for the task
-        let resolver = resolver_from_state(&self.state);
-        let request = if let Some(tab) = self.state.active_tab() {
-            let mut req = tab.request.clone();
-            req.url = resolver.resolve_for_send(&req.url);
-            for header in &mut req.headers {
-                if header.enabled {
-                    header.key = resolver.resolve_for_send(&header.key);
-                    header.value = resolver.resolve_for_send(&header.value);
-                }
-            }
-            req
-        } else {
-            return;
-        };
+            let gap = Duration::from_millis(self.state.workspace.queue_gap_ms);
+            let now = Instant::now();
+            let delay = self
+                .queue_last_sent
+                .map_or(Duration::ZERO, |at| (at + gap).saturating_duration_since(now));
+            self.queue_last_sent = Some(now + delay);
+            self.queue_audit = self.audit_entry_for(&request);
+            let token = CancellationToken::new();
+            self.queue_cancel = Some(token.clone());
+            let tab_id = entry.tab_id.clone();
+            self.state.send_queue.running = Some(entry);
 
-        let client = self.client.clone();
-        let tx = self.tx.clone();
+            let client = self.client.clone();
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                execute_queued(client, request, tab_id, delay, tx, token).await;
+            });
+            return;
+        }
+    }
 
-        tokio::spawn(async move {
-            execute(client, request, tx, token).await;
-        });
+    fn handle_queued_response(&mut self, tab_id: String, result: Result<ResponseState, AppError>) {
+        if self.state.send_queue.running.as_ref().is_none_or(|r| r.tab_id != tab_id) {
+            return;
+        }
+        self.state.send_queue.running = None;
+        self.queue_cancel = None;
+        let audit = self.queue_audit.take();
+        write_audit_entry(&self.state.workspace.name, audit, &result);
+        if let Some(idx) = self.tab_index(&tab_id) {
+            let started = self.queue_last_sent;
+            let collection_id = self.state.workspace.open_tabs[idx].collection_id.clone();
+            self.apply_response(idx, collection_id, started, result);
+            // The tab has more sends waiting
+            if self.state.send_queue.entries.iter().any(|e| e.tab_id == tab_id)
+                && let Some(tab) = self.state.workspace.open_tabs.get_mut(idx)
+                && matches!(tab.request_status, RequestStatus::Idle)
+            {
+                tab.request_status = RequestStatus::Queued;
+            }
+        }
+        self.dispatch_send_queue();
     }
 
-    pub fn cancel_request(&mut self) {
-        if let Some(token) = self.cancel.take() {
+    /// Drop the queued sends of the tab with request id `tab_id` and cancel
+    /// its in-flight one.
+    fn cancel_queued_sends(&mut self, tab_id: &str) {
+        let queue = &mut self.state.send_queue;
+        queue.entries.retain(|e| e.tab_id != tab_id);
+        queue.selected = queue.selected.min(queue.entries.len().saturating_sub(1));
+        if queue.running.as_ref().is_some_and(|r| r.tab_id == tab_id)
+            && let Some(token) = self.queue_cancel.take()
+        {
             token.cancel();
         }
-        if let Some(tab) = self.state.active_tab_mut() {
-            tab.request_status = RequestStatus::Idle;
-        }
     }
-}
 
-// ─── Trait extension for HttpMethod ──────────────────────────────────────────
+    /// Whether `send_id` is the in-flight direct send of tab `tab_id`.
+    fn is_current_send(&self, tab_id: &str, send_id: u64) -> bool {
+        self.in_flight.get(tab_id).is_some_and(|f| f.send_id == send_id)
+    }
+
+    fn tab_index(&self, tab_id: &str) -> Option<usize> {
+        self.state.workspace.open_tabs.iter().position(|t| t.request.id == tab_id)
+    }
 
-trait HttpMethodExt {
-    fn from_str_or_get(s: &str) -> crate::state::request_state::HttpMethod;
-}
+    fn open_send_queue(&mut self) {
+        self.state.send_queue.selected = 0;
+        self.state.active_popup = ActivePopup::SendQueue;
+    }
 
-impl HttpMethodExt for crate::state::request_state::HttpMethod {
-    fn from_str_or_get(s: &str) -> Self {
-        use crate::state::request_state::HttpMethod;
-        match s {
-            "GET" => HttpMethod::Get,
-            "POST" => HttpMethod::Post,
-            "PUT" => HttpMethod::Put,
-            "PATCH" => HttpMethod::Patch,
-            "DELETE" => HttpMethod::Delete,
-            "HEAD" => HttpMethod::Head,
-            "OPTIONS" => HttpMethod::Options,
-            _ => HttpMethod::Get,
+    fn handle_send_queue_key(&mut self, key: KeyEvent) {
+        let queue = &mut self.state.send_queue;
+        let len = queue.entries.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state.active_popup = ActivePopup::None,
+            KeyCode::Char('j') | KeyCode::Down if queue.selected + 1 < len => queue.selected += 1,
+            KeyCode::Char('k') | KeyCode::Up => queue.selected = queue.selected.saturating_sub(1),
+            // Move the selected entry later / earlier in the queue
+            KeyCode::Char('J') if queue.selected + 1 < len => {
+                queue.entries.swap(queue.selected, queue.selected + 1);
+                queue.selected += 1;
+            }
+            KeyCode::Char('K') if queue.selected > 0 => {
+                queue.entries.swap(queue.selected, queue.selected - 1);
+                queue.selected -= 1;
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') if queue.selected < len => {
+                let entry = queue.entries.remove(queue.selected);
+                queue.selected = queue.selected.min(queue.entries.len().saturating_sub(1));
+                let still_queued = queue.entries.iter().any(|e| e.tab_id == entry.tab_id);
+                if !still_queued
+                    && let Some(idx) = self.tab_index(&entry.tab_id)
+                    && let Some(tab) = self.state.workspace.open_tabs.get_mut(idx)
+                    && matches!(tab.request_status, RequestStatus::Queued)
+                {
+                    tab.request_status = RequestStatus::Idle;
+                }
+            }
+            // Cancel the in-flight send; its tab shows it as cancelled
+            KeyCode::Char('c') => {
+                if let Some(token) = self.queue_cancel.take() {
+                    token.cancel();
+                }
+            }
+            KeyCode::Char('m') => {
+                let ws = &mut self.state.workspace;
+                ws.send_queue = !ws.send_queue;
+                self.save_workspace_file();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let ws = &mut self.state.workspace;
+                ws.queue_gap_ms = (ws.queue_gap_ms + QUEUE_GAP_STEP_MS).min(MAX_QUEUE_GAP_MS);
+                self.save_workspace_file();
+            }
+            KeyCode::Char('-') => {
+                let ws = &mut self.state.workspace;
+                ws.queue_gap_ms = ws.queue_gap_ms.saturating_sub(QUEUE_GAP_STEP_MS);
+                self.save_workspace_file();
+            }
+            _ => {}
         }
     }
 }
@@ -2148,6 +7424,31 @@ fn cycle_method_prev(m: &str) -> String {
     METHODS[(pos + METHODS.len() - 1) % METHODS.len()].to_string()
 }
 
+/// Switch a text body between rendered markdown and the raw text. The
+/// rendered text is built once, in the theme's colors at the time.
+fn toggle_markdown(resp: &mut ResponseState, theme: &Theme) {
+    let ResponseBody::Text(text) = &resp.body else {
+        return;
+    };
+    if resp.markdown_body.is_none() {
+        resp.markdown_body = Some(render_markdown(text, theme));
+    }
+    resp.markdown_view = !resp.markdown_view;
+}
+
+/// The text body of a tab's response, if it has one.
+fn response_text(tab: &RequestTab) -> Option<&str> {
+    match tab.response.as_ref().map(|r| &r.body) {
+        Some(ResponseBody::Text(text)) => Some(text),
+        _ => None,
+    }
+}
+
+/// A tab's request name, or "Untitled".
+fn tab_label(tab: &RequestTab) -> String {
+    if tab.request.name.is_empty() { "Untitled".to_string() } else { tab.request.name.clone() }
+}
+
 // ─── Collection tree helpers ──────────────────────────────────────────────────
 
 fn add_request_to_folder(
@@ -2212,6 +7513,96 @@ fn remove_item_from_list(items: &mut Vec<CollectionItem>, id: &str) -> bool {
     false
 }
 
+/// Remove the request or folder `id` from anywhere in `items` and return it.
+fn take_item_from_list(items: &mut Vec<CollectionItem>, id: &str) -> Option<CollectionItem> {
+    if let Some(idx) = items.iter().position(|item| match item {
+        CollectionItem::Folder(f) => f.id == id,
+        CollectionItem::Request(r) => r.id == id,
+    }) {
+        return Some(items.remove(idx));
+    }
+    items.iter_mut().find_map(|item| match item {
+        CollectionItem::Folder(f) => take_item_from_list(&mut f.items, id),
+        CollectionItem::Request(_) => None,
+    })
+}
+
+/// Swap the request or folder `id` with its neighbouring sibling in whichever
+/// list holds it. False when it is already first / last or not found.
+fn swap_item_in_list(items: &mut [CollectionItem], id: &str, down: bool) -> bool {
+    if let Some(idx) = items.iter().position(|item| match item {
+        CollectionItem::Folder(f) => f.id == id,
+        CollectionItem::Request(r) => r.id == id,
+    }) {
+        return swap_sibling(items, idx, down);
+    }
+    items.iter_mut().any(|item| match item {
+        CollectionItem::Folder(f) => swap_item_in_list(&mut f.items, id, down),
+        CollectionItem::Request(_) => false,
+    })
+}
+
+/// Swap `items[idx]` with the element after (`down`) or before it.
+fn swap_sibling<T>(items: &mut [T], idx: usize, down: bool) -> bool {
+    let other = if down { idx + 1 } else { idx.wrapping_sub(1) };
+    if other >= items.len() {
+        return false;
+    }
+    items.swap(idx, other);
+    true
+}
+
+/// Give every folder and request in `items` a fresh id, pointing
+/// `depends_on` links inside the copied subtree at the copies.
+fn rekey_items(items: &mut [CollectionItem]) {
+    fn assign(items: &mut [CollectionItem], ids: &mut HashMap<String, String>) {
+        for item in items {
+            let new_id = uuid::Uuid::new_v4().to_string();
+            match item {
+                CollectionItem::Folder(f) => {
+                    ids.insert(std::mem::replace(&mut f.id, new_id), f.id.clone());
+                    assign(&mut f.items, ids);
+                }
+                CollectionItem::Request(r) => {
+                    ids.insert(std::mem::replace(&mut r.id, new_id), r.id.clone());
+                }
+            }
+        }
+    }
+    fn relink(items: &mut [CollectionItem], ids: &HashMap<String, String>) {
+        for item in items {
+            match item {
+                CollectionItem::Folder(f) => relink(&mut f.items, ids),
+                CollectionItem::Request(r) => {
+                    if let Some(new_id) = r.depends_on.as_ref().and_then(|d| ids.get(d)) {
+                        r.depends_on = Some(new_id.clone());
+                    }
+                }
+            }
+        }
+    }
+    let mut ids = HashMap::new();
+    assign(items, &mut ids);
+    relink(items, &ids);
+}
+
+fn find_folder_in_list_mut<'a>(items: &'a mut [CollectionItem], id: &str) -> Option<&'a mut Folder> {
+    items.iter_mut().find_map(|item| match item {
+        CollectionItem::Folder(f) => {
+            if f.id == id { Some(f) } else { find_folder_in_list_mut(&mut f.items, id) }
+        }
+        CollectionItem::Request(_) => None,
+    })
+}
+
+fn find_item_in_list<'a>(items: &'a [CollectionItem], id: &str) -> Option<&'a CollectionItem> {
+    items.iter().find_map(|item| match item {
+        CollectionItem::Folder(f) if f.id == id => Some(item),
+        CollectionItem::Folder(f) => find_item_in_list(&f.items, id),
+        CollectionItem::Request(r) => (r.id == id).then_some(item),
+    })
+}
+
 fn item_exists_in_list(items: &[CollectionItem], id: &str) -> bool {
     for item in items {
         match item {
@@ -2253,58 +7644,63 @@ fn insert_after_in_list(
     false
 }
 
-fn find_col_request_by_id<'a>(
-    collections: &'a [Collection],
-    id: &str,
-) -> Option<&'a CollectionRequest> {
-    for col in collections {
-        if let Some(r) = find_request_in_items(&col.items, id) {
-            return Some(r);
+/// Complete and append an audit entry started when the request was sent.
+/// Cancelled requests are not recorded.
+fn write_audit_entry(
+    workspace: &str,
+    pending: Option<(AuditEntry, Instant)>,
+    result: &Result<ResponseState, AppError>,
+) {
+    let Some((mut entry, started)) = pending else {
+        return;
+    };
+    match result {
+        Ok(response) => {
+            entry.status = Some(response.status);
+            entry.duration_ms = response.timing.total_ms;
         }
+        Err(AppError::Cancelled) => return,
+        Err(_) => entry.duration_ms = started.elapsed().as_millis() as u64,
     }
-    None
+    let _ = audit_storage::append(workspace, &entry);
 }
 
-fn find_request_in_items<'a>(
-    items: &'a [CollectionItem],
+fn find_request_in_items_mut<'a>(
+    items: &'a mut [CollectionItem],
     id: &str,
-) -> Option<&'a CollectionRequest> {
-    for item in items {
-        match item {
-            CollectionItem::Request(r) if r.id == id => return Some(r),
-            CollectionItem::Folder(f) => {
-                if let Some(r) = find_request_in_items(&f.items, id) {
-                    return Some(r);
-                }
-            }
-            _ => {}
+) -> Option<&'a mut CollectionRequest> {
+    items.iter_mut().find_map(|item| match item {
+        CollectionItem::Request(r) => (r.id == id).then_some(r),
+        CollectionItem::Folder(f) => find_request_in_items_mut(&mut f.items, id),
+    })
+}
+
+fn set_request_dependency(
+    items: &mut [CollectionItem],
+    id: &str,
+    dep: Option<String>,
+) -> bool {
+    items.iter_mut().any(|item| match item {
+        CollectionItem::Request(r) if r.id == id => {
+            r.depends_on = dep.clone();
+            true
         }
-    }
-    None
+        CollectionItem::Folder(f) => set_request_dependency(&mut f.items, id, dep.clone()),
+        _ => false,
+    })
 }
 
 fn update_col_request_state(
     items: &mut Vec<CollectionItem>,
     id: &str,
-    url: &str,
-    method: &str,
-    body_raw: &str,
+    request: &RequestState,
 ) -> bool {
-    for item in items.iter_mut() {
-        match item {
-            CollectionItem::Request(r) if r.id == id => {
-                r.url = url.to_string();
-                r.method = method.to_string();
-                r.body_raw = body_raw.to_string();
-                return true;
-            }
-            CollectionItem::Folder(f) => {
-                if update_col_request_state(&mut f.items, id, url, method, body_raw) {
-                    return true;
-                }
-            }
-            _ => {}
+    items.iter_mut().any(|item| match item {
+        CollectionItem::Request(r) if r.id == id => {
+            store_request_state(r, request);
+            true
         }
-    }
-    false
+        CollectionItem::Folder(f) => update_col_request_state(&mut f.items, id, request),
+        _ => false,
+    })
 }
@@ -0,0 +1,98 @@
+// Non-interactive entry points, e.g. `forge run <workspace> <collection>` for CI
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+
+use crate::actions::collection::subtree_request_ids;
+use crate::actions::request::{prepare_request, saved_request_state};
+use crate::actions::runner::{check_failures, format_report};
+use crate::actions::collection::find_col_request_by_id;
+use crate::env::chain::ChainSource;
+use crate::env::resolver::active_env_vars;
+use crate::http::{client::build_client, executor::do_execute};
+use crate::scripting::runner::{run_post_response, ScriptResult};
+use crate::state::app_state::{AppState, NodeRunStatus, RunnerRow};
+use crate::storage::workspace as ws_storage;
+
+/// Run every request of a collection top to bottom, print the results table
+/// and return whether all of them passed their checks.
+pub async fn run(args: &[String]) -> anyhow::Result<bool> {
+    let [workspace, collection] = args else {
+        bail!("usage: forge run <workspace> <collection>");
+    };
+    if !ws_storage::list_workspaces().contains(workspace) {
+        bail!("workspace '{workspace}' not found");
+    }
+    let mut state = AppState {
+        workspace: ws_storage::load_workspace_full(workspace),
+        ..Default::default()
+    };
+    let col = state
+        .workspace
+        .collections
+        .iter()
+        .find(|c| c.name == *collection || c.id == *collection)
+        .cloned()
+        .with_context(|| format!("collection '{collection}' not found in '{workspace}'"))?;
+
+    // Cookies set during the run are sent on later requests but not saved
+    let client = build_client(&state.workspace.cookies, &state.workspace.proxy);
+    let mut rows = Vec::new();
+    for id in subtree_request_ids(&state.workspace.collections, &col.id) {
+        let Some(saved) = find_col_request_by_id(&state.workspace.collections, &id).cloned()
+        else {
+            continue;
+        };
+        let mut row = RunnerRow {
+            request_id: id.clone(),
+            name: saved.name.clone(),
+            method: saved.method.clone(),
+            status: NodeRunStatus::Failed,
+            failures: Vec::new(),
+            response: None,
+        };
+        let (prepared, pre_script) = prepare_request(&state, saved_request_state(&saved), Some(&id));
+        if let Some(result) = pre_script {
+            apply_script_result(&mut state, &saved.name, result);
+        }
+        match prepared {
+            Ok(request) if request.url.is_empty() => row.failures.push("no URL".to_string()),
+            Ok(request) => match do_execute(client.clone(), request).await {
+                Ok(response) => {
+                    row.status = NodeRunStatus::Done {
+                        status: response.status,
+                        total_ms: response.timing.total_ms,
+                    };
+                    row.failures = check_failures(&saved.checks, &response);
+                    let source = Arc::new(ChainSource::from_response(&response));
+                    state.workspace.last_responses.insert(id.clone(), source);
+                    if !saved.scripts.post_response.trim().is_empty() {
+                        let env = active_env_vars(&state);
+                        let result = run_post_response(&saved.scripts.post_response, &response, &env);
+                        apply_script_result(&mut state, &saved.name, result);
+                    }
+                }
+                Err(e) => row.failures.push(e.to_string()),
+            },
+            Err(e) => row.failures.push(e),
+        }
+        rows.push(row);
+    }
+
+    print!("{}", format_report(&col.name, &rows));
+    Ok(rows.iter().all(|r| r.passed() == Some(true)))
+}
+
+/// Print a script's log output to stderr and apply its environment updates
+/// in memory, so later requests of the run see them. Nothing is saved.
+fn apply_script_result(state: &mut AppState, request: &str, result: ScriptResult) {
+    for line in &result.logs {
+        eprintln!("[{request}] {line}");
+    }
+    let ws = &mut state.workspace;
+    if let Some(env) = ws.active_environment_idx.and_then(|i| ws.environments.get_mut(i)) {
+        for (key, value) in &result.env_updates {
+            env.set_var(key, value);
+        }
+    }
+}
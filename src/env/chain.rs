@@ -0,0 +1,74 @@
+// `{{response.<request>.body.$.path}}` and `{{response.<request>.header.Name}}`
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::actions::runner::json_path;
+use crate::state::response_state::{ResponseBody, ResponseState};
+
+/// The parts of a request's last response that chained references read.
+#[derive(Debug, Default)]
+pub struct ChainSource {
+    pub headers: Vec<(String, String)>,
+    /// The body parsed once when the response arrives; `None` if not JSON
+    pub json: Option<Value>,
+}
+
+impl ChainSource {
+    pub fn from_response(response: &ResponseState) -> Self {
+        let json = match &response.body {
+            ResponseBody::Text(text) => serde_json::from_str(text).ok(),
+            _ => None,
+        };
+        Self { headers: response.headers.clone(), json }
+    }
+}
+
+/// Resolve a `response.<request>.…` variable name against `sources`, keyed
+/// by request name or id. Values come from stored responses and are never
+/// resolved further, so a request can safely reference itself: it reads its
+/// previous response, or stays unresolved before the first one.
+pub fn resolve_chain(name: &str, sources: &HashMap<String, Arc<ChainSource>>) -> Option<String> {
+    let rest = name.strip_prefix("response.")?;
+    // Request names may contain dots, so split on the accessor instead
+    if let Some(i) = rest.find(".body.$") {
+        let source = sources.get(&rest[..i])?;
+        let value = json_path(source.json.as_ref()?, &rest[i + ".body.".len()..])?;
+        return Some(match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        });
+    }
+    let i = rest.rfind(".header.")?;
+    let header = &rest[i + ".header.".len()..];
+    sources
+        .get(&rest[..i])?
+        .headers
+        .iter()
+        .rev()
+        .find(|(k, _)| k.eq_ignore_ascii_case(header))
+        .map(|(_, v)| v.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_chain_body_and_header() {
+        let source = ChainSource {
+            headers: vec![("X-Request-Id".to_string(), "r-1".to_string())],
+            json: Some(serde_json::json!({"data": {"token": "abc", "ids": [7, 8]}})),
+        };
+        let sources = HashMap::from([("auth.login v2".to_string(), Arc::new(source))]);
+
+        let get = |name: &str| resolve_chain(name, &sources);
+        assert_eq!(get("response.auth.login v2.body.$.data.token").as_deref(), Some("abc"));
+        assert_eq!(get("response.auth.login v2.body.$.data.ids[1]").as_deref(), Some("8"));
+        assert_eq!(get("response.auth.login v2.header.x-request-id").as_deref(), Some("r-1"));
+        assert_eq!(get("response.auth.login v2.body.$.missing"), None);
+        assert_eq!(get("response.other.body.$.data"), None);
+        assert_eq!(get("auth.login v2.header.X-Request-Id"), None);
+    }
+}
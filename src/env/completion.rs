@@ -0,0 +1,72 @@
+use crate::state::app_state::{AppState, CompletionItem};
+use crate::state::collection::inherited_variables;
+use crate::state::environment::{EnvVariable, VarType};
+use crate::util::fuzzy::fuzzy_match;
+
+/// Every variable name `{{…}}` can resolve to, in resolver priority order:
+/// the active environment first, then the active tab's collection and
+/// folder variables, then OS environment variables. Names are deduplicated
+/// (the higher-priority layer wins).
+pub fn variable_candidates(state: &AppState) -> Vec<CompletionItem> {
+    let mut items: Vec<CompletionItem> = Vec::new();
+
+    let env_vars: &[EnvVariable] = state
+        .active_environment_idx()
+        .and_then(|i| state.workspace.environments.get(i))
+        .map_or(&[], |env| &env.variables);
+    let collection_vars = state
+        .active_tab()
+        .and_then(|t| t.collection_id.as_deref())
+        .map(|id| inherited_variables(&state.workspace.collections, id))
+        .unwrap_or_default();
+    for var in env_vars.iter().chain(&collection_vars).filter(|v| v.enabled && !v.key.is_empty()) {
+        if !items.iter().any(|i| i.key == var.key) {
+            items.push(CompletionItem {
+                key: var.key.clone(),
+                secret: var.var_type == VarType::Secret,
+            });
+        }
+    }
+
+    let mut os_keys: Vec<String> = std::env::vars().map(|(k, _)| k).collect();
+    os_keys.sort();
+    for key in os_keys {
+        if !items.iter().any(|i| i.key == key) {
+            items.push(CompletionItem { key, secret: false });
+        }
+    }
+    items
+}
+
+/// Candidates matching the typed `prefix`, best match first. Ties keep the
+/// input order, so environment variables stay ahead of OS ones.
+pub fn filter_candidates(items: Vec<CompletionItem>, prefix: &str) -> Vec<CompletionItem> {
+    let mut scored: Vec<(i64, CompletionItem)> = items
+        .into_iter()
+        .filter_map(|item| fuzzy_match(prefix, &item.key).map(|m| (m.score, item)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(key: &str) -> CompletionItem {
+        CompletionItem { key: key.to_string(), secret: false }
+    }
+
+    #[test]
+    fn test_filter_empty_prefix_keeps_order() {
+        let items = vec![item("host"), item("token"), item("HOME")];
+        assert_eq!(filter_candidates(items.clone(), ""), items);
+    }
+
+    #[test]
+    fn test_filter_prefers_prefix_match() {
+        let items = vec![item("api_token"), item("token"), item("other")];
+        let keys: Vec<String> = filter_candidates(items, "tok").into_iter().map(|i| i.key).collect();
+        assert_eq!(keys, vec!["token", "api_token"]);
+    }
+}
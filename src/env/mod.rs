@@ -1,2 +1,4 @@
 pub mod resolver;
 pub mod interpolator;
+pub mod completion;
+pub mod chain;
@@ -1,7 +1,11 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use crate::actions::collection::find_col_request_by_id;
+use crate::env::chain::{resolve_chain, ChainSource};
 use crate::env::interpolator::parse_vars;
 use crate::state::app_state::AppState;
+use crate::state::collection::inherited_variables;
 use crate::state::environment::VarType;
 
 pub enum VarStatus {
@@ -25,11 +29,13 @@ pub struct ResolvedString {
 pub struct EnvResolver {
     pub layers: Vec<HashMap<String, String>>,
     pub secret_keys: HashSet<String>,
+    /// Last responses for `{{response.<request>.…}}`, by request name and id
+    pub chains: HashMap<String, Arc<ChainSource>>,
 }
 
 impl EnvResolver {
     pub fn new(layers: Vec<HashMap<String, String>>, secret_keys: HashSet<String>) -> Self {
-        Self { layers, secret_keys }
+        Self { layers, secret_keys, chains: HashMap::new() }
     }
 
     /// Resolve a string for display. Secrets are replaced with `••••••••`.
@@ -108,14 +114,15 @@ impl EnvResolver {
         output
     }
 
-    /// Look up a variable name across all layers (display version — no secrets).
+    /// Look up a variable name across all layers, then chained responses
+    /// (display version — no secrets).
     fn lookup(&self, name: &str) -> Option<String> {
         for layer in &self.layers {
             if let Some(val) = layer.get(name) {
                 return Some(val.clone());
             }
         }
-        None
+        resolve_chain(name, &self.chains)
     }
 
     /// Look up a variable including secret values (for sending).
@@ -124,33 +131,68 @@ impl EnvResolver {
     }
 }
 
-/// Build an `EnvResolver` from the current `AppState`.
-/// Priority: active environment variables > OS environment variables.
-pub fn resolver_from_state(state: &AppState) -> EnvResolver {
+/// Enabled variables of the active environment (empty if none is active).
+pub fn active_env_vars(state: &AppState) -> HashMap<String, String> {
+    state
+        .active_environment_idx()
+        .and_then(|idx| state.workspace.environments.get(idx))
+        .map(|env| {
+            env.variables
+                .iter()
+                .filter(|v| v.enabled)
+                .map(|v| (v.key.clone(), v.value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build an `EnvResolver` from the current `AppState` for a request saved as
+/// `collection_id`. Priority: active environment variables > the request's
+/// collection and folder variables > OS environment variables.
+pub fn resolver_for(state: &AppState, collection_id: Option<&str>) -> EnvResolver {
     let mut layers: Vec<HashMap<String, String>> = Vec::new();
     let mut secret_keys: HashSet<String> = HashSet::new();
 
     // Layer 0: active environment
-    if let Some(idx) = state.workspace.active_environment_idx {
-        if let Some(env) = state.workspace.environments.get(idx) {
-            let mut map = HashMap::new();
-            for var in &env.variables {
-                if var.enabled {
-                    map.insert(var.key.clone(), var.value.clone());
-                    if var.var_type == VarType::Secret {
-                        secret_keys.insert(var.key.clone());
-                    }
-                }
+    if let Some(idx) = state.active_environment_idx()
+        && let Some(env) = state.workspace.environments.get(idx)
+    {
+        for var in &env.variables {
+            if var.enabled && var.var_type == VarType::Secret {
+                secret_keys.insert(var.key.clone());
             }
-            layers.push(map);
         }
+        layers.push(active_env_vars(state));
     }
 
-    // Layer 1: OS environment variables (lowest priority)
+    // Layer 1: the request's collection and folder variables
+    if let Some(id) = collection_id {
+        let vars = inherited_variables(&state.workspace.collections, id);
+        for var in &vars {
+            if var.var_type == VarType::Secret && !layers.iter().any(|l| l.contains_key(&var.key)) {
+                secret_keys.insert(var.key.clone());
+            }
+        }
+        layers.push(vars.into_iter().map(|v| (v.key, v.value)).collect());
+    }
+
+    // Layer 2: OS environment variables (lowest priority)
     let os_map: HashMap<String, String> = std::env::vars().collect();
     layers.push(os_map);
 
-    EnvResolver::new(layers, secret_keys)
+    let mut resolver = EnvResolver::new(layers, secret_keys);
+    for (id, source) in &state.workspace.last_responses {
+        if let Some(saved) = find_col_request_by_id(&state.workspace.collections, id) {
+            resolver.chains.insert(saved.name.clone(), source.clone());
+        }
+        resolver.chains.insert(id.clone(), source.clone());
+    }
+    resolver
+}
+
+/// `resolver_for` the active tab's request.
+pub fn active_tab_resolver(state: &AppState) -> EnvResolver {
+    resolver_for(state, state.active_tab().and_then(|t| t.collection_id.as_deref()))
 }
 
 #[cfg(test)]
@@ -201,6 +243,58 @@ mod tests {
         assert_eq!(result, "Bearer supersecret");
     }
 
+    #[test]
+    fn test_collection_variables_sit_between_environment_and_os() {
+        use crate::state::collection::{Collection, CollectionItem, CollectionRequest, Folder};
+        use crate::state::environment::{EnvVariable, Environment};
+
+        let var = |key: &str, value: &str| EnvVariable { key: key.into(), value: value.into(), ..Default::default() };
+        let mut col = Collection::new("api");
+        col.variables = vec![var("host", "col.test"), var("version", "1"), var("token", "col-token")];
+        let mut folder = Folder::new("v2");
+        folder.variables = vec![var("version", "2")];
+        let req = CollectionRequest::new("users");
+        folder.items.push(CollectionItem::Request(req.clone()));
+        col.items.push(CollectionItem::Folder(folder));
+
+        let mut state = AppState::default();
+        state.workspace.collections.push(col);
+        let mut env = Environment::default();
+        env.variables.push(var("token", "env-token"));
+        state.workspace.environments.push(env);
+        state.workspace.active_environment_idx = Some(0);
+
+        let r = resolver_for(&state, Some(&req.id));
+        assert_eq!(r.resolve_for_send("{{host}}/v{{version}} {{token}}"), "col.test/v2 env-token");
+        let outside = resolver_for(&state, None);
+        assert_eq!(outside.resolve_for_send("{{host}}"), "{{host}}");
+    }
+
+    #[test]
+    fn test_tab_environment_overrides_workspace() {
+        use crate::state::environment::{EnvVariable, Environment};
+        use crate::state::workspace::RequestTab;
+
+        let mut state = AppState::default();
+        for (name, host) in [("dev", "dev.test"), ("staging", "staging.test")] {
+            let mut env = Environment { name: name.into(), ..Environment::default() };
+            env.variables.push(EnvVariable { key: "host".into(), value: host.into(), ..Default::default() });
+            state.workspace.environments.push(env);
+        }
+        state.workspace.active_environment_idx = Some(0);
+        state.workspace.open_tabs = vec![RequestTab::default()];
+        state.workspace.active_tab_idx = 0;
+        assert_eq!(active_tab_resolver(&state).resolve_for_send("{{host}}"), "dev.test");
+
+        state.workspace.open_tabs[0].env_override = Some(state.workspace.environments[1].id.clone());
+        assert_eq!(active_tab_resolver(&state).resolve_for_send("{{host}}"), "staging.test");
+        assert_eq!(state.active_environment_name(), Some("staging"));
+
+        // An override naming a deleted environment falls back to the workspace's
+        state.workspace.open_tabs[0].env_override = Some("gone".into());
+        assert_eq!(active_tab_resolver(&state).resolve_for_send("{{host}}"), "dev.test");
+    }
+
     #[test]
     fn test_resolve_for_send_found() {
         let r = make_resolver(&[("host", "example.com")], &[]);
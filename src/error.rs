@@ -8,6 +8,9 @@ pub enum AppError {
     Json(#[from] serde_json::Error),
     #[error("Request timed out")]
     Timeout,
+    /// The OAuth2 token endpoint refused or failed, so the request wasn't sent
+    #[error("OAuth2 token request failed: {0}")]
+    OAuth(String),
     #[error("Request cancelled")]
     Cancelled,
     #[error("{0}")]
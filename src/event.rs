@@ -1,12 +1,70 @@
+use std::collections::HashMap;
+
 use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::text::Text;
+use tokio_util::sync::CancellationToken;
+
 use crate::state::response_state::ResponseState;
 use crate::error::AppError;
+use crate::http::sse::SseEvent;
+use crate::state::request_state::OAuthToken;
+use crate::state::workspace::WorkspaceSummary;
 
 #[derive(Debug)]
 pub enum Event {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Tick,
-    Response(Result<ResponseState, AppError>),
+    /// Result of a direct send from the tab with request id `tab_id`;
+    /// `send_id` tells it from an earlier send of the tab that was replaced.
+    Response {
+        tab_id: String,
+        send_id: u64,
+        result: Result<ResponseState, AppError>,
+    },
+    /// Result of a sidebar run-in-place, keyed by collection request id.
+    SidebarRunResult {
+        request_id: String,
+        result: Result<ResponseState, AppError>,
+    },
+    /// Result of a send from the send queue, keyed by the tab's request id.
+    QueuedResponse {
+        tab_id: String,
+        result: Result<ResponseState, AppError>,
+    },
+    /// A send fetched a new OAuth2 access token for request `request_id`
+    /// (a tab's or collection request's id), to cache for later sends.
+    OAuthToken { request_id: String, token: OAuthToken },
+    /// A tab's WebSocket finished its handshake. `conn_id` names the
+    /// connection, so events from one the tab has since replaced are dropped.
+    WsOpened { conn_id: String },
+    /// A frame received on a tab's WebSocket.
+    WsMessage { conn_id: String, text: String },
+    /// A tab's WebSocket closed or failed to open; `code` comes from the
+    /// close frame.
+    WsClosed {
+        conn_id: String,
+        code: Option<u16>,
+        reason: String,
+    },
+    /// The response to tab `tab_id`'s send is an event stream: `response`
+    /// carries its status and headers, and `cancel` stops it.
+    SseOpened {
+        tab_id: String,
+        send_id: u64,
+        conn_id: String,
+        response: ResponseState,
+        cancel: CancellationToken,
+    },
+    /// An event parsed from a tab's event stream.
+    SseMessage { conn_id: String, event: SseEvent },
+    /// A tab's event stream ended, was closed, or failed.
+    SseClosed { conn_id: String, reason: String },
+    /// A background highlight of the response of the tab with request id
+    /// `tab_id` finished; `job` matches `ResponseState::highlight_job` unless
+    /// the response or its language has changed since.
+    Highlighted { tab_id: String, job: u64, text: Text<'static> },
+    /// Background scan of every workspace's size, for the switcher.
+    WorkspaceSummaries(HashMap<String, WorkspaceSummary>),
     Resize(u16, u16),
 }
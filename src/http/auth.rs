@@ -1 +1,144 @@
 // Authentication header injection (Bearer, Basic, API key, OAuth2)
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::state::request_state::{AuthConfig, OAuthGrant, OAuthToken, RequestState};
+
+/// Token endpoint response (RFC 6749 §5.1 / §5.2).
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    expires_in: Option<i64>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+/// For an OAuth2 request without a fresh cached token, fetch one and store
+/// it in `request.auth` so the builder sends it. Returns the new token, or
+/// `None` when nothing was fetched.
+pub async fn authorize(client: &Client, request: &mut RequestState) -> Result<Option<OAuthToken>, AppError> {
+    let AuthConfig::OAuth2 {
+        token_url,
+        client_id,
+        client_secret,
+        scope,
+        grant,
+        refresh_token,
+        token,
+        ..
+    } = &mut request.auth
+    else {
+        return Ok(None);
+    };
+    if token.as_ref().is_some_and(|t| t.is_fresh(Utc::now())) {
+        return Ok(None);
+    }
+
+    let mut form = vec![("grant_type", grant.as_str())];
+    if !scope.is_empty() {
+        form.push(("scope", scope));
+    }
+    if *grant == OAuthGrant::RefreshToken {
+        form.push(("refresh_token", refresh_token));
+    }
+    let sent_at = Utc::now();
+    let response = client
+        .post(super::builder::normalize_url(token_url))
+        .basic_auth(&*client_id, Some(&*client_secret))
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| AppError::OAuth(e.to_string()))?;
+
+    let status = response.status();
+    let body = response.text().await.map_err(|e| AppError::OAuth(e.to_string()))?;
+    let parsed: Option<TokenResponse> = serde_json::from_str(&body).ok();
+    let fetched = match parsed {
+        Some(TokenResponse { access_token: Some(access_token), expires_in, .. }) if status.is_success() => OAuthToken {
+            access_token,
+            expires_at: expires_in.map(|secs| sent_at + Duration::seconds(secs)),
+        },
+        Some(TokenResponse { error: Some(error), error_description, .. }) => {
+            let detail = error_description.map(|d| format!(": {d}")).unwrap_or_default();
+            return Err(AppError::OAuth(format!("{} {error}{detail}", status.as_u16())));
+        }
+        _ => return Err(AppError::OAuth(format!("{} with no access_token", status.as_u16()))),
+    };
+    *token = Some(fetched.clone());
+    Ok(Some(fetched))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// Serve one HTTP response and hand back the request it answered.
+    async fn serve_once(response: String) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://127.0.0.1:{}/token", listener.local_addr().unwrap().port());
+        let handle = tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = tcp.read(&mut buf).await.unwrap();
+            tcp.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).into_owned()
+        });
+        (url, handle)
+    }
+
+    fn oauth_request(token_url: String, token: Option<OAuthToken>) -> RequestState {
+        RequestState {
+            auth: AuthConfig::OAuth2 {
+                token_url,
+                client_id: "app".into(),
+                client_secret: "s3cret".into(),
+                scope: "read".into(),
+                grant: OAuthGrant::ClientCredentials,
+                refresh_token: String::new(),
+                token_var: String::new(),
+                persist_token: false,
+                token,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorize_fetches_and_caches_token() {
+        let body = r#"{"access_token":"abc","token_type":"Bearer","expires_in":3600}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let (url, server) = serve_once(response).await;
+        let mut request = oauth_request(url, None);
+
+        let token = authorize(&Client::new(), &mut request).await.unwrap().unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert!(token.is_fresh(Utc::now() + Duration::minutes(50)));
+        assert!(!token.is_fresh(Utc::now() + Duration::minutes(61)));
+        let sent = server.await.unwrap();
+        assert!(sent.contains("grant_type=client_credentials&scope=read"));
+        // Client credentials go in a Basic header (`app:s3cret`)
+        assert!(sent.contains("authorization: Basic YXBwOnMzY3JldA=="));
+
+        // A fresh cached token is reused without a round trip
+        assert!(authorize(&Client::new(), &mut request).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authorize_surfaces_token_endpoint_errors() {
+        let body = r#"{"error":"invalid_client","error_description":"unknown client"}"#;
+        let response = format!("HTTP/1.1 401 Unauthorized\r\ncontent-length: {}\r\n\r\n{body}", body.len());
+        let (url, _server) = serve_once(response).await;
+        let expired = OAuthToken { access_token: "old".into(), expires_at: Some(Utc::now()) };
+        let mut request = oauth_request(url, Some(expired));
+
+        let err = authorize(&Client::new(), &mut request).await.unwrap_err();
+        assert!(matches!(&err, AppError::OAuth(msg) if msg == "401 invalid_client: unknown client"), "{err}");
+    }
+}
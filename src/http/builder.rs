@@ -60,6 +60,9 @@ pub fn build_request(client: &Client, state: &RequestState) -> Result<RequestBui
                 builder.query(&[(key.as_str(), value.as_str())])
             }
         }
+        // The executor fetched the token first (`auth::authorize`)
+        AuthConfig::OAuth2 { token: Some(token), .. } => builder.bearer_auth(&token.access_token),
+        AuthConfig::OAuth2 { token: None, .. } => builder,
     };
 
     builder = match &state.body {
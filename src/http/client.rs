@@ -1,10 +1,184 @@
-use reqwest::Client;
+use std::sync::Arc;
 use std::time::Duration;
 
-pub fn build_client() -> Client {
-    Client::builder()
+use chrono::Utc;
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, NoProxy, Proxy};
+
+use crate::state::cookie_jar::SharedJar;
+use crate::state::workspace::ProxySettings;
+
+/// Client for a workspace: cookies from responses go into `jar`, and
+/// matching ones are sent on later requests unless a request sets its own
+/// `Cookie` header. Requests go through the workspace's proxy, except to
+/// hosts on its no-proxy list. Rebuild it when either changes.
+pub fn build_client(jar: &SharedJar, proxy: &ProxySettings) -> Client {
+    // Only the resolved proxies apply; reqwest would read the environment
+    // variables on its own, ignoring the workspace's settings
+    let mut builder = Client::builder()
         .timeout(Duration::from_secs(30))
         .use_rustls_tls()
-        .build()
-        .expect("Failed to build HTTP client")
+        .cookie_provider(Arc::new(jar.clone()))
+        .no_proxy();
+    for proxy in ResolvedProxy::resolve(proxy, env_var).proxies(proxy) {
+        builder = builder.proxy(proxy);
+    }
+    builder.build().expect("Failed to build HTTP client")
+}
+
+/// Value of environment variable `name`, or of its lower-case spelling.
+pub fn env_var(name: &str) -> Option<String> {
+    [name.to_string(), name.to_lowercase()]
+        .iter()
+        .find_map(|n| std::env::var(n).ok())
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Why `url` can't be used as a proxy, if it can't.
+pub fn check_proxy_url(url: &str) -> Result<(), String> {
+    Proxy::all(url.trim()).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Host and port of proxy `url`, leaving out any credentials in it.
+pub fn proxy_host(url: &str) -> String {
+    let parsed = url::Url::parse(url).or_else(|_| url::Url::parse(&format!("http://{url}")));
+    let Some(host) = parsed.as_ref().ok().and_then(|u| u.host_str()) else {
+        return url.to_string();
+    };
+    match parsed.as_ref().ok().and_then(|u| u.port()) {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    }
+}
+
+/// The proxies a workspace's client uses: each empty setting replaced by
+/// its environment variable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedProxy {
+    pub http: Option<String>,
+    pub https: Option<String>,
+    pub no_proxy: String,
+}
+
+impl ResolvedProxy {
+    pub fn resolve(settings: &ProxySettings, var: impl Fn(&str) -> Option<String>) -> Self {
+        let pick = |set: &str, name: &str| match set.trim() {
+            "" => var(name).map(|v| v.trim().to_string()),
+            set => Some(set.to_string()),
+        };
+        Self {
+            http: pick(&settings.http, "HTTP_PROXY"),
+            https: pick(&settings.https, "HTTPS_PROXY"),
+            no_proxy: pick(&settings.no_proxy, "NO_PROXY").unwrap_or_default(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.http.is_some() || self.https.is_some()
+    }
+
+    /// Host and port of the proxy, `https`'s first, for the status bar.
+    pub fn label(&self) -> Option<String> {
+        self.https.as_deref().or(self.http.as_deref()).map(proxy_host)
+    }
+
+    /// The reqwest proxies, with the settings' credentials and this
+    /// no-proxy list. URLs reqwest can't parse are skipped.
+    fn proxies(&self, settings: &ProxySettings) -> Vec<Proxy> {
+        let no_proxy = NoProxy::from_string(&self.no_proxy);
+        let http = self.http.as_deref().and_then(|url| Proxy::http(url).ok());
+        let https = self.https.as_deref().and_then(|url| Proxy::https(url).ok());
+        http.into_iter()
+            .chain(https)
+            .map(|proxy| match settings.username.as_str() {
+                "" => proxy,
+                user => proxy.basic_auth(user, &settings.password),
+            })
+            .map(|proxy| proxy.no_proxy(no_proxy.clone()))
+            .collect()
+    }
+}
+
+impl CookieStore for SharedJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &url::Url) {
+        let now = Utc::now();
+        let mut jar = self.write();
+        for header in cookie_headers.filter_map(|h| h.to_str().ok()) {
+            jar.store(header, url, now);
+        }
+    }
+
+    fn cookies(&self, url: &url::Url) -> Option<HeaderValue> {
+        let header = self.read().header_for(url, Utc::now())?;
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_falls_back_to_env_per_field() {
+        let env = |name: &str| match name {
+            "HTTP_PROXY" => Some("http://env-proxy:8080".to_string()),
+            "NO_PROXY" => Some("localhost,.internal".to_string()),
+            _ => None,
+        };
+        let resolved = ResolvedProxy::resolve(&ProxySettings::default(), env);
+        assert_eq!(resolved.http.as_deref(), Some("http://env-proxy:8080"));
+        assert_eq!(resolved.https, None);
+        assert_eq!(resolved.no_proxy, "localhost,.internal");
+        assert_eq!(resolved.label().as_deref(), Some("env-proxy:8080"));
+
+        let settings = ProxySettings {
+            https: "http://user:pw@proxy.corp:3128".into(),
+            no_proxy: "api.internal".into(),
+            ..ProxySettings::default()
+        };
+        let resolved = ResolvedProxy::resolve(&settings, env);
+        assert_eq!(resolved.http.as_deref(), Some("http://env-proxy:8080"));
+        assert_eq!(resolved.no_proxy, "api.internal");
+        assert_eq!(resolved.label().as_deref(), Some("proxy.corp:3128"));
+
+        assert!(!ResolvedProxy::resolve(&ProxySettings::default(), |_| None).is_active());
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_hosts_bypass_the_proxy() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Answers with the request line it got: absolute-form through a
+        // proxy, origin-form when reached directly
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (mut tcp, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 1024];
+                let n = tcp.read(&mut buf).await.unwrap();
+                let head = String::from_utf8_lossy(&buf[..n]);
+                let line = head.lines().next().unwrap_or_default().to_string();
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{line}",
+                    line.len()
+                );
+                tcp.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+
+        let settings = ProxySettings {
+            http: format!("http://127.0.0.1:{port}"),
+            no_proxy: "127.0.0.1".into(),
+            ..ProxySettings::default()
+        };
+        let client = build_client(&SharedJar::default(), &settings);
+        let get = |url: String| {
+            let client = client.clone();
+            async move { client.get(url).send().await.unwrap().text().await.unwrap() }
+        };
+        assert_eq!(get("http://forge.test/a".into()).await, "GET http://forge.test/a HTTP/1.1");
+        assert_eq!(get(format!("http://127.0.0.1:{port}/b")).await, "GET /b HTTP/1.1");
+    }
 }
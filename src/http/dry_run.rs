@@ -0,0 +1,35 @@
+// Dry run: the request a send would put on the wire, without sending it
+use reqwest::Client;
+
+use crate::error::AppError;
+use crate::state::request_state::{AuthConfig, RequestState};
+use super::builder::build_request;
+
+/// A prepared request as the client would send it: query string, auth and
+/// body content type applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRun {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// OAuth2 with no token yet: a send would fetch one first, and its
+    /// `Authorization` header isn't known
+    pub fetches_token: bool,
+}
+
+/// Build `request` (already through `prepare_request`) without sending it.
+pub fn dry_run(client: &Client, request: &RequestState) -> Result<DryRun, AppError> {
+    let built = build_request(client, request)?.build()?;
+    Ok(DryRun {
+        method: built.method().to_string(),
+        url: built.url().to_string(),
+        headers: built
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v.as_bytes()).into_owned()))
+            .collect(),
+        body: built.body().and_then(|b| b.as_bytes()).map(<[u8]>::to_vec).unwrap_or_default(),
+        fetches_token: matches!(request.auth, AuthConfig::OAuth2 { token: None, .. }),
+    })
+}
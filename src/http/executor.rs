@@ -1,6 +1,6 @@
-use std::time::Instant;
-use chrono::Utc;
-use reqwest::Client;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Response};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
 
@@ -8,29 +8,149 @@ use crate::error::AppError;
 use crate::event::Event;
 use crate::state::request_state::RequestState;
 use crate::state::response_state::{Cookie, RequestTiming, ResponseBody, ResponseState};
+use crate::util::xml;
+use super::auth::authorize;
 use super::builder::build_request;
+use super::sse::is_event_stream;
+use super::stream::relay_events;
+
+/// Longest body text kept for viewing and highlighting; the rest is
+/// replaced by a note of how much was left out.
+pub const MAX_DISPLAY_BYTES: usize = 10_000_000;
 
 pub async fn execute(
     client: Client,
     request: RequestState,
+    tab_id: String,
+    send_id: u64,
     tx: UnboundedSender<Event>,
     cancel: CancellationToken,
 ) {
-    let result = tokio::select! {
-        res = do_execute(client, request) => res,
+    let mut request = request;
+    let sent = tokio::select! {
+        res = authorize_and_send(&client, &mut request, &tx) => res,
         _ = cancel.cancelled() => Err(AppError::Cancelled),
     };
-    let _ = tx.send(Event::Response(result));
+    // Event streams stay open and are relayed as they arrive
+    let result = match sent {
+        Ok((response, started)) if is_event_stream(&content_type(&response)) => {
+            let head = response_head(&response, &started);
+            relay_events(response, head, tab_id, send_id, tx, cancel).await;
+            return;
+        }
+        Ok((response, started)) => tokio::select! {
+            res = read_response(response, started, request.max_body_bytes) => res,
+            _ = cancel.cancelled() => Err(AppError::Cancelled),
+        },
+        Err(e) => Err(e),
+    };
+    let _ = tx.send(Event::Response { tab_id, send_id, result });
 }
 
-async fn do_execute(client: Client, state: RequestState) -> Result<ResponseState, AppError> {
+/// Like `execute`, but reports the result as a sidebar run of `request_id`
+/// instead of a response for a tab.
+pub async fn execute_for(
+    client: Client,
+    mut request: RequestState,
+    request_id: String,
+    tx: UnboundedSender<Event>,
+    cancel: CancellationToken,
+) {
+    // A fetched OAuth2 token is reported for the collection request
+    request.id = request_id.clone();
+    let result = run_cancellable(client, request, &tx, cancel).await;
+    let _ = tx.send(Event::SidebarRunResult { request_id, result });
+}
+
+/// Like `execute`, but waits `delay` first (the send queue's gap) and reports
+/// the result for the tab whose request id is `tab_id`.
+pub async fn execute_queued(
+    client: Client,
+    request: RequestState,
+    tab_id: String,
+    delay: Duration,
+    tx: UnboundedSender<Event>,
+    cancel: CancellationToken,
+) {
+    let waiting = cancel.clone();
+    let result = tokio::select! {
+        _ = tokio::time::sleep(delay) => run_cancellable(client, request, &tx, cancel).await,
+        _ = waiting.cancelled() => Err(AppError::Cancelled),
+    };
+    let _ = tx.send(Event::QueuedResponse { tab_id, result });
+}
+
+async fn run_cancellable(
+    client: Client,
+    mut request: RequestState,
+    tx: &UnboundedSender<Event>,
+    cancel: CancellationToken,
+) -> Result<ResponseState, AppError> {
+    let run = async {
+        let (response, started) = authorize_and_send(&client, &mut request, tx).await?;
+        read_response(response, started, request.max_body_bytes).await
+    };
+    tokio::select! {
+        res = run => res,
+        _ = cancel.cancelled() => Err(AppError::Cancelled),
+    }
+}
+
+pub async fn do_execute(client: Client, mut state: RequestState) -> Result<ResponseState, AppError> {
+    authorize(&client, &mut state).await?;
+    let (response, started) = send(&client, &state).await?;
+    read_response(response, started, state.max_body_bytes).await
+}
+
+/// Fetch a missing or expired OAuth2 token first, reporting it so the app
+/// caches it for later sends, then send.
+async fn authorize_and_send(
+    client: &Client,
+    request: &mut RequestState,
+    tx: &UnboundedSender<Event>,
+) -> Result<(Response, Started), AppError> {
+    if let Some(token) = authorize(client, request).await? {
+        let _ = tx.send(Event::OAuthToken { request_id: request.id.clone(), token });
+    }
+    send(client, request).await
+}
+
+/// When and how a request went out and how long its response headers took.
+struct Started {
+    start: Instant,
+    sent_at: DateTime<Utc>,
+    ttfb_ms: u64,
+    method: String,
+    url: String,
+}
+
+/// Send `state` and wait for the response headers.
+async fn send(client: &Client, state: &RequestState) -> Result<(Response, Started), AppError> {
     let start = Instant::now();
+    let sent_at = Utc::now();
 
-    let builder = build_request(&client, &state)?;
+    let builder = build_request(client, state)?;
     let request = builder.build().map_err(AppError::Http)?;
+    let method = request.method().to_string();
+    let url = request.url().to_string();
     let response = client.execute(request).await?;
 
     let ttfb_ms = start.elapsed().as_millis() as u64;
+    Ok((response, Started { start, sent_at, ttfb_ms, method, url }))
+}
+
+fn content_type(response: &Response) -> String {
+    response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Status, headers and cookies of `response`, with an empty body.
+fn response_head(response: &Response, started: &Started) -> ResponseState {
+    let url = response.url().to_string();
 
     let status = response.status();
     let status_code = status.as_u16();
@@ -42,13 +162,6 @@ async fn do_execute(client: Client, state: RequestState) -> Result<ResponseState
         .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
 
-    let content_type = response
-        .headers()
-        .get("content-type")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("")
-        .to_string();
-
     // Parse cookies from Set-Cookie headers
     let cookies: Vec<Cookie> = response
         .headers()
@@ -58,9 +171,60 @@ async fn do_execute(client: Client, state: RequestState) -> Result<ResponseState
         .map(|header| parse_set_cookie(header))
         .collect();
 
-    let bytes = response.bytes().await?;
-    let download_ms = start.elapsed().as_millis() as u64 - ttfb_ms;
-    let total_ms = start.elapsed().as_millis() as u64;
+    ResponseState {
+        status: status_code,
+        status_text,
+        headers,
+        body: ResponseBody::Empty,
+        cookies,
+        timing: RequestTiming {
+            dns_lookup_ms: 0,
+            tcp_connect_ms: 0,
+            tls_handshake_ms: 0,
+            time_to_first_byte_ms: started.ttfb_ms,
+            download_ms: 0,
+            total_ms: started.ttfb_ms,
+        },
+        size_bytes: 0,
+        truncated_at: None,
+        truncated: false,
+        received_at: Utc::now(),
+        url,
+        request_method: started.method.clone(),
+        request_url: started.url.clone(),
+        environment: None, // stamped by app.rs, which knows the environment
+        sent_at: started.sent_at,
+        scroll_offset: 0,
+        h_scroll: 0,
+        cookies_scroll: 0,
+        headers_cursor: 0,
+        highlighted_body: None, // computed by app.rs once the response arrives
+        highlight_job: 0,
+        force_lang: None,
+        sorted_view: false,
+        sorted_body: None,
+        markdown_view: false,
+        markdown_body: None,
+        tree_view: false,
+        tree_value: None,
+        tree_collapsed: Default::default(),
+        tree_cursor: 0,
+        filter: Default::default(),
+    }
+}
+
+/// Read the body of `response`, up to `max` bytes, into a `ResponseState`.
+async fn read_response(
+    mut response: Response,
+    started: Started,
+    max: Option<usize>,
+) -> Result<ResponseState, AppError> {
+    let mut state = response_head(&response, &started);
+    let content_type = content_type(&response);
+
+    let (bytes, truncated) = read_body(&mut response, max).await?;
+    state.truncated_at = max.filter(|_| truncated);
+    let total_ms = started.start.elapsed().as_millis() as u64;
     let size_bytes = bytes.len();
 
     let body = if content_type.contains("application/json") {
@@ -68,9 +232,10 @@ async fn do_execute(client: Client, state: RequestState) -> Result<ResponseState
             Ok(json) => ResponseBody::Text(serde_json::to_string_pretty(&json)?),
             Err(_) => ResponseBody::Text(String::from_utf8_lossy(&bytes).into_owned()),
         }
+    } else if is_xml(&content_type) {
+        let text = String::from_utf8_lossy(&bytes);
+        ResponseBody::Text(xml::pretty(&text).unwrap_or_else(|| text.into_owned()))
     } else if content_type.contains("text/")
-        || content_type.contains("application/xml")
-        || content_type.contains("application/xhtml")
         || content_type.contains("application/javascript")
     {
         ResponseBody::Text(String::from_utf8_lossy(&bytes).into_owned())
@@ -83,25 +248,56 @@ async fn do_execute(client: Client, state: RequestState) -> Result<ResponseState
         }
     };
 
-    Ok(ResponseState {
-        status: status_code,
-        status_text,
-        headers,
-        body,
-        cookies,
-        timing: RequestTiming {
-            dns_lookup_ms: 0,
-            tcp_connect_ms: 0,
-            tls_handshake_ms: 0,
-            time_to_first_byte_ms: ttfb_ms,
-            download_ms,
-            total_ms,
-        },
-        size_bytes,
-        received_at: Utc::now(),
-        scroll_offset: 0,
-        highlighted_body: None, // computed by app.rs once the response arrives
-    })
+    state.body = match body {
+        ResponseBody::Text(text) if text.len() > MAX_DISPLAY_BYTES => {
+            state.truncated = true;
+            ResponseBody::Text(truncate_for_display(text, MAX_DISPLAY_BYTES))
+        }
+        body => body,
+    };
+    state.size_bytes = size_bytes;
+    state.timing.download_ms = total_ms - started.ttfb_ms;
+    state.timing.total_ms = total_ms;
+    state.received_at = Utc::now();
+    Ok(state)
+}
+
+/// The first `max` bytes of `text` (back to a char boundary), then a note
+/// of how many were cut.
+fn truncate_for_display(mut text: String, max: usize) -> String {
+    let mut end = max.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let remaining = text.len() - end;
+    text.truncate(end);
+    text.push_str(&format!("\n--- [truncated: {remaining} bytes remaining] ---"));
+    text
+}
+
+/// Read `response`'s body chunk by chunk, stopping once `max` bytes are in
+/// so a huge body can't exhaust memory. Returns whether it was cut short.
+/// Cancelling a send drops this future, which stops the download between
+/// (or during) chunks.
+async fn read_body(response: &mut Response, max: Option<usize>) -> Result<(Vec<u8>, bool), AppError> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if let Some(max) = max
+            && body.len() + chunk.len() > max
+        {
+            body.extend_from_slice(&chunk[..max - body.len()]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+/// XML bodies worth re-indenting: `application/xml`, `text/xml`, XHTML and
+/// `+xml` types such as SOAP, RSS and Atom.
+fn is_xml(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    mime.ends_with("xml") || mime.starts_with("application/xhtml")
 }
 
 /// Minimal Set-Cookie header parser.
@@ -112,15 +308,81 @@ fn parse_set_cookie(header: &str) -> Cookie {
     let name = nv.next().unwrap_or("").trim().to_string();
     let value = nv.next().unwrap_or("").trim().to_string();
 
-    let mut domain = String::new();
-    let mut path = "/".to_string();
+    let mut cookie = Cookie { name, value, path: "/".to_string(), ..Cookie::default() };
     for attr in parts.next().unwrap_or("").split(';') {
-        let attr = attr.trim();
-        if let Some(d) = attr.strip_prefix("Domain=") {
-            domain = d.to_string();
-        } else if let Some(p) = attr.strip_prefix("Path=") {
-            path = p.to_string();
+        let (key, val) = attr.split_once('=').unwrap_or((attr, ""));
+        let val = val.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "domain" => cookie.domain = val.to_string(),
+            "path" => cookie.path = val.to_string(),
+            // Also accepts the dashed `Wed, 21-Oct-2026 07:28:00 GMT` form
+            "expires" => {
+                cookie.expires = DateTime::parse_from_rfc2822(&val.replace('-', " "))
+                    .ok()
+                    .map(|at| at.with_timezone(&Utc));
+            }
+            "httponly" => cookie.http_only = true,
+            "secure" => cookie.secure = true,
+            "samesite" if !val.is_empty() => cookie.same_site = Some(val.to_string()),
+            _ => {}
         }
     }
-    Cookie { name, value, domain, path }
+    cookie
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_set_cookie_attributes() {
+        let cookie = parse_set_cookie(
+            "sid=abc; Domain=.example.com; Path=/api; Expires=Wed, 21-Oct-2026 07:28:00 GMT; \
+             HttpOnly; Secure; SameSite=Lax",
+        );
+        assert_eq!((cookie.name.as_str(), cookie.value.as_str()), ("sid", "abc"));
+        assert_eq!((cookie.domain.as_str(), cookie.path.as_str()), (".example.com", "/api"));
+        assert_eq!(cookie.expires.map(|at| at.to_rfc3339()).as_deref(), Some("2026-10-21T07:28:00+00:00"));
+        assert!(cookie.http_only && cookie.secure);
+        assert_eq!(cookie.same_site.as_deref(), Some("Lax"));
+
+        let session = parse_set_cookie("theme=dark");
+        assert_eq!(session.path, "/");
+        assert_eq!(session.expires, None);
+        assert!(!session.http_only && !session.secure);
+    }
+
+    #[test]
+    fn test_truncate_for_display_keeps_whole_chars() {
+        assert_eq!(truncate_for_display("abcdef".into(), 4), "abcd\n--- [truncated: 2 bytes remaining] ---");
+        // "é" is two bytes; the cut backs off rather than split it
+        assert_eq!(truncate_for_display("abcé".into(), 4), "abc\n--- [truncated: 2 bytes remaining] ---");
+    }
+
+    #[tokio::test]
+    async fn test_body_over_the_cap_is_truncated() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = tcp.read(&mut buf).await.unwrap();
+            let body = "0123456789".repeat(10);
+            let head = format!("HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\ncontent-length: {}\r\n\r\n", body.len());
+            tcp.write_all(head.as_bytes()).await.unwrap();
+            tcp.write_all(body.as_bytes()).await.unwrap();
+        });
+
+        let request = RequestState {
+            url: format!("http://127.0.0.1:{port}/"),
+            max_body_bytes: Some(15),
+            ..Default::default()
+        };
+        let response = do_execute(Client::new(), request).await.unwrap();
+        assert_eq!(response.truncated_at, Some(15));
+        assert_eq!(response.size_bytes, 15);
+        assert!(matches!(&response.body, ResponseBody::Text(text) if text == "012345678901234"));
+    }
 }
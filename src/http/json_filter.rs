@@ -0,0 +1,74 @@
+// JSONPath and jq-style filters over a JSON response body
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// Evaluate `filter` against `body` and pretty-print the result.
+///
+/// Accepts JSONPath (`$.data.items[0].name`) or a small jq subset: `.` paths
+/// with `[n]` and `[]` (every element), chained with `|`, e.g.
+/// `.items[] | .id`. A single match prints as itself; several print as an
+/// array for JSONPath and one per line for jq, as `jq` does.
+pub fn apply_filter(body: &str, filter: &str) -> Result<String, String> {
+    let root: Value = serde_json::from_str(body).map_err(|_| "response body is not JSON".to_string())?;
+    let filter = filter.trim();
+    if filter.starts_with('$') {
+        let matches = query(&root, filter)?;
+        let out = match matches.as_slice() {
+            [single] => (*single).clone(),
+            _ => Value::Array(matches.into_iter().cloned().collect()),
+        };
+        return Ok(pretty(&out));
+    }
+
+    let mut values = vec![root];
+    for stage in filter.split('|') {
+        let path = jq_to_jsonpath(stage.trim())?;
+        let mut next = Vec::new();
+        for value in &values {
+            next.extend(query(value, &path)?.into_iter().cloned());
+        }
+        values = next;
+    }
+    Ok(values.iter().map(pretty).collect::<Vec<_>>().join("\n"))
+}
+
+fn query<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>, String> {
+    let path = JsonPath::parse(path).map_err(|e| e.to_string())?;
+    Ok(path.query(value).all())
+}
+
+/// `.a.b[0]` → `$.a.b[0]`, `.items[]` → `$.items[*]`, `.` → `$`.
+fn jq_to_jsonpath(stage: &str) -> Result<String, String> {
+    let Some(rest) = stage.strip_prefix('.') else {
+        return Err(format!("expected a path starting with `.` or `$`, got `{stage}`"));
+    };
+    let rest = rest.replace("[]", "[*]");
+    Ok(if rest.is_empty() || rest.starts_with('[') {
+        format!("${rest}")
+    } else {
+        format!("$.{rest}")
+    })
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{"data":{"items":[{"id":1,"name":"a"},{"id":2,"name":"b"}]}}"#;
+
+    #[test]
+    fn test_jsonpath_and_jq_filters() {
+        assert_eq!(apply_filter(BODY, "$.data.items[0].name").unwrap(), r#""a""#);
+        assert_eq!(apply_filter(BODY, "$.data.items[*].id").unwrap(), "[\n  1,\n  2\n]");
+        assert_eq!(apply_filter(BODY, ".data.items[1].name").unwrap(), r#""b""#);
+        assert_eq!(apply_filter(BODY, ".data.items[] | .id").unwrap(), "1\n2");
+        assert_eq!(apply_filter(BODY, ".data | .items[0] | .id").unwrap(), "1");
+        assert!(apply_filter(BODY, "$.data[").is_err());
+        assert!(apply_filter(BODY, "data").is_err());
+        assert!(apply_filter("plain text", ".").is_err());
+    }
+}
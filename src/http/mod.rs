@@ -3,3 +3,8 @@ pub mod executor;
 pub mod builder;
 pub mod auth;
 pub mod stream;
+pub mod sse;
+pub mod retry;
+pub mod json_filter;
+pub mod websocket;
+pub mod dry_run;
@@ -0,0 +1,56 @@
+// Retry-After handling for rate-limited (429) and unavailable (503) responses
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Delay a 429 or 503 response asks for in its `Retry-After` header, in
+/// either the delay-seconds or the HTTP-date form, capped at `max`. `None`
+/// for other statuses or a missing or unparseable header.
+pub fn retry_after(
+    status: u16,
+    headers: &[(String, String)],
+    now: DateTime<Utc>,
+    max: Duration,
+) -> Option<Duration> {
+    if status != 429 && status != 503 {
+        return None;
+    }
+    let value = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+        .map(|(_, v)| v.trim())?;
+    let delay = match value.parse::<u64>() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => {
+            let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+            // A date in the past means "now"
+            (at - now).to_std().unwrap_or(Duration::ZERO)
+        }
+    };
+    Some(delay.min(max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(value: &str) -> Vec<(String, String)> {
+        vec![("Retry-After".to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn test_retry_after_seconds_and_date() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT").unwrap().with_timezone(&Utc);
+        let max = Duration::from_secs(60);
+        assert_eq!(retry_after(429, &header("12"), now, max), Some(Duration::from_secs(12)));
+        assert_eq!(
+            retry_after(503, &header("Wed, 21 Oct 2026 07:28:30 GMT"), now, max),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(retry_after(429, &header("Wed, 21 Oct 2026 07:00:00 GMT"), now, max), Some(Duration::ZERO));
+        assert_eq!(retry_after(429, &header("3600"), now, max), Some(max));
+        assert_eq!(retry_after(200, &header("12"), now, max), None);
+        assert_eq!(retry_after(429, &header("soon"), now, max), None);
+        assert_eq!(retry_after(429, &[], now, max), None);
+    }
+}
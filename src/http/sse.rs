@@ -0,0 +1,170 @@
+// Server-Sent Events framing for `text/event-stream` bodies
+
+/// One dispatched event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event:` field, `message` when the event didn't name one
+    pub event: String,
+    /// The stream's last event id at dispatch, if one was set
+    pub id: Option<String>,
+    pub data: String,
+}
+
+/// Incremental parser: chunks are fed as they arrive, and events split
+/// across chunks (mid-line, between `\r` and `\n`, or mid-character) come
+/// out once their terminating blank line does.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes of the current, unterminated line
+    line: Vec<u8>,
+    /// The last byte fed was `\r`, so a following `\n` ends no new line
+    after_cr: bool,
+    /// Past the first line, where a byte-order mark may appear
+    started: bool,
+    data: String,
+    event: String,
+    last_id: Option<String>,
+    /// Reconnection time in milliseconds from the last `retry:` field
+    pub retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Parse `chunk` and return the events it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if std::mem::take(&mut self.after_cr) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\r' => {
+                    self.after_cr = true;
+                    self.end_line(&mut events);
+                }
+                b'\n' => self.end_line(&mut events),
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn end_line(&mut self, events: &mut Vec<SseEvent>) {
+        let bytes = std::mem::take(&mut self.line);
+        let mut line = String::from_utf8_lossy(&bytes).into_owned();
+        if !std::mem::replace(&mut self.started, true) && line.starts_with('\u{feff}') {
+            line.remove(0);
+        }
+
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+        // Lines starting with a colon are comments (often keep-alives)
+        if line.starts_with(':') {
+            return;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_str(), ""),
+        };
+        match field {
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "event" => self.event = value.to_string(),
+            "id" if !value.contains('\0') => {
+                self.last_id = (!value.is_empty()).then(|| value.to_string());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok();
+            }
+            _ => {}
+        }
+    }
+
+    /// A blank line ends the event; one without `data:` fields is dropped.
+    fn dispatch(&mut self, events: &mut Vec<SseEvent>) {
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        events.push(SseEvent {
+            event: if event.is_empty() { "message".to_string() } else { event },
+            id: self.last_id.clone(),
+            data,
+        });
+    }
+}
+
+/// Whether a response's `Content-Type` marks an event stream.
+pub fn is_event_stream(content_type: &str) -> bool {
+    content_type.trim_start().to_ascii_lowercase().starts_with("text/event-stream")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event: &str, id: Option<&str>, data: &str) -> SseEvent {
+        SseEvent {
+            event: event.to_string(),
+            id: id.map(str::to_string),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sse_parser_fields() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(
+            b"\xEF\xBB\xBF: keep-alive\n\
+              data: first\n\
+              data:second\n\
+              \n\
+              event: update\r\n\
+              id: 7\r\n\
+              retry: 3000\r\n\
+              data\r\n\
+              \r\n\
+              retry: soon\n\
+              id\n\
+              data: {\"n\": 1}\n\
+              \n\
+              event: ignored\n\
+              \n\
+              data: unterminated",
+        );
+        assert_eq!(
+            events,
+            [
+                event("message", None, "first\nsecond"),
+                event("update", Some("7"), ""),
+                event("message", None, "{\"n\": 1}"),
+            ]
+        );
+        assert_eq!(parser.retry, Some(3000));
+    }
+
+    #[test]
+    fn test_sse_parser_events_split_across_chunks() {
+        let stream = "event: tick\r\nid: 1\r\ndata: héllo\r\ndata: wörld\r\n\r\ndata: next\r\r".as_bytes();
+        let expected = [
+            event("tick", Some("1"), "héllo\nwörld"),
+            event("message", Some("1"), "next"),
+        ];
+        // Every split point, including inside `\r\n` and inside `é` / `ö`
+        for split in 0..=stream.len() {
+            let mut parser = SseParser::default();
+            let mut events = parser.feed(&stream[..split]);
+            events.extend(parser.feed(&stream[split..]));
+            assert_eq!(events, expected, "split at byte {split}");
+        }
+        // One byte at a time
+        let mut parser = SseParser::default();
+        let events: Vec<_> = stream.chunks(1).flat_map(|b| parser.feed(b)).collect();
+        assert_eq!(events, expected);
+    }
+}
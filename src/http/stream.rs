@@ -1 +1,102 @@
 // Streaming response and SSE support
+use reqwest::Response;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::event::Event;
+use crate::state::response_state::ResponseState;
+use super::sse::SseParser;
+
+/// Relay an event-stream response: `head` (status and headers) goes out as
+/// `SseOpened`, then each parsed event, until the server ends the stream or
+/// `cancel` fires.
+pub async fn relay_events(
+    mut response: Response,
+    head: ResponseState,
+    tab_id: String,
+    send_id: u64,
+    tx: UnboundedSender<Event>,
+    cancel: CancellationToken,
+) {
+    let conn_id = Uuid::new_v4().to_string();
+    let _ = tx.send(Event::SseOpened {
+        tab_id,
+        send_id,
+        conn_id: conn_id.clone(),
+        response: head,
+        cancel: cancel.clone(),
+    });
+
+    let mut parser = SseParser::default();
+    let reason = loop {
+        tokio::select! {
+            chunk = response.chunk() => match chunk {
+                Ok(Some(bytes)) => {
+                    for event in parser.feed(&bytes) {
+                        let _ = tx.send(Event::SseMessage { conn_id: conn_id.clone(), event });
+                    }
+                }
+                Ok(None) => break "ended by the server".to_string(),
+                Err(e) => break e.to_string(),
+            },
+            _ = cancel.cancelled() => break "closed".to_string(),
+        }
+    };
+    let _ = tx.send(Event::SseClosed { conn_id, reason });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::Client;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::http::executor::execute;
+    use crate::state::request_state::RequestState;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute_relays_event_stream_until_cancelled() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // One event split across two writes, then the connection stays open
+        tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = tcp.read(&mut buf).await.unwrap();
+            tcp.write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\nevent: tick\ndata: he")
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            tcp.write_all(b"llo\n\n").await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let request = RequestState { url: format!("http://127.0.0.1:{port}/"), ..Default::default() };
+        let cancel = CancellationToken::new();
+        tokio::spawn(execute(Client::new(), request, "tab".into(), 1, tx, cancel.clone()));
+
+        let conn = match events.recv().await {
+            Some(Event::SseOpened { conn_id, response, .. }) => {
+                assert_eq!(response.status, 200);
+                conn_id
+            }
+            other => panic!("expected the stream to open, got {other:?}"),
+        };
+        match events.recv().await {
+            Some(Event::SseMessage { conn_id, event }) => {
+                assert_eq!(conn_id, conn);
+                assert_eq!((event.event.as_str(), event.data.as_str()), ("tick", "hello"));
+            }
+            other => panic!("expected an event, got {other:?}"),
+        }
+        cancel.cancel();
+        match events.recv().await {
+            Some(Event::SseClosed { reason, .. }) => assert_eq!(reason, "closed"),
+            other => panic!("expected the close, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+// WebSocket connections (ws:// and wss:// URLs)
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::event::Event;
+use crate::http::auth::authorize;
+use crate::http::builder::build_request;
+use crate::state::request_state::RequestState;
+use crate::state::websocket::WsCommand;
+
+/// The handshake for a resolved request: its query params, headers and auth
+/// applied exactly as for an HTTP send, on the `ws`/`wss` URL.
+fn handshake(client: &Client, request: &RequestState) -> Result<Request, String> {
+    let url = request.url.trim();
+    let secure = url.to_ascii_lowercase().starts_with("wss://");
+    let (_, rest) = url.split_once("://").ok_or("Invalid WebSocket URL")?;
+    let http_url = format!("{}://{rest}", if secure { "https" } else { "http" });
+
+    let http_request = RequestState { url: http_url, ..request.clone() };
+    let built = build_request(client, &http_request)
+        .and_then(|b| b.build().map_err(Into::into))
+        .map_err(|e| e.to_string())?;
+    let mut ws_url = built.url().clone();
+    ws_url
+        .set_scheme(if secure { "wss" } else { "ws" })
+        .map_err(|_| "Invalid WebSocket URL".to_string())?;
+
+    let mut handshake = ws_url.as_str().into_client_request().map_err(|e| e.to_string())?;
+    for (name, value) in built.headers() {
+        handshake.headers_mut().append(name, value.clone());
+    }
+    Ok(handshake)
+}
+
+/// Open connection `conn_id` and relay frames until either side
+/// closes it. Text frames from `commands` are sent as they arrive; dropping
+/// the command sender closes the connection.
+pub async fn connect(
+    client: Client,
+    request: RequestState,
+    conn_id: String,
+    tx: UnboundedSender<Event>,
+    mut commands: UnboundedReceiver<WsCommand>,
+) {
+    let closed = |code: Option<u16>, reason: String| Event::WsClosed {
+        conn_id: conn_id.clone(),
+        code,
+        reason,
+    };
+    let mut request = request;
+    match authorize(&client, &mut request).await {
+        Ok(Some(token)) => {
+            let _ = tx.send(Event::OAuthToken { request_id: request.id.clone(), token });
+        }
+        Ok(None) => {}
+        Err(e) => {
+            let _ = tx.send(closed(None, e.to_string()));
+            return;
+        }
+    }
+    let stream = match handshake(&client, &request) {
+        Ok(handshake) => connect_async(handshake).await.map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
+    let (mut sink, mut source) = match stream {
+        Ok((stream, _)) => stream.split(),
+        Err(e) => {
+            let _ = tx.send(closed(None, e));
+            return;
+        }
+    };
+    let _ = tx.send(Event::WsOpened { conn_id: conn_id.clone() });
+
+    // After our close frame, keep reading until the server's reply
+    let mut closing = false;
+    loop {
+        tokio::select! {
+            command = commands.recv(), if !closing => match command {
+                Some(WsCommand::Send(text)) => {
+                    if let Err(e) = sink.send(Message::text(text)).await {
+                        let _ = tx.send(closed(None, e.to_string()));
+                        return;
+                    }
+                }
+                Some(WsCommand::Close) | None => {
+                    closing = true;
+                    if sink.send(Message::Close(None)).await.is_err() {
+                        let _ = tx.send(closed(None, "Connection closed".to_string()));
+                        return;
+                    }
+                }
+            },
+            frame = source.next() => match frame {
+                Some(Ok(Message::Text(text))) => {
+                    let _ = tx.send(Event::WsMessage { conn_id: conn_id.clone(), text });
+                }
+                Some(Ok(Message::Binary(bytes))) => {
+                    let text = format!("[binary frame: {} bytes]", bytes.len());
+                    let _ = tx.send(Event::WsMessage { conn_id: conn_id.clone(), text });
+                }
+                Some(Ok(Message::Close(frame))) => {
+                    let (code, reason) = frame
+                        .map(|f| (Some(u16::from(f.code)), f.reason.to_string()))
+                        .unwrap_or((None, String::new()));
+                    let _ = tx.send(closed(code, reason));
+                    return;
+                }
+                // Pings are answered by the library
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    let _ = tx.send(closed(None, e.to_string()));
+                    return;
+                }
+                None => {
+                    let _ = tx.send(closed(None, "Connection dropped".to_string()));
+                    return;
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::request_state::{AuthConfig, KeyValuePair};
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+    #[test]
+    fn test_handshake_keeps_params_headers_and_auth() {
+        let request = RequestState {
+            url: "wss://echo.test/socket".into(),
+            params: vec![KeyValuePair::new("room", "1")],
+            headers: vec![KeyValuePair::new("X-Client", "forge")],
+            auth: AuthConfig::Bearer { token: "t0k".into() },
+            ..Default::default()
+        };
+        let handshake = handshake(&Client::new(), &request).unwrap();
+        assert_eq!(handshake.uri().to_string(), "wss://echo.test/socket?room=1");
+        assert_eq!(handshake.headers()["x-client"], "forge");
+        assert_eq!(handshake.headers()["authorization"], "Bearer t0k");
+    }
+
+    #[tokio::test]
+    async fn test_connect_relays_frames_and_close() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // Echo one message, then close with 1000
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            if let Some(Ok(msg)) = ws.next().await {
+                ws.send(msg).await.unwrap();
+            }
+            let frame = CloseFrame { code: CloseCode::Normal, reason: "bye".into() };
+            ws.close(Some(frame)).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let (commands, rx) = tokio::sync::mpsc::unbounded_channel();
+        let request = RequestState { url: format!("ws://127.0.0.1:{port}/"), ..Default::default() };
+        tokio::spawn(connect(Client::new(), request, "c1".into(), tx, rx));
+
+        assert!(matches!(events.recv().await, Some(Event::WsOpened { .. })));
+        commands.send(WsCommand::Send("ping".into())).unwrap();
+        match events.recv().await {
+            Some(Event::WsMessage { conn_id, text }) => assert_eq!((conn_id.as_str(), text.as_str()), ("c1", "ping")),
+            other => panic!("expected the echo, got {other:?}"),
+        }
+        match events.recv().await {
+            Some(Event::WsClosed { code, reason, .. }) => assert_eq!((code, reason.as_str()), (Some(1000), "bye")),
+            other => panic!("expected the close, got {other:?}"),
+        }
+    }
+}
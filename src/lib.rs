@@ -0,0 +1,12 @@
+// App state, rendering and request handling. The binary (`main.rs`) adds the
+// event loop, the terminal, storage and the `forge run` CLI; the library is
+// what benches build against.
+pub mod actions;
+pub mod env;
+pub mod error;
+pub mod event;
+pub mod http;
+pub mod scripting;
+pub mod state;
+pub mod ui;
+pub mod util;
@@ -1,14 +1,9 @@
 mod app;
-mod event;
+mod cli;
 mod terminal;
-mod error;
-mod ui;
-mod state;
-mod actions;
-mod http;
 mod storage;
-mod env;
-mod scripting;
+
+use forge::{actions, env, error, event, http, scripting, state, ui, util};
 
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -18,8 +13,18 @@ use crate::event::Event;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("run") {
+        let passed = cli::run(&args[1..]).await?;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
     let (tx, mut rx) = mpsc::unbounded_channel::<Event>();
 
+    // Before the event thread starts, so it can't take the terminal's reply
+    // to the keyboard enhancement query
+    let mut terminal = terminal::init()?;
+
     // Background thread: read crossterm events and feed into channel
     let event_tx = tx.clone();
     std::thread::spawn(move || loop {
@@ -41,7 +46,6 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    let mut terminal = terminal::init()?;
     let mut app = App::new(tx);
 
     let result = run_loop(&mut terminal, &mut app, &mut rx).await;
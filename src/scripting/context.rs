@@ -1 +1,255 @@
 // Script execution context (request, response, env, console objects)
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rhai::{Dynamic, Engine, EvalAltResult, FnPtr, Map, NativeCallContext};
+
+use crate::scripting::stdlib::json_to_dynamic;
+use crate::state::request_state::KeyValuePair;
+
+/// Data a script can read and mutate. Pre-request scripts edit the request
+/// fields; post-response scripts read `response` and record `tests`.
+#[derive(Debug, Default)]
+pub struct ScriptContext {
+    pub url: String,
+    pub headers: Vec<KeyValuePair>,
+    pub variables: HashMap<String, String>,
+    /// Enabled variables of the active environment, updated by `forge.env.set`
+    pub env: HashMap<String, String>,
+    /// `forge.env.set` calls in order, to write back to the environment
+    pub env_updates: Vec<(String, String)>,
+    pub response: Option<ScriptResponse>,
+    /// `pm.test` outcomes in call order: (name, passed)
+    pub tests: Vec<(String, bool)>,
+    /// `forge.log` and `print` output in order
+    pub logs: Vec<String>,
+}
+
+/// The response as seen by a post-response script.
+#[derive(Debug, Default)]
+pub struct ScriptResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+type Shared = Rc<RefCell<ScriptContext>>;
+
+/// The `pm` object. All handles share one context, so mutations made through
+/// chained accessors (`pm.request.headers.add(..)`) land in the same place.
+#[derive(Clone)]
+pub struct Pm(pub Shared);
+
+#[derive(Clone)]
+pub struct PmRequest(Shared);
+
+#[derive(Clone)]
+pub struct PmHeaders(Shared);
+
+#[derive(Clone)]
+pub struct PmVariables(Shared);
+
+#[derive(Clone)]
+pub struct PmResponse(Shared);
+
+/// The `forge` object: environment access and logging, plus the same
+/// `response` accessors as `pm`.
+#[derive(Clone)]
+pub struct Forge(pub Shared);
+
+#[derive(Clone)]
+pub struct ForgeEnv(Shared);
+
+/// `pm.expect(value)`; `to` and `be` are readability chain words.
+#[derive(Clone)]
+pub struct PmExpect(Dynamic);
+
+/// Register the `pm` API types on `engine`:
+/// - `pm.request.url` (get/set)
+/// - `pm.request.headers.add(key, value)`, `pm.request.headers.get(key)`
+/// - `pm.variables.set(key, value)`, `pm.variables.get(key)`
+/// - `pm.response.status`, `pm.response.headers`, `pm.response.body`,
+///   `pm.response.json()`
+/// - `pm.test(name, || { .. })`, `pm.expect(value).to.equal(expected)`
+pub fn register_pm(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<Pm>("Pm")
+        .register_type_with_name::<PmRequest>("PmRequest")
+        .register_type_with_name::<PmHeaders>("PmHeaders")
+        .register_type_with_name::<PmVariables>("PmVariables")
+        .register_type_with_name::<PmResponse>("PmResponse")
+        .register_type_with_name::<PmExpect>("PmExpect");
+
+    engine.register_get("request", |pm: &mut Pm| PmRequest(pm.0.clone()));
+    engine.register_get("variables", |pm: &mut Pm| PmVariables(pm.0.clone()));
+
+    engine.register_get_set(
+        "url",
+        |r: &mut PmRequest| r.0.borrow().url.clone(),
+        |r: &mut PmRequest, url: String| r.0.borrow_mut().url = url,
+    );
+    engine.register_get("headers", |r: &mut PmRequest| PmHeaders(r.0.clone()));
+
+    engine.register_fn("add", |h: &mut PmHeaders, key: &str, value: &str| {
+        h.0.borrow_mut().headers.push(KeyValuePair::new(key, value));
+    });
+    engine.register_fn("get", |h: &mut PmHeaders, key: &str| -> String {
+        h.0.borrow()
+            .headers
+            .iter()
+            .rev()
+            .find(|p| p.enabled && p.key.eq_ignore_ascii_case(key))
+            .map(|p| p.value.clone())
+            .unwrap_or_default()
+    });
+
+    engine.register_fn("set", |v: &mut PmVariables, key: &str, value: &str| {
+        v.0.borrow_mut().variables.insert(key.to_string(), value.to_string());
+    });
+    engine.register_fn("get", |v: &mut PmVariables, key: &str| -> String {
+        v.0.borrow().variables.get(key).cloned().unwrap_or_default()
+    });
+
+    engine.register_get("response", |pm: &mut Pm| PmResponse(pm.0.clone()));
+    engine.register_get("status", |r: &mut PmResponse| -> i64 {
+        r.0.borrow().response.as_ref().map_or(0, |resp| resp.status as i64)
+    });
+    engine.register_get("body", |r: &mut PmResponse| -> String {
+        r.0.borrow().response.as_ref().map(|resp| resp.body.clone()).unwrap_or_default()
+    });
+    // Header names are lowercased; a repeated header keeps its last value
+    engine.register_get("headers", |r: &mut PmResponse| -> Map {
+        let ctx = r.0.borrow();
+        let headers = ctx.response.as_ref().map(|resp| resp.headers.as_slice()).unwrap_or_default();
+        headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase().into(), v.clone().into()))
+            .collect()
+    });
+    engine.register_fn("json", |r: &mut PmResponse| -> Result<Dynamic, Box<EvalAltResult>> {
+        let ctx = r.0.borrow();
+        let body = ctx.response.as_ref().map(|resp| resp.body.as_str()).unwrap_or_default();
+        let value: serde_json::Value = serde_json::from_str(body)
+            .map_err(|e| format!("response body is not JSON: {e}"))?;
+        Ok(json_to_dynamic(&value))
+    });
+
+    engine.register_fn(
+        "test",
+        |ctx: NativeCallContext, pm: &mut Pm, name: &str, f: FnPtr| run_test(&ctx, &pm.0, name, f),
+    );
+    engine.register_fn("expect", |_pm: &mut Pm, value: Dynamic| PmExpect(value));
+    engine.register_get("to", |e: &mut PmExpect| e.clone());
+    engine.register_get("be", |e: &mut PmExpect| e.clone());
+    engine.register_fn(
+        "equal",
+        |e: &mut PmExpect, expected: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            if dynamic_eq(&e.0, &expected) {
+                Ok(())
+            } else {
+                Err(format!("expected {expected} but got {}", e.0).into())
+            }
+        },
+    );
+}
+
+/// Run one test closure and record its outcome. A test passes when its
+/// closure runs without throwing; a failing assertion only fails the test.
+fn run_test(ctx: &NativeCallContext, shared: &Shared, name: &str, f: FnPtr) {
+    let passed = f.call_within_context::<Dynamic>(ctx, ()).is_ok();
+    shared.borrow_mut().tests.push((name.to_string(), passed));
+}
+
+/// Loose equality for assertions: numbers compare by value across int/float,
+/// everything else must match in type and rendered value.
+fn dynamic_eq(a: &Dynamic, b: &Dynamic) -> bool {
+    let as_f64 = |d: &Dynamic| {
+        d.as_int()
+            .map(|i| i as f64)
+            .or_else(|_| d.as_float())
+            .ok()
+    };
+    match (as_f64(a), as_f64(b)) {
+        (Some(x), Some(y)) => x == y,
+        _ => a.type_name() == b.type_name() && a.to_string() == b.to_string(),
+    }
+}
+
+/// Register the `forge` API types on `engine`:
+/// - `forge.env.get(key)`, `forge.env.set(key, value)` on the active environment
+/// - `forge.response` (same accessors as `pm.response`)
+/// - `forge.log(value)`
+/// - `forge.test(name, || { .. })` with `forge.expect(value)` matchers:
+///   `toBe(expected)`, `toContain(item)`, `toBeTruthy()`
+pub fn register_forge(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<Forge>("Forge")
+        .register_type_with_name::<ForgeEnv>("ForgeEnv");
+
+    engine.register_get("env", |f: &mut Forge| ForgeEnv(f.0.clone()));
+    engine.register_get("response", |f: &mut Forge| PmResponse(f.0.clone()));
+
+    engine.register_fn("get", |e: &mut ForgeEnv, key: &str| -> String {
+        e.0.borrow().env.get(key).cloned().unwrap_or_default()
+    });
+    engine.register_fn("set", |e: &mut ForgeEnv, key: &str, value: Dynamic| {
+        let mut ctx = e.0.borrow_mut();
+        let value = value.to_string();
+        ctx.env.insert(key.to_string(), value.clone());
+        ctx.env_updates.push((key.to_string(), value));
+    });
+
+    engine.register_fn("log", |f: &mut Forge, value: Dynamic| {
+        f.0.borrow_mut().logs.push(value.to_string());
+    });
+
+    engine.register_fn(
+        "test",
+        |ctx: NativeCallContext, f: &mut Forge, name: &str, test: FnPtr| {
+            run_test(&ctx, &f.0, name, test)
+        },
+    );
+    engine.register_fn("expect", |_f: &mut Forge, value: Dynamic| PmExpect(value));
+    engine.register_fn(
+        "toBe",
+        |e: &mut PmExpect, expected: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            if dynamic_eq(&e.0, &expected) {
+                Ok(())
+            } else {
+                Err(format!("expected {expected} but got {}", e.0).into())
+            }
+        },
+    );
+    // Substring of a string, or element of an array
+    engine.register_fn(
+        "toContain",
+        |e: &mut PmExpect, item: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            let found = if let Some(text) = e.0.read_lock::<rhai::ImmutableString>() {
+                text.contains(item.to_string().as_str())
+            } else if let Some(items) = e.0.read_lock::<rhai::Array>() {
+                items.iter().any(|v| dynamic_eq(v, &item))
+            } else {
+                false
+            };
+            if found {
+                Ok(())
+            } else {
+                Err(format!("expected {} to contain {item}", e.0).into())
+            }
+        },
+    );
+    // Anything but false, 0, "" and ()
+    engine.register_fn("toBeTruthy", |e: &mut PmExpect| -> Result<(), Box<EvalAltResult>> {
+        let v = &e.0;
+        let falsy = v.is_unit()
+            || v.as_bool() == Ok(false)
+            || v.as_int() == Ok(0)
+            || v.read_lock::<rhai::ImmutableString>().is_some_and(|s| s.is_empty());
+        if falsy {
+            Err(format!("expected {v} to be truthy").into())
+        } else {
+            Ok(())
+        }
+    });
+}
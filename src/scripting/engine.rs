@@ -1 +1,18 @@
 // Rhai scripting engine initialization and sandboxing
+use rhai::Engine;
+
+/// Upper bound on operations per script run, so a runaway loop can't hang the UI.
+const MAX_OPERATIONS: u64 = 500_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 1024 * 1024;
+
+/// A sandboxed engine: no filesystem or module access, bounded operations,
+/// call depth and string size.
+pub fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_expr_depths(64, 32);
+    engine
+}
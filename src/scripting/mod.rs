@@ -1,3 +1,4 @@
 pub mod engine;
 pub mod context;
 pub mod stdlib;
+pub mod runner;
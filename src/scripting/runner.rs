@@ -0,0 +1,241 @@
+// Runs request scripts against request/response state
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rhai::Scope;
+
+use crate::scripting::context::{register_forge, register_pm, Forge, Pm, ScriptContext, ScriptResponse};
+use crate::scripting::engine::new_engine;
+use crate::state::request_state::RequestState;
+use crate::state::response_state::{ResponseBody, ResponseState};
+
+/// Outcome of a script run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptResult {
+    /// `pm.test` results in call order: (name, passed)
+    pub tests: Vec<(String, bool)>,
+    /// Set when the script itself failed (syntax error, uncaught throw, …).
+    /// Tests recorded before the failure are kept.
+    pub error: Option<String>,
+    /// `forge.log` and `print` output in order
+    pub logs: Vec<String>,
+    /// `forge.env.set` calls in order, to apply to the active environment
+    pub env_updates: Vec<(String, String)>,
+}
+
+/// Run `script` with `pm` and `forge` bound to `ctx`, returning the error
+/// message if it failed.
+fn run_script(script: &str, ctx: &Rc<RefCell<ScriptContext>>) -> Option<String> {
+    let mut engine = new_engine();
+    register_pm(&mut engine);
+    register_forge(&mut engine);
+    let logs = ctx.clone();
+    engine.on_print(move |text| logs.borrow_mut().logs.push(text.to_string()));
+
+    let mut scope = Scope::new();
+    // Constants, so closures passed to `pm.test` don't capture (and lock) them
+    scope.push_constant("pm", Pm(ctx.clone()));
+    scope.push_constant("forge", Forge(ctx.clone()));
+
+    engine
+        .run_with_scope(&mut scope, script)
+        .err()
+        .map(|e| e.to_string())
+}
+
+/// Run a pre-request script, applying its changes to `state`: URL edits,
+/// headers added with `pm.request.headers.add`, and variables set with
+/// `pm.variables.set` (stored in `state.script_variables`). `env` holds the
+/// active environment's variables. On error `state` is left untouched and
+/// no environment updates are returned.
+pub fn run_pre_request(
+    script: &str,
+    state: &mut RequestState,
+    env: &HashMap<String, String>,
+) -> ScriptResult {
+    let ctx = Rc::new(RefCell::new(ScriptContext {
+        url: state.url.clone(),
+        headers: state.headers.clone(),
+        variables: state.script_variables.clone(),
+        env: env.clone(),
+        ..ScriptContext::default()
+    }));
+
+    let error = run_script(script, &ctx);
+    let ctx = ctx.take();
+    if error.is_some() {
+        return ScriptResult { error, logs: ctx.logs, ..ScriptResult::default() };
+    }
+    state.url = ctx.url;
+    state.headers = ctx.headers;
+    state.script_variables = ctx.variables;
+    ScriptResult { logs: ctx.logs, env_updates: ctx.env_updates, ..ScriptResult::default() }
+}
+
+/// Run a post-response script against `resp`, collecting `pm.test` results.
+pub fn run_post_response(
+    script: &str,
+    resp: &ResponseState,
+    env: &HashMap<String, String>,
+) -> ScriptResult {
+    let body = match &resp.body {
+        ResponseBody::Text(text) => text.clone(),
+        ResponseBody::Empty | ResponseBody::Binary(_) => String::new(),
+    };
+    let ctx = Rc::new(RefCell::new(ScriptContext {
+        env: env.clone(),
+        response: Some(ScriptResponse {
+            status: resp.status,
+            headers: resp.headers.clone(),
+            body,
+        }),
+        ..ScriptContext::default()
+    }));
+
+    let error = run_script(script, &ctx);
+    let ctx = ctx.take();
+    ScriptResult { tests: ctx.tests, error, logs: ctx.logs, env_updates: ctx.env_updates }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::request_state::KeyValuePair;
+
+    fn response(status: u16, body: &str) -> ResponseState {
+        ResponseState {
+            status,
+            status_text: String::new(),
+            headers: Vec::new(),
+            body: ResponseBody::Text(body.to_string()),
+            cookies: Vec::new(),
+            timing: Default::default(),
+            size_bytes: body.len(),
+            received_at: chrono::Utc::now(),
+            scroll_offset: 0,
+            highlighted_body: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pre_request_adds_header() {
+        let mut req = RequestState::default();
+        let result = run_pre_request(r#"pm.request.headers.add("X-Trace", "abc");"#, &mut req, &HashMap::new());
+        assert_eq!(result.error, None);
+        assert_eq!(req.headers, vec![KeyValuePair::new("X-Trace", "abc")]);
+    }
+
+    #[test]
+    fn test_pre_request_mutates_url() {
+        let mut req = RequestState { url: "https://api.test/users".into(), ..Default::default() };
+        let result = run_pre_request(r#"pm.request.url = pm.request.url + "?page=2";"#, &mut req, &HashMap::new());
+        assert_eq!(result.error, None);
+        assert_eq!(req.url, "https://api.test/users?page=2");
+    }
+
+    #[test]
+    fn test_pre_request_sets_variables() {
+        let mut req = RequestState::default();
+        let script = r#"
+            pm.variables.set("ts", "1700000000");
+            pm.request.headers.add("X-Ts", pm.variables.get("ts"));
+        "#;
+        assert_eq!(run_pre_request(script, &mut req, &HashMap::new()).error, None);
+        assert_eq!(req.script_variables.get("ts").map(String::as_str), Some("1700000000"));
+        assert_eq!(req.headers[0].value, "1700000000");
+    }
+
+    #[test]
+    fn test_pre_request_error_leaves_state_untouched() {
+        let mut req = RequestState { url: "https://api.test".into(), ..Default::default() };
+        let script = r#"pm.request.url = "changed"; undefined_fn();"#;
+        assert!(run_pre_request(script, &mut req, &HashMap::new()).error.is_some());
+        assert_eq!(req.url, "https://api.test");
+    }
+
+    #[test]
+    fn test_pre_request_runaway_loop_is_bounded() {
+        let mut req = RequestState::default();
+        assert!(run_pre_request("loop {}", &mut req, &HashMap::new()).error.is_some());
+    }
+
+    #[test]
+    fn test_post_response_collects_results() {
+        let script = r#"
+            pm.test("status is 200", || { pm.expect(pm.response.status).to.equal(200) });
+            pm.test("status is 201", || { pm.expect(pm.response.status).to.equal(201) });
+            pm.test("body mentions ok", || { pm.expect(pm.response.body.contains("ok")).to.be.equal(true) });
+        "#;
+        let result = run_post_response(script, &response(200, r#"{"ok":true}"#), &HashMap::new());
+        assert_eq!(
+            result.tests,
+            vec![
+                ("status is 200".to_string(), true),
+                ("status is 201".to_string(), false),
+                ("body mentions ok".to_string(), true),
+            ]
+        );
+        assert_eq!(result.error, None);
+    }
+
+    #[test]
+    fn test_post_response_throw_fails_test() {
+        let script = r#"pm.test("boom", || { throw "nope"; });"#;
+        let result = run_post_response(script, &response(500, ""), &HashMap::new());
+        assert_eq!(result.tests, vec![("boom".to_string(), false)]);
+    }
+
+    #[test]
+    fn test_post_response_script_error_keeps_earlier_tests() {
+        let script = r#"
+            pm.test("first", || { pm.expect(1).to.equal(1.0) });
+            undefined_fn();
+        "#;
+        let result = run_post_response(script, &response(200, ""), &HashMap::new());
+        assert_eq!(result.tests, vec![("first".to_string(), true)]);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_forge_reads_response_and_sets_env() {
+        let mut resp = response(200, r#"{"data":{"token":"t-123","ids":[1,2]}}"#);
+        resp.headers = vec![("X-Request-Id".to_string(), "r1".to_string())];
+        let env = HashMap::from([("user".to_string(), "ada".to_string())]);
+        let script = r#"
+            let body = forge.response.json();
+            forge.env.set("token", body.data.token);
+            forge.log(`${forge.env.get("user")} ${body.data.ids.len()} ${forge.response.headers["x-request-id"]}`);
+            print(forge.env.get("token"));
+        "#;
+        let result = run_post_response(script, &resp, &env);
+        assert_eq!(result.error, None);
+        assert_eq!(result.env_updates, vec![("token".to_string(), "t-123".to_string())]);
+        assert_eq!(result.logs, vec!["ada 2 r1".to_string(), "t-123".to_string()]);
+    }
+
+    #[test]
+    fn test_forge_assertions() {
+        let script = r#"
+            forge.test("status is 200", || forge.expect(forge.response.status).toBe(200));
+            forge.test("lists ids", || forge.expect(forge.response.json().ids).toContain(2));
+            forge.test("has name", || forge.expect(forge.response.json().name).toBeTruthy());
+            forge.test("mentions admin", || forge.expect(forge.response.body).toContain("admin"));
+        "#;
+        let result = run_post_response(script, &response(200, r#"{"ids":[1,2],"name":""}"#), &HashMap::new());
+        assert_eq!(result.error, None);
+        let passed: Vec<bool> = result.tests.iter().map(|(_, ok)| *ok).collect();
+        assert_eq!(passed, vec![true, true, false, false]);
+    }
+
+    #[test]
+    fn test_pre_request_error_keeps_logs_drops_env_updates() {
+        let mut req = RequestState::default();
+        let script = r#"forge.log("before"); forge.env.set("a", "1"); throw "stop";"#;
+        let result = run_pre_request(script, &mut req, &HashMap::new());
+        assert!(result.error.is_some());
+        assert_eq!(result.logs, vec!["before".to_string()]);
+        assert!(result.env_updates.is_empty());
+    }
+}
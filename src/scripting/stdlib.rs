@@ -1 +1,23 @@
 // Standard library functions exposed to Rhai scripts
+use rhai::{Array, Dynamic, Map};
+use serde_json::Value;
+
+/// Convert parsed JSON into script values: objects become maps, arrays
+/// arrays, and numbers ints when they fit.
+pub fn json_to_dynamic(value: &Value) -> Dynamic {
+    match value {
+        Value::Null => Dynamic::UNIT,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64().unwrap_or_default().into(),
+        },
+        Value::String(s) => s.clone().into(),
+        Value::Array(items) => items.iter().map(json_to_dynamic).collect::<Array>().into(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| (k.as_str().into(), json_to_dynamic(v)))
+            .collect::<Map>()
+            .into(),
+    }
+}
@@ -1,9 +1,26 @@
-use std::collections::HashSet;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use ratatui::layout::Rect;
+
+use crate::scripting::runner::ScriptResult;
+use crate::ui::diff::DiffLine;
+use crate::ui::sidebar::SidebarNode;
+use crate::ui::theme::Theme;
 
 use super::{
+    audit::AuditEntry,
+    check::CheckCandidate,
+    config::{Config, ThemeKind},
+    environment::{filter_variables, EnvVariable, Environment, VarOrigin},
     focus::Focus,
     mode::Mode,
-    workspace::{RequestTab, WorkspaceState},
+    request_state::{KeyValuePair, RequestState},
+    response_state::ResponseState,
+    workspace::{RequestTab, WorkspaceState, WorkspaceSummary},
 };
 
 // ─── Request/Response tab enums ──────────────────────────────────────────────
@@ -16,6 +33,8 @@ pub enum ActiveTab {
     Auth,
     Params,
     Scripts,
+    Checks,
+    Docs,
 }
 
 impl ActiveTab {
@@ -25,17 +44,21 @@ impl ActiveTab {
             ActiveTab::Body => ActiveTab::Auth,
             ActiveTab::Auth => ActiveTab::Params,
             ActiveTab::Params => ActiveTab::Scripts,
-            ActiveTab::Scripts => ActiveTab::Headers,
+            ActiveTab::Scripts => ActiveTab::Checks,
+            ActiveTab::Checks => ActiveTab::Docs,
+            ActiveTab::Docs => ActiveTab::Headers,
         }
     }
 
     pub fn prev(&self) -> ActiveTab {
         match self {
-            ActiveTab::Headers => ActiveTab::Scripts,
+            ActiveTab::Headers => ActiveTab::Docs,
             ActiveTab::Body => ActiveTab::Headers,
             ActiveTab::Auth => ActiveTab::Body,
             ActiveTab::Params => ActiveTab::Auth,
             ActiveTab::Scripts => ActiveTab::Params,
+            ActiveTab::Checks => ActiveTab::Scripts,
+            ActiveTab::Docs => ActiveTab::Checks,
         }
     }
 }
@@ -47,6 +70,32 @@ pub enum ResponseTab {
     Headers,
     Cookies,
     Timing,
+    Tests,
+    Console,
+}
+
+impl ResponseTab {
+    pub fn next(&self) -> ResponseTab {
+        match self {
+            ResponseTab::Body => ResponseTab::Headers,
+            ResponseTab::Headers => ResponseTab::Cookies,
+            ResponseTab::Cookies => ResponseTab::Timing,
+            ResponseTab::Timing => ResponseTab::Tests,
+            ResponseTab::Tests => ResponseTab::Console,
+            ResponseTab::Console => ResponseTab::Body,
+        }
+    }
+
+    pub fn prev(&self) -> ResponseTab {
+        match self {
+            ResponseTab::Body => ResponseTab::Console,
+            ResponseTab::Headers => ResponseTab::Body,
+            ResponseTab::Cookies => ResponseTab::Headers,
+            ResponseTab::Timing => ResponseTab::Cookies,
+            ResponseTab::Tests => ResponseTab::Timing,
+            ResponseTab::Console => ResponseTab::Tests,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -54,6 +103,8 @@ pub enum RequestStatus {
     #[default]
     Idle,
     Loading { spinner_tick: u8 },
+    /// Waiting in the workspace send queue
+    Queued,
     Error(String),
 }
 
@@ -68,6 +119,28 @@ pub enum ActivePopup {
     WorkspaceSwitcher,
     CollectionNaming,
     ConfirmDelete,
+    CheckPicker,
+    RequestFinder,
+    AuditLog,
+    DependencyPicker,
+    DependencyPrompt,
+    ConfirmSend,
+    ConfirmClose,
+    RunHistory,
+    Runner,
+    ThemePicker,
+    Cookies,
+    Capture,
+    SendQueue,
+    JsonValue,
+    Diff,
+    SaveRequest,
+    Variables,
+    DefaultHeaders,
+    Settings,
+    Help,
+    CommandPalette,
+    CollectionVariables,
 }
 
 // ─── Env popup state (unchanged from Round 2) ─────────────────────────────────
@@ -78,8 +151,22 @@ pub struct EnvSwitcherState {
     pub search: String,
     pub search_cursor: usize,
     pub naming: bool,
+    /// Text of the prompt: the new environment's name, or a path when
+    /// `file_prompt` is set
     pub new_name: String,
     pub new_name_cursor: usize,
+    /// The prompt asks for a file to export to or import from
+    pub file_prompt: Option<EnvFilePrompt>,
+    /// Opened from the URL bar: the pick applies to the active tab only
+    pub for_tab: bool,
+}
+
+/// What the environment switcher's path prompt is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvFilePrompt {
+    /// Export environment `env_idx`; `redact` blanks secret values
+    Export { env_idx: usize, redact: bool },
+    Import,
 }
 
 impl Default for EnvSwitcherState {
@@ -91,6 +178,8 @@ impl Default for EnvSwitcherState {
             naming: false,
             new_name: String::new(),
             new_name_cursor: 0,
+            file_prompt: None,
+            for_tab: false,
         }
     }
 }
@@ -105,6 +194,18 @@ pub struct EnvEditorState {
     pub editing: bool,
     pub editing_name: bool,
     pub name_cursor: usize,
+    /// Hex color being typed (`C`), applied on Enter if it is `#rrggbb`
+    pub color_input: Option<String>,
+    /// Typing a `/` filter on key or description
+    pub search_mode: bool,
+    pub search: String,
+}
+
+impl EnvEditorState {
+    /// Index into `vars` of the selected row; `row` counts filtered rows only.
+    pub fn var_index(&self, vars: &[EnvVariable]) -> Option<usize> {
+        filter_variables(vars, &self.search).get(self.row).copied()
+    }
 }
 
 impl Default for EnvEditorState {
@@ -118,6 +219,9 @@ impl Default for EnvEditorState {
             editing: false,
             editing_name: false,
             name_cursor: 0,
+            color_input: None,
+            search_mode: false,
+            search: String::new(),
         }
     }
 }
@@ -131,6 +235,32 @@ pub struct SidebarState {
     pub search_mode: bool,
     pub search_query: String,
     pub scroll_offset: usize,
+    /// Request or folder marked with `x`, moved by `p`
+    pub cut: Option<String>,
+    /// Inline result of the last run-in-place (`s` / `S`) per request id.
+    pub run_status: HashMap<String, NodeRunStatus>,
+    /// Node just moved among its siblings and when, drawn highlighted briefly
+    pub moved: Option<(String, Instant)>,
+    /// The visible nodes in display order, flattened on first use; see
+    /// `invalidate_nodes`
+    pub nodes: OnceCell<Rc<[SidebarNode]>>,
+}
+
+impl SidebarState {
+    /// Drop the cached node list. Call after changing the collections,
+    /// `collapsed_ids` or the search.
+    pub fn invalidate_nodes(&mut self) {
+        self.nodes.take();
+    }
+}
+
+/// Run-in-place state of a sidebar request node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeRunStatus {
+    Queued,
+    Running,
+    Done { status: u16, total_ms: u64 },
+    Failed,
 }
 
 // ─── Round 3: Workspace switcher popup ───────────────────────────────────────
@@ -143,6 +273,12 @@ pub struct WorkspaceSwitcherState {
     pub naming: bool,
     pub new_name: String,
     pub new_name_cursor: usize,
+    /// List by name instead of most recently opened first (`Alt+s`)
+    pub alphabetical: bool,
+    /// The naming prompt renames this workspace instead of creating one
+    pub renaming: Option<String>,
+    /// The naming prompt copies this workspace instead of creating one
+    pub duplicating: Option<String>,
 }
 
 // ─── Round 3: Collection/folder/request naming popup ─────────────────────────
@@ -153,6 +289,8 @@ pub enum NamingTarget {
     NewFolder { collection_id: String },
     NewRequest { collection_id: String, folder_id: Option<String> },
     Rename { id: String, old_name: String },
+    /// An open tab, by `RequestState::id`
+    RenameTab { tab_id: String },
 }
 
 impl Default for NamingTarget {
@@ -186,6 +324,422 @@ impl Default for NamingState {
 pub struct ConfirmDeleteState {
     pub message: String,
     pub target_id: String,
+    /// `target_id` names a workspace, deleted from the workspace switcher
+    pub workspace: bool,
+}
+
+// ─── Check picker popup ("copy as test") ─────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct CheckPickerState {
+    /// Status code of the response the checks are generated from.
+    pub status: u16,
+    /// Empty when the body is not JSON — only the status check is generated.
+    pub candidates: Vec<CheckCandidate>,
+    pub checked: Vec<bool>,
+    pub selected: usize,
+}
+
+// ─── Request finder popup (Ctrl+P) ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestFinderState {
+    pub query: String,
+    pub cursor: usize,
+    pub selected: usize,
+}
+
+// ─── Command palette popup ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteState {
+    pub query: String,
+    pub cursor: usize,
+    pub selected: usize,
+}
+
+// ─── Mouse hit regions ───────────────────────────────────────────────────────
+
+/// Clickable screen regions recorded during the last render, so mouse clicks
+/// can be mapped back to what was drawn there.
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub request_tabs: Vec<(Rect, ActiveTab)>,
+    pub response_tabs: Vec<(Rect, ResponseTab)>,
+    /// Text area of the URL bar, for keeping the cursor scrolled into view
+    pub url_input: Rect,
+    /// Rows of sidebar nodes; empty while the sidebar is hidden
+    pub sidebar_list: Rect,
+    /// Whole panels, so a click anywhere inside focuses them. The URL bar and
+    /// editor are empty in zen mode.
+    pub url_bar: Rect,
+    pub editor: Rect,
+    pub response: Rect,
+    /// Text area of the body editor, for keeping its cursor in view and
+    /// paging by its height
+    pub body_editor: Rect,
+    /// Response body area and how many lines (wrapped rows when wrapping)
+    /// were drawn in it, for paging and for not scrolling past the last one
+    pub response_body: Rect,
+    pub response_body_lines: usize,
+    /// Columns the widest unwrapped response line runs past the view, the
+    /// furthest it scrolls sideways
+    pub response_body_overflow: usize,
+}
+
+// ─── Request dependency popups ───────────────────────────────────────────────
+
+/// Picker for the request the sidebar-selected request depends on.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyPickerState {
+    pub request_id: String,
+    pub request_name: String,
+    pub query: String,
+    pub cursor: usize,
+    /// 0 is "(none)"; 1.. index the matching requests
+    pub selected: usize,
+    /// Set when the chosen dependency would create a cycle
+    pub error: Option<String>,
+}
+
+/// "Run dependencies first?" prompt shown when sending a dependent request.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyPromptState {
+    /// Dependencies to run, in order, before the request being sent
+    pub order: Vec<String>,
+    pub message: String,
+}
+
+/// A send held back by the protected-environment prompt.
+#[derive(Debug, Clone, Default)]
+pub enum PendingSend {
+    #[default]
+    None,
+    /// The active tab's request exactly as it was resolved, so confirming
+    /// sends what the prompt showed. The pre-request script's variable
+    /// writes are applied only once confirmed.
+    Prepared {
+        request: Box<RequestState>,
+        pre_script: Option<ScriptResult>,
+        name: String,
+    },
+    /// The active tab's WebSocket connect or message
+    WebSocket,
+    /// The active tab's request, into the send queue
+    Enqueue,
+    /// A sidebar or runner run of these collection requests
+    RunInPlace(Vec<String>),
+}
+
+/// "Send to a protected environment?" prompt.
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmSendState {
+    pub pending: PendingSend,
+    pub environment_id: String,
+    pub environment: String,
+    /// Method and resolved URL of the send, or what a run covers
+    pub method: String,
+    pub target: String,
+    /// Skip the prompt for sends to this environment in the next minute once
+    /// confirmed
+    pub skip_minute: bool,
+    /// Popup to return to once answered
+    pub back_to: ActivePopup,
+}
+
+/// "Save changes?" prompt shown when closing a saved request's tab with
+/// unsaved edits.
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmCloseState {
+    /// Id of the tab's request, so the prompt acts on the tab it was opened for
+    pub request_id: String,
+    pub name: String,
+}
+
+// ─── Run history popup (g h) ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct RunHistoryState {
+    pub request_id: String,
+    pub request_name: String,
+    pub selected: usize,
+}
+
+// ─── Cookie jar popup ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct CookieJarState {
+    /// Index into `CookieJar::sorted`
+    pub selected: usize,
+}
+
+// ─── Default headers popup ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct DefaultHeadersState {
+    /// Collection or folder whose headers are edited
+    pub target_id: String,
+    pub title: String,
+    /// Working copy, written back after every change
+    pub headers: Vec<KeyValuePair>,
+    pub selected: usize,
+    /// 0 = key, 1 = value
+    pub col: u8,
+    /// Cell being edited and its cursor
+    pub editing: Option<(String, usize)>,
+}
+
+// ─── Workspace settings popup ────────────────────────────────────────────────
+
+/// Workspace settings popup (`W`): the proxy fields, saved as each edit is
+/// confirmed.
+#[derive(Debug, Clone, Default)]
+pub struct SettingsState {
+    /// Index into `ProxySettings::field`
+    pub selected: usize,
+    /// Field being edited and its cursor
+    pub editing: Option<(String, usize)>,
+}
+
+// ─── Collection variables popup ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct CollectionVarsState {
+    /// Collection or folder whose variables are edited
+    pub target_id: String,
+    pub title: String,
+    /// Working copy, written back after every change
+    pub variables: Vec<EnvVariable>,
+    pub selected: usize,
+    /// 0 = key, 1 = value
+    pub col: u8,
+    /// Cell being edited and its cursor
+    pub editing: Option<(String, usize)>,
+    pub show_secrets: bool,
+}
+
+// ─── Help popup ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct HelpState {
+    /// Lines scrolled past the top of the list
+    pub scroll: usize,
+}
+
+// ─── Variables popup ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct VariablesState {
+    /// Index into `ui::variables::session_variables`
+    pub selected: usize,
+    /// Value being edited inline and its cursor
+    pub editing: Option<(String, usize)>,
+    pub show_secrets: bool,
+    /// Opened over the runner, which Esc returns to
+    pub from_runner: bool,
+}
+
+// ─── Theme picker popup ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct ThemePickerState {
+    pub selected: usize,
+    /// Theme active when the picker opened, restored on Esc
+    pub original: ThemeKind,
+    /// Moving in the syntax theme column rather than the UI theme one
+    pub syntax_column: bool,
+    /// 0 is "Match UI theme"; 1.. index `highlight::syntax_themes()`
+    pub syntax_selected: usize,
+    pub original_syntax: Option<String>,
+}
+
+// ─── Capture-to-env popup (e in the response viewer) ────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct CaptureState {
+    /// `$.data.token` or the dot-path shorthand `data.token`
+    pub path: String,
+    pub path_cursor: usize,
+    pub name: String,
+    pub name_cursor: usize,
+    /// Editing the variable name rather than the path
+    pub on_name: bool,
+    pub secret: bool,
+    /// Why the last Enter didn't capture; the popup stays open to fix it
+    pub error: Option<String>,
+}
+
+// ─── Full-value popup (Enter on a tree view leaf) ────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct JsonValueState {
+    /// JSONPath of the value, shown as the title
+    pub path: String,
+    /// Strings unquoted, other values as JSON
+    pub value: String,
+    pub scroll: u16,
+}
+
+// ─── Response diff (D) ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffState {
+    /// Other tabs with a text response: request id and label
+    pub candidates: Vec<(String, String)>,
+    pub selected: usize,
+    pub current_name: String,
+    pub other_name: String,
+    /// The diff once a tab is picked
+    pub lines: Option<Vec<DiffLine>>,
+    pub scroll: usize,
+}
+
+// ─── Save a scratch tab (Ctrl+S) ─────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct SaveRequestState {
+    pub name: String,
+    pub cursor: usize,
+    /// Selected row of `sidebar::save_targets`
+    pub selected: usize,
+    /// Collections and folders open in the target tree
+    pub expanded: HashSet<String>,
+    /// Second step: the target is picked and the name is being confirmed
+    pub naming: bool,
+}
+
+// ─── Send queue (Q) ───────────────────────────────────────────────────────────
+
+/// A send waiting in the queue. Scripts and variables are applied when it
+/// is dispatched, so it sees values set by the sends before it.
+#[derive(Debug, Clone)]
+pub struct QueuedSend {
+    /// `RequestState::id` of the tab that queued it
+    pub tab_id: String,
+    /// The request as it was when queued
+    pub request: RequestState,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SendQueueState {
+    pub entries: Vec<QueuedSend>,
+    pub selected: usize,
+    /// Tab id and request of the send in flight
+    pub running: Option<QueuedSend>,
+}
+
+// ─── Collection runner popup ─────────────────────────────────────────────────
+
+/// One request of a collection run.
+#[derive(Debug, Clone)]
+pub struct RunnerRow {
+    pub request_id: String,
+    pub name: String,
+    pub method: String,
+    pub status: NodeRunStatus,
+    /// Failed checks, or the send error, for a finished request.
+    pub failures: Vec<String>,
+    /// Captured response, shown when the row is opened.
+    pub response: Option<ResponseState>,
+}
+
+impl RunnerRow {
+    /// `None` until the request has finished.
+    pub fn passed(&self) -> Option<bool> {
+        match self.status {
+            NodeRunStatus::Done { .. } => Some(self.failures.is_empty()),
+            NodeRunStatus::Failed => Some(false),
+            NodeRunStatus::Queued | NodeRunStatus::Running => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunnerState {
+    /// Name of the collection or folder being run.
+    pub title: String,
+    pub rows: Vec<RunnerRow>,
+    pub selected: usize,
+}
+
+impl RunnerState {
+    pub fn finished(&self) -> bool {
+        self.rows.iter().all(|r| r.passed().is_some())
+    }
+
+    /// (passed, failed) among the finished rows.
+    pub fn counts(&self) -> (usize, usize) {
+        let passed = self.rows.iter().filter(|r| r.passed() == Some(true)).count();
+        let failed = self.rows.iter().filter(|r| r.passed() == Some(false)).count();
+        (passed, failed)
+    }
+
+    pub fn total_ms(&self) -> u64 {
+        self.rows
+            .iter()
+            .map(|r| match r.status {
+                NodeRunStatus::Done { total_ms, .. } => total_ms,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+// ─── Audit log viewer popup ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogState {
+    /// Entries loaded when the popup opened, newest first.
+    pub entries: Vec<AuditEntry>,
+    /// Host substring or date prefix (`2026-10`, `2026-10-16`)
+    pub filter: String,
+    pub filter_cursor: usize,
+    pub selected: usize,
+    /// Editing the workspace audit note instead of the filter
+    pub editing_note: bool,
+    pub note_input: String,
+    pub note_cursor: usize,
+}
+
+impl AuditLogState {
+    /// Entries matching the filter. Digits-and-dashes filters match the
+    /// entry's UTC date; anything else matches the host.
+    pub fn visible(&self) -> Vec<&AuditEntry> {
+        let filter = self.filter.trim().to_lowercase();
+        let is_date = !filter.is_empty() && filter.chars().all(|c| c.is_ascii_digit() || c == '-');
+        self.entries
+            .iter()
+            .filter(|e| {
+                if filter.is_empty() {
+                    true
+                } else if is_date {
+                    e.timestamp.format("%Y-%m-%d").to_string().starts_with(&filter)
+                } else {
+                    e.host.to_lowercase().contains(&filter)
+                }
+            })
+            .collect()
+    }
+}
+
+// ─── {{variable}} completion ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    pub key: String,
+    pub secret: bool,
+}
+
+/// Inline completion opened by typing `{{` in the URL bar, a header cell or
+/// the body editor.
+#[derive(Debug, Clone, Default)]
+pub struct CompletionState {
+    pub active: bool,
+    /// Byte offset just past the `{{` that opened the completion; the typed
+    /// prefix is `text[anchor..cursor]`.
+    pub anchor: usize,
+    pub items: Vec<CompletionItem>,
+    pub selected: usize,
 }
 
 // ─── AppState ─────────────────────────────────────────────────────────────────
@@ -195,6 +749,9 @@ pub struct AppState {
     pub mode: Mode,
     pub focus: Focus,
     pub sidebar_visible: bool,
+    /// The response viewer fills the right panel, hiding the request rows
+    /// (`z` / `Ctrl+F`)
+    pub zen: bool,
     pub should_quit: bool,
     /// Set to `true` whenever visible state changes. The render loop skips
     /// `terminal.draw()` when `false`, avoiding redundant work on idle ticks.
@@ -211,16 +768,89 @@ pub struct AppState {
     pub naming: NamingState,
     pub confirm_delete: ConfirmDeleteState,
     pub ws_switcher: WorkspaceSwitcherState,
+    /// When each workspace was last opened (from `state.toml`)
+    pub workspace_last_opened: HashMap<String, DateTime<Utc>>,
+    /// Collection and request counts per workspace, rescanned in the
+    /// background whenever the switcher opens
+    pub workspace_summaries: HashMap<String, WorkspaceSummary>,
+    pub check_picker: CheckPickerState,
+    pub request_finder: RequestFinderState,
+    pub audit_log: AuditLogState,
+    pub dependency_picker: DependencyPickerState,
+    pub dependency_prompt: DependencyPromptState,
+    pub confirm_send: ConfirmSendState,
+    pub confirm_close: ConfirmCloseState,
+    /// Collection request ids that got a 2xx response this session
+    pub succeeded_requests: HashSet<String>,
+    pub run_history: RunHistoryState,
+    pub runner: RunnerState,
+    pub theme_picker: ThemePickerState,
+    pub cookie_jar: CookieJarState,
+    /// Origin of each variable set by a script, capture or token this session
+    pub var_origins: HashMap<String, VarOrigin>,
+    pub variables: VariablesState,
+    pub default_headers: DefaultHeadersState,
+    pub settings: SettingsState,
+    pub collection_vars: CollectionVarsState,
+    pub help: HelpState,
+    pub command_palette: CommandPaletteState,
+    pub capture: CaptureState,
+    pub send_queue: SendQueueState,
+    pub json_value: JsonValueState,
+    pub diff: DiffState,
+    pub save_request: SaveRequestState,
+    /// Wrap long response body lines to the viewer width (`w`)
+    pub word_wrap: bool,
+    /// Confirmation shown in the status bar, and when it was raised
+    pub toast: Option<(String, Instant)>,
+    /// Position and time of the last left click, to spot double-clicks
+    pub last_click: Option<(u16, u16, Instant)>,
+    /// First key of a two-key Normal-mode sequence (`g h`)
+    pub pending_key: Option<char>,
+    /// Body line taken by `yy` or `dd`, put below the cursor line by `p`
+    pub line_register: Option<String>,
+    /// Time of the last edit to a request; dirty tabs auto-save once it is
+    /// a couple of seconds old.
+    pub last_edit_instant: Option<Instant>,
+    /// Written by the renderer (which only sees `&AppState`), read on click
+    pub hit_areas: RefCell<HitAreas>,
+    /// Tab drawn in place of the active one while the renderer draws the
+    /// split pane without focus
+    pub render_tab: Cell<Option<usize>>,
+    pub config: Config,
+    /// Palette loaded from `theme.toml`, shown by the Custom theme
+    pub custom_theme: Option<Theme>,
+    pub completion: CompletionState,
 }
 
 impl AppState {
     /// Returns a reference to the currently active request tab, if any.
     pub fn active_tab(&self) -> Option<&RequestTab> {
-        self.workspace.open_tabs.get(self.workspace.active_tab_idx)
+        let idx = self.render_tab.get().unwrap_or(self.workspace.active_tab_idx);
+        self.workspace.open_tabs.get(idx)
     }
 
     /// Returns a mutable reference to the currently active request tab, if any.
     pub fn active_tab_mut(&mut self) -> Option<&mut RequestTab> {
         self.workspace.open_tabs.get_mut(self.workspace.active_tab_idx)
     }
+
+    /// Index of the environment the active tab resolves with: its own
+    /// override, or the workspace's active environment.
+    pub fn active_environment_idx(&self) -> Option<usize> {
+        let ws = &self.workspace;
+        self.active_tab()
+            .and_then(|t| t.env_override.as_deref())
+            .and_then(|id| ws.environments.iter().position(|e| e.id == id))
+            .or(ws.active_environment_idx)
+    }
+
+    /// Name of the active tab's environment, if one is selected.
+    pub fn active_environment_name(&self) -> Option<&str> {
+        self.active_environment().map(|e| e.name.as_str())
+    }
+
+    pub fn active_environment(&self) -> Option<&Environment> {
+        self.active_environment_idx().and_then(|i| self.workspace.environments.get(i))
+    }
 }
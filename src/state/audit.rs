@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::util::redact::redact_secrets;
+
+/// One line of a workspace's `audit.jsonl`: what was sent where and when.
+/// Never contains bodies, headers, query values or secret values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub workspace: String,
+    pub env: Option<String>,
+    pub method: String,
+    pub host: String,
+    pub path: String,
+    /// FNV-1a hash of the query string, so identical queries can be
+    /// correlated without storing their values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_hash: Option<String>,
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl AuditEntry {
+    /// Build an entry for a request about to be sent to `resolved_url`.
+    /// Any of `secrets` appearing in the host, path or note is redacted; the
+    /// query string is replaced by its hash. Status and duration are filled
+    /// in when the response arrives.
+    pub fn new(
+        workspace: &str,
+        env: Option<&str>,
+        method: &str,
+        resolved_url: &str,
+        note: &str,
+        secrets: &[String],
+    ) -> Self {
+        let (host, path, query) = split_url(resolved_url);
+        Self {
+            timestamp: Utc::now(),
+            workspace: workspace.to_string(),
+            env: env.map(str::to_string),
+            method: method.to_string(),
+            host: redact_secrets(&host, secrets),
+            path: redact_secrets(&path, secrets),
+            query_hash: query.filter(|q| !q.is_empty()).map(|q| format!("{:016x}", fnv1a(&q))),
+            status: None,
+            duration_ms: 0,
+            note: redact_secrets(note, secrets),
+        }
+    }
+}
+
+/// Split a URL into (host[:port], path, query). Userinfo is dropped.
+fn split_url(raw: &str) -> (String, String, Option<String>) {
+    match url::Url::parse(raw) {
+        Ok(u) => {
+            let host = match (u.host_str(), u.port()) {
+                (Some(h), Some(p)) => format!("{h}:{p}"),
+                (Some(h), None) => h.to_string(),
+                (None, _) => String::new(),
+            };
+            (host, u.path().to_string(), u.query().map(str::to_string))
+        }
+        // Unparseable: keep only what precedes the query, and never userinfo
+        Err(_) => {
+            let (before, query) = match raw.split_once('?') {
+                Some((b, q)) => (b, Some(q.to_string())),
+                None => (raw, None),
+            };
+            let before = before.split_once("://").map(|(_, rest)| rest).unwrap_or(before);
+            let before = before.rsplit_once('@').map(|(_, rest)| rest).unwrap_or(before);
+            match before.split_once('/') {
+                Some((h, p)) => (h.to_string(), format!("/{p}"), query),
+                None => (before.to_string(), String::new(), query),
+            }
+        }
+    }
+}
+
+fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in text.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::redact::REDACTED;
+
+    #[test]
+    fn test_entry_strips_query_and_userinfo() {
+        let e = AuditEntry::new(
+            "default",
+            Some("prod"),
+            "GET",
+            "https://ada:pw@api.test:8443/users?id=7&key=zzz",
+            "",
+            &[],
+        );
+        assert_eq!(e.host, "api.test:8443");
+        assert_eq!(e.path, "/users");
+        assert_eq!(e.query_hash.as_deref(), Some(format!("{:016x}", fnv1a("id=7&key=zzz")).as_str()));
+        let line = serde_json::to_string(&e).unwrap();
+        assert!(!line.contains("zzz"));
+        assert!(!line.contains("pw"));
+    }
+
+    #[test]
+    fn test_entry_redacts_secrets_in_path_and_note() {
+        let secrets = vec!["tok_live_1".to_string()];
+        let e = AuditEntry::new(
+            "default",
+            None,
+            "DELETE",
+            "https://api.test/keys/tok_live_1",
+            "rotating tok_live_1",
+            &secrets,
+        );
+        assert_eq!(e.path, format!("/keys/{REDACTED}"));
+        assert_eq!(e.note, format!("rotating {REDACTED}"));
+        assert!(!serde_json::to_string(&e).unwrap().contains("tok_live_1"));
+    }
+
+    #[test]
+    fn test_entry_unparseable_url() {
+        let e = AuditEntry::new("w", None, "GET", "not a url/path?q=1", "", &[]);
+        assert_eq!(e.host, "not a url");
+        assert_eq!(e.path, "/path");
+        assert!(e.query_hash.is_some());
+    }
+}
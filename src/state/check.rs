@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CheckKind {
+    #[default]
+    Status,
+    JsonPath,
+    BodyContains,
+}
+
+impl CheckKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckKind::Status => "Status",
+            CheckKind::JsonPath => "JSONPath",
+            CheckKind::BodyContains => "Contains",
+        }
+    }
+
+    /// Whether the check reads a JSONPath (the other kinds have no path).
+    pub fn has_path(&self) -> bool {
+        matches!(self, CheckKind::JsonPath)
+    }
+
+    pub fn next(&self) -> CheckKind {
+        match self {
+            CheckKind::Status => CheckKind::JsonPath,
+            CheckKind::JsonPath => CheckKind::BodyContains,
+            CheckKind::BodyContains => CheckKind::Status,
+        }
+    }
+}
+
+/// A declarative response check attached to a request, e.g. "status equals 200",
+/// "`$.data.id` equals `42`" or "body contains `ok`". `expected` holds a JSON
+/// literal when possible.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Check {
+    pub enabled: bool,
+    pub kind: CheckKind,
+    #[serde(default)]
+    pub path: String,
+    pub expected: String,
+}
+
+impl Default for Check {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kind: CheckKind::JsonPath,
+            path: String::new(),
+            expected: String::new(),
+        }
+    }
+}
+
+impl Check {
+    pub fn status(code: u16) -> Self {
+        Self {
+            enabled: true,
+            kind: CheckKind::Status,
+            path: String::new(),
+            expected: code.to_string(),
+        }
+    }
+
+    pub fn json_path(path: impl Into<String>, expected: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            kind: CheckKind::JsonPath,
+            path: path.into(),
+            expected: expected.into(),
+        }
+    }
+}
+
+/// A field offered by the "copy as test" picker: a JSONPath into the response
+/// body and the value currently found there (serialised as JSON).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckCandidate {
+    pub path: String,
+    pub value: String,
+}
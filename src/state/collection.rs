@@ -1,10 +1,29 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::check::Check;
+use super::environment::EnvVariable;
+use super::request_state::{AuthConfig, KeyValuePair, Scripts};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     pub id: String,
     pub name: String,
+    /// Notes on the collection, e.g. the setup it needs; line breaks are kept
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Environment switched to when one of its requests is opened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_env_id: Option<String>,
+    /// Headers sent with every request in the collection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_headers: Vec<KeyValuePair>,
+    /// Variables for every request in the collection, under the active
+    /// environment's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<EnvVariable>,
     pub items: Vec<CollectionItem>,
 }
 
@@ -13,6 +32,10 @@ impl Collection {
         Self {
             id: Uuid::new_v4().to_string(),
             name: name.into(),
+            description: String::new(),
+            default_env_id: None,
+            default_headers: Vec::new(),
+            variables: Vec::new(),
             items: Vec::new(),
         }
     }
@@ -22,6 +45,18 @@ impl Collection {
 pub struct Folder {
     pub id: String,
     pub name: String,
+    /// Notes on the folder, e.g. the setup it needs; line breaks are kept
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Headers sent with every request in the folder, over the collection's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_headers: Vec<KeyValuePair>,
+    /// Variables for every request in the folder, over the collection's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<EnvVariable>,
+    /// Environment switched to when one of its requests is opened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_env_id: Option<String>,
     pub items: Vec<CollectionItem>,
 }
 
@@ -30,6 +65,10 @@ impl Folder {
         Self {
             id: Uuid::new_v4().to_string(),
             name: name.into(),
+            description: String::new(),
+            default_env_id: None,
+            default_headers: Vec::new(),
+            variables: Vec::new(),
             items: Vec::new(),
         }
     }
@@ -41,15 +80,30 @@ pub enum CollectionItem {
     Request(CollectionRequest),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CollectionRequest {
     pub id: String,
     pub name: String,
     pub method: String,
+    /// Notes on the request, e.g. the setup it needs; line breaks are kept
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
     #[serde(default)]
     pub url: String,
     #[serde(default)]
     pub body_raw: String,
+    #[serde(default)]
+    pub checks: Vec<Check>,
+    #[serde(default)]
+    pub scripts: Scripts,
+    #[serde(default, skip_serializing_if = "AuthConfig::is_none")]
+    pub auth: AuthConfig,
+    /// Id of a request that must succeed (this session) before this one is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<String>,
+    /// Re-send automatically after a 429/503 `Retry-After` delay
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_retry: bool,
 }
 
 impl CollectionRequest {
@@ -58,8 +112,185 @@ impl CollectionRequest {
             id: Uuid::new_v4().to_string(),
             name: name.into(),
             method: "GET".into(),
+            description: String::new(),
             url: String::new(),
             body_raw: String::new(),
+            checks: Vec::new(),
+            scripts: Scripts::default(),
+            auth: AuthConfig::None,
+            depends_on: None,
+            auto_retry: false,
         }
     }
 }
+
+/// Point the collection's and its folders' default environments at new ids,
+/// for a copy whose environments were given fresh ones. Ids missing from
+/// `ids` are left as they are.
+pub fn remap_env_ids(col: &mut Collection, ids: &HashMap<String, String>) {
+    fn remap(env_id: &mut Option<String>, ids: &HashMap<String, String>) {
+        if let Some(new) = env_id.as_ref().and_then(|id| ids.get(id)) {
+            *env_id = Some(new.clone());
+        }
+    }
+    fn walk(items: &mut [CollectionItem], ids: &HashMap<String, String>) {
+        for item in items {
+            if let CollectionItem::Folder(f) = item {
+                remap(&mut f.default_env_id, ids);
+                walk(&mut f.items, ids);
+            }
+        }
+    }
+
+    remap(&mut col.default_env_id, ids);
+    walk(&mut col.items, ids);
+}
+
+/// The collection holding `request_id` and the folders enclosing it, from
+/// the outside in.
+fn enclosing<'a>(collections: &'a [Collection], request_id: &str) -> Option<(&'a Collection, Vec<&'a Folder>)> {
+    fn path<'a>(items: &'a [CollectionItem], id: &str, out: &mut Vec<&'a Folder>) -> bool {
+        for item in items {
+            match item {
+                CollectionItem::Request(r) if r.id == id => return true,
+                CollectionItem::Request(_) => {}
+                CollectionItem::Folder(f) => {
+                    out.push(f);
+                    if path(&f.items, id, out) {
+                        return true;
+                    }
+                    out.pop();
+                }
+            }
+        }
+        false
+    }
+
+    collections.iter().find_map(|col| {
+        let mut folders = Vec::new();
+        path(&col.items, request_id, &mut folders).then_some((col, folders))
+    })
+}
+
+/// The enabled default headers a request inherits: its collection's, then
+/// each enclosing folder's from the outside in, a folder's header replacing
+/// an outer one with the same key. Empty when the request isn't found.
+pub fn inherited_headers(collections: &[Collection], request_id: &str) -> Vec<KeyValuePair> {
+    let Some((col, folders)) = enclosing(collections, request_id) else {
+        return Vec::new();
+    };
+    let mut headers: Vec<KeyValuePair> = Vec::new();
+    let layers = std::iter::once(&col.default_headers).chain(folders.iter().map(|f| &f.default_headers));
+    for header in layers.flatten().filter(|h| h.enabled && !h.key.is_empty()) {
+        headers.retain(|h| !h.key.eq_ignore_ascii_case(&header.key));
+        headers.push(header.clone());
+    }
+    headers
+}
+
+/// The enabled variables a request inherits, layered like
+/// `inherited_headers`: a folder's variable replaces an outer one with the
+/// same key.
+pub fn inherited_variables(collections: &[Collection], request_id: &str) -> Vec<EnvVariable> {
+    let Some((col, folders)) = enclosing(collections, request_id) else {
+        return Vec::new();
+    };
+    let mut vars: Vec<EnvVariable> = Vec::new();
+    let layers = std::iter::once(&col.variables).chain(folders.iter().map(|f| &f.variables));
+    for var in layers.flatten().filter(|v| v.enabled && !v.key.is_empty()) {
+        vars.retain(|v| v.key != var.key);
+        vars.push(var.clone());
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_round_trips_through_toml() {
+        let mut col = Collection::new("api");
+        let mut req = CollectionRequest::new("me");
+        req.auth = AuthConfig::ApiKey { key: "X-Key".into(), value: "{{key}}".into(), in_header: false };
+        col.items.push(CollectionItem::Request(req.clone()));
+        col.items.push(CollectionItem::Request(CollectionRequest::new("open")));
+
+        let text = toml::to_string(&col).unwrap();
+        let back: Collection = toml::from_str(&text).unwrap();
+        let auths: Vec<&AuthConfig> = back
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                CollectionItem::Request(r) => Some(&r.auth),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(auths, vec![&req.auth, &AuthConfig::None]);
+    }
+
+    #[test]
+    fn test_remap_env_ids_reaches_nested_folders() {
+        let ids = HashMap::from([("old".to_string(), "new".to_string())]);
+        let mut col = Collection::new("api");
+        col.default_env_id = Some("old".into());
+        let mut outer = Folder::new("outer");
+        let mut inner = Folder::new("inner");
+        inner.default_env_id = Some("old".into());
+        outer.default_env_id = Some("other".into());
+        outer.items.push(CollectionItem::Folder(inner));
+        col.items.push(CollectionItem::Folder(outer));
+
+        remap_env_ids(&mut col, &ids);
+        assert_eq!(col.default_env_id.as_deref(), Some("new"));
+        let CollectionItem::Folder(outer) = &col.items[0] else { panic!() };
+        assert_eq!(outer.default_env_id.as_deref(), Some("other"));
+        let CollectionItem::Folder(inner) = &outer.items[0] else { panic!() };
+        assert_eq!(inner.default_env_id.as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn test_default_env_round_trips_through_toml() {
+        let mut col = Collection::new("api");
+        col.default_env_id = Some("env-1".into());
+        col.default_headers = vec![KeyValuePair::new("Accept", "application/json")];
+        let text = toml::to_string_pretty(&col).unwrap();
+        let back: Collection = toml::from_str(&text).unwrap();
+        assert_eq!(back.default_env_id.as_deref(), Some("env-1"));
+
+        let plain = toml::to_string_pretty(&Collection::new("api")).unwrap();
+        assert!(!plain.contains("default_env_id"));
+        assert!(toml::from_str::<Collection>(&plain).unwrap().default_env_id.is_none());
+    }
+
+    #[test]
+    fn test_inherited_headers_folder_overrides_collection() {
+        let mut col = Collection::new("api");
+        col.default_headers = vec![
+            KeyValuePair::new("Accept", "application/json"),
+            KeyValuePair::new("Authorization", "Bearer {{token}}"),
+            KeyValuePair { enabled: false, ..KeyValuePair::new("X-Off", "1") },
+        ];
+        let mut folder = Folder::new("admin");
+        folder.default_headers = vec![KeyValuePair::new("authorization", "Bearer {{admin}}")];
+        let inner = CollectionRequest::new("users");
+        let outer = CollectionRequest::new("health");
+        folder.items.push(CollectionItem::Request(inner.clone()));
+        col.items.push(CollectionItem::Folder(folder));
+        col.items.push(CollectionItem::Request(outer.clone()));
+        let collections = [col];
+
+        let pairs = |id: &str| -> Vec<(String, String)> {
+            inherited_headers(&collections, id).into_iter().map(|h| (h.key, h.value)).collect()
+        };
+        assert_eq!(
+            pairs(&inner.id),
+            [
+                ("Accept".to_string(), "application/json".to_string()),
+                ("authorization".to_string(), "Bearer {{admin}}".to_string()),
+            ]
+        );
+        assert_eq!(pairs(&outer.id).len(), 2);
+        assert!(pairs("missing").is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// Global user configuration (saved to `config.toml`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub ui: UiConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Sidebar width in columns when rendered in full mode.
+    pub sidebar_width: u16,
+    pub sidebar_mode: SidebarMode,
+    pub theme: ThemeKind,
+    /// syntect theme for highlighted bodies; unset follows `theme`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syntax_theme: Option<String>,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 28,
+            sidebar_mode: SidebarMode::Auto,
+            theme: ThemeKind::Auto,
+            syntax_theme: None,
+        }
+    }
+}
+
+/// How the sidebar is rendered. `Auto` switches to compact on narrow terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SidebarMode {
+    #[default]
+    Auto,
+    Compact,
+    Full,
+}
+
+impl SidebarMode {
+    pub fn next(&self) -> SidebarMode {
+        match self {
+            SidebarMode::Auto => SidebarMode::Compact,
+            SidebarMode::Compact => SidebarMode::Full,
+            SidebarMode::Full => SidebarMode::Auto,
+        }
+    }
+}
+
+/// Color theme: a built-in palette (they live in `ui::theme`) or the user's
+/// `theme.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeKind {
+    /// Tokyo Night or its light variant, following the terminal background
+    #[default]
+    Auto,
+    #[serde(alias = "tokyonight")]
+    TokyoNight,
+    #[serde(rename = "tokyonight-light", alias = "light")]
+    Light,
+    HighContrast,
+    /// The palette in `theme.toml`
+    Custom,
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 5] = [
+        ThemeKind::Auto,
+        ThemeKind::TokyoNight,
+        ThemeKind::Light,
+        ThemeKind::HighContrast,
+        ThemeKind::Custom,
+    ];
+
+    /// The theme named `name` as written in `config.toml` ("tokyo-night",
+    /// "light", …), ignoring case.
+    pub fn from_name(name: &str) -> Option<ThemeKind> {
+        toml::Value::String(name.trim().to_lowercase()).try_into().ok()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeKind::Auto => "Auto",
+            ThemeKind::TokyoNight => "Tokyo Night",
+            ThemeKind::Light => "Tokyo Night Light",
+            ThemeKind::HighContrast => "High contrast",
+            ThemeKind::Custom => "Custom",
+        }
+    }
+}
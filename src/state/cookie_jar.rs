@@ -0,0 +1,238 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A cookie kept in the workspace jar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Lowercase host, without a leading dot
+    pub domain: String,
+    pub path: String,
+    /// Set without a `Domain` attribute: sent to exactly `domain`, not its
+    /// subdomains.
+    #[serde(default)]
+    pub host_only: bool,
+    #[serde(default)]
+    pub secure: bool,
+    /// `None` for session cookies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+    /// When the jar first stored this cookie (kept when a response updates it)
+    pub created: DateTime<Utc>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|at| at <= now)
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str().map(str::to_lowercase) else {
+            return false;
+        };
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            host == self.domain || host.ends_with(&format!(".{}", self.domain))
+        };
+        let path = url.path();
+        let path_ok = path == self.path
+            || (path.starts_with(&self.path)
+                && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_ok && path_ok && (!self.secure || url.scheme() == "https")
+    }
+
+    fn same_key(&self, other: &StoredCookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+}
+
+/// Cookies collected from responses and sent back on matching requests.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CookieJar {
+    #[serde(default)]
+    pub cookies: Vec<StoredCookie>,
+}
+
+impl CookieJar {
+    /// Apply one `Set-Cookie` header received from `url`. An expired or
+    /// `Max-Age=0` cookie removes the stored one. Returns `false` if the
+    /// header was rejected (unparseable, or a `Domain` the host can't set).
+    pub fn store(&mut self, header: &str, url: &Url, now: DateTime<Utc>) -> bool {
+        let Some(host) = url.host_str().map(str::to_lowercase) else {
+            return false;
+        };
+        let mut parts = header.split(';');
+        let Some((name, value)) = parts.next().and_then(|nv| nv.split_once('=')) else {
+            return false;
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return false;
+        }
+
+        let mut cookie = StoredCookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            path: "/".to_string(),
+            host_only: true,
+            secure: false,
+            expires: None,
+            created: now,
+        };
+        let mut max_age = None;
+        for attr in parts {
+            let (key, val) = attr.split_once('=').unwrap_or((attr, ""));
+            let val = val.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !val.is_empty() => {
+                    let domain = val.trim_start_matches('.').to_lowercase();
+                    if host != domain && !host.ends_with(&format!(".{domain}")) {
+                        return false;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if val.starts_with('/') => cookie.path = val.to_string(),
+                "secure" => cookie.secure = true,
+                "max-age" => max_age = val.parse::<i64>().ok(),
+                "expires" => {
+                    cookie.expires = DateTime::parse_from_rfc2822(&val.replace('-', " "))
+                        .ok()
+                        .map(|at| at.with_timezone(&Utc));
+                }
+                _ => {}
+            }
+        }
+        // Max-Age wins over Expires
+        if let Some(secs) = max_age {
+            cookie.expires = Some(now + Duration::seconds(secs));
+        }
+
+        let existing = self.cookies.iter().position(|c| c.same_key(&cookie));
+        match existing {
+            _ if cookie.is_expired(now) => {
+                if let Some(idx) = existing {
+                    self.cookies.remove(idx);
+                }
+            }
+            Some(idx) => {
+                cookie.created = self.cookies[idx].created;
+                self.cookies[idx] = cookie;
+            }
+            None => self.cookies.push(cookie),
+        }
+        true
+    }
+
+    /// `Cookie` header value for a request to `url`, longest paths first.
+    pub fn header_for(&self, url: &Url, now: DateTime<Utc>) -> Option<String> {
+        let mut matching: Vec<&StoredCookie> = self
+            .cookies
+            .iter()
+            .filter(|c| !c.is_expired(now) && c.matches(url))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        let pairs: Vec<String> = matching.iter().map(|c| format!("{}={}", c.name, c.value)).collect();
+        Some(pairs.join("; "))
+    }
+
+    /// The stored cookie a response to `url` set as `name`/`domain`/`path`.
+    /// An empty `domain` means host-only.
+    pub fn find(&self, url: &Url, name: &str, domain: &str, path: &str) -> Option<&StoredCookie> {
+        let domain = if domain.is_empty() {
+            url.host_str()?.to_lowercase()
+        } else {
+            domain.trim_start_matches('.').to_lowercase()
+        };
+        self.cookies
+            .iter()
+            .find(|c| c.name == name && c.domain == domain && c.path == path)
+    }
+
+    /// Drop cookies whose expiry has passed.
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) {
+        self.cookies.retain(|c| !c.is_expired(now));
+    }
+
+    /// Cookies ordered for display: by domain, then path and name.
+    pub fn sorted(&self) -> Vec<&StoredCookie> {
+        let mut cookies: Vec<&StoredCookie> = self.cookies.iter().collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        cookies
+    }
+
+    pub fn remove(&mut self, cookie: &StoredCookie) {
+        self.cookies.retain(|c| !c.same_key(cookie));
+    }
+}
+
+/// A workspace's jar, shared with the HTTP client that fills it.
+#[derive(Debug, Clone, Default)]
+pub struct SharedJar(Arc<RwLock<CookieJar>>);
+
+impl SharedJar {
+    pub fn new(jar: CookieJar) -> Self {
+        Self(Arc::new(RwLock::new(jar)))
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, CookieJar> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, CookieJar> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_store_and_send_back() {
+        let now = Utc::now();
+        let mut jar = CookieJar::default();
+        let login = url("https://api.example.com/login");
+        assert!(jar.store("sid=abc; Path=/; HttpOnly", &login, now));
+        assert!(jar.store("theme=dark; Domain=.example.com; Path=/app", &login, now));
+        assert!(!jar.store("x=1; Domain=other.com", &login, now));
+
+        assert_eq!(jar.header_for(&url("https://api.example.com/me"), now).as_deref(), Some("sid=abc"));
+        assert_eq!(
+            jar.header_for(&url("https://www.example.com/app/x"), now).as_deref(),
+            Some("theme=dark")
+        );
+        assert_eq!(jar.header_for(&url("https://www.example.com/apple"), now), None);
+        assert_eq!(jar.header_for(&url("https://other.com/"), now), None);
+    }
+
+    #[test]
+    fn test_update_keeps_created_and_max_age_zero_removes() {
+        let t0 = Utc::now();
+        let later = t0 + Duration::seconds(5);
+        let u = url("http://localhost:3000/");
+        let mut jar = CookieJar::default();
+        jar.store("sid=1", &u, t0);
+        jar.store("sid=2; Max-Age=60", &u, later);
+        assert_eq!(jar.cookies.len(), 1);
+        assert_eq!(jar.cookies[0].value, "2");
+        assert_eq!(jar.cookies[0].created, t0);
+
+        assert!(jar.header_for(&u, later + Duration::seconds(61)).is_none());
+        jar.store("sid=; Max-Age=0", &u, later);
+        assert!(jar.cookies.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -33,6 +34,9 @@ pub struct Environment {
     pub id: String,
     pub name: String,
     pub color: String,
+    /// Sends while this environment is active ask for confirmation first
+    #[serde(default)]
+    pub protected: bool,
     pub variables: Vec<EnvVariable>,
 }
 
@@ -42,7 +46,140 @@ impl Default for Environment {
             id: uuid::Uuid::new_v4().to_string(),
             name: String::from("New Environment"),
             color: String::from("#7aa2f7"),
+            protected: false,
             variables: Vec::new(),
         }
     }
 }
+
+/// Colors `c` in the environment editor cycles through: blue, green,
+/// yellow, orange, red, purple, cyan, grey.
+pub const COLOR_PALETTE: [&str; 8] =
+    ["#7aa2f7", "#9ece6a", "#e0af68", "#ff9e64", "#f7768e", "#bb9af7", "#2ac3de", "#a9b1d6"];
+
+/// Parse a `#rrggbb` color.
+pub fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.trim().strip_prefix('#')?;
+    if digits.len() != 6 || !digits.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+impl Environment {
+    /// The environment's color as RGB, or `None` if it isn't `#rrggbb`.
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_color(&self.color)
+    }
+
+    /// Switch to the next color of `COLOR_PALETTE`, or its first when the
+    /// color is a custom one.
+    pub fn cycle_color(&mut self) {
+        let current = COLOR_PALETTE.iter().position(|c| c.eq_ignore_ascii_case(&self.color));
+        let next = current.map_or(0, |i| (i + 1) % COLOR_PALETTE.len());
+        self.color = COLOR_PALETTE[next].to_string();
+    }
+
+    /// Set `key` to `value`, enabling an existing variable or adding a text
+    /// variable at the end. Used for values written by scripts and captures.
+    pub fn set_var(&mut self, key: &str, value: &str) -> &mut EnvVariable {
+        let idx = match self.variables.iter().position(|v| v.key == key) {
+            Some(idx) => idx,
+            None => {
+                self.variables.push(EnvVariable { key: key.to_string(), ..EnvVariable::default() });
+                self.variables.len() - 1
+            }
+        };
+        let var = &mut self.variables[idx];
+        var.value = value.to_string();
+        var.enabled = true;
+        var
+    }
+}
+
+/// Where a variable's current value came from: the request whose script,
+/// capture or token set it, and when. Kept for the session only.
+#[derive(Debug, Clone)]
+pub struct VarOrigin {
+    /// Request name; empty for values edited by hand
+    pub request: String,
+    /// "pre-request script", "post-response script", "capture", ...
+    pub via: &'static str,
+    pub at: DateTime<Local>,
+}
+
+impl VarOrigin {
+    pub fn now(request: &str, via: &'static str) -> Self {
+        Self { request: request.to_string(), via, at: Local::now() }
+    }
+}
+
+/// Indices into `vars` of the variables whose key or description contains
+/// `query` (case-insensitive), in order. An empty query matches all.
+pub fn filter_variables(vars: &[EnvVariable], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    vars.iter()
+        .enumerate()
+        .filter(|(_, v)| {
+            query.is_empty()
+                || v.key.to_lowercase().contains(&query)
+                || v.description.to_lowercase().contains(&query)
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(key: &str, description: &str) -> EnvVariable {
+        EnvVariable { key: key.into(), description: description.into(), ..EnvVariable::default() }
+    }
+
+    #[test]
+    fn test_filter_variables_maps_to_original_indices() {
+        let vars = vec![
+            var("base_url", ""),
+            var("token", "Auth bearer"),
+            var("user_id", ""),
+            var("AUTH_HEADER", ""),
+        ];
+        assert_eq!(filter_variables(&vars, ""), vec![0, 1, 2, 3]);
+        assert_eq!(filter_variables(&vars, "auth"), vec![1, 3]);
+        assert_eq!(filter_variables(&vars, " _ID "), vec![2]);
+        assert!(filter_variables(&vars, "nope").is_empty());
+    }
+
+    #[test]
+    fn test_env_color_parse_and_cycle() {
+        assert_eq!(parse_hex_color("#7aa2f7"), Some((0x7a, 0xa2, 0xf7)));
+        assert_eq!(parse_hex_color(" #FF0000 "), Some((255, 0, 0)));
+        assert_eq!(parse_hex_color("ff0000"), None);
+        assert_eq!(parse_hex_color("#ff00"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+        assert_eq!(parse_hex_color("#ffé000"), None);
+
+        let mut env = Environment::default();
+        env.cycle_color();
+        assert_eq!(env.color, COLOR_PALETTE[1]);
+        env.color = "#123456".into();
+        env.cycle_color();
+        assert_eq!(env.color, COLOR_PALETTE[0]);
+        env.color = COLOR_PALETTE[7].into();
+        env.cycle_color();
+        assert_eq!(env.color, COLOR_PALETTE[0]);
+    }
+
+    #[test]
+    fn test_protected_defaults_off_and_round_trips() {
+        let old = "id = \"e1\"\nname = \"prod\"\ncolor = \"#f7768e\"\nvariables = []\n";
+        let env: Environment = toml::from_str(old).unwrap();
+        assert!(!env.protected);
+
+        let env = Environment { protected: true, ..env };
+        let back: Environment = toml::from_str(&toml::to_string_pretty(&env).unwrap()).unwrap();
+        assert!(back.protected);
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many recent sends are kept per collection request.
+pub const MAX_RUNS: usize = 20;
+
+/// One send of a collection request. `status` is `None` when no response
+/// arrived (connection error, timeout).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub status: Option<u16>,
+    pub total_ms: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RunRecord {
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Some(200..=299))
+    }
+}
+
+/// Append `run`, dropping the oldest entries beyond [`MAX_RUNS`].
+pub fn push_run(runs: &mut Vec<RunRecord>, run: RunRecord) {
+    runs.push(run);
+    if runs.len() > MAX_RUNS {
+        runs.drain(..runs.len() - MAX_RUNS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(ms: u64) -> RunRecord {
+        RunRecord { status: Some(200), total_ms: ms, timestamp: Utc::now() }
+    }
+
+    #[test]
+    fn test_push_run_keeps_last_max_runs() {
+        let mut runs = Vec::new();
+        for ms in 0..(MAX_RUNS as u64 + 5) {
+            push_run(&mut runs, run(ms));
+        }
+        assert_eq!(runs.len(), MAX_RUNS);
+        assert_eq!(runs.first().map(|r| r.total_ms), Some(5));
+        assert_eq!(runs.last().map(|r| r.total_ms), Some(MAX_RUNS as u64 + 4));
+    }
+
+    #[test]
+    fn test_is_success() {
+        assert!(run(1).is_success());
+        assert!(!RunRecord { status: Some(404), ..run(1) }.is_success());
+        assert!(!RunRecord { status: None, ..run(1) }.is_success());
+    }
+}
@@ -1,8 +1,16 @@
 pub mod app_state;
+pub mod audit;
+pub mod check;
+pub mod config;
 pub mod collection;
 pub mod environment;
 pub mod focus;
+pub mod history;
 pub mod mode;
 pub mod workspace;
 pub mod request_state;
 pub mod response_state;
+pub mod undo;
+pub mod cookie_jar;
+pub mod websocket;
+pub mod sse;
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::check::Check;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum HttpMethod {
     #[default]
@@ -98,6 +103,177 @@ pub enum AuthConfig {
     Bearer { token: String },
     Basic { username: String, password: String },
     ApiKey { key: String, value: String, in_header: bool },
+    /// Access token fetched from `token_url` before sending, then sent as a
+    /// bearer token
+    OAuth2 {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        #[serde(default)]
+        scope: String,
+        #[serde(default)]
+        grant: OAuthGrant,
+        /// Used by the refresh-token grant
+        #[serde(default)]
+        refresh_token: String,
+        /// Environment variable each fetched token is also written to
+        #[serde(default)]
+        token_var: String,
+        /// Save the cached token with the collection; otherwise it lives in
+        /// memory only
+        #[serde(default)]
+        persist_token: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<OAuthToken>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthGrant {
+    #[default]
+    ClientCredentials,
+    RefreshToken,
+}
+
+impl OAuthGrant {
+    /// The `grant_type` sent to the token endpoint.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuthGrant::ClientCredentials => "client_credentials",
+            OAuthGrant::RefreshToken => "refresh_token",
+        }
+    }
+
+    pub fn toggle(&self) -> OAuthGrant {
+        match self {
+            OAuthGrant::ClientCredentials => OAuthGrant::RefreshToken,
+            OAuthGrant::RefreshToken => OAuthGrant::ClientCredentials,
+        }
+    }
+}
+
+/// Access token from an OAuth2 token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    /// `None` when the server didn't say (`expires_in`)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OAuthToken {
+    /// Whether the token is still usable at `now`, leaving a margin so it
+    /// doesn't expire in flight.
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|at| at - Duration::seconds(30) > now)
+    }
+}
+
+impl AuthConfig {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuthConfig::None => "No auth",
+            AuthConfig::Bearer { .. } => "Bearer token",
+            AuthConfig::Basic { .. } => "Basic auth",
+            AuthConfig::ApiKey { .. } => "API key",
+            AuthConfig::OAuth2 { .. } => "OAuth 2.0",
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, AuthConfig::None)
+    }
+
+    /// Next auth type, with empty fields
+    pub fn next(&self) -> AuthConfig {
+        match self {
+            AuthConfig::None => AuthConfig::Bearer { token: String::new() },
+            AuthConfig::Bearer { .. } => AuthConfig::Basic { username: String::new(), password: String::new() },
+            AuthConfig::Basic { .. } => AuthConfig::ApiKey { key: String::new(), value: String::new(), in_header: true },
+            AuthConfig::ApiKey { .. } => AuthConfig::oauth2(),
+            AuthConfig::OAuth2 { .. } => AuthConfig::None,
+        }
+    }
+
+    pub fn prev(&self) -> AuthConfig {
+        match self {
+            AuthConfig::None => AuthConfig::oauth2(),
+            AuthConfig::Bearer { .. } => AuthConfig::None,
+            AuthConfig::Basic { .. } => AuthConfig::Bearer { token: String::new() },
+            AuthConfig::ApiKey { .. } => AuthConfig::Basic { username: String::new(), password: String::new() },
+            AuthConfig::OAuth2 { .. } => AuthConfig::ApiKey { key: String::new(), value: String::new(), in_header: true },
+        }
+    }
+
+    fn oauth2() -> AuthConfig {
+        AuthConfig::OAuth2 {
+            token_url: String::new(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            scope: String::new(),
+            grant: OAuthGrant::default(),
+            refresh_token: String::new(),
+            token_var: String::new(),
+            persist_token: false,
+            token: None,
+        }
+    }
+
+    /// The copy saved with a collection: an OAuth2 token cache only goes to
+    /// disk when the user opted in.
+    pub fn for_storage(&self) -> AuthConfig {
+        let mut auth = self.clone();
+        if let AuthConfig::OAuth2 { persist_token: false, token, .. } = &mut auth {
+            *token = None;
+        }
+        auth
+    }
+
+    /// Editable text fields as `(label, value, masked)`, in display order.
+    pub fn fields(&self) -> Vec<(&'static str, &str, bool)> {
+        match self {
+            AuthConfig::None => Vec::new(),
+            AuthConfig::Bearer { token } => vec![("Token", token, true)],
+            AuthConfig::Basic { username, password } => {
+                vec![("Username", username, false), ("Password", password, true)]
+            }
+            AuthConfig::ApiKey { key, value, .. } => vec![("Key", key, false), ("Value", value, false)],
+            AuthConfig::OAuth2 { token_url, client_id, client_secret, scope, grant, refresh_token, token_var, .. } => {
+                let mut fields = vec![
+                    ("Token URL", token_url.as_str(), false),
+                    ("Client ID", client_id, false),
+                    ("Secret", client_secret, true),
+                    ("Scope", scope, false),
+                ];
+                if *grant == OAuthGrant::RefreshToken {
+                    fields.push(("Refresh", refresh_token, true));
+                }
+                fields.push(("Save to", token_var, false));
+                fields
+            }
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            AuthConfig::None => Vec::new(),
+            AuthConfig::Bearer { token } => vec![token],
+            AuthConfig::Basic { username, password } => vec![username, password],
+            AuthConfig::ApiKey { key, value, .. } => vec![key, value],
+            AuthConfig::OAuth2 { token_url, client_id, client_secret, scope, grant, refresh_token, token_var, .. } => {
+                let mut fields = vec![token_url, client_id, client_secret, scope];
+                if *grant == OAuthGrant::RefreshToken {
+                    fields.push(refresh_token);
+                }
+                fields.push(token_var);
+                fields
+            }
+        }
+    }
+
+    pub fn field_mut(&mut self, idx: usize) -> Option<&mut String> {
+        self.fields_mut().into_iter().nth(idx)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -106,6 +282,46 @@ pub struct Scripts {
     pub post_response: String,
 }
 
+impl Scripts {
+    pub fn get(&self, kind: &ScriptKind) -> &str {
+        match kind {
+            ScriptKind::PreRequest => &self.pre_request,
+            ScriptKind::PostResponse => &self.post_response,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: &ScriptKind) -> &mut String {
+        match kind {
+            ScriptKind::PreRequest => &mut self.pre_request,
+            ScriptKind::PostResponse => &mut self.post_response,
+        }
+    }
+}
+
+/// Which script the Scripts tab is editing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ScriptKind {
+    #[default]
+    PreRequest,
+    PostResponse,
+}
+
+impl ScriptKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScriptKind::PreRequest => "Pre-request",
+            ScriptKind::PostResponse => "Post-response",
+        }
+    }
+
+    pub fn toggle(&self) -> ScriptKind {
+        match self {
+            ScriptKind::PreRequest => ScriptKind::PostResponse,
+            ScriptKind::PostResponse => ScriptKind::PreRequest,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestState {
     pub id: String,
@@ -113,12 +329,29 @@ pub struct RequestState {
     pub method: HttpMethod,
     pub url: String,
     pub url_cursor: usize,
+    /// Characters of the URL scrolled off the left edge of the URL bar
+    #[serde(default)]
+    pub url_scroll_offset: usize,
+    /// Selected byte range of the URL (`Ctrl+A`); empty when equal
+    #[serde(skip)]
+    pub url_select_start: usize,
+    #[serde(skip)]
+    pub url_select_end: usize,
     pub headers: Vec<KeyValuePair>,
     pub params: Vec<KeyValuePair>,
     pub body: RequestBody,
     pub auth: AuthConfig,
     pub scripts: Scripts,
     #[serde(default)]
+    pub checks: Vec<Check>,
+    /// Notes shown and edited on the Docs tab
+    #[serde(default)]
+    pub description: String,
+    /// Request-scoped variables set by the pre-request script
+    /// (`pm.variables.set`). They take priority over environment variables.
+    #[serde(skip)]
+    pub script_variables: HashMap<String, String>,
+    #[serde(default)]
     pub body_cursor: usize,
     #[serde(default)]
     pub body_scroll_offset: u16,
@@ -128,6 +361,33 @@ pub struct RequestState {
     pub headers_col: u8,
     #[serde(default)]
     pub headers_cursor: usize,
+    #[serde(default)]
+    pub checks_row: usize,
+    #[serde(default)]
+    pub checks_col: u8,
+    #[serde(default)]
+    pub checks_cursor: usize,
+    #[serde(default)]
+    pub scripts_kind: ScriptKind,
+    #[serde(default)]
+    pub scripts_cursor: usize,
+    #[serde(default)]
+    pub description_cursor: usize,
+    /// Selected auth text field (index into `AuthConfig::fields`)
+    #[serde(default)]
+    pub auth_field: usize,
+    #[serde(default)]
+    pub auth_cursor: usize,
+    /// Show the bearer token and password instead of bullets
+    #[serde(skip)]
+    pub auth_reveal: bool,
+    /// Re-send automatically after a 429/503 `Retry-After` delay
+    #[serde(default)]
+    pub auto_retry: bool,
+    /// Largest response body read; set from the workspace by
+    /// `prepare_request`, `None` reads it whole
+    #[serde(skip)]
+    pub max_body_bytes: Option<usize>,
 }
 
 impl Default for RequestState {
@@ -138,16 +398,80 @@ impl Default for RequestState {
             method: HttpMethod::default(),
             url: String::new(),
             url_cursor: 0,
+            url_scroll_offset: 0,
+            url_select_start: 0,
+            url_select_end: 0,
             headers: Vec::new(),
             params: Vec::new(),
             body: RequestBody::None,
             auth: AuthConfig::None,
             scripts: Scripts::default(),
+            checks: Vec::new(),
+            description: String::new(),
+            script_variables: HashMap::new(),
             body_cursor: 0,
             body_scroll_offset: 0,
             headers_row: 0,
             headers_col: 0,
             headers_cursor: 0,
+            checks_row: 0,
+            checks_col: 0,
+            checks_cursor: 0,
+            scripts_kind: ScriptKind::default(),
+            scripts_cursor: 0,
+            description_cursor: 0,
+            auth_field: 0,
+            auth_cursor: 0,
+            auth_reveal: false,
+            auto_retry: false,
+            max_body_bytes: None,
         }
     }
 }
+
+impl RequestState {
+    /// The selected part of the URL, if any.
+    pub fn url_selection(&self) -> Option<std::ops::Range<usize>> {
+        let end = self.url_select_end.min(self.url.len());
+        (self.url_select_start < end).then_some(self.url_select_start..end)
+    }
+
+    pub fn select_all_url(&mut self) {
+        self.url_select_start = 0;
+        self.url_select_end = self.url.len();
+        self.url_cursor = self.url.len();
+    }
+
+    pub fn clear_url_selection(&mut self) {
+        self.url_select_start = 0;
+        self.url_select_end = 0;
+    }
+
+    /// Remove the selected text, leaving the cursor where it was. Returns
+    /// `false` if nothing was selected.
+    pub fn delete_url_selection(&mut self) -> bool {
+        let Some(range) = self.url_selection() else {
+            return false;
+        };
+        self.url_cursor = range.start;
+        self.url.drain(range);
+        self.clear_url_selection();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_selection_is_replaced() {
+        let mut req = RequestState { url: "https://old.test/a".into(), ..Default::default() };
+        assert_eq!(req.url_selection(), None);
+        req.select_all_url();
+        assert_eq!(req.url_selection(), Some(0..18));
+        assert!(req.delete_url_selection());
+        assert_eq!((req.url.as_str(), req.url_cursor), ("", 0));
+        assert!(!req.delete_url_selection());
+    }
+}
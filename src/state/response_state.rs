@@ -1,6 +1,9 @@
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use ratatui::text::Text;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RequestTiming {
@@ -18,6 +21,16 @@ pub struct Cookie {
     pub value: String,
     pub domain: String,
     pub path: String,
+    /// `Expires`, if present and parseable
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub secure: bool,
+    /// `SameSite` as sent (`Strict`, `Lax`, `None`)
+    #[serde(default)]
+    pub same_site: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -37,12 +50,112 @@ pub struct ResponseState {
     pub cookies: Vec<Cookie>,
     pub timing: RequestTiming,
     pub size_bytes: usize,
+    /// Body cut off at this many bytes, the workspace's `max_body_mb`
+    #[serde(default)]
+    pub truncated_at: Option<usize>,
+    /// Body text cut to `MAX_DISPLAY_BYTES` for viewing
+    #[serde(default)]
+    pub truncated: bool,
     pub received_at: DateTime<Utc>,
+    /// Final URL after redirects
+    #[serde(default)]
+    pub url: String,
+    /// Method and URL the request actually went out with, variables resolved
+    #[serde(default)]
+    pub request_method: String,
+    #[serde(default)]
+    pub request_url: String,
+    /// Environment active when the request was sent
+    #[serde(default)]
+    pub environment: Option<String>,
+    /// When the request went out; cookies the jar first stored after this
+    /// were set by this response.
+    #[serde(default = "Utc::now")]
+    pub sent_at: DateTime<Utc>,
+    /// First line shown, or first wrapped row when word wrap is on
     pub scroll_offset: u16,
+    /// Columns an unwrapped body is scrolled right (`←` / `→`)
+    #[serde(skip)]
+    pub h_scroll: u16,
+    /// First row shown in the Cookies tab
+    #[serde(skip)]
+    pub cookies_scroll: u16,
+    /// Selected header in the Headers tab (`y` copies it)
+    #[serde(skip)]
+    pub headers_cursor: usize,
     /// Pre-computed syntax-highlighted body. Computed once on response arrival;
     /// skipped during serialisation since it can be trivially recomputed.
     #[serde(skip)]
     pub highlighted_body: Option<Text<'static>>,
+    /// Background highlight `highlighted_body` is waiting for (0 for none);
+    /// the body shows as plain text meanwhile.
+    #[serde(skip)]
+    pub highlight_job: u64,
+    /// Highlight the body as this language instead of the detected one
+    /// (`Ctrl+L` in the viewer cycles through `highlight::LANGS`).
+    #[serde(skip)]
+    pub force_lang: Option<String>,
+    /// Show the JSON body with keys sorted recursively (`o` in the viewer).
+    #[serde(skip)]
+    pub sorted_view: bool,
+    /// Highlighted sorted-keys body, computed the first time the view is shown.
+    #[serde(skip)]
+    pub sorted_body: Option<Text<'static>>,
+    /// Show a markdown body rendered rather than raw (`m` in the viewer; on
+    /// by default for `text/markdown`).
+    #[serde(skip)]
+    pub markdown_view: bool,
+    /// Rendered markdown body, computed the first time the view is shown.
+    #[serde(skip)]
+    pub markdown_body: Option<Text<'static>>,
+    /// Show the JSON body as a collapsible tree (`t` in the viewer).
+    #[serde(skip)]
+    pub tree_view: bool,
+    /// Parsed body for the tree view, parsed the first time it is shown.
+    #[serde(skip)]
+    pub tree_value: Option<Value>,
+    /// Paths of folded tree nodes (`$.data.items`)
+    #[serde(skip)]
+    pub tree_collapsed: HashSet<String>,
+    /// Selected tree row
+    #[serde(skip)]
+    pub tree_cursor: usize,
+    #[serde(skip)]
+    pub filter: BodyFilter,
+}
+
+/// The response viewer's filter bar (`f`): a JSONPath or jq-style
+/// expression evaluated against the full body.
+#[derive(Debug, Clone, Default)]
+pub struct BodyFilter {
+    pub input: String,
+    pub cursor: usize,
+    /// Typing into the bar
+    pub editing: bool,
+    /// Expression whose result is shown
+    pub applied: String,
+    /// Highlighted result of `applied`
+    pub result: Option<Text<'static>>,
+    /// Why `input` doesn't evaluate; the last good result stays shown
+    pub error: Option<String>,
+}
+
+impl ResponseState {
+    /// Whether the response declares a markdown body.
+    pub fn is_markdown(&self) -> bool {
+        self.content_type().is_some_and(|ct| {
+            let mime = ct.split(';').next().unwrap_or("").trim();
+            mime.eq_ignore_ascii_case("text/markdown") || mime.eq_ignore_ascii_case("text/x-markdown")
+        })
+    }
+
+    /// The `Content-Type` header, if the response has one.
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str())
+    }
 }
 
 impl Default for ResponseState {
@@ -55,9 +168,30 @@ impl Default for ResponseState {
             cookies: Vec::new(),
             timing: RequestTiming::default(),
             size_bytes: 0,
+            truncated_at: None,
+            truncated: false,
             received_at: Utc::now(),
+            url: String::new(),
+            request_method: String::new(),
+            request_url: String::new(),
+            environment: None,
+            sent_at: Utc::now(),
             scroll_offset: 0,
+            h_scroll: 0,
+            cookies_scroll: 0,
+            headers_cursor: 0,
             highlighted_body: None,
+            highlight_job: 0,
+            force_lang: None,
+            sorted_view: false,
+            sorted_body: None,
+            markdown_view: false,
+            markdown_body: None,
+            tree_view: false,
+            tree_value: None,
+            tree_collapsed: HashSet::new(),
+            tree_cursor: 0,
+            filter: BodyFilter::default(),
         }
     }
 }
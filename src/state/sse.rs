@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+use crate::http::sse::SseEvent;
+
+/// One received event in the log.
+#[derive(Debug, Clone)]
+pub struct SseEntry {
+    pub at: DateTime<Local>,
+    pub event: SseEvent,
+}
+
+/// A tab's open `text/event-stream` response and the events it carried.
+#[derive(Debug, Clone)]
+pub struct SseStream {
+    /// Tags the stream's events
+    pub id: String,
+    /// Why the stream ended; `None` while it is open
+    pub closed: Option<String>,
+    pub log: Vec<SseEntry>,
+    /// Lines scrolled back from the newest event
+    pub scroll: u16,
+    /// Dropping it stops the stream
+    pub stop: Option<Arc<DropGuard>>,
+}
+
+impl SseStream {
+    pub fn new(id: String, cancel: CancellationToken) -> Self {
+        Self {
+            id,
+            closed: None,
+            log: Vec::new(),
+            scroll: 0,
+            stop: Some(Arc::new(cancel.drop_guard())),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.closed.is_none()
+    }
+
+    pub fn push(&mut self, event: SseEvent) {
+        // Keep the lines the user scrolled back to in view
+        if self.scroll > 0 {
+            self.scroll = self.scroll.saturating_add(entry_lines(&event) as u16);
+        }
+        self.log.push(SseEntry { at: Local::now(), event });
+    }
+
+    /// Rows the log takes: a header row per event plus its data lines.
+    pub fn line_count(&self) -> usize {
+        self.log.iter().map(|e| entry_lines(&e.event)).sum()
+    }
+}
+
+fn entry_lines(event: &SseEvent) -> usize {
+    1 + event.data.split('\n').count()
+}
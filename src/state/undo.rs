@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use super::request_state::KeyValuePair;
+
+/// Most entries kept per tab; the oldest are dropped first.
+const MAX_ENTRIES: usize = 100;
+/// Characters typed closer together than this undo as one word.
+const MERGE_WINDOW: Duration = Duration::from_millis(300);
+
+/// Part of a request an undo entry belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditField {
+    Url,
+    Body,
+    Header { row: usize, col: u8 },
+    Auth { field: usize },
+    /// The HTTP method, as `text`
+    Method,
+    /// The whole header list (row added, deleted or toggled), as `rows`
+    HeaderRows,
+}
+
+/// A field's text and cursor as they were before an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub field: EditField,
+    pub text: String,
+    /// Cursor in `text`, or the selected row for `EditField::HeaderRows`
+    pub cursor: usize,
+    pub rows: Vec<KeyValuePair>,
+}
+
+/// Per-tab undo/redo history for URL, header, auth and body edits.
+#[derive(Debug, Clone, Default)]
+pub struct UndoStack {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    /// Field and time of the last single-character insert, for merging
+    last_typed: Option<(EditField, Instant)>,
+    /// Field edited in the current Insert-mode session
+    session: Option<EditField>,
+}
+
+impl UndoStack {
+    /// Record the state before an edit and drop the redo history. A typed
+    /// character merges into the previous entry when it follows another
+    /// typed character in the same field within `MERGE_WINDOW`.
+    pub fn record(&mut self, entry: UndoEntry, typed: bool, now: Instant) {
+        let merge = typed
+            && self.last_typed.is_some_and(|(field, at)| {
+                field == entry.field && now.duration_since(at) <= MERGE_WINDOW
+            });
+        self.last_typed = typed.then_some((entry.field, now));
+        self.redo.clear();
+        if !merge {
+            self.push(entry);
+        }
+    }
+
+    /// Record the state before an edit made in Insert mode. Every edit to
+    /// the same field until `end_session` undoes as one unit.
+    pub fn record_insert(&mut self, entry: UndoEntry) {
+        self.last_typed = None;
+        self.redo.clear();
+        if self.session != Some(entry.field) {
+            self.session = Some(entry.field);
+            self.push(entry);
+        }
+    }
+
+    /// Close the undo unit of the Insert-mode session (leaving Insert mode).
+    pub fn end_session(&mut self) {
+        self.session = None;
+    }
+
+    /// Pop the entry to restore. The caller hands back the state it
+    /// replaces via `push_redo`.
+    pub fn undo(&mut self) -> Option<UndoEntry> {
+        self.last_typed = None;
+        self.session = None;
+        self.undo.pop()
+    }
+
+    pub fn redo(&mut self) -> Option<UndoEntry> {
+        self.last_typed = None;
+        self.session = None;
+        self.redo.pop()
+    }
+
+    pub fn push_redo(&mut self, entry: UndoEntry) {
+        self.redo.push(entry);
+    }
+
+    /// Push without touching the redo history (used when redoing).
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.undo.push(entry);
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(text: &str) -> UndoEntry {
+        UndoEntry { field: EditField::Url, text: text.to_string(), cursor: text.len(), rows: Vec::new() }
+    }
+
+    #[test]
+    fn test_typing_merges_within_window() {
+        let mut stack = UndoStack::default();
+        let t0 = Instant::now();
+        stack.record(url(""), true, t0);
+        stack.record(url("a"), true, t0 + Duration::from_millis(100));
+        stack.record(url("ab"), true, t0 + Duration::from_millis(200));
+        // Pause, then keep typing: a new entry
+        stack.record(url("abc"), true, t0 + Duration::from_millis(900));
+        // A deletion never merges
+        stack.record(url("abcd"), false, t0 + Duration::from_millis(950));
+
+        assert_eq!(stack.undo(), Some(url("abcd")));
+        assert_eq!(stack.undo(), Some(url("abc")));
+        assert_eq!(stack.undo(), Some(url("")));
+        assert_eq!(stack.undo(), None);
+    }
+
+    #[test]
+    fn test_insert_session_is_one_unit_per_field() {
+        let mut stack = UndoStack::default();
+        stack.record_insert(url(""));
+        stack.record_insert(url("a"));
+        stack.record_insert(url("ab"));
+        let body = UndoEntry { field: EditField::Body, ..url("") };
+        stack.record_insert(body.clone());
+        stack.end_session();
+        // A new session in the same field is a new unit
+        stack.record_insert(url("abc"));
+
+        assert_eq!(stack.undo(), Some(url("abc")));
+        assert_eq!(stack.undo(), Some(body));
+        assert_eq!(stack.undo(), Some(url("")));
+        assert_eq!(stack.undo(), None);
+    }
+
+    #[test]
+    fn test_record_clears_redo_and_caps_size() {
+        let mut stack = UndoStack::default();
+        let now = Instant::now();
+        for i in 0..150 {
+            stack.record(url(&i.to_string()), false, now);
+        }
+        assert_eq!(stack.undo.len(), MAX_ENTRIES);
+        assert_eq!(stack.undo.first(), Some(&url("50")));
+
+        let entry = stack.undo().unwrap();
+        stack.push_redo(entry);
+        stack.record(url("x"), false, now);
+        assert_eq!(stack.redo(), None);
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Local};
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+/// What the UI asks of an open connection.
+#[derive(Debug, Clone)]
+pub enum WsCommand {
+    /// Send a text frame
+    Send(String),
+    /// Start the closing handshake
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsStatus {
+    Connecting,
+    Open,
+    /// `code` is the close frame's status code, `None` when the connection
+    /// failed or dropped without one.
+    Closed { code: Option<u16>, reason: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsDirection {
+    Sent,
+    Received,
+}
+
+/// One message in the log.
+#[derive(Debug, Clone)]
+pub struct WsEntry {
+    pub at: DateTime<Local>,
+    pub direction: WsDirection,
+    pub text: String,
+}
+
+/// A tab's WebSocket connection and the messages it carried.
+#[derive(Debug, Clone)]
+pub struct WsSession {
+    /// Tags the connection's events
+    pub id: String,
+    pub status: WsStatus,
+    pub log: Vec<WsEntry>,
+    /// Lines scrolled back from the newest message
+    pub scroll: u16,
+    /// Commands for the connection task; dropping it closes the socket
+    pub commands: Option<UnboundedSender<WsCommand>>,
+}
+
+impl WsSession {
+    pub fn new(commands: UnboundedSender<WsCommand>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            status: WsStatus::Connecting,
+            log: Vec::new(),
+            scroll: 0,
+            commands: Some(commands),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        !matches!(self.status, WsStatus::Closed { .. })
+    }
+
+    pub fn push(&mut self, direction: WsDirection, text: String) {
+        // Keep the lines the user scrolled back to in view
+        if self.scroll > 0 {
+            self.scroll = self.scroll.saturating_add(text.split('\n').count() as u16);
+        }
+        self.log.push(WsEntry { at: Local::now(), direction, text });
+    }
+
+    /// Rows the log takes, one per line of each message.
+    pub fn line_count(&self) -> usize {
+        self.log.iter().map(|e| e.text.split('\n').count()).sum()
+    }
+}
+
+/// Whether `url` opens a WebSocket rather than sending an HTTP request.
+pub fn is_websocket_url(url: &str) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
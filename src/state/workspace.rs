@@ -1,16 +1,168 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
 use serde::{Deserialize, Serialize};
 
+use crate::env::chain::ChainSource;
+use crate::http::dry_run::DryRun;
+use crate::scripting::runner::ScriptResult;
 use crate::state::app_state::{ActiveTab, RequestStatus, ResponseTab};
 use crate::state::collection::Collection;
+use crate::state::cookie_jar::SharedJar;
 use crate::state::environment::Environment;
+use crate::state::history::RunRecord;
 use crate::state::request_state::RequestState;
 use crate::state::response_state::ResponseState;
+use crate::state::sse::SseStream;
+use crate::state::undo::UndoStack;
+use crate::state::websocket::WsSession;
 
 /// Persisted workspace metadata (saved to `workspace.toml`).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceFile {
     pub name: String,
     pub active_environment_idx: Option<usize>,
+    #[serde(default = "default_true")]
+    pub show_line_numbers: bool,
+    /// Opt-in request audit log (`audit.jsonl`).
+    #[serde(default)]
+    pub audit_log: bool,
+    /// Note attached to every audit entry, e.g. a ticket reference.
+    #[serde(default)]
+    pub audit_note: String,
+    /// Save the cookie jar to `cookies.toml`; off keeps cookies for the
+    /// session only.
+    #[serde(default = "default_true")]
+    pub persist_cookies: bool,
+    /// Queue sends and dispatch them one at a time
+    #[serde(default)]
+    pub send_queue: bool,
+    /// Minimum time between queued sends
+    #[serde(default = "default_queue_gap_ms")]
+    pub queue_gap_ms: u64,
+    /// Re-send every request after a 429/503 `Retry-After` delay
+    #[serde(default)]
+    pub auto_retry: bool,
+    /// Longest `Retry-After` delay waited for; longer ones are capped
+    #[serde(default = "default_retry_max_secs")]
+    pub retry_max_secs: u64,
+    /// Largest response body read, in MB; the rest is dropped. 0 reads
+    /// bodies whole however large.
+    #[serde(default = "default_max_body_mb")]
+    pub max_body_mb: u64,
+    /// Sidebar order of collections by id; collections not listed follow
+    /// in name order
+    #[serde(default)]
+    pub collection_order: Vec<String>,
+    /// Collection request ids of pinned tabs, reopened with the workspace
+    #[serde(default)]
+    pub pinned_tabs: Vec<String>,
+    #[serde(default, skip_serializing_if = "ProxySettings::is_empty")]
+    pub proxy: ProxySettings,
+}
+
+impl WorkspaceFile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            active_environment_idx: None,
+            show_line_numbers: true,
+            audit_log: false,
+            audit_note: String::new(),
+            persist_cookies: true,
+            send_queue: false,
+            queue_gap_ms: default_queue_gap_ms(),
+            auto_retry: false,
+            retry_max_secs: default_retry_max_secs(),
+            max_body_mb: default_max_body_mb(),
+            collection_order: Vec::new(),
+            pinned_tabs: Vec::new(),
+            proxy: ProxySettings::default(),
+        }
+    }
+}
+
+/// Proxy the workspace's requests go through. Empty URL and bypass fields
+/// fall back to `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    /// Proxy for `http://` URLs
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub http: String,
+    /// Proxy for `https://` URLs
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub https: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub username: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub password: String,
+    /// Comma-separated hosts reached directly, e.g. `localhost, .corp.example`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub no_proxy: String,
+}
+
+impl ProxySettings {
+    /// Number of editable fields, in settings popup order
+    pub const FIELDS: usize = 5;
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Field `idx` in settings popup order: HTTP and HTTPS proxy, username,
+    /// password, no-proxy list.
+    pub fn field(&self, idx: usize) -> &str {
+        match idx {
+            0 => &self.http,
+            1 => &self.https,
+            2 => &self.username,
+            3 => &self.password,
+            _ => &self.no_proxy,
+        }
+    }
+
+    pub fn field_mut(&mut self, idx: usize) -> &mut String {
+        match idx {
+            0 => &mut self.http,
+            1 => &mut self.https,
+            2 => &mut self.username,
+            3 => &mut self.password,
+            _ => &mut self.no_proxy,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_queue_gap_ms() -> u64 {
+    1000
+}
+
+fn default_retry_max_secs() -> u64 {
+    60
+}
+
+fn default_max_body_mb() -> u64 {
+    50
+}
+
+/// The environment a send went out with, as it was then, so renaming or
+/// deleting it while the send is out doesn't change what's shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentEnvironment {
+    pub id: String,
+    pub name: String,
+    /// `#rrggbb`, as `Environment::color`
+    pub color: String,
+}
+
+impl SentEnvironment {
+    pub fn of(env: &Environment) -> Self {
+        Self { id: env.id.clone(), name: env.name.clone(), color: env.color.clone() }
+    }
 }
 
 /// A single open request tab (in-memory only).
@@ -18,11 +170,34 @@ pub struct WorkspaceFile {
 pub struct RequestTab {
     pub request: RequestState,
     pub response: Option<ResponseState>,
+    /// Pre-request script outcome of the last send
+    pub pre_script_result: Option<ScriptResult>,
+    /// Post-response script outcome for `response`
+    pub script_result: Option<ScriptResult>,
     pub active_tab: ActiveTab,
     pub response_tab: ResponseTab,
     pub is_dirty: bool,
     pub collection_id: Option<String>,
     pub request_status: RequestStatus,
+    /// URL, header and body edits (`Ctrl+Z` / `Ctrl+Y`)
+    pub undo: UndoStack,
+    /// When a pending `Retry-After` re-send is due (`Esc` cancels it)
+    pub retry_at: Option<Instant>,
+    /// Connection and message log of a `ws://` / `wss://` request
+    pub ws: Option<WsSession>,
+    /// Event log of a `text/event-stream` response
+    pub sse: Option<SseStream>,
+    /// What the last dry run (`Ctrl+Shift+D`) would have sent; cleared by a send
+    pub dry_run: Option<DryRun>,
+    /// Environment active when the send in flight went out (`None` for
+    /// none), its name handed to the response
+    pub sent_environment: Option<SentEnvironment>,
+    /// Id of the environment this tab resolves with instead of the
+    /// workspace's active one (`Ctrl+E` on the URL bar)
+    pub env_override: Option<String>,
+    /// Pinned tabs can't be closed and reopen with the workspace (`p` on
+    /// the open-tabs bar)
+    pub pinned: bool,
 }
 
 impl Default for RequestTab {
@@ -30,17 +205,44 @@ impl Default for RequestTab {
         Self {
             request: RequestState::default(),
             response: None,
+            pre_script_result: None,
+            script_result: None,
             active_tab: ActiveTab::default(),
             response_tab: ResponseTab::default(),
             is_dirty: false,
             collection_id: None,
             request_status: RequestStatus::default(),
+            undo: UndoStack::default(),
+            retry_at: None,
+            ws: None,
+            sse: None,
+            dry_run: None,
+            sent_environment: None,
+            env_override: None,
+            pinned: false,
         }
     }
 }
 
-/// Full in-memory workspace state.
+/// Size of a workspace, shown in the workspace switcher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkspaceSummary {
+    pub collections: usize,
+    pub requests: usize,
+}
+
+/// Request tabs shown in a second pane, right of the first
+/// (`Ctrl+Shift+Right`). The tabs themselves stay in `open_tabs`.
 #[derive(Debug, Clone, Default)]
+pub struct TabGroup {
+    /// Request ids of the right-hand pane's tabs
+    pub tab_ids: Vec<String>,
+    /// Request id of the tab shown by the pane without focus
+    pub other_shown: String,
+}
+
+/// Full in-memory workspace state.
+#[derive(Debug, Clone)]
 pub struct WorkspaceState {
     pub name: String,
     pub collections: Vec<Collection>,
@@ -48,4 +250,144 @@ pub struct WorkspaceState {
     pub active_environment_idx: Option<usize>,
     pub open_tabs: Vec<RequestTab>,
     pub active_tab_idx: usize,
+    /// Tabs split off into a right-hand pane, if the request area is split
+    pub split: Option<TabGroup>,
+    /// Line-number gutter in the body editor (persisted per workspace).
+    pub show_line_numbers: bool,
+    /// Append each sent request to the workspace audit log.
+    pub audit_log: bool,
+    pub audit_note: String,
+    /// Recent sends per collection request id (persisted in `history.toml`)
+    pub run_history: HashMap<String, Vec<RunRecord>>,
+    /// Whether each request passed in the last collection runner pass, by
+    /// collection request id (persisted in `runner.toml`)
+    pub runner_results: HashMap<String, bool>,
+    pub cookies: SharedJar,
+    pub persist_cookies: bool,
+    pub send_queue: bool,
+    pub queue_gap_ms: u64,
+    pub auto_retry: bool,
+    pub retry_max_secs: u64,
+    pub max_body_mb: u64,
+    pub proxy: ProxySettings,
+    /// Last response per collection request id, for
+    /// `{{response.<request>.…}}` references (in memory only)
+    pub last_responses: HashMap<String, Arc<ChainSource>>,
+}
+
+impl Default for WorkspaceState {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            collections: Vec::new(),
+            environments: Vec::new(),
+            active_environment_idx: None,
+            open_tabs: Vec::new(),
+            active_tab_idx: 0,
+            split: None,
+            show_line_numbers: true,
+            audit_log: false,
+            audit_note: String::new(),
+            run_history: HashMap::new(),
+            runner_results: HashMap::new(),
+            cookies: SharedJar::default(),
+            persist_cookies: true,
+            send_queue: false,
+            queue_gap_ms: default_queue_gap_ms(),
+            auto_retry: false,
+            retry_max_secs: default_retry_max_secs(),
+            max_body_mb: default_max_body_mb(),
+            proxy: ProxySettings::default(),
+            last_responses: HashMap::new(),
+        }
+    }
+}
+
+impl WorkspaceState {
+    /// Whether tab `idx` sits in the right-hand pane of a split.
+    pub fn in_right_pane(&self, idx: usize) -> bool {
+        let (Some(split), Some(tab)) = (&self.split, self.open_tabs.get(idx)) else {
+            return false;
+        };
+        split.tab_ids.contains(&tab.request.id)
+    }
+
+    /// Indices of the tabs in the same pane as tab `idx`.
+    pub fn pane_tabs(&self, idx: usize) -> Vec<usize> {
+        let right = self.in_right_pane(idx);
+        (0..self.open_tabs.len()).filter(|&i| self.in_right_pane(i) == right).collect()
+    }
+
+    /// Index of the tab shown by the pane without focus, or `None` when the
+    /// request area isn't split.
+    pub fn other_pane_tab(&self) -> Option<usize> {
+        let split = self.split.as_ref()?;
+        let right = !self.in_right_pane(self.active_tab_idx);
+        self.open_tabs
+            .iter()
+            .position(|t| t.request.id == split.other_shown)
+            .filter(|&i| self.in_right_pane(i) == right)
+            .or_else(|| (0..self.open_tabs.len()).find(|&i| self.in_right_pane(i) == right))
+    }
+
+    /// Make tab `idx` the active one. Moving into the other pane leaves the
+    /// pane being left showing the tab it had.
+    pub fn focus_tab(&mut self, idx: usize) {
+        let leaving = self.in_right_pane(self.active_tab_idx) != self.in_right_pane(idx);
+        let current = self.open_tabs.get(self.active_tab_idx).map(|t| t.request.id.clone());
+        if let (Some(split), Some(current), true) = (self.split.as_mut(), current, leaving) {
+            split.other_shown = current;
+        }
+        self.active_tab_idx = idx;
+    }
+
+    /// Drop the split once either pane has no tabs left.
+    pub fn normalize_split(&mut self) {
+        let Some(split) = &mut self.split else {
+            return;
+        };
+        let ids: Vec<&str> = self.open_tabs.iter().map(|t| t.request.id.as_str()).collect();
+        split.tab_ids.retain(|id| ids.contains(&id.as_str()));
+        if split.tab_ids.is_empty() || split.tab_ids.len() == ids.len() {
+            self.split = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(tabs: usize) -> WorkspaceState {
+        let mut ws = WorkspaceState::default();
+        for i in 0..tabs {
+            let mut tab = RequestTab::default();
+            tab.request.id = format!("t{i}");
+            ws.open_tabs.push(tab);
+        }
+        ws
+    }
+
+    #[test]
+    fn test_split_panes_and_focus() {
+        let mut ws = workspace(4);
+        assert_eq!(ws.pane_tabs(0), vec![0, 1, 2, 3]);
+        assert_eq!(ws.other_pane_tab(), None);
+
+        ws.split = Some(TabGroup { tab_ids: vec!["t1".into(), "t3".into()], other_shown: "t3".into() });
+        ws.active_tab_idx = 2;
+        assert_eq!(ws.pane_tabs(2), vec![0, 2]);
+        assert_eq!(ws.pane_tabs(3), vec![1, 3]);
+        assert_eq!(ws.other_pane_tab(), Some(3));
+
+        // Crossing panes remembers the tab the left pane showed
+        ws.focus_tab(1);
+        assert_eq!(ws.other_pane_tab(), Some(2));
+        ws.focus_tab(3);
+        assert_eq!(ws.other_pane_tab(), Some(2));
+
+        ws.open_tabs.retain(|t| t.request.id != "t1" && t.request.id != "t3");
+        ws.normalize_split();
+        assert!(ws.split.is_none());
+    }
 }
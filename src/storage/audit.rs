@@ -0,0 +1,114 @@
+// Per-workspace request audit log (JSON Lines, rotated by size)
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::state::audit::AuditEntry;
+
+/// Rotate `audit.jsonl` once it grows past this many bytes.
+const MAX_BYTES: u64 = 1024 * 1024;
+/// Rotated files kept alongside the live one (`audit.jsonl.1` is the newest).
+const KEEP_ROTATED: usize = 3;
+
+fn audit_path(ws_name: &str) -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge")
+        .join("workspaces")
+        .join(ws_name)
+        .join("audit.jsonl")
+}
+
+/// Append one entry to the workspace's audit log, rotating first if needed.
+pub fn append(ws_name: &str, entry: &AuditEntry) -> anyhow::Result<()> {
+    append_to(&audit_path(ws_name), entry, MAX_BYTES)
+}
+
+/// Load every entry of the workspace's audit log (rotated files included),
+/// newest first. Unparseable lines are skipped.
+pub fn load(ws_name: &str) -> Vec<AuditEntry> {
+    load_from(&audit_path(ws_name))
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+fn append_to(path: &Path, entry: &AuditEntry, max_bytes: u64) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size >= max_bytes {
+        let _ = std::fs::remove_file(rotated(path, KEEP_ROTATED));
+        for n in (1..KEEP_ROTATED).rev() {
+            let _ = std::fs::rename(rotated(path, n), rotated(path, n + 1));
+        }
+        std::fs::rename(path, rotated(path, 1))?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn load_from(path: &Path) -> Vec<AuditEntry> {
+    let files = std::iter::once(path.to_path_buf())
+        .chain((1..=KEEP_ROTATED).map(|n| rotated(path, n)));
+    let mut entries = Vec::new();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        entries.extend(
+            content
+                .lines()
+                .rev()
+                .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok()),
+        );
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> AuditEntry {
+        AuditEntry::new("ws", None, "GET", &format!("https://api.test{path}"), "", &[])
+    }
+
+    #[test]
+    fn test_append_and_load_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        append_to(&path, &entry("/a"), MAX_BYTES).unwrap();
+        append_to(&path, &entry("/b"), MAX_BYTES).unwrap();
+        let paths: Vec<String> = load_from(&path).into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["/b", "/a"]);
+    }
+
+    #[test]
+    fn test_rotation_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        // A tiny limit rotates before every append after the first
+        for p in ["/1", "/2", "/3", "/4", "/5", "/6"] {
+            append_to(&path, &entry(p), 1).unwrap();
+        }
+        assert!(rotated(&path, KEEP_ROTATED).exists());
+        assert!(!rotated(&path, KEEP_ROTATED + 1).exists());
+        let paths: Vec<String> = load_from(&path).into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["/6", "/5", "/4", "/3"]);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_from(&dir.path().join("audit.jsonl")).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use crate::state::collection::Collection;
+use crate::state::workspace::WorkspaceSummary;
 
 fn collections_dir(ws_name: &str) -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -27,6 +28,40 @@ pub fn load_all_collections(ws_name: &str) -> Vec<Collection> {
     collections
 }
 
+/// Count a workspace's collections and requests from the table headers of
+/// each `collection.toml`, without parsing them.
+pub fn scan_collections(ws_name: &str) -> WorkspaceSummary {
+    let mut summary = WorkspaceSummary::default();
+    let Ok(entries) = std::fs::read_dir(collections_dir(ws_name)) else {
+        return summary;
+    };
+    for entry in entries.flatten() {
+        if let Ok(content) = std::fs::read_to_string(entry.path().join("collection.toml")) {
+            summary.collections += 1;
+            summary.requests += count_requests(&content);
+        }
+    }
+    summary
+}
+
+/// Requests serialize as `[items.Request]` tables, nested as
+/// `[items.Folder.items.Request]` inside folders. Lines inside multi-line
+/// strings (request bodies) are skipped.
+fn count_requests(content: &str) -> usize {
+    let mut count = 0;
+    let mut in_string = false;
+    for line in content.lines() {
+        if !in_string && line.starts_with("[items.") && line.trim_end().ends_with(".Request]") {
+            count += 1;
+        }
+        let quotes = line.matches("\"\"\"").count() + line.matches("'''").count();
+        if quotes % 2 == 1 {
+            in_string = !in_string;
+        }
+    }
+    count
+}
+
 /// Save a collection's metadata to `<ws>/collections/<slug>/collection.toml`.
 pub fn save_collection_meta(ws_name: &str, col: &Collection) -> anyhow::Result<()> {
     let slug = col.name.to_lowercase().replace(' ', "_");
@@ -46,3 +81,26 @@ pub fn delete_collection(ws_name: &str, col_name: &str) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::collection::{CollectionItem, CollectionRequest, Folder};
+
+    #[test]
+    fn test_count_requests_matches_saved_collection() {
+        let mut folder = Folder::new("Users");
+        folder.items.push(CollectionItem::Request(CollectionRequest::new("List")));
+        folder.items.push(CollectionItem::Request(CollectionRequest::new("Get")));
+        let mut col = Collection::new("API");
+        col.items.push(CollectionItem::Request(CollectionRequest::new("Health")));
+        col.items.push(CollectionItem::Folder(folder));
+        let mut request = CollectionRequest::new("Raw");
+        // Body text that merely looks like a header doesn't count
+        request.body_raw = "line\n[items.Request]\n".into();
+        col.items.push(CollectionItem::Request(request));
+
+        let content = toml::to_string_pretty(&col).unwrap();
+        assert_eq!(count_requests(&content), 4);
+    }
+}
@@ -1 +1,27 @@
 // User configuration TOML persistence
+use std::path::PathBuf;
+
+use crate::state::config::Config;
+
+fn config_path() -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge").join("config.toml")
+}
+
+/// Load `config.toml`. Returns the default config on any error.
+pub fn load_config() -> Config {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the config to disk, creating the directory if needed.
+pub fn save_config(config: &Config) -> anyhow::Result<()> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, toml::to_string_pretty(config)?)?;
+    Ok(())
+}
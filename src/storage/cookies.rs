@@ -0,0 +1,38 @@
+// Workspace cookie jar TOML persistence
+use std::path::PathBuf;
+
+use crate::state::cookie_jar::CookieJar;
+
+fn cookies_path(ws_name: &str) -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge")
+        .join("workspaces")
+        .join(ws_name)
+        .join("cookies.toml")
+}
+
+/// Load a workspace's saved cookies. Empty on any error.
+pub fn load_cookies(ws_name: &str) -> CookieJar {
+    std::fs::read_to_string(cookies_path(ws_name))
+        .ok()
+        .and_then(|content| toml::from_str::<CookieJar>(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the jar, creating the directory if needed.
+pub fn save_cookies(ws_name: &str, jar: &CookieJar) -> anyhow::Result<()> {
+    let path = cookies_path(ws_name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, toml::to_string_pretty(jar)?)?;
+    Ok(())
+}
+
+/// Remove the saved jar (the workspace switched to session-only cookies).
+pub fn delete_cookies(ws_name: &str) -> anyhow::Result<()> {
+    match std::fs::remove_file(cookies_path(ws_name)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
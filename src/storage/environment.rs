@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::state::environment::Environment;
+use anyhow::Context;
+
+use crate::state::environment::{Environment, VarType};
 
 fn data_dir() -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -94,3 +96,80 @@ pub fn load_all_ws(ws_name: &str) -> Vec<Environment> {
     }
     envs
 }
+
+// ─── Export / import ─────────────────────────────────────────────────────────
+
+/// A path typed by the user, with a leading `~/` meaning the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+/// Write `env` to `path` as JSON when it ends in `.json`, TOML otherwise.
+/// `redact` blanks the values of secret variables.
+pub fn export(env: &Environment, path: &Path, redact: bool) -> anyhow::Result<()> {
+    let mut env = env.clone();
+    if redact {
+        for var in env.variables.iter_mut().filter(|v| v.var_type == VarType::Secret) {
+            var.value.clear();
+        }
+    }
+    let content = if is_json(path) {
+        serde_json::to_string_pretty(&env)?
+    } else {
+        toml::to_string_pretty(&env)?
+    };
+    std::fs::write(path, content).with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
+
+/// Read an environment exported by `export`, under a new id so it can't
+/// clash with one already in the workspace.
+pub fn import(path: &Path) -> anyhow::Result<Environment> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut env: Environment = if is_json(path) {
+        serde_json::from_str(&content)?
+    } else {
+        toml::from_str(&content)?
+    };
+    env.id = uuid::Uuid::new_v4().to_string();
+    Ok(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::environment::EnvVariable;
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut env = Environment { name: "staging".into(), ..Environment::default() };
+        env.variables.push(EnvVariable { key: "host".into(), value: "api.example.com".into(), ..EnvVariable::default() });
+        env.variables.push(EnvVariable {
+            key: "token".into(),
+            value: "s3cret".into(),
+            var_type: VarType::Secret,
+            ..EnvVariable::default()
+        });
+        let dir = std::env::temp_dir().join(format!("forge-env-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for (file, redact) in [("env.json", true), ("env.toml", false)] {
+            let path = dir.join(file);
+            export(&env, &path, redact).unwrap();
+            let back = import(&path).unwrap();
+            assert_ne!(back.id, env.id);
+            assert_eq!(back.name, "staging");
+            assert_eq!(back.variables[0].value, "api.example.com");
+            assert_eq!(back.variables[1].value, if redact { "" } else { "s3cret" });
+        }
+        assert!(import(&dir.join("missing.toml")).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
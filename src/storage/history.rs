@@ -1 +1,77 @@
 // Request history TOML persistence
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::history::RunRecord;
+
+/// `history.toml`: recent runs keyed by collection request id.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    #[serde(default)]
+    runs: HashMap<String, Vec<RunRecord>>,
+}
+
+fn history_path(ws_name: &str) -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge")
+        .join("workspaces")
+        .join(ws_name)
+        .join("history.toml")
+}
+
+/// Load recent runs per request for a workspace. Empty on any error.
+pub fn load_run_history(ws_name: &str) -> HashMap<String, Vec<RunRecord>> {
+    std::fs::read_to_string(history_path(ws_name))
+        .ok()
+        .and_then(|content| toml::from_str::<HistoryFile>(&content).ok())
+        .map(|file| file.runs)
+        .unwrap_or_default()
+}
+
+/// Persist recent runs per request, creating the directory if needed.
+pub fn save_run_history(
+    ws_name: &str,
+    runs: &HashMap<String, Vec<RunRecord>>,
+) -> anyhow::Result<()> {
+    let path = history_path(ws_name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file = HistoryFile { runs: runs.clone() };
+    std::fs::write(path, toml::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// `runner.toml`: whether each request passed in the last collection runner
+/// pass, keyed by collection request id.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RunnerFile {
+    #[serde(default)]
+    passed: HashMap<String, bool>,
+}
+
+fn runner_path(ws_name: &str) -> PathBuf {
+    history_path(ws_name).with_file_name("runner.toml")
+}
+
+/// Load the last runner pass's results for a workspace. Empty on any error.
+pub fn load_runner_results(ws_name: &str) -> HashMap<String, bool> {
+    std::fs::read_to_string(runner_path(ws_name))
+        .ok()
+        .and_then(|content| toml::from_str::<RunnerFile>(&content).ok())
+        .map(|file| file.passed)
+        .unwrap_or_default()
+}
+
+/// Persist the last runner pass's results, creating the directory if needed.
+pub fn save_runner_results(ws_name: &str, passed: &HashMap<String, bool>) -> anyhow::Result<()> {
+    let path = runner_path(ws_name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file = RunnerFile { passed: passed.clone() };
+    std::fs::write(path, toml::to_string_pretty(&file)?)?;
+    Ok(())
+}
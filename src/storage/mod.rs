@@ -3,3 +3,7 @@ pub mod collection;
 pub mod environment;
 pub mod history;
 pub mod config;
+pub mod audit;
+pub mod cookies;
+pub mod theme;
+pub mod state;
@@ -0,0 +1,37 @@
+// App-wide state shared by every workspace (`state.toml`): what forge
+// remembers between runs, as opposed to what the user configures
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharedState {
+    /// When each workspace was last opened, by name
+    pub last_opened: HashMap<String, DateTime<Utc>>,
+}
+
+fn state_path() -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge").join("state.toml")
+}
+
+/// Load `state.toml`. Returns the default state on any error.
+pub fn load_state() -> SharedState {
+    std::fs::read_to_string(state_path())
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the shared state, creating the directory if needed.
+pub fn save_state(state: &SharedState) -> anyhow::Result<()> {
+    let path = state_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, toml::to_string_pretty(state)?)?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+// Custom color theme (`theme.toml`) loading
+use std::path::PathBuf;
+
+use crate::ui::theme::Theme;
+
+pub fn theme_path() -> PathBuf {
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("forge").join("theme.toml")
+}
+
+/// Load `theme.toml`. `Ok(None)` when the file doesn't exist.
+pub fn load_theme() -> anyhow::Result<Option<Theme>> {
+    let path = theme_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(Some(Theme::from_toml(&content)?))
+}
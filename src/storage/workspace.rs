@@ -1,8 +1,13 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use crate::state::collection::remap_env_ids;
+use crate::state::cookie_jar::{CookieJar, SharedJar};
 use crate::state::workspace::{WorkspaceFile, WorkspaceState};
 use crate::storage::collection as col_storage;
+use crate::storage::cookies as cookie_storage;
 use crate::storage::environment as env_storage;
+use crate::storage::history as history_storage;
 
 fn workspaces_dir() -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -36,10 +41,7 @@ pub fn load_workspace(name: &str) -> WorkspaceFile {
             return ws;
         }
     }
-    WorkspaceFile {
-        name: name.to_string(),
-        active_environment_idx: None,
-    }
+    WorkspaceFile::new(name)
 }
 
 /// Persist the workspace file to disk, creating the directory if needed.
@@ -51,11 +53,110 @@ pub fn save_workspace(ws: &WorkspaceFile) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Load a `WorkspaceState` by name, including its collections and environments.
-/// Open tabs start empty — they are not persisted.
+/// Delete the workspace `name` with its collections, environments and
+/// history.
+pub fn delete_workspace(name: &str) -> anyhow::Result<()> {
+    let dir = workspaces_dir().join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// Rename workspace `old` to `new` by moving its directory. Fails if a
+/// workspace called `new` already exists.
+pub fn rename_workspace(old: &str, new: &str) -> anyhow::Result<()> {
+    let from = workspaces_dir().join(old);
+    let to = workspaces_dir().join(new);
+    if to.exists() {
+        anyhow::bail!("workspace \"{new}\" already exists");
+    }
+    // A workspace that was never saved has no directory yet
+    std::fs::create_dir_all(&from)?;
+    std::fs::rename(&from, &to)?;
+    let mut ws = load_workspace(new);
+    ws.name = new.to_string();
+    save_workspace(&ws)
+}
+
+/// Copy workspace `src` to `new`, giving its collections and environments
+/// fresh ids so the two never collide. Fails if a workspace called `new`
+/// already exists.
+pub fn duplicate_workspace(src: &str, new: &str) -> anyhow::Result<()> {
+    let to = workspaces_dir().join(new);
+    if to.exists() {
+        anyhow::bail!("workspace \"{new}\" already exists");
+    }
+    let result = copy_workspace(src, new, &to);
+    if result.is_err() {
+        // A half-made copy would hold the name from then on
+        let _ = std::fs::remove_dir_all(&to);
+    }
+    result
+}
+
+/// Copy workspace `src` to directory `to` as `new`, giving its collections
+/// and environments fresh ids.
+fn copy_workspace(src: &str, new: &str, to: &Path) -> anyhow::Result<()> {
+    let from = workspaces_dir().join(src);
+    if from.exists() {
+        copy_dir(&from, to)?;
+    }
+
+    let mut ws = load_workspace(new);
+    ws.name = new.to_string();
+    // The active environment is saved by position, which the renamed
+    // files below needn't keep; remember it by id instead
+    let active_env = ws
+        .active_environment_idx
+        .and_then(|i| env_storage::load_all_ws(src).get(i).map(|e| e.id.clone()));
+
+    let mut env_ids = HashMap::new();
+    for mut env in env_storage::load_all_ws(new) {
+        env_storage::delete_ws(new, &env.id)?;
+        let id = uuid::Uuid::new_v4().to_string();
+        env_ids.insert(std::mem::replace(&mut env.id, id), env.id.clone());
+        env_storage::save_ws(new, &env)?;
+    }
+    if let Some(id) = active_env.and_then(|old| env_ids.get(&old)) {
+        ws.active_environment_idx = env_storage::load_all_ws(new).iter().position(|e| e.id == *id);
+    }
+    for mut col in col_storage::load_all_collections(new) {
+        let id = uuid::Uuid::new_v4().to_string();
+        for ordered in ws.collection_order.iter_mut().filter(|o| **o == col.id) {
+            *ordered = id.clone();
+        }
+        col.id = id;
+        remap_env_ids(&mut col, &env_ids);
+        col_storage::save_collection_meta(new, &col)?;
+    }
+    save_workspace(&ws)
+}
+
+/// Copy the directory tree at `from` to `to`.
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Load a `WorkspaceState` by name, including its collections, environments
+/// and cookie jar.
+/// Open tabs start empty — only pinned ones are persisted, and the app
+/// reopens those.
 pub fn load_workspace_full(name: &str) -> WorkspaceState {
     let ws_file = load_workspace(name);
-    let collections = col_storage::load_all_collections(name);
+    let mut collections = col_storage::load_all_collections(name);
+    let order = &ws_file.collection_order;
+    collections.sort_by_key(|c| order.iter().position(|id| *id == c.id).unwrap_or(usize::MAX));
     let environments = env_storage::load_all_ws(name);
     let active_environment_idx = ws_file.active_environment_idx
         .filter(|&i| i < environments.len())
@@ -68,5 +169,24 @@ pub fn load_workspace_full(name: &str) -> WorkspaceState {
         active_environment_idx,
         open_tabs: Vec::new(),
         active_tab_idx: 0,
+        split: None,
+        show_line_numbers: ws_file.show_line_numbers,
+        audit_log: ws_file.audit_log,
+        audit_note: ws_file.audit_note,
+        run_history: history_storage::load_run_history(name),
+        runner_results: history_storage::load_runner_results(name),
+        cookies: SharedJar::new(if ws_file.persist_cookies {
+            cookie_storage::load_cookies(name)
+        } else {
+            CookieJar::default()
+        }),
+        persist_cookies: ws_file.persist_cookies,
+        send_queue: ws_file.send_queue,
+        queue_gap_ms: ws_file.queue_gap_ms,
+        auto_retry: ws_file.auto_retry,
+        retry_max_secs: ws_file.retry_max_secs,
+        max_body_mb: ws_file.max_body_mb,
+        proxy: ws_file.proxy,
+        last_responses: HashMap::new(),
     }
 }
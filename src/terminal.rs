@@ -1,22 +1,38 @@
 use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
 use crossterm::{
     execute,
-    terminal::{enable_raw_mode, disable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    event::{EnableMouseCapture, DisableMouseCapture},
+    terminal::{enable_raw_mode, disable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{
+        EnableMouseCapture, DisableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
+/// Whether `init` turned on the keyboard enhancement that `restore` undoes
+static ENHANCED_KEYS: AtomicBool = AtomicBool::new(false);
+
 pub fn init() -> io::Result<Tui> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // Terminals with the kitty keyboard protocol can then report Ctrl+Shift+P
+    // and Ctrl+Shift+D apart from Ctrl+P and Ctrl+D
+    if supports_keyboard_enhancement().unwrap_or(false) {
+        execute!(stdout, PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES))?;
+        ENHANCED_KEYS.store(true, Ordering::Relaxed);
+    }
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
 
 pub fn restore() -> io::Result<()> {
+    if ENHANCED_KEYS.swap(false, Ordering::Relaxed) {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
     Ok(())
@@ -0,0 +1,160 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let audit = &state.audit_log;
+    let enabled = state.workspace.audit_log;
+    let title = if enabled { " Audit Log (on) " } else { " Audit Log (off) " };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title)
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 4 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Filter row
+    let filter_line = Line::from(vec![
+        Span::styled("filter> ", Style::default().fg(theme.accent)),
+        Span::styled(audit.filter.clone(), Style::default().fg(theme.text)),
+        Span::styled(
+            if audit.filter.is_empty() { "host or YYYY-MM-DD" } else { "" },
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(filter_line), chunks[0]);
+
+    // Note row
+    let note = if audit.editing_note { &audit.note_input } else { &state.workspace.audit_note };
+    let note_line = Line::from(vec![
+        Span::styled("note> ", Style::default().fg(theme.accent)),
+        Span::styled(note.clone(), Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(Paragraph::new(note_line), chunks[1]);
+
+    let (row, prefix, text, cursor) = if audit.editing_note {
+        (chunks[1], 6, &audit.note_input, audit.note_cursor)
+    } else {
+        (chunks[0], 8, &audit.filter, audit.filter_cursor)
+    };
+    let col_offset = text[..cursor.min(text.len())].chars().count() as u16;
+    frame.set_cursor_position(Position { x: row.x + prefix + col_offset, y: row.y });
+
+    // Entries
+    let entries = audit.visible();
+    let list_area = chunks[2];
+    if entries.is_empty() {
+        let msg = if enabled {
+            "No matching entries"
+        } else {
+            "Audit logging is off for this workspace — Alt+e to enable"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                msg,
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+
+    let visible = list_area.height as usize;
+    let offset = audit.selected.saturating_sub(visible.saturating_sub(1));
+    for (i, entry) in entries.iter().enumerate().skip(offset) {
+        let y = list_area.y + (i - offset) as u16;
+        if y >= list_area.y + list_area.height {
+            break;
+        }
+        let base = if i == audit.selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let (status, status_color) = match entry.status {
+            Some(code) => (
+                code.to_string(),
+                match code {
+                    200..=299 => theme.success,
+                    300..=399 => theme.accent,
+                    400..=499 => theme.warning,
+                    500..=599 => theme.error,
+                    _ => theme.highlight,
+                },
+            ),
+            None => ("ERR".to_string(), theme.error),
+        };
+        let mut spans = vec![
+            Span::styled(
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S ").to_string(),
+                base.fg(theme.muted),
+            ),
+            Span::styled(format!("{:<7} ", entry.method), base.fg(theme.method_color(&entry.method))),
+            Span::styled(format!("{status:<4}"), base.fg(status_color)),
+            Span::styled(format!("{:>6}ms ", entry.duration_ms), base.fg(theme.muted)),
+            Span::styled(format!("{}{}", entry.host, entry.path), base),
+        ];
+        if entry.query_hash.is_some() {
+            spans.push(Span::styled("?…", base.fg(theme.muted)));
+        }
+        if let Some(env) = &entry.env {
+            spans.push(Span::styled(format!("  [{env}]"), base.fg(theme.muted)));
+        }
+        if !entry.note.is_empty() {
+            spans.push(Span::styled(format!("  {}", entry.note), base.fg(theme.muted)));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), Rect { y, height: 1, ..list_area });
+    }
+
+    // Hint bar
+    let hint = if audit.editing_note {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" save note  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("↑↓", Style::default().fg(theme.text)),
+            Span::styled(" move  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+e", Style::default().fg(theme.text)),
+            Span::styled(" toggle logging  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+n", Style::default().fg(theme.text)),
+            Span::styled(" note  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ])
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[3],
+    );
+}
@@ -0,0 +1,92 @@
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::theme::theme;
+
+/// Width of the field label column ("JSON path  ")
+const LABEL_W: u16 = 11;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let capture = &state.capture;
+    // Borders, two fields, the secret toggle, an error line and hints
+    let width = 64.min(area.width);
+    let height = 7.min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup_area);
+
+    let env_name = state.active_environment_name().unwrap_or("no environment");
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Capture to {env_name} "))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 5 {
+        return;
+    }
+
+    let field = |label: &str, value: &str, active: bool| {
+        let label_fg = if active { theme.highlight } else { theme.muted };
+        let placeholder = value.is_empty() && !active;
+        Line::from(vec![
+            Span::styled(format!("{:<w$}", label, w = LABEL_W as usize), Style::default().fg(label_fg)),
+            if placeholder {
+                Span::styled("—", Style::default().fg(theme.border))
+            } else {
+                Span::styled(value.to_string(), Style::default().fg(theme.text))
+            },
+        ])
+        .style(Style::default().bg(if active { theme.surface } else { theme.bg }))
+    };
+    let (check, check_fg) = if capture.secret { ("[✓]", theme.purple) } else { ("[ ]", theme.muted) };
+    let status = match &capture.error {
+        Some(err) => Line::from(Span::styled(err.clone(), Style::default().fg(theme.error))),
+        None => Line::default(),
+    };
+    let hint = Line::from(vec![
+        Span::styled("Tab", Style::default().fg(theme.text)),
+        Span::styled(" field  ", Style::default().fg(theme.muted)),
+        Span::styled("^S", Style::default().fg(theme.text)),
+        Span::styled(" secret  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" capture  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ])
+    .style(theme.hint_style());
+
+    let lines = vec![
+        field("JSON path", &capture.path, !capture.on_name),
+        field("Variable", &capture.name, capture.on_name),
+        Line::from(vec![
+            Span::styled(format!("{:<w$}", "", w = LABEL_W as usize), Style::default()),
+            Span::styled(check, Style::default().fg(check_fg).add_modifier(Modifier::BOLD)),
+            Span::styled(" secret", Style::default().fg(theme.text_dim)),
+        ]),
+        status,
+        hint,
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    let (text, cursor, row) = if capture.on_name {
+        (&capture.name, capture.name_cursor, 1)
+    } else {
+        (&capture.path, capture.path_cursor, 0)
+    };
+    let col = text[..cursor.min(text.len())].chars().count() as u16;
+    frame.set_cursor_position(Position { x: inner.x + LABEL_W + col, y: inner.y + row });
+}
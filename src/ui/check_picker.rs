@@ -0,0 +1,108 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let picker = &state.check_picker;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Copy as Test ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Status check — always generated
+    let status_line = Line::from(vec![
+        Span::styled("[✓] ", Style::default().fg(theme.success)),
+        Span::styled("status == ", Style::default().fg(theme.muted)),
+        Span::styled(picker.status.to_string(), Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(Paragraph::new(status_line), chunks[0]);
+
+    // Field checklist
+    let list_area = chunks[1];
+    if picker.candidates.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Body is not JSON — only the status check will be added",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+
+    let visible = list_area.height as usize;
+    let offset = picker.selected.saturating_sub(visible.saturating_sub(1));
+    for (row, candidate) in picker.candidates.iter().enumerate().skip(offset) {
+        let y = list_area.y + (row - offset) as u16;
+        if y >= list_area.y + list_area.height {
+            break;
+        }
+        let checked = picker.checked.get(row).copied().unwrap_or(false);
+        let (box_str, box_fg) = if checked {
+            ("[✓] ", theme.success)
+        } else {
+            ("[ ] ", theme.muted)
+        };
+        let is_selected = row == picker.selected;
+        let path_style = if is_selected {
+            Style::default()
+                .fg(theme.highlight)
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let row_area = Rect { y, height: 1, ..list_area };
+        let line = Line::from(vec![
+            Span::styled(box_str, Style::default().fg(box_fg)),
+            Span::styled(candidate.path.clone(), path_style),
+            Span::styled("  ", Style::default()),
+            Span::styled(candidate.value.clone(), Style::default().fg(theme.muted)),
+        ]);
+        frame.render_widget(Paragraph::new(line), row_area);
+    }
+
+    // Hint bar
+    let hint = Line::from(vec![
+        Span::styled("Space", Style::default().fg(theme.text)),
+        Span::styled(" toggle  ", Style::default().fg(theme.muted)),
+        Span::styled("a", Style::default().fg(theme.text)),
+        Span::styled(" all  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" generate  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[2],
+    );
+}
@@ -1 +1,124 @@
 // Command palette overlay
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::actions::palette::{palette_matches, theme_command};
+use crate::state::app_state::AppState;
+use crate::state::config::ThemeKind;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Command Palette ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Search row
+    let palette = &state.command_palette;
+    let search_line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(palette.query.clone(), Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(Paragraph::new(search_line), chunks[0]);
+    let col_offset = palette.query[..palette.cursor.min(palette.query.len())]
+        .chars()
+        .count() as u16;
+    frame.set_cursor_position(Position {
+        x: chunks[0].x + 2 + col_offset,
+        y: chunks[0].y,
+    });
+
+    // Hint bar
+    let hint = Line::from(vec![
+        Span::styled("↑↓/C-j C-k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" run  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[2],
+    );
+
+    // `theme <name>` shows the theme Enter switches to instead of actions
+    let list_area = chunks[1];
+    if let Some(name) = theme_command(&palette.query) {
+        let line = match ThemeKind::from_name(name) {
+            Some(kind) => Line::from(vec![
+                Span::styled("Switch theme to ", Style::default().fg(theme.text)),
+                Span::styled(kind.label(), Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)),
+            ]),
+            None => Line::from(Span::styled(
+                "Unknown theme — auto, tokyo-night, light, high-contrast or custom",
+                Style::default().fg(theme.warning),
+            )),
+        };
+        frame.render_widget(Paragraph::new(line), list_area);
+        return;
+    }
+
+    // Results: label with matched chars highlighted, keybinding right-aligned
+    let matches = palette_matches(&palette.query);
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No matching actions",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+    let visible = list_area.height as usize;
+    let offset = palette.selected.saturating_sub(visible.saturating_sub(1));
+    for (row, (action, matched)) in matches.iter().enumerate().skip(offset).take(visible) {
+        let y = list_area.y + (row - offset) as u16;
+        let base = if row == palette.selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let label = action.label();
+        let keys = action.keys();
+        let mut spans: Vec<Span> = label
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                let style = if matched.contains(&i) { base.fg(theme.orange) } else { base };
+                Span::styled(ch.to_string(), style)
+            })
+            .collect();
+        let gap = (list_area.width as usize)
+            .saturating_sub(label.chars().count() + keys.chars().count());
+        spans.push(Span::styled(" ".repeat(gap), base));
+        spans.push(Span::styled(keys, base.fg(theme.muted)));
+        frame.render_widget(Paragraph::new(Line::from(spans)), Rect { y, height: 1, ..list_area });
+    }
+}
@@ -0,0 +1,75 @@
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::theme::theme;
+
+const MAX_ROWS: usize = 8;
+const LOCK: &str = "🔒";
+
+/// Floating `{{variable}}` list anchored just below `cursor`, or above it
+/// when there is no room underneath.
+pub fn render(frame: &mut Frame, cursor: Position, state: &AppState) {
+    let theme = theme(state);
+    let completion = &state.completion;
+    if !completion.active || completion.items.is_empty() {
+        return;
+    }
+    let screen = frame.area();
+
+    let rows = completion.items.len().min(MAX_ROWS);
+    let label_w = completion
+        .items
+        .iter()
+        .map(|i| i.key.chars().count() + if i.secret { 3 } else { 0 })
+        .max()
+        .unwrap_or(0);
+    let width = ((label_w + 4) as u16).clamp(16, 48).min(screen.width);
+    let height = rows as u16 + 2;
+
+    let below = cursor.y + 1;
+    let y = if below + height <= screen.y + screen.height {
+        below
+    } else {
+        cursor.y.saturating_sub(height)
+    };
+    let x = cursor.x.saturating_sub(1).min(screen.x + screen.width - width);
+    let area = Rect { x, y, width, height: height.min(screen.height) };
+
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Keep the selection in view
+    let offset = completion.selected.saturating_sub(rows - 1);
+    for (row, item) in completion.items.iter().enumerate().skip(offset).take(rows) {
+        let is_selected = row == completion.selected;
+        let style = if is_selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let mut spans = vec![Span::styled(item.key.clone(), style)];
+        if item.secret {
+            spans.push(Span::styled(format!(" {LOCK}"), Style::default().fg(theme.muted)));
+        }
+        let row_area = Rect { y: inner.y + (row - offset) as u16, height: 1, ..inner };
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(if is_selected {
+                Style::default().bg(theme.surface)
+            } else {
+                Style::default()
+            }),
+            row_area,
+        );
+    }
+}
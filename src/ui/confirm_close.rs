@@ -0,0 +1,76 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(40, 20, area);
+    let popup_area = Rect {
+        height: popup_area.height.max(6),
+        ..popup_area
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .title(" Unsaved Changes ")
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let name = &state.confirm_close.name;
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Save changes to ", Style::default().fg(theme.text)),
+            Span::styled(name.as_str(), Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            Span::styled("?", Style::default().fg(theme.text)),
+        ]))
+        .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            "─".repeat(inner.width as usize),
+            Style::default().fg(theme.muted),
+        ))),
+        chunks[1],
+    );
+
+    let hint = Line::from(vec![
+        Span::styled("y/Enter", Style::default().fg(theme.accent)),
+        Span::styled(" Save  ", Style::default().fg(theme.muted)),
+        Span::styled("n", Style::default().fg(theme.error)),
+        Span::styled(" Discard  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" Cancel", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[2],
+    );
+}
@@ -1,20 +1,17 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use crate::state::app_state::AppState;
 use crate::ui::popup::centered_rect;
-
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
-const BG: Color = Color::Rgb(26, 27, 38);
-const STATUS_ERR: Color = Color::Rgb(247, 118, 142);
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let popup_area = centered_rect(40, 20, area);
     let popup_area = Rect {
         height: popup_area.height.min(5).max(5),
@@ -25,9 +22,9 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(STATUS_ERR))
+        .border_style(Style::default().fg(theme.error))
         .title(" Confirm Delete ")
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme.bg));
 
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
@@ -50,7 +47,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(
         Paragraph::new(Line::from(Span::styled(
             msg.as_str(),
-            Style::default().fg(TEXT_PRIMARY),
+            Style::default().fg(theme.text),
         ))),
         chunks[0],
     );
@@ -59,20 +56,20 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(
         Paragraph::new(Line::from(Span::styled(
             "─".repeat(inner.width as usize),
-            Style::default().fg(TEXT_MUTED),
+            Style::default().fg(theme.muted),
         ))),
         chunks[1],
     );
 
     // Footer hints
     let hint = Line::from(vec![
-        Span::styled("y/Enter", Style::default().fg(STATUS_ERR)),
-        Span::styled(" Delete  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("n/Esc", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" Cancel", Style::default().fg(TEXT_MUTED)),
+        Span::styled("y/Enter", Style::default().fg(theme.error)),
+        Span::styled(" Delete  ", Style::default().fg(theme.muted)),
+        Span::styled("n/Esc", Style::default().fg(theme.text)),
+        Span::styled(" Cancel", Style::default().fg(theme.muted)),
     ]);
     frame.render_widget(
-        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        Paragraph::new(hint).style(theme.hint_style()),
         chunks[2],
     );
 }
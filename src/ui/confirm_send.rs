@@ -0,0 +1,84 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(50, 20, area);
+    let popup_area = Rect {
+        height: popup_area.height.max(7),
+        ..popup_area
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .title(" Protected Environment ")
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let confirm = &state.confirm_send;
+    let mut lines = vec![Line::from(Span::styled(
+        format!("Send to {}?", confirm.environment),
+        Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+    ))];
+    let mut target = Vec::new();
+    if !confirm.method.is_empty() {
+        target.push(Span::styled(
+            format!("{} ", confirm.method),
+            Style::default().fg(theme.method_color(&confirm.method)).add_modifier(Modifier::BOLD),
+        ));
+    }
+    target.push(Span::styled(confirm.target.as_str(), Style::default().fg(theme.text)));
+    lines.push(Line::from(target));
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            "─".repeat(inner.width as usize),
+            Style::default().fg(theme.muted),
+        ))),
+        chunks[1],
+    );
+
+    let hint = Line::from(vec![
+        Span::styled("y/Enter", Style::default().fg(theme.accent)),
+        Span::styled(" Send  ", Style::default().fg(theme.muted)),
+        Span::styled("n/Esc", Style::default().fg(theme.text)),
+        Span::styled(" Cancel  ", Style::default().fg(theme.muted)),
+        Span::styled("m", Style::default().fg(theme.text)),
+        Span::styled(
+            if confirm.skip_minute { " [x] don't ask for a minute" } else { " [ ] don't ask for a minute" },
+            Style::default().fg(theme.muted),
+        ),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[2],
+    );
+}
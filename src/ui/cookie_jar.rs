@@ -0,0 +1,106 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let persist = if state.workspace.persist_cookies { "saved" } else { "session only" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Cookies — {} ({persist}) ", state.workspace.name))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let jar = state.workspace.cookies.read();
+    let cookies = jar.sorted();
+    if cookies.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "The jar is empty — cookies set by responses show up here",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            chunks[0],
+        );
+    }
+
+    // One header line per domain, then its cookies
+    let selected = state.cookie_jar.selected;
+    let name_w = cookies.iter().map(|c| c.name.chars().count()).max().unwrap_or(0).max(4);
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (i, cookie) in cookies.iter().enumerate() {
+        if i == 0 || cookies[i - 1].domain != cookie.domain {
+            let domain = if cookie.host_only {
+                cookie.domain.clone()
+            } else {
+                format!("{} (and subdomains)", cookie.domain)
+            };
+            lines.push(Line::from(Span::styled(
+                domain,
+                Style::default().fg(theme.purple).add_modifier(Modifier::BOLD),
+            )));
+        }
+        if i == selected {
+            selected_line = lines.len();
+        }
+        let base = if i == selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let expires = cookie.expires.map_or("session".to_string(), |at| {
+            at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()
+        });
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<name_w$}  ", cookie.name), base.fg(theme.accent)),
+            Span::styled(format!("{:<6}", cookie.path), base.fg(theme.muted)),
+            Span::styled(format!("{expires:<17}"), base.fg(theme.muted)),
+            Span::styled(cookie.value.clone(), base),
+        ]));
+    }
+    let visible = chunks[0].height as usize;
+    let offset = (selected_line + 1).saturating_sub(visible) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("d", Style::default().fg(theme.text)),
+        Span::styled(" delete  ", Style::default().fg(theme.muted)),
+        Span::styled("D", Style::default().fg(theme.text)),
+        Span::styled(" clear jar  ", Style::default().fg(theme.muted)),
+        Span::styled("p", Style::default().fg(theme.text)),
+        Span::styled(
+            if state.workspace.persist_cookies { " session only  " } else { " save to disk  " },
+            Style::default().fg(theme.muted),
+        ),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[1],
+    );
+}
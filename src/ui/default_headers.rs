@@ -0,0 +1,135 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+/// Columns before the key: the enabled checkbox
+const CHECK_W: usize = 5;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup = &state.default_headers;
+    let popup_area = centered_rect(70, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Default headers — {} ", popup.title))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    if popup.headers.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No default headers — a adds one; requests inside send it unless they set the same header",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            chunks[0],
+        );
+    }
+
+    let key_w = popup
+        .headers
+        .iter()
+        .map(|h| h.key.chars().count())
+        .chain(popup.editing.iter().filter(|_| popup.col == 0).map(|(t, _)| t.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max(12);
+    let mut cursor = None;
+    let lines: Vec<Line> = popup
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let selected = i == popup.selected;
+            let row_bg = if selected { theme.surface } else { theme.bg };
+            let cell = |col: u8, text: &str| {
+                let active = selected && popup.col == col;
+                let fg = if !header.enabled {
+                    theme.muted
+                } else if active {
+                    theme.highlight
+                } else {
+                    theme.text
+                };
+                let mut style = Style::default().fg(fg).bg(row_bg);
+                if active {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                let text = match &popup.editing {
+                    Some((edit, _)) if active => edit.clone(),
+                    _ => text.to_string(),
+                };
+                (text, style)
+            };
+            if selected && let Some((text, at)) = &popup.editing {
+                let col = text[..*at].chars().count();
+                let x = if popup.col == 0 { CHECK_W + col } else { CHECK_W + key_w + 2 + col };
+                cursor = Some((i, x));
+            }
+            let (check, check_fg) = if header.enabled { ("[✓]", theme.success) } else { ("[ ]", theme.muted) };
+            let (key, key_style) = cell(0, &header.key);
+            let (value, value_style) = cell(1, &header.value);
+            Line::from(vec![
+                Span::styled(format!(" {check} "), Style::default().fg(check_fg).bg(row_bg)),
+                Span::styled(format!("{key:<key_w$}  "), key_style),
+                Span::styled(value, value_style),
+            ])
+        })
+        .collect();
+    let offset = (popup.selected + 1).saturating_sub(chunks[0].height as usize);
+    frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[0]);
+
+    if let Some((row, x)) = cursor {
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + x as u16,
+            y: chunks[0].y + (row - offset) as u16,
+        });
+    }
+
+    let hint = if popup.editing.is_some() {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" done  ", Style::default().fg(theme.muted)),
+            Span::styled("Tab", Style::default().fg(theme.text)),
+            Span::styled(" next cell  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("a", Style::default().fg(theme.text)),
+            Span::styled(" add  ", Style::default().fg(theme.muted)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" edit  ", Style::default().fg(theme.muted)),
+            Span::styled("Tab", Style::default().fg(theme.text)),
+            Span::styled(" key/value  ", Style::default().fg(theme.muted)),
+            Span::styled("Space", Style::default().fg(theme.text)),
+            Span::styled(" toggle  ", Style::default().fg(theme.muted)),
+            Span::styled("d", Style::default().fg(theme.text)),
+            Span::styled(" delete  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[1]);
+}
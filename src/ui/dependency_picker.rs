@@ -0,0 +1,95 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::request_finder::{render_entries, request_matches, FinderEntry};
+use crate::ui::theme::theme;
+
+/// Requests the picked request may depend on: every match except itself.
+pub fn dependency_candidates(state: &AppState) -> Vec<FinderEntry> {
+    let picker = &state.dependency_picker;
+    request_matches(state, &picker.query)
+        .into_iter()
+        .filter(|e| e.id != picker.request_id)
+        .collect()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let picker = &state.dependency_picker;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" {} depends on ", picker.request_name))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 4 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Search row
+    let search_line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(picker.query.clone(), Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(Paragraph::new(search_line), chunks[0]);
+    let col_offset = picker.query[..picker.cursor.min(picker.query.len())]
+        .chars()
+        .count() as u16;
+    frame.set_cursor_position(Position {
+        x: chunks[0].x + 2 + col_offset,
+        y: chunks[0].y,
+    });
+
+    // "(none)" clears the dependency
+    let none_style = if picker.selected == 0 {
+        Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled("(none)", none_style))),
+        chunks[1],
+    );
+
+    let entries = dependency_candidates(state);
+    render_entries(frame, theme, chunks[2], &entries, picker.selected.checked_sub(1));
+
+    // Hint bar, or the cycle error
+    let hint = match &picker.error {
+        Some(err) => Line::from(Span::styled(err.clone(), Style::default().fg(theme.error))),
+        None => Line::from(vec![
+            Span::styled("↑↓/C-j C-k", Style::default().fg(theme.text)),
+            Span::styled(" move  ", Style::default().fg(theme.muted)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" set  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]),
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[3],
+    );
+}
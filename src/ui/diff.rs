@@ -0,0 +1,205 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+/// Largest LCS table diffed line by line; beyond it the differing middle
+/// shows as removed then added.
+const MAX_TABLE: usize = 4_000_000;
+
+/// One row of a line diff from `a` to `b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    /// Only in `a`
+    Removed(String),
+    /// Only in `b`
+    Added(String),
+}
+
+/// Line diff of `a` to `b` by longest common subsequence. The common
+/// prefix and suffix are matched first so only the middle needs a table.
+pub fn lcs_diff(a: &[&str], b: &[&str]) -> Vec<DiffLine> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut out: Vec<DiffLine> = a[..prefix].iter().map(|l| DiffLine::Same(l.to_string())).collect();
+    let (n, m) = (mid_a.len(), mid_b.len());
+    if (n + 1) * (m + 1) > MAX_TABLE {
+        out.extend(mid_a.iter().map(|l| DiffLine::Removed(l.to_string())));
+        out.extend(mid_b.iter().map(|l| DiffLine::Added(l.to_string())));
+    } else {
+        // table[i][j]: LCS length of mid_a[i..] and mid_b[j..]
+        let width = m + 1;
+        let mut table = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                table[i * width + j] = if mid_a[i] == mid_b[j] {
+                    table[(i + 1) * width + j + 1] + 1
+                } else {
+                    table[(i + 1) * width + j].max(table[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if mid_a[i] == mid_b[j] {
+                out.push(DiffLine::Same(mid_a[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+                out.push(DiffLine::Removed(mid_a[i].to_string()));
+                i += 1;
+            } else {
+                out.push(DiffLine::Added(mid_b[j].to_string()));
+                j += 1;
+            }
+        }
+        out.extend(mid_a[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+        out.extend(mid_b[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    }
+    out.extend(a[a.len() - suffix..].iter().map(|l| DiffLine::Same(l.to_string())));
+    out
+}
+
+/// The response diff popup: a picker of tabs to compare with, then the two
+/// bodies side by side, the other tab's on the left.
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let diff = &state.diff;
+    let title = match &diff.lines {
+        Some(_) => format!(" Diff: {} ↔ {} ", diff.other_name, diff.current_name),
+        None => " Compare Response With ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title)
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let hint = match &diff.lines {
+        Some(lines) => {
+            frame.render_widget(Paragraph::new(side_by_side(lines, chunks[0], diff.scroll, state)), chunks[0]);
+            let changed = lines.iter().filter(|l| !matches!(l, DiffLine::Same(_))).count();
+            Line::from(vec![
+                Span::styled("j/k", Style::default().fg(theme.text)),
+                Span::styled(" scroll  ", Style::default().fg(theme.muted)),
+                Span::styled("Esc", Style::default().fg(theme.text)),
+                Span::styled(" back  ", Style::default().fg(theme.muted)),
+                Span::styled(format!("{changed} changed lines"), Style::default().fg(theme.muted)),
+            ])
+        }
+        None => {
+            let items: Vec<ListItem> = diff
+                .candidates
+                .iter()
+                .map(|(_, name)| ListItem::new(Line::from(Span::styled(name.clone(), Style::default().fg(theme.text)))))
+                .collect();
+            let list = List::new(items)
+                .highlight_style(Style::default().fg(theme.cursor_fg).bg(theme.cursor_bg).add_modifier(Modifier::BOLD));
+            let mut list_state = ListState::default().with_selected(Some(diff.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut list_state);
+            Line::from(vec![
+                Span::styled("j/k", Style::default().fg(theme.text)),
+                Span::styled(" move  ", Style::default().fg(theme.muted)),
+                Span::styled("Enter", Style::default().fg(theme.text)),
+                Span::styled(" compare  ", Style::default().fg(theme.muted)),
+                Span::styled("Esc", Style::default().fg(theme.text)),
+                Span::styled(" close", Style::default().fg(theme.muted)),
+            ])
+        }
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[1]);
+}
+
+/// Diff rows from `scroll` as two columns: removed lines in red on the
+/// left, added lines in green on the right, shared lines on both.
+fn side_by_side(lines: &[DiffLine], area: Rect, scroll: usize, state: &AppState) -> Vec<Line<'static>> {
+    let theme = theme(state);
+    let half = (area.width as usize).saturating_sub(3) / 2;
+    let cell = |text: &str| {
+        let mut cell: String = text.chars().take(half).collect();
+        let pad = half.saturating_sub(cell.chars().count());
+        cell.extend(std::iter::repeat_n(' ', pad));
+        cell
+    };
+    let separator = Span::styled(" │ ", Style::default().fg(theme.border));
+    lines
+        .iter()
+        .skip(scroll)
+        .take(area.height as usize)
+        .map(|line| {
+            let (left, right) = match line {
+                DiffLine::Same(text) => (
+                    Span::styled(cell(text), Style::default().fg(theme.text)),
+                    Span::styled(cell(text), Style::default().fg(theme.text)),
+                ),
+                DiffLine::Removed(text) => {
+                    (Span::styled(cell(text), Style::default().fg(theme.error)), Span::raw(cell("")))
+                }
+                DiffLine::Added(text) => {
+                    (Span::raw(cell("")), Span::styled(cell(text), Style::default().fg(theme.success)))
+                }
+            };
+            Line::from(vec![left, separator.clone(), right])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcs_diff_marks_changed_lines() {
+        let a = ["{", "  \"id\": 1,", "  \"name\": \"a\"", "}"];
+        let b = ["{", "  \"id\": 2,", "  \"name\": \"a\"", "  \"new\": true", "}"];
+        assert_eq!(
+            lcs_diff(&a, &b),
+            vec![
+                DiffLine::Same("{".into()),
+                DiffLine::Removed("  \"id\": 1,".into()),
+                DiffLine::Added("  \"id\": 2,".into()),
+                DiffLine::Same("  \"name\": \"a\"".into()),
+                DiffLine::Added("  \"new\": true".into()),
+                DiffLine::Same("}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lcs_diff_edges() {
+        assert!(lcs_diff(&[], &[]).is_empty());
+        assert_eq!(lcs_diff(&["x"], &[]), vec![DiffLine::Removed("x".into())]);
+        assert_eq!(lcs_diff(&["a", "a"], &["a"]), vec![DiffLine::Same("a".into()), DiffLine::Removed("a".into())]);
+        let same = ["a", "b", "c"];
+        assert!(lcs_diff(&same, &same).iter().all(|l| matches!(l, DiffLine::Same(_))));
+    }
+}
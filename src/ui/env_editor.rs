@@ -1,31 +1,31 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Position, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use crate::state::app_state::AppState;
-use crate::state::environment::VarType;
+use crate::state::app_state::{AppState, EnvFilePrompt};
+use crate::state::environment::{filter_variables, Environment, VarType};
 use crate::ui::popup::centered_rect;
-use crate::ui::layout::ACCENT_BLUE;
-
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
-const SURFACE: Color = Color::Rgb(36, 40, 59);
-const BG: Color = Color::Rgb(26, 27, 38);
+use crate::ui::theme::theme;
 
 /// Render the environment switcher popup (~50% wide × 40% tall).
 pub fn render_switcher(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let popup_area = centered_rect(50, 40, area);
     frame.render_widget(Clear, popup_area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(ACCENT_BLUE))
-        .title(" Environments (Ctrl+E) ")
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme.accent))
+        .title(if state.env_switcher.for_tab {
+            " Environment for this tab (Ctrl+E) "
+        } else {
+            " Environments (Ctrl+E) "
+        })
+        .style(Style::default().bg(theme.bg));
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
@@ -46,22 +46,30 @@ pub fn render_switcher(frame: &mut Frame, area: Rect, state: &AppState) {
     // Search / naming row
     if state.env_switcher.naming {
         let new_name = &state.env_switcher.new_name;
+        let label = match state.env_switcher.file_prompt {
+            Some(EnvFilePrompt::Export { .. }) => "Export to: ",
+            Some(EnvFilePrompt::Import) => "Import from: ",
+            None => "Name: ",
+        };
         let name_line = Line::from(vec![
-            Span::styled("Name: ", Style::default().fg(TEXT_MUTED)),
-            Span::styled(new_name.clone(), Style::default().fg(TEXT_PRIMARY)),
+            Span::styled(label, Style::default().fg(theme.muted)),
+            Span::styled(new_name.clone(), Style::default().fg(theme.text)),
         ]);
         frame.render_widget(Paragraph::new(name_line), chunks[0]);
         let col_offset = new_name[..state.env_switcher.new_name_cursor.min(new_name.len())]
             .chars()
             .count() as u16;
-        frame.set_cursor_position(Position { x: chunks[0].x + 6 + col_offset, y: chunks[0].y });
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + label.len() as u16 + col_offset,
+            y: chunks[0].y,
+        });
     } else {
         let search = &state.env_switcher.search;
         let search_line = if search.is_empty() {
-            Line::from(Span::styled("Search…", Style::default().fg(TEXT_MUTED)))
+            Line::from(Span::styled("Search…", Style::default().fg(theme.muted)))
         } else {
             Line::from(vec![
-                Span::styled("/ ", Style::default().fg(ACCENT_BLUE)),
+                Span::styled("/ ", Style::default().fg(theme.accent)),
                 Span::raw(search.clone()),
             ])
         };
@@ -70,80 +78,115 @@ pub fn render_switcher(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // Filtered environment list
     let filter = state.env_switcher.search.to_lowercase();
-    let envs_filtered: Vec<(usize, &str)> = state
+    let envs_filtered: Vec<(usize, &Environment)> = state
         .workspace
         .environments
         .iter()
         .enumerate()
         .filter(|(_, e)| filter.is_empty() || e.name.to_lowercase().contains(&filter))
-        .map(|(i, e)| (i, e.name.as_str()))
         .collect();
 
     let list_area = chunks[1];
-    for (row, &(orig_idx, name)) in envs_filtered.iter().enumerate() {
+    for (row, &(orig_idx, env)) in envs_filtered.iter().enumerate() {
         let y = list_area.y + row as u16;
         if y >= list_area.y + list_area.height {
             break;
         }
-        let is_active = state.workspace.active_environment_idx == Some(orig_idx);
+        let is_active = if state.env_switcher.for_tab {
+            state.active_environment_idx() == Some(orig_idx)
+        } else {
+            state.workspace.active_environment_idx == Some(orig_idx)
+        };
         let is_selected = row == state.env_switcher.selected;
         let marker = if is_active { "● " } else { "○ " };
-        let marker_color = if is_active { Color::Rgb(158, 206, 106) } else { TEXT_MUTED };
+        let marker_color = if is_active { theme.success } else { theme.muted };
+        // Names keep their environment's color, so prod and dev stand apart
         let name_style = if is_selected {
-            Style::default().fg(Color::White).bg(SURFACE).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.env_color(env)).bg(theme.surface).add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(TEXT_PRIMARY)
+            Style::default().fg(theme.env_color(env))
         };
         let row_area = Rect { y, height: 1, ..list_area };
         let line = Line::from(vec![
             Span::styled(marker, Style::default().fg(marker_color)),
-            Span::styled(name, name_style),
+            Span::styled(env.name.as_str(), name_style),
         ]);
         frame.render_widget(Paragraph::new(line), row_area);
     }
 
     // Hint bar
-    let hint = if state.env_switcher.naming {
+    let hint = if let Some(EnvFilePrompt::Export { redact, .. }) = state.env_switcher.file_prompt {
         Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" confirm  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" cancel", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" export  ", Style::default().fg(theme.muted)),
+            Span::styled("Tab", Style::default().fg(theme.text)),
+            Span::styled(" secrets: ", Style::default().fg(theme.muted)),
+            if redact {
+                Span::styled("redacted  ", Style::default().fg(theme.success))
+            } else {
+                Span::styled("included  ", Style::default().fg(theme.warning))
+            },
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ])
-    } else {
+    } else if state.env_switcher.naming {
         Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" select  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Alt+e", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" edit  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Alt+n", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" new  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Alt+d", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" del  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" close", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" confirm  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ])
+    } else {
+        let mut spans = vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" select  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+e", Style::default().fg(theme.text)),
+            Span::styled(" edit  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+n", Style::default().fg(theme.text)),
+            Span::styled(" new  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+d", Style::default().fg(theme.text)),
+            Span::styled(" del  ", Style::default().fg(theme.muted)),
+        ];
+        if state.env_switcher.for_tab {
+            spans.push(Span::styled("Alt+c", Style::default().fg(theme.text)));
+            spans.push(Span::styled(" workspace env  ", Style::default().fg(theme.muted)));
+        }
+        spans.extend([
+            Span::styled("Alt+x/i", Style::default().fg(theme.text)),
+            Span::styled(" export/import  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]);
+        Line::from(spans)
     };
     frame.render_widget(
-        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        Paragraph::new(hint).style(theme.hint_style()),
         chunks[2],
     );
 }
 
 /// Render the full environment editor popup (~70% wide × 70% tall).
 pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let popup_area = centered_rect(70, 70, area);
     frame.render_widget(Clear, popup_area);
 
     let env = state.workspace.environments.get(state.env_editor.env_idx);
     let env_name = env.map(|e| e.name.as_str()).unwrap_or("(none)");
 
-    let title = format!(" Environment: {} ", env_name);
+    let editor = &state.env_editor;
+    let shown = env.map(|e| filter_variables(&e.variables, &editor.search)).unwrap_or_default();
+    let title = if editor.search.is_empty() {
+        format!(" Environment: {} ", env_name)
+    } else {
+        let total = env.map_or(0, |e| e.variables.len());
+        format!(" Environment: {} · {}/{} shown ", env_name, shown.len(), total)
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(ACCENT_BLUE))
+        .border_style(Style::default().fg(theme.accent))
         .title(title)
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme.bg));
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
@@ -164,15 +207,33 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // Name row
     let name_style = if state.env_editor.editing_name {
-        Style::default().fg(Color::White).add_modifier(Modifier::UNDERLINED)
+        Style::default().fg(theme.highlight).add_modifier(Modifier::UNDERLINED)
     } else {
-        Style::default().fg(TEXT_PRIMARY)
+        Style::default().fg(theme.text)
     };
-    let name_line = Line::from(vec![
-        Span::styled("  Name: ", Style::default().fg(TEXT_MUTED)),
+    let mut name_spans = vec![
+        Span::styled("  Name: ", Style::default().fg(theme.muted)),
         Span::styled(env_name, name_style),
-    ]);
-    frame.render_widget(Paragraph::new(name_line), chunks[0]);
+    ];
+    if let Some(env) = env {
+        name_spans.push(Span::styled("    Color: ", Style::default().fg(theme.muted)));
+        name_spans.push(Span::styled("● ", Style::default().fg(theme.env_color(env))));
+        match &editor.color_input {
+            Some(input) => {
+                let x = Line::from(name_spans.clone()).width() + input.len();
+                name_spans.push(Span::styled(
+                    input.clone(),
+                    Style::default().fg(theme.highlight).add_modifier(Modifier::UNDERLINED),
+                ));
+                frame.set_cursor_position(Position { x: inner.x + x as u16, y: chunks[0].y });
+            }
+            None => name_spans.push(Span::styled(env.color.clone(), Style::default().fg(theme.text_dim))),
+        }
+        if env.protected {
+            name_spans.push(Span::styled("    🔒 protected", Style::default().fg(theme.warning)));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(name_spans)), chunks[0]);
 
     // Cursor when editing the name
     if state.env_editor.editing_name {
@@ -196,10 +257,10 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
     // Header row
     let header_line = Line::from(vec![
         Span::styled("    ", Style::default()),
-        Span::styled(pad_right("Key", key_w as usize), Style::default().fg(Color::Yellow)),
-        Span::styled(pad_right("Value", val_w as usize), Style::default().fg(Color::Yellow)),
-        Span::styled(pad_right("Description", desc_w as usize), Style::default().fg(Color::Yellow)),
-        Span::styled("Type    ", Style::default().fg(Color::Yellow)),
+        Span::styled(pad_right("Key", key_w as usize), Style::default().fg(theme.warning)),
+        Span::styled(pad_right("Value", val_w as usize), Style::default().fg(theme.warning)),
+        Span::styled(pad_right("Description", desc_w as usize), Style::default().fg(theme.warning)),
+        Span::styled("Type    ", Style::default().fg(theme.warning)),
     ]);
     frame.render_widget(Paragraph::new(header_line), chunks[1]);
 
@@ -207,18 +268,21 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
     let body_area = chunks[2];
     let sel_row = state.env_editor.row;
     let sel_col = state.env_editor.col;
+    // Scroll so the selected row stays visible
+    let offset = sel_row.saturating_sub((body_area.height as usize).saturating_sub(1));
 
     if let Some(env) = env {
-        for (i, var) in env.variables.iter().enumerate() {
-            let y = body_area.y + i as u16;
+        for (pos, &i) in shown.iter().enumerate().skip(offset) {
+            let var = &env.variables[i];
+            let y = body_area.y + (pos - offset) as u16;
             if y >= body_area.y + body_area.height {
                 break;
             }
-            let is_selected = i == sel_row;
-            let row_bg = if is_selected { SURFACE } else { BG };
+            let is_selected = pos == sel_row;
+            let row_bg = if is_selected { theme.surface } else { theme.bg };
 
             let check_str = if var.enabled { "[✓] " } else { "[ ] " };
-            let check_fg = if var.enabled { Color::Rgb(158, 206, 106) } else { TEXT_MUTED };
+            let check_fg = if var.enabled { theme.success } else { theme.muted };
 
             let is_secret = var.var_type == VarType::Secret;
             let display_value = if is_secret && !state.env_editor.show_secret {
@@ -228,13 +292,13 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
             };
 
             let type_str = if is_secret { "Secret  " } else { "Text    " };
-            let type_fg = if is_secret { Color::Rgb(187, 154, 247) } else { TEXT_MUTED };
+            let type_fg = if is_secret { theme.purple } else { theme.muted };
 
             let col_fg = |col: u8| {
                 if is_selected && sel_col == col {
-                    Color::White
+                    theme.highlight
                 } else {
-                    TEXT_PRIMARY
+                    theme.text
                 }
             };
 
@@ -256,8 +320,8 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
             let cursor = state.env_editor.cursor;
             let row = state.env_editor.row;
             let col = state.env_editor.col;
-            if let Some(var) = env.variables.get(row) {
-                let row_y = body_area.y + row as u16;
+            if let Some(var) = shown.get(row).map(|&i| &env.variables[i]) {
+                let row_y = body_area.y + (row - offset) as u16;
                 if row_y < body_area.y + body_area.height {
                     let (cell_x, text): (u16, &str) = match col {
                         0 => (body_area.x + check_w, var.key.as_str()),
@@ -271,23 +335,56 @@ pub fn render_editor(frame: &mut Frame, area: Rect, state: &AppState) {
         }
     }
 
-    // Hint bar
+    // Search bar while typing a filter, otherwise key hints
+    if editor.search_mode {
+        let search_line = Line::from(vec![
+            Span::styled("/ ", Style::default().fg(theme.accent)),
+            Span::styled(editor.search.clone(), Style::default().fg(theme.text)),
+        ]);
+        frame.render_widget(Paragraph::new(search_line), chunks[3]);
+        frame.set_cursor_position(Position {
+            x: chunks[3].x + 2 + editor.search.chars().count() as u16,
+            y: chunks[3].y,
+        });
+        return;
+    }
+    if editor.color_input.is_some() {
+        let hint = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" apply #rrggbb  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ]);
+        frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[3]);
+        return;
+    }
     let hint = Line::from(vec![
-        Span::styled("a", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" add  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("d", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" del  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("i/Enter", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" edit  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("r", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" rename  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("Space", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" toggle  ", Style::default().fg(TEXT_MUTED)),
-        Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(" save+close", Style::default().fg(TEXT_MUTED)),
+        Span::styled("a", Style::default().fg(theme.text)),
+        Span::styled(" add  ", Style::default().fg(theme.muted)),
+        Span::styled("d", Style::default().fg(theme.text)),
+        Span::styled(" del  ", Style::default().fg(theme.muted)),
+        Span::styled("i/Enter", Style::default().fg(theme.text)),
+        Span::styled(" edit  ", Style::default().fg(theme.muted)),
+        Span::styled("r", Style::default().fg(theme.text)),
+        Span::styled(" rename  ", Style::default().fg(theme.muted)),
+        Span::styled("c/C", Style::default().fg(theme.text)),
+        Span::styled(" color  ", Style::default().fg(theme.muted)),
+        Span::styled("P", Style::default().fg(theme.text)),
+        Span::styled(" protect  ", Style::default().fg(theme.muted)),
+        Span::styled("Space", Style::default().fg(theme.text)),
+        Span::styled(" toggle  ", Style::default().fg(theme.muted)),
+        Span::styled("S-Space", Style::default().fg(theme.text)),
+        Span::styled(" toggle shown  ", Style::default().fg(theme.muted)),
+        Span::styled("/", Style::default().fg(theme.text)),
+        Span::styled(" search  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(
+            if editor.search.is_empty() { " save+close" } else { " clear search" },
+            Style::default().fg(theme.muted),
+        ),
     ]);
     frame.render_widget(
-        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        Paragraph::new(hint).style(theme.hint_style()),
         chunks[3],
     );
 }
@@ -0,0 +1,211 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+/// Every keybinding, grouped by where it applies. The key handlers in
+/// `app.rs` are the source of truth; change this list along with them.
+pub const SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    ("Global", &[
+        ("Ctrl+R", "Send the request (connect / send a frame on a WebSocket tab)"),
+        ("Ctrl+Shift+D", "Dry run: show what a send would put on the wire (or from the palette)"),
+        ("Ctrl+E", "Environment switcher (Alt+x / Alt+i export / import a file)"),
+        ("Ctrl+W", "Workspace switcher (deletes the previous word while typing)"),
+        ("Alt+r / Alt+d (workspaces)", "Rename the selected workspace in place / delete it"),
+        ("Alt+c (workspaces)", "Duplicate the selected workspace and switch to the copy"),
+        ("Ctrl+P", "Go to any request in the workspace"),
+        (": / Ctrl+Shift+P", "Command palette: search and run any action"),
+        ("Ctrl+S", "Save the tab to its collection"),
+        ("Ctrl+Z / Ctrl+Y", "Undo / redo edits, method changes and header rows"),
+    ]),
+    ("Normal mode", &[
+        ("?", "This help"),
+        (":", "Command palette; `theme <name>` switches the theme"),
+        ("{ / }", "Previous / next environment"),
+        ("Tab / Shift+Tab", "Cycle focus between panels"),
+        ("1 – 4", "Sidebar / URL bar / request editor / response viewer"),
+        ("Ctrl+B / \\", "Show or hide the sidebar"),
+        ("Ctrl+F", "Zen: the response fills the right panel"),
+        ("[ / ]", "Previous / next open tab (cycle the method on the URL bar)"),
+        ("Alt+1 – Alt+9", "Jump to an open tab of the pane"),
+        ("Ctrl+Shift+Right / Left", "Move the tab into a right-hand split pane / back"),
+        ("Ctrl+Left / Ctrl+Right", "Focus the left / right split pane"),
+        ("Alt+w", "Close the active tab, asking to save unsaved edits"),
+        ("Alt+b", "Auto-retry 429/503 responses for the whole workspace"),
+        ("u / U", "Undo / redo on the URL bar or request editor"),
+        ("A", "Audit log"),
+        ("T", "Theme picker: interface and syntax highlighting"),
+        ("W", "Workspace settings: proxy and no-proxy hosts"),
+        ("C", "Cookie jar"),
+        ("V", "Variables set this session"),
+        ("Q", "Send queue"),
+        ("Esc", "Cancel the request in flight"),
+        ("q", "Quit"),
+    ]),
+    ("Sidebar", &[
+        ("j / k", "Move"),
+        ("h / l", "Collapse / expand"),
+        ("Ctrl+- / Ctrl+=", "Collapse / expand everything"),
+        ("Enter", "Open the request, or fold the folder"),
+        ("/", "Search requests"),
+        ("n / f", "New request / folder here"),
+        ("Ctrl+N", "New collection"),
+        ("r", "Rename"),
+        ("d", "Delete"),
+        ("D", "Duplicate the request, folder or collection"),
+        ("x / p", "Mark, then move into or after the item under the cursor"),
+        ("J / K, Ctrl+J / Ctrl+K", "Move down / up among siblings"),
+        ("L", "Pick the requests this one depends on"),
+        ("s / S", "Run the request / the whole folder in place"),
+        ("R", "Collection runner"),
+        ("c", "Clear the last runner results"),
+        ("H", "Default headers of the collection or folder"),
+        ("v", "Variables of the collection or folder"),
+        ("E", "Cycle the collection's default environment"),
+        ("w", "Sidebar width: auto, compact, full"),
+    ]),
+    ("Open tabs", &[
+        ("← / →", "Previous / next tab"),
+        ("x", "Close the tab"),
+        ("r", "Rename the tab and its saved request"),
+        ("p", "Pin the tab: it can't be closed and reopens next time"),
+        ("Enter", "Go to the URL bar"),
+    ]),
+    ("URL bar", &[
+        ("i / Enter", "Edit the URL"),
+        ("[ / ]", "Cycle the HTTP method"),
+        ("Ctrl+E", "Environment for this tab only"),
+        ("Enter (Insert)", "Send"),
+        ("Ctrl+A / Ctrl+C (Insert)", "Select the whole URL / copy the selection"),
+        ("w / b / e, 0 / $", "Word motions, start / end of the URL"),
+        ("x", "Delete the char under the cursor"),
+        ("Ctrl+← / Ctrl+→, Alt+b / Alt+f (Insert)", "Jump by word; / . ? & = split words"),
+        ("Ctrl+W / Ctrl+U (Insert)", "Delete the previous word / to the start"),
+    ]),
+    ("Request editor", &[
+        ("h / l (tab bar)", "Previous / next editor tab"),
+        ("i / Enter", "Edit the selected cell, field or body"),
+        ("j / k", "Move between rows"),
+        ("← / →", "Key or value column; cycle the auth type on the Auth tab"),
+        ("a", "Add a header or check"),
+        ("x / d", "Delete the header or check"),
+        ("Space", "Enable or disable the header or check"),
+        ("t", "Cycle the check kind / switch script / move API key / OAuth2 grant"),
+        ("v (Auth)", "Reveal the token or password"),
+        ("i (Docs)", "Write the request's description; shown in the sidebar footer"),
+        ("p / x (OAuth2)", "Keep the token between sessions / forget it"),
+        ("# / Ctrl+L", "Line numbers and cursor line in the body and script editors"),
+        ("w / b / e, 0 / $ (Body)", "Word motions, start / end of the line"),
+        ("x / dd (Body)", "Delete the char under the cursor / the line"),
+        ("yy / p (Body)", "Copy the line / put the copied or deleted line below"),
+        ("PageUp / PageDown, Ctrl+U / Ctrl+D (Body)", "Page / half-page up or down"),
+        ("gg / G (Body)", "First / last line"),
+        ("Ctrl+W / Ctrl+U (Insert)", "Delete the previous word / to the line start"),
+        ("Alt+b / Alt+f (Insert)", "Jump by word, in headers and auth fields too"),
+        ("Esc (Insert)", "Back to Normal mode"),
+    ]),
+    ("Response", &[
+        ("j / k", "Scroll, or select a header on the Headers tab"),
+        ("PageUp / PageDown, Ctrl+U / Ctrl+D", "Scroll the body a page / half a page"),
+        ("gg / G", "Top / bottom of the body (first / last node in the tree view)"),
+        ("h / l", "Previous / next response tab"),
+        ("f", "Filter the body with JSONPath or jq-style paths"),
+        ("t", "Collapsible tree view of a JSON body"),
+        ("o", "Sorted-keys view of a JSON body"),
+        ("m", "Markdown rendered or raw"),
+        ("w", "Wrap long lines"),
+        ("D", "Diff the body against another tab's response"),
+        ("← / → (Body, unwrapped)", "Scroll sideways"),
+        ("#", "Line numbers beside the body (unwrapped)"),
+        ("z", "Zen: hide the request rows while reading"),
+        ("Ctrl+L", "Cycle the highlight language"),
+        ("e", "Capture a JSON value into a variable"),
+        ("c", "Generate checks from the response"),
+        ("y (Headers)", "Copy the selected header"),
+        ("b", "Auto-retry this request after Retry-After"),
+        ("g h", "Run history of the request"),
+    ]),
+    ("Env editor", &[
+        ("j / k / h / l", "Move between cells"),
+        ("i / Enter", "Edit the cell"),
+        ("Ctrl+W / Ctrl+U (editing)", "Delete the previous word / to the start"),
+        ("a", "Add a variable"),
+        ("d", "Delete the variable"),
+        ("Space", "Enable / reveal / mark secret, by column"),
+        ("Shift+Space", "Enable or disable every shown row"),
+        ("/", "Filter by key or description"),
+        ("r", "Rename the environment"),
+        ("c / C", "Next color from the palette / type a #rrggbb color"),
+        ("P", "Protect the environment: sends ask for confirmation first"),
+        ("Esc", "Save and close"),
+    ]),
+];
+
+/// Rows the help list takes: a heading and a blank line per section plus
+/// its bindings.
+pub fn line_count() -> usize {
+    SECTIONS.iter().map(|(_, keys)| keys.len() + 2).sum()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Keybindings ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let key_w = SECTIONS
+        .iter()
+        .flat_map(|(_, keys)| keys.iter().map(|(k, _)| k.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::with_capacity(line_count());
+    for (title, keys) in SECTIONS {
+        lines.push(Line::from(Span::styled(
+            format!(" {title}"),
+            Style::default().fg(theme.purple).add_modifier(Modifier::BOLD),
+        )));
+        for (key, action) in *keys {
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {key:<key_w$}  "), Style::default().fg(theme.accent)),
+                Span::styled(*action, Style::default().fg(theme.text)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    let max_scroll = lines.len().saturating_sub(chunks[0].height as usize);
+    let scroll = state.help.scroll.min(max_scroll);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" scroll  ", Style::default().fg(theme.muted)),
+        Span::styled("g/G", Style::default().fg(theme.text)),
+        Span::styled(" top/bottom  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc/q", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[1]);
+}
@@ -1,22 +1,72 @@
-use std::sync::LazyLock;
+use std::sync::{LazyLock, RwLock};
 
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span, Text};
 use syntect::easy::HighlightLines;
-use syntect::highlighting::ThemeSet;
+use syntect::highlighting::{Theme as SyntaxTheme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
+use crate::state::config::{ThemeKind, UiConfig};
+use crate::state::response_state::{ResponseBody, ResponseState};
+use crate::ui::theme::resolve;
+
 static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
 static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+/// The syntect theme `highlight_text` uses, resolved once per change by
+/// `apply_syntax_theme` so each call doesn't look it up by name.
+static SYNTAX_THEME: RwLock<Option<&'static SyntaxTheme>> = RwLock::new(None);
+
+/// Names of the syntect themes that can be picked, sorted.
+pub fn syntax_themes() -> impl Iterator<Item = &'static str> {
+    LazyLock::force(&THEME_SET).themes.keys().map(String::as_str)
+}
+
+/// The syntect theme that goes with a UI theme when none is picked.
+pub fn default_syntax_theme(kind: ThemeKind) -> &'static str {
+    match resolve(kind) {
+        ThemeKind::Light => "Solarized (light)",
+        _ => "Solarized (dark)",
+    }
+}
+
+/// Highlight with the syntect theme `ui` names, or the one matching its UI
+/// theme. A name syntect doesn't know falls back to any dark theme.
+pub fn apply_syntax_theme(ui: &UiConfig) {
+    let name = ui.syntax_theme.as_deref().unwrap_or(default_syntax_theme(ui.theme));
+    let themes = &LazyLock::force(&THEME_SET).themes;
+    let theme = themes.get(name).or_else(|| themes.values().find(|t| is_dark(t))).or(themes.values().next());
+    if let Ok(mut slot) = SYNTAX_THEME.write() {
+        *slot = theme;
+    }
+}
+
+fn is_dark(theme: &SyntaxTheme) -> bool {
+    theme.settings.background.is_some_and(|bg| {
+        (u32::from(bg.r) * 299 + u32::from(bg.g) * 587 + u32::from(bg.b) * 114) / 1000 < 128
+    })
+}
+
+/// Bodies up to this size are highlighted as they arrive; bigger ones show
+/// as plain text while a background job highlights them.
+pub const HIGHLIGHT_SYNC_BYTES: usize = 64 * 1024;
+/// Bodies over this size aren't highlighted at all.
+pub const HIGHLIGHT_MAX_BYTES: usize = 2 * 1024 * 1024;
 
 pub fn highlight_text(text: &str, lang: &str) -> Text<'static> {
+    if text.len() > HIGHLIGHT_MAX_BYTES {
+        return Text::raw(text.to_string());
+    }
+    if lang == "csv" {
+        return highlight_csv(text);
+    }
     let syntax = SYNTAX_SET
         .find_syntax_by_extension(lang)
         .or_else(|| SYNTAX_SET.find_syntax_by_name(lang))
         .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
 
-    let theme = match THEME_SET.themes.get("Solarized (dark)") {
+    let chosen = SYNTAX_THEME.read().ok().and_then(|t| *t);
+    let theme = match chosen.or_else(|| THEME_SET.themes.get(default_syntax_theme(ThemeKind::TokyoNight))) {
         Some(t) => t,
         None => return Text::raw(text.to_string()),
     };
@@ -46,13 +96,167 @@ pub fn highlight_text(text: &str, lang: &str) -> Text<'static> {
     Text::from(lines)
 }
 
-pub fn detect_lang(text: &str) -> &'static str {
-    let t = text.trim_start();
-    if t.starts_with('{') || t.starts_with('[') {
+/// Column colours for CSV, from the Solarized accents the other languages
+/// are highlighted with.
+const CSV_COLUMNS: [Color; 6] = [
+    Color::Rgb(38, 139, 210),
+    Color::Rgb(133, 153, 0),
+    Color::Rgb(181, 137, 0),
+    Color::Rgb(211, 54, 130),
+    Color::Rgb(42, 161, 152),
+    Color::Rgb(203, 75, 22),
+];
+const CSV_SEPARATOR: Color = Color::Rgb(88, 110, 117);
+
+/// syntect ships no CSV syntax: colour each column in turn, like a rainbow
+/// CSV view. Commas inside double quotes don't start a column.
+fn highlight_csv(text: &str) -> Text<'static> {
+    let lines = text.lines().map(|line| {
+        let mut spans = Vec::new();
+        let mut field = String::new();
+        let mut column = 0;
+        let mut quoted = false;
+        for c in line.chars() {
+            match c {
+                '"' => {
+                    quoted = !quoted;
+                    field.push(c);
+                }
+                ',' if !quoted => {
+                    let color = CSV_COLUMNS[column % CSV_COLUMNS.len()];
+                    spans.push(Span::styled(std::mem::take(&mut field), Style::default().fg(color)));
+                    spans.push(Span::styled(",", Style::default().fg(CSV_SEPARATOR)));
+                    column += 1;
+                }
+                _ => field.push(c),
+            }
+        }
+        let color = CSV_COLUMNS[column % CSV_COLUMNS.len()];
+        spans.push(Span::styled(field, Style::default().fg(color)));
+        Line::from(spans)
+    });
+    Text::from(lines.collect::<Vec<_>>())
+}
+
+/// Languages `Ctrl+L` in the response viewer cycles through, by the
+/// extension syntect knows them by (`csv` is highlighted here instead).
+pub const LANGS: &[&str] = &["json", "xml", "html", "css", "js", "yaml", "csv", "txt"];
+
+/// Bytes of the body looked at when the content type doesn't say.
+const SNIFF_LEN: usize = 512;
+
+/// Language of a body: from its content type, or from a look at its first
+/// bytes when the type is missing or generic (`text/plain`,
+/// `application/octet-stream`).
+pub fn detect_lang(content_type: Option<&str>, text: &str) -> &'static str {
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    let by_type = if mime.ends_with("json") {
+        Some("json")
+    } else if mime.ends_with("xml") || mime.starts_with("application/xhtml") {
+        Some("xml")
+    } else if mime.contains("html") {
+        Some("html")
+    } else if mime == "text/css" {
+        Some("css")
+    } else if mime.contains("javascript") || mime.contains("ecmascript") {
+        Some("js")
+    } else if mime.contains("yaml") {
+        Some("yaml")
+    } else if mime == "text/csv" || mime == "application/csv" {
+        Some("csv")
+    } else {
+        None
+    };
+    by_type.unwrap_or_else(|| sniff_lang(text))
+}
+
+fn sniff_lang(text: &str) -> &'static str {
+    let end = (0..=SNIFF_LEN.min(text.len())).rev().find(|&i| text.is_char_boundary(i)).unwrap_or(0);
+    let head = text[..end].trim_start().to_ascii_lowercase();
+    if head.starts_with('{') || head.starts_with('[') {
         "json"
-    } else if t.starts_with('<') {
+    } else if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        "html"
+    } else if head.starts_with('<') {
         "xml"
+    } else if head.starts_with("---\n") || head.starts_with("%yaml") {
+        "yaml"
     } else {
         "txt"
     }
 }
+
+/// Language a response body is highlighted as: the one forced with
+/// `Ctrl+L`, else the detected one.
+pub fn response_lang(resp: &ResponseState) -> &str {
+    match (&resp.force_lang, &resp.body) {
+        (Some(lang), _) => lang,
+        (None, ResponseBody::Text(text)) => detect_lang(resp.content_type(), text),
+        (None, _) => "txt",
+    }
+}
+
+/// Recompute a text response's highlighted body.
+pub fn highlight_response(resp: &mut ResponseState) {
+    if let ResponseBody::Text(text) = &resp.body {
+        resp.highlighted_body = Some(highlight_text(text, response_lang(resp)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_lang() {
+        assert_eq!(detect_lang(Some("text/html; charset=utf-8"), "hi"), "html");
+        assert_eq!(detect_lang(Some("application/problem+json"), ""), "json");
+        assert_eq!(detect_lang(Some("application/atom+xml"), ""), "xml");
+        assert_eq!(detect_lang(Some("application/xhtml+xml"), ""), "xml");
+        assert_eq!(detect_lang(Some("text/css"), ""), "css");
+        assert_eq!(detect_lang(Some("Application/JSON; charset=utf-8"), "<not json>"), "json");
+        assert_eq!(detect_lang(Some("application/vnd.api+json"), ""), "json");
+        assert_eq!(detect_lang(Some("text/xml"), ""), "xml");
+        assert_eq!(detect_lang(Some("text/javascript"), ""), "js");
+        assert_eq!(detect_lang(Some("application/x-yaml"), ""), "yaml");
+        assert_eq!(detect_lang(Some("text/csv; header=present"), "a,b"), "csv");
+        // Generic types fall back to the body
+        assert_eq!(detect_lang(Some("text/plain"), "  <!DOCTYPE html><p>"), "html");
+        assert_eq!(detect_lang(Some("application/octet-stream"), "<?xml ?>"), "xml");
+        assert_eq!(detect_lang(None, "[1, 2]"), "json");
+        assert_eq!(detect_lang(None, "plain words"), "txt");
+    }
+
+    #[test]
+    fn test_highlight_text_leaves_huge_bodies_plain() {
+        let big = format!("[{}]", "1,".repeat(HIGHLIGHT_MAX_BYTES / 2));
+        let text = highlight_text(&big, "json");
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(text.lines[0].spans[0].style, Style::default());
+    }
+
+    #[test]
+    fn test_default_syntax_themes_ship_with_syntect() {
+        let names: Vec<&str> = syntax_themes().collect();
+        for kind in ThemeKind::ALL {
+            assert!(names.contains(&default_syntax_theme(kind)));
+        }
+        assert_eq!(default_syntax_theme(ThemeKind::Light), "Solarized (light)");
+        assert!(THEME_SET.themes.values().any(is_dark));
+    }
+
+    #[test]
+    fn test_highlight_csv_colours_columns() {
+        let text = highlight_text("id,\"name, full\"\n1,Ann", "csv");
+        assert_eq!(text.lines.len(), 2);
+        let header = &text.lines[0].spans;
+        assert_eq!(header[0].content, "id");
+        assert_eq!(header[2].content, "\"name, full\"");
+        assert_eq!(header[2].style.fg, Some(CSV_COLUMNS[1]));
+        assert_eq!(text.lines[1].spans[2].style.fg, Some(CSV_COLUMNS[1]));
+    }
+}
@@ -1,35 +1,65 @@
+use std::rc::Rc;
+
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::Color,
+    style::Style,
+    widgets::{Block, Borders},
 };
 
-use crate::state::app_state::{ActivePopup, ActiveTab, AppState};
+use crate::state::app_state::{ActivePopup, ActiveTab, AppState, ResponseTab};
+use crate::state::focus::Focus;
 use super::{
+    audit_log,
+    check_picker,
+    completion_popup,
+    cookie_jar,
     confirm_delete,
+    confirm_send,
+    confirm_close,
+    dependency_picker,
+    dependency_prompt,
+    diff,
     env_editor,
     naming_popup,
+    request_finder,
+    settings,
     request_tabs,
+    run_history,
+    runner,
     sidebar,
     status_bar,
     workspace_switcher,
+    theme::theme,
+    theme_picker,
+    capture_popup,
+    send_queue,
+    json_value_popup,
+    save_request,
+    variables,
+    default_headers,
+    help,
+    command_palette,
+    collection_variables,
     request::{
         url_bar, tab_bar as req_tab_bar,
-        headers_editor, body_editor, auth_editor, params_editor, scripts_editor,
+        headers_editor, body_editor, auth_editor, params_editor, scripts_editor, checks_editor, docs_editor,
     },
-    response::{render_meta, body_viewer, tab_bar as resp_tab_bar},
+    response::{render_meta, body_viewer, cookies_viewer, headers_viewer, tab_bar as resp_tab_bar, tests_viewer, timing_viewer, console_viewer},
 };
 
-// TokyoNight palette
-pub const ACCENT_BLUE: Color = Color::Rgb(122, 162, 247);  // #7aa2f7
-pub const BORDER_INACTIVE: Color = Color::Rgb(65, 72, 104); // #414868
-pub const BG: Color = Color::Rgb(26, 27, 38);               // #1a1b26
-
 pub const SPINNER_FRAMES: &[char] = &['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
 
 pub fn render(frame: &mut Frame, state: &AppState) {
     let area = frame.area();
 
+    // Paint the theme background so light themes don't depend on the terminal's
+    let theme = theme(state);
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.bg).fg(theme.text)),
+        area,
+    );
+
     // Split off status bar at bottom
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -40,17 +70,103 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     let status_area = vertical[1];
 
     // Optional sidebar
-    let right_area = if state.sidebar_visible {
+    let (sidebar_area, right_area) = if state.sidebar_visible {
+        let width = sidebar::sidebar_width(state, area.width);
         let horiz = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(28), Constraint::Min(0)])
+            .constraints([Constraint::Length(width), Constraint::Min(0)])
             .split(main_area);
         sidebar::render(frame, horiz[0], state);
-        horiz[1]
+        (Some(horiz[0]), horiz[1])
     } else {
-        main_area
+        state.hit_areas.borrow_mut().sidebar_list = Rect::default();
+        (None, main_area)
+    };
+
+    let chunks = match state.workspace.other_pane_tab() {
+        Some(other) => {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Length(1), Constraint::Min(0)])
+                .split(right_area);
+            frame.render_widget(
+                Block::default().borders(Borders::LEFT).border_style(Style::default().fg(theme.border)),
+                panes[1],
+            );
+            let (focused, unfocused) = if state.workspace.in_right_pane(state.workspace.active_tab_idx) {
+                (panes[2], panes[0])
+            } else {
+                (panes[0], panes[2])
+            };
+            // The pane with focus renders last so its click areas and
+            // cursor are the ones kept
+            state.render_tab.set(Some(other));
+            render_pane(frame, unfocused, state);
+            state.render_tab.set(None);
+            render_pane(frame, focused, state)
+        }
+        None => render_pane(frame, right_area, state),
     };
+    let active_tab = state.active_tab().map(|t| &t.active_tab);
+
+    status_bar::render(frame, status_area, state);
+
+    if let Some(sidebar_area) = sidebar_area {
+        sidebar::render_cursor_overlay(frame, sidebar_area, state);
+    }
+
+    // `{{variable}}` completion, anchored at the cursor of the field being edited
+    if state.completion.active && !state.zen {
+        let cursor = match (&state.focus, active_tab) {
+            (Focus::UrlBar, _) => url_bar::cursor_position(chunks[1], state),
+            (Focus::Editor, Some(ActiveTab::Headers)) => {
+                headers_editor::cursor_position(chunks[3], state)
+            }
+            (Focus::Editor, Some(ActiveTab::Body)) => body_editor::cursor_position(chunks[3], state),
+            (Focus::Editor, Some(ActiveTab::Auth)) => auth_editor::cursor_position(chunks[3], state),
+            _ => None,
+        };
+        if let Some(cursor) = cursor {
+            completion_popup::render(frame, cursor, state);
+        }
+    }
+
+    // Overlay popups — rendered last so they appear on top
+    match &state.active_popup {
+        ActivePopup::None => {}
+        ActivePopup::EnvSwitcher => env_editor::render_switcher(frame, area, state),
+        ActivePopup::EnvEditor => env_editor::render_editor(frame, area, state),
+        ActivePopup::WorkspaceSwitcher => workspace_switcher::render(frame, area, state),
+        ActivePopup::CollectionNaming => naming_popup::render(frame, area, state),
+        ActivePopup::ConfirmDelete => confirm_delete::render(frame, area, state),
+        ActivePopup::CheckPicker => check_picker::render(frame, area, state),
+        ActivePopup::RequestFinder => request_finder::render(frame, area, state),
+        ActivePopup::AuditLog => audit_log::render(frame, area, state),
+        ActivePopup::DependencyPicker => dependency_picker::render(frame, area, state),
+        ActivePopup::DependencyPrompt => dependency_prompt::render(frame, area, state),
+        ActivePopup::ConfirmSend => confirm_send::render(frame, area, state),
+        ActivePopup::ConfirmClose => confirm_close::render(frame, area, state),
+        ActivePopup::RunHistory => run_history::render(frame, area, state),
+        ActivePopup::Runner => runner::render(frame, area, state),
+        ActivePopup::ThemePicker => theme_picker::render(frame, area, state),
+        ActivePopup::Cookies => cookie_jar::render(frame, area, state),
+        ActivePopup::Capture => capture_popup::render(frame, area, state),
+        ActivePopup::SendQueue => send_queue::render(frame, area, state),
+        ActivePopup::JsonValue => json_value_popup::render(frame, area, state),
+        ActivePopup::Diff => diff::render(frame, area, state),
+        ActivePopup::SaveRequest => save_request::render(frame, area, state),
+        ActivePopup::Variables => variables::render(frame, area, state),
+        ActivePopup::DefaultHeaders => default_headers::render(frame, area, state),
+        ActivePopup::Settings => settings::render(frame, area, state),
+        ActivePopup::Help => help::render(frame, area, state),
+        ActivePopup::CommandPalette => command_palette::render(frame, area, state),
+        ActivePopup::CollectionVariables => collection_variables::render(frame, area, state),
+    }
+}
 
+/// Draw one request pane — open tabs, URL bar, editor and response — for
+/// the active tab (or `state.render_tab`), returning its row layout.
+fn render_pane(frame: &mut Frame, right_area: Rect, state: &AppState) -> Rc<[Rect]> {
     // Right panel vertical split
     // chunks[0] = open-tabs row (Length 1)
     // chunks[1] = url bar (Length 3)
@@ -59,17 +175,22 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     // chunks[4] = response meta (Length 1)
     // chunks[5] = response tab bar (Length 1)
     // chunks[6] = response viewer (flexible)
-    let total_fixed: u16 = 1 + 3 + 1 + 1 + 1; // 7 rows fixed
+    // In zen mode chunks[0..4] are empty and the viewer takes their rows
+    let (request_rows, total_fixed): ([u16; 3], u16) = if state.zen {
+        ([0, 0, 0], 1 + 1)
+    } else {
+        ([1, 3, 1], 1 + 3 + 1 + 1 + 1) // 7 rows fixed
+    };
     let remaining = right_area.height.saturating_sub(total_fixed);
-    let editor_h = ((remaining as u32 * 35 / 100) as u16).max(3);
+    let editor_h = if state.zen { 0 } else { ((remaining as u32 * 35 / 100) as u16).max(3) };
     let viewer_h = remaining.saturating_sub(editor_h).max(3);
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),          // open tabs bar
-            Constraint::Length(3),          // url bar
-            Constraint::Length(1),          // request tab bar
+            Constraint::Length(request_rows[0]), // open tabs bar
+            Constraint::Length(request_rows[1]), // url bar
+            Constraint::Length(request_rows[2]), // request tab bar
             Constraint::Length(editor_h),   // request editor
             Constraint::Length(1),          // response meta line
             Constraint::Length(1),          // response tab bar
@@ -77,34 +198,49 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         ])
         .split(right_area);
 
-    request_tabs::render(frame, chunks[0], state);
-    url_bar::render(frame, chunks[1], state);
-    req_tab_bar::render(frame, chunks[2], state);
+    {
+        let mut hits = state.hit_areas.borrow_mut();
+        hits.url_bar = chunks[1];
+        hits.editor = chunks[2].union(chunks[3]);
+        hits.response = chunks[4].union(chunks[5]).union(chunks[6]);
+    }
 
     let active_tab = state.active_tab().map(|t| &t.active_tab);
-    match active_tab.unwrap_or(&ActiveTab::Headers) {
-        ActiveTab::Headers => headers_editor::render(frame, chunks[3], state),
-        ActiveTab::Body    => body_editor::render(frame, chunks[3], state),
-        ActiveTab::Auth    => auth_editor::render(frame, chunks[3], state),
-        ActiveTab::Params  => params_editor::render(frame, chunks[3], state),
-        ActiveTab::Scripts => scripts_editor::render(frame, chunks[3], state),
+    if state.zen {
+        // Nothing to click where the request rows were
+        let mut hits = state.hit_areas.borrow_mut();
+        hits.request_tabs.clear();
+        hits.url_bar = Rect::default();
+        hits.editor = Rect::default();
+    } else {
+        request_tabs::render(frame, chunks[0], state);
+        url_bar::render(frame, chunks[1], state);
+        req_tab_bar::render(frame, chunks[2], state);
+
+        match active_tab.unwrap_or(&ActiveTab::Headers) {
+            ActiveTab::Headers => headers_editor::render(frame, chunks[3], state),
+            ActiveTab::Body    => body_editor::render(frame, chunks[3], state),
+            ActiveTab::Auth    => auth_editor::render(frame, chunks[3], state),
+            ActiveTab::Params  => params_editor::render(frame, chunks[3], state),
+            ActiveTab::Scripts => scripts_editor::render(frame, chunks[3], state),
+            ActiveTab::Checks  => checks_editor::render(frame, chunks[3], state),
+            ActiveTab::Docs    => docs_editor::render(frame, chunks[3], state),
+        }
     }
 
     render_meta(frame, chunks[4], state);
     resp_tab_bar::render(frame, chunks[5], state);
-    body_viewer::render(frame, chunks[6], state);
-
-    status_bar::render(frame, status_area, state);
-
-    // Overlay popups — rendered last so they appear on top
-    match &state.active_popup {
-        ActivePopup::None => {}
-        ActivePopup::EnvSwitcher => env_editor::render_switcher(frame, area, state),
-        ActivePopup::EnvEditor => env_editor::render_editor(frame, area, state),
-        ActivePopup::WorkspaceSwitcher => workspace_switcher::render(frame, area, state),
-        ActivePopup::CollectionNaming => naming_popup::render(frame, area, state),
-        ActivePopup::ConfirmDelete => confirm_delete::render(frame, area, state),
+    let response_tab = state.active_tab().map(|t| &t.response_tab);
+    match response_tab.unwrap_or(&ResponseTab::Body) {
+        ResponseTab::Tests => tests_viewer::render(frame, chunks[6], state),
+        ResponseTab::Console => console_viewer::render(frame, chunks[6], state),
+        ResponseTab::Cookies => cookies_viewer::render(frame, chunks[6], state),
+        ResponseTab::Headers => headers_viewer::render(frame, chunks[6], state),
+        ResponseTab::Timing => timing_viewer::render(frame, chunks[6], state),
+        ResponseTab::Body => body_viewer::render(frame, chunks[6], state),
     }
+
+    chunks
 }
 
 /// Helper used by sub-widgets to decide whether a rect is visible
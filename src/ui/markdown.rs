@@ -0,0 +1,221 @@
+// Light markdown-to-ratatui converter for text/markdown response bodies
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+
+use crate::ui::highlight::highlight_text;
+use crate::ui::theme::Theme;
+
+/// Width of a `---` horizontal rule
+const RULE_W: usize = 40;
+
+/// Render `source` as styled text: headings bold and colored, bullets for
+/// list items, quotes behind a bar, fenced code highlighted and indented,
+/// and inline bold, italic, code and links.
+pub fn render_markdown(source: &str, theme: &Theme) -> Text<'static> {
+    let mut lines = Vec::new();
+    let mut fence: Option<(String, String)> = None;
+
+    for raw in source.lines() {
+        let trimmed = raw.trim_start();
+        if let Some(rest) = trimmed.strip_prefix("```") {
+            match fence.take() {
+                Some((lang, code)) => lines.extend(code_block(&code, &lang)),
+                None => fence = Some((rest.trim().to_string(), String::new())),
+            }
+            continue;
+        }
+        if let Some((_, code)) = &mut fence {
+            code.push_str(raw);
+            code.push('\n');
+            continue;
+        }
+        lines.push(block_line(raw, theme));
+    }
+    // An unclosed fence still shows its code
+    if let Some((lang, code)) = fence {
+        lines.extend(code_block(&code, &lang));
+    }
+    Text::from(lines)
+}
+
+/// Fenced code, highlighted as its info-string language and indented.
+fn code_block(code: &str, lang: &str) -> Vec<Line<'static>> {
+    let lang = if lang.is_empty() { "txt" } else { lang };
+    highlight_text(code, lang)
+        .lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = vec![Span::raw("    ")];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// One line outside a code fence.
+fn block_line(raw: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = raw.trim_start();
+    let indent = raw.len() - trimmed.len();
+
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        let color = if level <= 2 { theme.accent } else { theme.purple };
+        let mut style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if level == 1 {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        return Line::from(Span::styled(trimmed[level..].trim().to_string(), style));
+    }
+
+    let is_rule = trimmed.len() >= 3
+        && ['-', '*', '_'].iter().any(|&m| trimmed.chars().all(|c| c == m || c == ' '));
+    if is_rule {
+        return Line::from(Span::styled("─".repeat(RULE_W), Style::default().fg(theme.border)));
+    }
+
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::styled("│ ", Style::default().fg(theme.border))];
+        let style = Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC);
+        spans.extend(inline_spans(quote.trim_start(), style, theme));
+        return Line::from(spans);
+    }
+
+    // Two spaces of source indent per nesting level
+    let pad = "  ".repeat(indent / 2 + 1);
+    let bullet = ["- ", "* ", "+ "].iter().find_map(|m| trimmed.strip_prefix(m));
+    if let Some(item) = bullet {
+        let mut spans = vec![Span::styled(format!("{pad}• "), Style::default().fg(theme.accent))];
+        spans.extend(inline_spans(item, Style::default().fg(theme.text), theme));
+        return Line::from(spans);
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && trimmed[digits..].starts_with(". ") {
+        let mut spans = vec![Span::styled(
+            format!("{pad}{} ", &trimmed[..=digits]),
+            Style::default().fg(theme.accent),
+        )];
+        spans.extend(inline_spans(&trimmed[digits + 2..], Style::default().fg(theme.text), theme));
+        return Line::from(spans);
+    }
+
+    Line::from(inline_spans(raw, Style::default().fg(theme.text), theme))
+}
+
+/// Inline markup: `**bold**`, `*italic*` / `_italic_`, `` `code` `` and
+/// `[text](url)`. Unclosed markers are kept as text.
+fn inline_spans(text: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => closed(rest, "`").map(|(inner, len)| {
+                (vec![Span::styled(inner.to_string(), Style::default().fg(theme.orange))], len)
+            }),
+            '*' if rest.starts_with("**") => closed(rest, "**").map(|(inner, len)| {
+                (inline_spans(inner, base.add_modifier(Modifier::BOLD), theme), len)
+            }),
+            '*' | '_' => closed(rest, &c.to_string()).map(|(inner, len)| {
+                (inline_spans(inner, base.add_modifier(Modifier::ITALIC), theme), len)
+            }),
+            '[' => link(rest).map(|(label, url, len)| {
+                let link_style = Style::default().fg(theme.accent).add_modifier(Modifier::UNDERLINED);
+                let spans = vec![
+                    Span::styled(label.to_string(), link_style),
+                    Span::styled(format!(" ({url})"), Style::default().fg(theme.muted)),
+                ];
+                (spans, len)
+            }),
+            _ => None,
+        };
+        match styled {
+            Some((styled, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.extend(styled);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// The non-empty text between `marker` at the start of `text` and its next
+/// occurrence, and the bytes taken including both markers.
+fn closed<'a>(text: &'a str, marker: &str) -> Option<(&'a str, usize)> {
+    let after = &text[marker.len()..];
+    let end = after.find(marker).filter(|&end| end > 0)?;
+    Some((&after[..end], marker.len() * 2 + end))
+}
+
+/// `[label](url)` at the start of `text`: label, url and bytes taken.
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.find("](")?;
+    let url_end = text[close + 2..].find(')')?;
+    let label = &text[1..close];
+    let url = &text[close + 2..close + 2 + url_end];
+    Some((label, url, close + 3 + url_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::theme::TOKYO_NIGHT;
+
+    const DOC: &str = "\
+# Users API
+
+Returns **all** users, see [the guide](https://docs.test/users).
+
+## Fields
+- `id`: *unique*
+  - nested
+1. first
+> Note: paginated
+
+```json
+{\"id\": 1}
+```
+---";
+
+    #[test]
+    fn test_render_markdown_snapshot() {
+        let text = render_markdown(DOC, &TOKYO_NIGHT);
+        let rendered: Vec<String> = text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            rendered,
+            [
+                "Users API",
+                "",
+                "Returns all users, see the guide (https://docs.test/users).",
+                "",
+                "Fields",
+                "  • id: unique",
+                "    • nested",
+                "  1. first",
+                "│ Note: paginated",
+                "",
+                "    {\"id\": 1}",
+                &"─".repeat(RULE_W),
+            ]
+        );
+
+        let heading = &text.lines[0].spans[0];
+        assert!(heading.style.add_modifier.contains(Modifier::BOLD));
+        let bold = &text.lines[2].spans[1];
+        assert_eq!(bold.content, "all");
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+        let link = &text.lines[2].spans[3];
+        assert_eq!(link.content, "the guide");
+        assert!(link.style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+}
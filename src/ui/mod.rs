@@ -10,4 +10,29 @@ pub mod response;
 pub mod request_tabs;
 pub mod naming_popup;
 pub mod confirm_delete;
+pub mod confirm_send;
+pub mod confirm_close;
 pub mod workspace_switcher;
+pub mod check_picker;
+pub mod request_finder;
+pub mod completion_popup;
+pub mod audit_log;
+pub mod dependency_picker;
+pub mod dependency_prompt;
+pub mod diff;
+pub mod run_history;
+pub mod runner;
+pub mod theme;
+pub mod theme_picker;
+pub mod settings;
+pub mod cookie_jar;
+pub mod text_utils;
+pub mod capture_popup;
+pub mod send_queue;
+pub mod json_value_popup;
+pub mod save_request;
+pub mod markdown;
+pub mod variables;
+pub mod default_headers;
+pub mod help;
+pub mod collection_variables;
@@ -1,20 +1,17 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Position, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use crate::state::app_state::{AppState, NamingTarget};
-use crate::ui::layout::ACCENT_BLUE;
 use crate::ui::popup::centered_rect;
-
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
-const BG: Color = Color::Rgb(26, 27, 38);
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let is_new_request = matches!(state.naming.target, NamingTarget::NewRequest { .. });
 
     let popup_area = centered_rect(50, 30, area);
@@ -34,13 +31,14 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         NamingTarget::NewFolder { .. } => " New Folder ",
         NamingTarget::NewRequest { .. } => " New Request ",
         NamingTarget::Rename { .. } => " Rename ",
+        NamingTarget::RenameTab { .. } => " Rename Tab ",
     };
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(ACCENT_BLUE))
+        .border_style(Style::default().fg(theme.accent))
         .title(title)
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme.bg));
 
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
@@ -86,9 +84,9 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     };
 
     let input_line = Line::from(vec![
-        Span::styled(before, Style::default().fg(TEXT_PRIMARY)),
-        Span::styled(cursor_char, Style::default().bg(Color::White).fg(Color::Black)),
-        Span::styled(after, Style::default().fg(TEXT_PRIMARY)),
+        Span::styled(before, Style::default().fg(theme.text)),
+        Span::styled(cursor_char, Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg)),
+        Span::styled(after, Style::default().fg(theme.text)),
     ]);
 
     frame.render_widget(Paragraph::new(input_line), chunks[0]);
@@ -103,12 +101,12 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     if is_new_request {
         // Method row
         let method_line = Line::from(vec![
-            Span::styled("◀ ", Style::default().fg(TEXT_MUTED)),
+            Span::styled("◀ ", Style::default().fg(theme.muted)),
             Span::styled(
                 state.naming.method.clone(),
-                Style::default().fg(ACCENT_BLUE).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" ▶", Style::default().fg(TEXT_MUTED)),
+            Span::styled(" ▶", Style::default().fg(theme.muted)),
         ]);
         frame.render_widget(Paragraph::new(method_line), chunks[1]);
 
@@ -116,22 +114,22 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         frame.render_widget(
             Paragraph::new(Line::from(Span::styled(
                 "─".repeat(inner.width as usize),
-                Style::default().fg(TEXT_MUTED),
+                Style::default().fg(theme.muted),
             ))),
             chunks[2],
         );
 
         // Footer hints (with Tab method)
         let hint = Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" confirm  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Tab", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" method  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" cancel", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" confirm  ", Style::default().fg(theme.muted)),
+            Span::styled("Tab", Style::default().fg(theme.text)),
+            Span::styled(" method  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ]);
         frame.render_widget(
-            Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+            Paragraph::new(hint).style(theme.hint_style()),
             chunks[3],
         );
     } else {
@@ -139,20 +137,20 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         frame.render_widget(
             Paragraph::new(Line::from(Span::styled(
                 "─".repeat(inner.width as usize),
-                Style::default().fg(TEXT_MUTED),
+                Style::default().fg(theme.muted),
             ))),
             chunks[1],
         );
 
         // Footer hints
         let hint = Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" confirm  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" cancel", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" confirm  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ]);
         frame.render_widget(
-            Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+            Paragraph::new(hint).style(theme.hint_style()),
             chunks[2],
         );
     }
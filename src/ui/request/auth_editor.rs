@@ -1,10 +1,184 @@
-// Authentication editor (Bearer, Basic, API key, OAuth2)
-use ratatui::{Frame, layout::{Alignment, Rect}, style::{Modifier, Style}, widgets::Paragraph};
+// Authentication editor (Bearer, Basic, API key, OAuth 2.0)
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use chrono::Utc;
+
 use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::state::mode::Mode;
+use crate::state::request_state::{AuthConfig, OAuthGrant};
+use crate::ui::theme::theme;
+use crate::util::time::span;
+
+/// Width of the field label column ("Username  ")
+const LABEL_W: u16 = 10;
+/// Rows above the first field: the type selector and a blank line
+const FIELDS_TOP: u16 = 2;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    if area.width < 4 || area.height < 2 {
+        return;
+    }
+
+    let focused = state.focus == Focus::Editor;
+    let border_color = if focused { theme.accent } else { theme.border };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(" Auth ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 4 || inner.height < 2 {
+        return;
+    }
+
+    let hint_area = Rect { y: inner.y + inner.height - 1, height: 1, ..inner };
+    let body_area = Rect { height: inner.height - 1, ..inner };
+
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+    let request = &tab.request;
+    let auth = &request.auth;
+
+    let mut hint_spans = vec![
+        Span::styled("←→", Style::default().fg(theme.text_dim)),
+        Span::styled(" type  ", Style::default().fg(theme.muted)),
+    ];
+    if !auth.is_none() {
+        hint_spans.extend([
+            Span::styled("j/k", Style::default().fg(theme.text_dim)),
+            Span::styled(" field  ", Style::default().fg(theme.muted)),
+            Span::styled("i", Style::default().fg(theme.text_dim)),
+            Span::styled(" edit  ", Style::default().fg(theme.muted)),
+            Span::styled("v", Style::default().fg(theme.text_dim)),
+            Span::styled(if request.auth_reveal { " hide" } else { " reveal" }, Style::default().fg(theme.muted)),
+        ]);
+    }
+    if matches!(auth, AuthConfig::ApiKey { .. }) {
+        hint_spans.extend([
+            Span::styled("  t", Style::default().fg(theme.text_dim)),
+            Span::styled(" header/query", Style::default().fg(theme.muted)),
+        ]);
+    }
+    if matches!(auth, AuthConfig::OAuth2 { .. }) {
+        hint_spans.extend([
+            Span::styled("  t", Style::default().fg(theme.text_dim)),
+            Span::styled(" grant  ", Style::default().fg(theme.muted)),
+            Span::styled("p", Style::default().fg(theme.text_dim)),
+            Span::styled(" keep token  ", Style::default().fg(theme.muted)),
+            Span::styled("x", Style::default().fg(theme.text_dim)),
+            Span::styled(" forget token", Style::default().fg(theme.muted)),
+        ]);
+    }
+    frame.render_widget(Paragraph::new(Line::from(hint_spans)).style(theme.hint_style()), hint_area);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<w$}", "Type", w = LABEL_W as usize), Style::default().fg(theme.muted)),
+            Span::styled("‹ ", Style::default().fg(theme.border)),
+            Span::styled(auth.label(), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(" ›", Style::default().fg(theme.border)),
+        ]),
+        Line::default(),
+    ];
+
+    for (i, (label, value, masked)) in auth.fields().into_iter().enumerate() {
+        let selected = i == request.auth_field;
+        let label_fg = if focused && selected { theme.highlight } else { theme.muted };
+        let value_fg = if focused && selected { theme.highlight } else { theme.text_dim };
+        let shown = if masked && !request.auth_reveal {
+            "•".repeat(value.chars().count())
+        } else {
+            value.to_string()
+        };
+        let row_bg = if selected { theme.surface } else { theme.bg };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<w$}", label, w = LABEL_W as usize), Style::default().fg(label_fg)),
+            Span::styled(shown, Style::default().fg(value_fg)),
+        ]).style(Style::default().bg(row_bg)));
+    }
+
+    match auth {
+        AuthConfig::None => lines.push(Line::from(Span::styled(
+            "This request is sent without authentication",
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
+        ))),
+        AuthConfig::ApiKey { in_header, .. } => {
+            let (on, off) = if *in_header { ("Header", "Query") } else { ("Query", "Header") };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<w$}", "Add to", w = LABEL_W as usize), Style::default().fg(theme.muted)),
+                Span::styled(on, Style::default().fg(theme.accent)),
+                Span::styled(format!("  {off}"), Style::default().fg(theme.border)),
+            ]));
+        }
+        AuthConfig::OAuth2 { grant, persist_token, token, .. } => {
+            let label = |text: &str| {
+                Span::styled(format!("{:<w$}", text, w = LABEL_W as usize), Style::default().fg(theme.muted))
+            };
+            let (on, off) = match grant {
+                OAuthGrant::ClientCredentials => ("client_credentials", "refresh_token"),
+                OAuthGrant::RefreshToken => ("refresh_token", "client_credentials"),
+            };
+            lines.push(Line::from(vec![
+                label("Grant"),
+                Span::styled(on, Style::default().fg(theme.accent)),
+                Span::styled(format!("  {off}"), Style::default().fg(theme.border)),
+            ]));
+            let now = Utc::now();
+            let status = match token {
+                None => Span::styled("none yet, fetched on send", Style::default().fg(theme.muted)),
+                Some(token) if !token.is_fresh(now) => {
+                    Span::styled("expired, refetched on send", Style::default().fg(theme.warning))
+                }
+                Some(token) => {
+                    let expiry = match token.expires_at {
+                        Some(at) => format!("cached, expires in {}", span((at - now).num_seconds())),
+                        None => "cached, no expiry".to_string(),
+                    };
+                    Span::styled(expiry, Style::default().fg(theme.success))
+                }
+            };
+            lines.push(Line::from(vec![label("Token"), status]));
+            let keep = if *persist_token { "saved with the collection" } else { "in memory only" };
+            lines.push(Line::from(vec![label("Keep"), Span::styled(keep, Style::default().fg(theme.text_dim))]));
+        }
+        _ => {}
+    }
+
+    frame.render_widget(Paragraph::new(lines), body_area);
+
+    if focused && state.mode == Mode::Insert
+        && let Some(position) = cursor_position(area, state)
+    {
+        frame.set_cursor_position(position);
+    }
+}
 
-pub fn render(frame: &mut Frame, area: Rect, _state: &AppState) {
-    let p = Paragraph::new("Auth — coming soon")
-        .alignment(Alignment::Center)
-        .style(Style::default().add_modifier(Modifier::DIM));
-    frame.render_widget(p, area);
+/// Screen position of the text cursor in the selected field when the editor
+/// is drawn in `area`, or `None` if the field is not visible.
+pub fn cursor_position(area: Rect, state: &AppState) -> Option<Position> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    if inner.width < 4 || inner.height < 2 {
+        return None;
+    }
+    let request = &state.active_tab()?.request;
+    let fields = request.auth.fields();
+    let (_, value, _) = fields.get(request.auth_field)?;
+    let row = FIELDS_TOP + request.auth_field as u16;
+    if row >= inner.height - 1 {
+        return None;
+    }
+    // Masked values draw one bullet per character, so chars() counts both
+    let col = value[..request.auth_cursor.min(value.len())].chars().count() as u16;
+    Some(Position { x: inner.x + LABEL_W + col, y: inner.y + row })
 }
@@ -2,7 +2,7 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Position, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
@@ -11,25 +11,30 @@ use crate::state::app_state::AppState;
 use crate::state::focus::Focus;
 use crate::state::mode::Mode;
 use crate::state::request_state::RequestBody;
+use crate::state::websocket::is_websocket_url;
 use crate::ui::highlight::highlight_text;
-use crate::ui::layout::{ACCENT_BLUE, BORDER_INACTIVE};
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     if area.width < 4 || area.height < 2 {
         return;
     }
 
     let focused = state.focus == Focus::Editor;
-    let border_color = if focused { ACCENT_BLUE } else { BORDER_INACTIVE };
+    let border_color = if focused { theme.accent } else { theme.border };
+    // On a ws:// tab the body is the message composer
+    let is_websocket = state.active_tab().is_some_and(|t| is_websocket_url(&t.request.url));
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .title(" Body ");
+        .title(if is_websocket { " Message  ·  Ctrl+R sends " } else { " Body " });
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    state.hit_areas.borrow_mut().body_editor = inner;
     if inner.width == 0 || inner.height == 0 {
         return;
     }
@@ -54,8 +59,8 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             Line::from(Span::styled(
                 "Press i to start editing…",
                 Style::default()
-                    .fg(Color::Rgb(86, 95, 137))
-                    .add_modifier(Modifier::DIM),
+                    .fg(theme.muted)
+                    .patch(theme.hint_style()),
             ))
         )
         .alignment(Alignment::Center);
@@ -63,21 +68,124 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
+    let cursor_pos = render_code(
+        frame,
+        inner,
+        state,
+        text,
+        lang,
+        cursor,
+        scroll,
+    );
+
+    // Show cursor when focused
+    if focused && let Some(pos) = cursor_pos {
+        frame.set_cursor_position(pos);
+    }
+}
+
+/// Draw `text` highlighted as `lang` in `area`, scrolled down `scroll` rows,
+/// with the line-number gutter if the workspace shows one. Returns the screen
+/// position of the byte offset `cursor` if it is visible.
+pub fn render_code(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    text: &str,
+    lang: &str,
+    cursor: usize,
+    scroll: u16,
+) -> Option<Position> {
+    let theme = theme(state);
+    let (cursor_row, cursor_col) = cursor_row_col(text, cursor);
+    let line_count = text.split('\n').count();
+    let text_area = render_gutter(frame, area, state, line_count, scroll, Some(cursor_row));
+
     let highlighted = highlight_text(text, lang);
     let para = Paragraph::new(highlighted).scroll((scroll, 0));
-    frame.render_widget(para, inner);
+    frame.render_widget(para, text_area);
 
-    // Show cursor when focused
-    if focused {
-        let (cursor_row, cursor_col) = cursor_row_col(text, cursor);
-        let visible_row = cursor_row.saturating_sub(scroll as usize);
-        if visible_row < inner.height as usize {
-            frame.set_cursor_position(Position {
-                x: inner.x + cursor_col as u16,
-                y: inner.y + visible_row as u16,
-            });
-        }
+    let visible_row = cursor_row.checked_sub(scroll as usize)?;
+    if visible_row >= area.height as usize {
+        return None;
+    }
+    let y = area.y + visible_row as u16;
+    // Current line across the full width, alongside its gutter number
+    if state.workspace.show_line_numbers {
+        frame.buffer_mut().set_style(Rect { y, height: 1, ..area }, Style::default().bg(theme.surface));
+    }
+    Some(Position { x: text_area.x + cursor_col as u16, y })
+}
+
+/// Draw 1-based line numbers for `line_count` lines scrolled down `scroll`
+/// rows down the left of `area`, if the workspace shows them, with
+/// `current_row` brighter. Returns the part of `area` left for the text.
+pub fn render_gutter(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    line_count: usize,
+    scroll: u16,
+    current_row: Option<usize>,
+) -> Rect {
+    let theme = theme(state);
+    let gutter_w = gutter_width(state.workspace.show_line_numbers, line_count, area.width);
+    if gutter_w == 0 {
+        return area;
+    }
+    let digits = (gutter_w - 1) as usize;
+    let first = scroll as usize;
+    let last = (first + area.height as usize).min(line_count);
+    let gutter_lines: Vec<Line> = (first..last)
+        .map(|row| {
+            let style = if Some(row) == current_row {
+                Style::default().fg(theme.text)
+            } else {
+                Style::default()
+                    .fg(theme.muted)
+                    .patch(theme.hint_style())
+            };
+            Line::from(Span::styled(format!("{:>digits$} ", row + 1), style))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(gutter_lines),
+        Rect { width: gutter_w, ..area },
+    );
+    Rect {
+        x: area.x + gutter_w,
+        width: area.width - gutter_w,
+        ..area
+    }
+}
+
+/// Line-number gutter width: digits of the last line number plus one space,
+/// or 0 when line numbers are hidden.
+fn gutter_width(line_numbers: bool, line_count: usize, inner_width: u16) -> u16 {
+    if !line_numbers {
+        return 0;
+    }
+    ((line_count.to_string().len() + 1) as u16).min(inner_width.saturating_sub(1))
+}
+
+/// Screen position of the text cursor when the editor is drawn in `area`,
+/// or `None` if it is scrolled out of view.
+pub fn cursor_position(area: Rect, state: &AppState) -> Option<Position> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let request = &state.active_tab()?.request;
+    let text = match &request.body {
+        RequestBody::Json(s) | RequestBody::Text(s) => s.as_str(),
+        _ => "",
+    };
+    let (row, col) = cursor_row_col(text, request.body_cursor);
+    let visible_row = row.checked_sub(request.body_scroll_offset as usize)?;
+    if visible_row >= inner.height as usize {
+        return None;
     }
+    Some(Position {
+        x: inner.x + gutter_width(state.workspace.show_line_numbers, text.split('\n').count(), inner.width) + col as u16,
+        y: inner.y + visible_row as u16,
+    })
 }
 
 /// Returns (row, col) for a byte offset in text, both 0-indexed.
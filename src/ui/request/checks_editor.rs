@@ -0,0 +1,182 @@
+// Request checks editor — declarative response assertions (status / JSONPath equals /
+// body contains)
+use ratatui::{
+    Frame,
+    layout::{Alignment, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::state::mode::Mode;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    if area.width < 4 || area.height < 2 {
+        return;
+    }
+
+    let focused = state.focus == Focus::Editor;
+    let border_color = if focused { theme.accent } else { theme.border };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(" Checks ");
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 4 || inner.height < 2 {
+        return;
+    }
+
+    // Reserve bottom line for hint bar
+    let hint_area = Rect { y: inner.y + inner.height - 1, height: 1, ..inner };
+    let body_area = Rect { height: inner.height - 1, ..inner };
+
+    // Hint bar
+    let hint_spans = vec![
+        Span::styled("a", Style::default().fg(theme.text_dim)),
+        Span::styled(" add  ", Style::default().fg(theme.muted)),
+        Span::styled("x", Style::default().fg(theme.text_dim)),
+        Span::styled(" del  ", Style::default().fg(theme.muted)),
+        Span::styled("Space", Style::default().fg(theme.text_dim)),
+        Span::styled(" toggle  ", Style::default().fg(theme.muted)),
+        Span::styled("t", Style::default().fg(theme.text_dim)),
+        Span::styled(" kind  ", Style::default().fg(theme.muted)),
+        Span::styled("←→", Style::default().fg(theme.text_dim)),
+        Span::styled(" col  ", Style::default().fg(theme.muted)),
+        Span::styled("i", Style::default().fg(theme.text_dim)),
+        Span::styled(" edit", Style::default().fg(theme.muted)),
+    ];
+    let hint = Paragraph::new(Line::from(hint_spans))
+        .style(theme.hint_style());
+    frame.render_widget(hint, hint_area);
+
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+    let request = &tab.request;
+
+    // Placeholder when no checks
+    if request.checks.is_empty() {
+        let placeholder = Paragraph::new(Line::from(Span::styled(
+            "Press a to add a check, or c in the response viewer to generate them",
+            Style::default()
+                .fg(theme.muted)
+                .patch(theme.hint_style()),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(placeholder, body_area);
+        return;
+    }
+
+    // Column layout: [checkbox=4] [kind=10] [path=rest/2] [sep=1] [expected=rest-path]
+    let checkbox_w: u16 = 4;
+    let kind_w: u16 = 10;
+    let sep_w: u16 = 1;
+    let rest = body_area.width.saturating_sub(checkbox_w + kind_w + sep_w);
+    let path_w = rest / 2;
+    let exp_w = rest - path_w;
+    let path_x = body_area.x + checkbox_w + kind_w;
+    let exp_x = path_x + path_w + sep_w;
+
+    let sel_row = request.checks_row;
+    let sel_col = request.checks_col;
+
+    for (i, check) in request.checks.iter().enumerate() {
+        let row_y = body_area.y + i as u16;
+        if row_y >= body_area.y + body_area.height {
+            break;
+        }
+
+        let is_selected = i == sel_row;
+        let row_bg = if is_selected { theme.surface } else { theme.bg };
+        let row_style = Style::default().bg(row_bg);
+
+        // Checkbox
+        let (check_str, check_fg) = if check.enabled {
+            ("[✓] ", theme.success)
+        } else {
+            ("[ ] ", theme.muted)
+        };
+        let check_rect = Rect { x: body_area.x, y: row_y, width: checkbox_w, height: 1 };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                check_str,
+                Style::default().fg(check_fg).bg(row_bg),
+            ))),
+            check_rect,
+        );
+
+        // Kind
+        let kind_rect = Rect { x: body_area.x + checkbox_w, y: row_y, width: kind_w, height: 1 };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                check.kind.as_str(),
+                Style::default().fg(theme.purple),
+            )))
+            .style(row_style),
+            kind_rect,
+        );
+
+        // Path column — only JSONPath checks have a path
+        let path_active = is_selected && sel_col == 0;
+        let path_fg = if focused && path_active { theme.highlight } else { theme.text_dim };
+        let path_span = if !check.kind.has_path() {
+            Span::styled("—", Style::default().fg(theme.border))
+        } else {
+            Span::styled(check.path.as_str(), Style::default().fg(path_fg))
+        };
+        let path_rect = Rect { x: path_x, y: row_y, width: path_w, height: 1 };
+        frame.render_widget(Paragraph::new(Line::from(path_span)).style(row_style), path_rect);
+
+        // Separator
+        let sep_rect = Rect { x: path_x + path_w, y: row_y, width: sep_w, height: 1 };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "│",
+                Style::default().fg(theme.border).bg(row_bg),
+            ))),
+            sep_rect,
+        );
+
+        // Expected column
+        let exp_active = is_selected && sel_col == 1;
+        let exp_fg = if focused && exp_active { theme.highlight } else { theme.text_dim };
+        let exp_rect = Rect { x: exp_x, y: row_y, width: exp_w, height: 1 };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                check.expected.as_str(),
+                Style::default().fg(exp_fg),
+            )))
+            .style(row_style),
+            exp_rect,
+        );
+    }
+
+    // Cursor in Insert mode
+    if focused
+        && state.mode == Mode::Insert
+        && let Some(check) = request.checks.get(sel_row)
+    {
+        let cursor = request.checks_cursor;
+        let (cell_x, text) = if sel_col == 0 {
+            (path_x, check.path.as_str())
+        } else {
+            (exp_x, check.expected.as_str())
+        };
+        let col_offset = text[..cursor.min(text.len())].chars().count() as u16;
+        let row_y = body_area.y + sel_row as u16;
+        if row_y < body_area.y + body_area.height {
+            frame.set_cursor_position(Position {
+                x: cell_x + col_offset,
+                y: row_y,
+            });
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// Request description ("Docs" tab)
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::state::mode::Mode;
+use crate::ui::request::body_editor::{cursor_row_col, render_code};
+use crate::ui::theme::theme;
+
+/// The request's description: wrapped and read-only outside Insert mode,
+/// as an editor with a cursor while it is being written.
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    if area.width < 4 || area.height < 2 {
+        return;
+    }
+
+    let focused = state.focus == Focus::Editor;
+    let border_color = if focused { theme.accent } else { theme.border };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(" Docs ");
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 4 || inner.height < 1 {
+        return;
+    }
+
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+    let request = &tab.request;
+    let text = &request.description;
+    let editing = focused && state.mode == Mode::Insert;
+
+    if text.is_empty() && !editing {
+        let placeholder = Paragraph::new(Line::from(Span::styled(
+            "Press i to describe this request: what it needs set up, what it returns…",
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
+        )))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        frame.render_widget(placeholder, inner);
+        return;
+    }
+
+    if !editing {
+        let para = Paragraph::new(text.as_str())
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(para, inner);
+        return;
+    }
+
+    // Keep the cursor row in view
+    let (cursor_row, _) = cursor_row_col(text, request.description_cursor);
+    let scroll = cursor_row.saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+    if let Some(pos) = render_code(frame, inner, state, text, "txt", request.description_cursor, scroll) {
+        frame.set_cursor_position(pos);
+    }
+}
@@ -1,23 +1,25 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Position, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
 use crate::state::app_state::AppState;
+use crate::state::collection::inherited_headers;
 use crate::state::focus::Focus;
 use crate::state::mode::Mode;
-use crate::ui::layout::{ACCENT_BLUE, BORDER_INACTIVE};
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     if area.width < 4 || area.height < 2 {
         return;
     }
 
     let focused = state.focus == Focus::Editor;
-    let border_color = if focused { ACCENT_BLUE } else { BORDER_INACTIVE };
+    let border_color = if focused { theme.accent } else { theme.border };
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -37,33 +39,40 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // Hint bar
     let hint_spans = vec![
-        Span::styled("a", Style::default().fg(Color::Rgb(169, 177, 214))),
-        Span::styled(" add  ", Style::default().fg(Color::Rgb(100, 110, 140))),
-        Span::styled("x", Style::default().fg(Color::Rgb(169, 177, 214))),
-        Span::styled(" del  ", Style::default().fg(Color::Rgb(100, 110, 140))),
-        Span::styled("Space", Style::default().fg(Color::Rgb(169, 177, 214))),
-        Span::styled(" toggle  ", Style::default().fg(Color::Rgb(100, 110, 140))),
-        Span::styled("←→", Style::default().fg(Color::Rgb(169, 177, 214))),
-        Span::styled(" col  ", Style::default().fg(Color::Rgb(100, 110, 140))),
-        Span::styled("i", Style::default().fg(Color::Rgb(169, 177, 214))),
-        Span::styled(" edit", Style::default().fg(Color::Rgb(100, 110, 140))),
+        Span::styled("a", Style::default().fg(theme.text_dim)),
+        Span::styled(" add  ", Style::default().fg(theme.muted)),
+        Span::styled("x", Style::default().fg(theme.text_dim)),
+        Span::styled(" del  ", Style::default().fg(theme.muted)),
+        Span::styled("Space", Style::default().fg(theme.text_dim)),
+        Span::styled(" toggle  ", Style::default().fg(theme.muted)),
+        Span::styled("←→", Style::default().fg(theme.text_dim)),
+        Span::styled(" col  ", Style::default().fg(theme.muted)),
+        Span::styled("i", Style::default().fg(theme.text_dim)),
+        Span::styled(" edit", Style::default().fg(theme.muted)),
     ];
     let hint = Paragraph::new(Line::from(hint_spans))
-        .style(Style::default().add_modifier(Modifier::DIM));
+        .style(theme.hint_style());
     frame.render_widget(hint, hint_area);
 
     let Some(tab) = state.active_tab() else {
         return;
     };
     let request = &tab.request;
+    // Default headers of the tab's collection and folders, listed after the
+    // request's own and sent first
+    let inherited = tab
+        .collection_id
+        .as_deref()
+        .map(|id| inherited_headers(&state.workspace.collections, id))
+        .unwrap_or_default();
 
     // Placeholder when no headers
-    if request.headers.is_empty() {
+    if request.headers.is_empty() && inherited.is_empty() {
         let placeholder = Paragraph::new(Line::from(Span::styled(
             "Press a to add a header",
             Style::default()
-                .fg(Color::Rgb(86, 95, 137))
-                .add_modifier(Modifier::DIM),
+                .fg(theme.muted)
+                .patch(theme.hint_style()),
         )))
         .alignment(Alignment::Center);
         frame.render_widget(placeholder, body_area);
@@ -87,14 +96,14 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         }
 
         let is_selected = i == sel_row;
-        let row_bg = if is_selected { Color::Rgb(41, 45, 62) } else { Color::Reset };
+        let row_bg = if is_selected { theme.surface } else { theme.bg };
         let row_style = Style::default().bg(row_bg);
 
         // Checkbox
         let (check_str, check_fg) = if pair.enabled {
-            ("[✓] ", Color::Green)
+            ("[✓] ", theme.success)
         } else {
-            ("[ ] ", Color::Rgb(100, 110, 140))
+            ("[ ] ", theme.muted)
         };
         let check_rect = Rect { x: body_area.x, y: row_y, width: checkbox_w, height: 1 };
         frame.render_widget(
@@ -107,7 +116,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
 
         // Key column
         let key_active = is_selected && sel_col == 0;
-        let key_fg = if focused && key_active { Color::White } else { Color::Rgb(169, 177, 214) };
+        let key_fg = if focused && key_active { theme.highlight } else { theme.text_dim };
         let key_rect = Rect { x: body_area.x + checkbox_w, y: row_y, width: key_w, height: 1 };
         frame.render_widget(
             Paragraph::new(Line::from(Span::styled(
@@ -128,14 +137,14 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         frame.render_widget(
             Paragraph::new(Line::from(Span::styled(
                 "│",
-                Style::default().fg(BORDER_INACTIVE).bg(row_bg),
+                Style::default().fg(theme.border).bg(row_bg),
             ))),
             sep_rect,
         );
 
         // Value column
         let val_active = is_selected && sel_col == 1;
-        let val_fg = if focused && val_active { Color::White } else { Color::Rgb(169, 177, 214) };
+        let val_fg = if focused && val_active { theme.highlight } else { theme.text_dim };
         let val_rect = Rect {
             x: body_area.x + checkbox_w + key_w + sep_w,
             y: row_y,
@@ -152,6 +161,30 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         );
     }
 
+    // Inherited rows: greyed out, not selectable, struck through when an
+    // enabled header of the request replaces them
+    for (i, pair) in inherited.iter().enumerate() {
+        let row_y = body_area.y + (request.headers.len() + i) as u16;
+        if row_y >= body_area.y + body_area.height {
+            break;
+        }
+        let overridden = request
+            .headers
+            .iter()
+            .any(|h| h.enabled && h.key.eq_ignore_ascii_case(&pair.key));
+        let mut style = Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC);
+        if overridden {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        let line = Line::from(vec![
+            Span::styled(" ⇣  ", Style::default().fg(theme.muted)),
+            Span::styled(format!("{:<w$.w$}", pair.key, w = key_w as usize), style),
+            Span::styled("│", Style::default().fg(theme.border)),
+            Span::styled(pair.value.clone(), style),
+        ]);
+        frame.render_widget(Paragraph::new(line), Rect { y: row_y, height: 1, ..body_area });
+    }
+
     // Cursor in Insert mode
     if focused && state.mode == Mode::Insert {
         if let Some(pair) = request.headers.get(sel_row) {
@@ -172,3 +205,29 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         }
     }
 }
+
+/// Screen position of the text cursor in the selected cell when the editor is
+/// drawn in `area`, or `None` if the row is not visible.
+pub fn cursor_position(area: Rect, state: &AppState) -> Option<Position> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    if inner.width < 4 || inner.height < 2 {
+        return None;
+    }
+    let body_height = inner.height - 1;
+    let request = &state.active_tab()?.request;
+    let pair = request.headers.get(request.headers_row)?;
+
+    let checkbox_w: u16 = 4;
+    let sep_w: u16 = 1;
+    let key_w = inner.width.saturating_sub(checkbox_w + sep_w) / 2;
+    let (cell_x, text) = if request.headers_col == 0 {
+        (inner.x + checkbox_w, pair.key.as_str())
+    } else {
+        (inner.x + checkbox_w + key_w + sep_w, pair.value.as_str())
+    };
+    if request.headers_row >= body_height as usize {
+        return None;
+    }
+    let col = text[..request.headers_cursor.min(text.len())].chars().count() as u16;
+    Some(Position { x: cell_x + col, y: inner.y + request.headers_row as u16 })
+}
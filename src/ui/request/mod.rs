@@ -5,3 +5,5 @@ pub mod params_editor;
 pub mod body_editor;
 pub mod auth_editor;
 pub mod scripts_editor;
+pub mod checks_editor;
+pub mod docs_editor;
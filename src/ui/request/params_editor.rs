@@ -1,10 +1,11 @@
 // Query parameters key/value editor
-use ratatui::{Frame, layout::{Alignment, Rect}, style::{Modifier, Style}, widgets::Paragraph};
+use ratatui::{Frame, layout::{Alignment, Rect}, widgets::Paragraph};
 use crate::state::app_state::AppState;
+use crate::ui::theme::theme;
 
-pub fn render(frame: &mut Frame, area: Rect, _state: &AppState) {
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     let p = Paragraph::new("Params")
         .alignment(Alignment::Center)
-        .style(Style::default().add_modifier(Modifier::DIM));
+        .style(theme(state).hint_style());
     frame.render_widget(p, area);
 }
@@ -1,10 +1,118 @@
 // Pre/post request scripts editor (Rhai)
-use ratatui::{Frame, layout::{Alignment, Rect}, style::{Modifier, Style}, widgets::Paragraph};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
 use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::state::mode::Mode;
+use crate::state::request_state::ScriptKind;
+use crate::ui::request::body_editor::{cursor_row_col, render_code};
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    if area.width < 4 || area.height < 2 {
+        return;
+    }
+
+    let focused = state.focus == Focus::Editor;
+    let border_color = if focused { theme.accent } else { theme.border };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(" Scripts ");
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 4 || inner.height < 3 {
+        return;
+    }
+
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+    let request = &tab.request;
+    let kind = &request.scripts_kind;
+
+    // Toggle row at the top, hint bar at the bottom
+    let toggle_area = Rect { height: 1, ..inner };
+    let hint_area = Rect { y: inner.y + inner.height - 1, height: 1, ..inner };
+    let editor_area = Rect { y: inner.y + 1, height: inner.height - 2, ..inner };
+
+    let mut toggle_spans = Vec::new();
+    for option in [ScriptKind::PreRequest, ScriptKind::PostResponse] {
+        let style = if &option == kind {
+            Style::default()
+                .fg(theme.info)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(theme.border)
+        };
+        toggle_spans.push(Span::styled(option.label(), style));
+        toggle_spans.push(Span::raw("  "));
+    }
+    frame.render_widget(Paragraph::new(Line::from(toggle_spans)), toggle_area);
+
+    let hint_spans = vec![
+        Span::styled("t", Style::default().fg(theme.text_dim)),
+        Span::styled(" switch script  ", Style::default().fg(theme.muted)),
+        Span::styled("i", Style::default().fg(theme.text_dim)),
+        Span::styled(" edit  ", Style::default().fg(theme.muted)),
+        Span::styled("#", Style::default().fg(theme.text_dim)),
+        Span::styled(" line numbers", Style::default().fg(theme.muted)),
+    ];
+    frame.render_widget(
+        Paragraph::new(Line::from(hint_spans)).style(theme.hint_style()),
+        hint_area,
+    );
+
+    let text = request.scripts.get(kind);
+    if text.is_empty() && state.mode != Mode::Insert {
+        let example = match kind {
+            ScriptKind::PreRequest => "pm.request.headers.add(\"X-Trace\", \"…\")",
+            ScriptKind::PostResponse => "pm.test(\"ok\", || pm.expect(pm.response.status).to.equal(200))",
+        };
+        let placeholder = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "Press i to start editing…",
+                Style::default()
+                    .fg(theme.muted)
+                    .patch(theme.hint_style()),
+            )),
+            Line::from(Span::styled(
+                example,
+                Style::default()
+                    .fg(theme.muted)
+                    .patch(theme.hint_style()),
+            )),
+        ])
+        .alignment(Alignment::Center);
+        frame.render_widget(placeholder, editor_area);
+        return;
+    }
+
+    // Keep the cursor row in view
+    let (cursor_row, _) = cursor_row_col(text, request.scripts_cursor);
+    let scroll = cursor_row.saturating_sub(editor_area.height.saturating_sub(1) as usize) as u16;
+
+    let cursor_pos = render_code(
+        frame,
+        editor_area,
+        state,
+        text,
+        "js",
+        request.scripts_cursor,
+        scroll,
+    );
 
-pub fn render(frame: &mut Frame, area: Rect, _state: &AppState) {
-    let p = Paragraph::new("Scripts — coming soon")
-        .alignment(Alignment::Center)
-        .style(Style::default().add_modifier(Modifier::DIM));
-    frame.render_widget(p, area);
+    if focused && let Some(pos) = cursor_pos {
+        frame.set_cursor_position(pos);
+    }
 }
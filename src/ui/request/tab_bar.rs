@@ -1,46 +1,148 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
 };
 
+use humansize::{format_size, DECIMAL};
+
 use crate::state::app_state::{ActiveTab, AppState};
 use crate::state::focus::Focus;
+use crate::state::request_state::{AuthConfig, RequestBody, RequestState};
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let tabs = [
         ("Headers", ActiveTab::Headers),
         ("Body", ActiveTab::Body),
         ("Auth", ActiveTab::Auth),
         ("Params", ActiveTab::Params),
         ("Scripts", ActiveTab::Scripts),
+        ("Checks", ActiveTab::Checks),
+        ("Docs", ActiveTab::Docs),
     ];
 
     let tab_focused = state.focus == Focus::TabBar;
     let active_tab = state.active_tab().map(|t| &t.active_tab);
 
     let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut hits = Vec::new();
+    let mut x = area.x;
     for (i, (name, tab)) in tabs.iter().enumerate() {
         if i > 0 {
             spans.push(Span::raw("  "));
+            x = x.saturating_add(2);
         }
         let is_active = active_tab == Some(tab);
         let style = if is_active {
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.info)
                 .add_modifier(Modifier::UNDERLINED)
         } else {
-            Style::default().fg(Color::Rgb(65, 72, 104))
+            Style::default().fg(theme.border)
         };
         let label: String = if is_active && tab_focused {
             format!("[{name}]")
         } else {
             name.to_string()
         };
+        let badge = state.active_tab().map(|t| tab_badge(tab, &t.request));
+        let badge_text = badge.as_ref().map_or(String::new(), |(text, _)| format!(" ({text})"));
+        let width = (label.chars().count() + badge_text.chars().count()) as u16;
+        hits.push((Rect { x, y: area.y, width, height: 1 }.intersection(area), tab.clone()));
+        x = x.saturating_add(width);
         spans.push(Span::styled(label, style));
+        if let Some((_, empty)) = badge {
+            let badge_style = if empty {
+                Style::default().fg(theme.border).patch(theme.hint_style())
+            } else {
+                Style::default().fg(theme.muted)
+            };
+            spans.push(Span::styled(badge_text, badge_style));
+        }
     }
+    state.hit_areas.borrow_mut().request_tabs = hits;
 
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
+
+/// Summary shown in parentheses after a tab label, and whether it describes
+/// nothing (drawn dimmed). Disabled rows are not counted.
+pub fn tab_badge(tab: &ActiveTab, request: &RequestState) -> (String, bool) {
+    let count = |n: usize| (n.to_string(), n == 0);
+    match tab {
+        ActiveTab::Headers => count(request.headers.iter().filter(|h| h.enabled).count()),
+        ActiveTab::Params => count(request.params.iter().filter(|p| p.enabled).count()),
+        ActiveTab::Checks => count(request.checks.iter().filter(|c| c.enabled).count()),
+        ActiveTab::Body => {
+            let size = |bytes: usize| (format_size(bytes, DECIMAL.decimal_places(1)), bytes == 0);
+            match &request.body {
+                RequestBody::None => size(0),
+                RequestBody::Text(text) | RequestBody::Json(text) => size(text.len()),
+                RequestBody::Binary(bytes) => size(bytes.len()),
+                RequestBody::Form(pairs) => {
+                    let n = pairs.iter().filter(|p| p.enabled).count();
+                    (format!("{n} fields"), n == 0)
+                }
+            }
+        }
+        ActiveTab::Auth => {
+            let kind = match request.auth {
+                AuthConfig::None => "None",
+                AuthConfig::Bearer { .. } => "Bearer",
+                AuthConfig::Basic { .. } => "Basic",
+                AuthConfig::ApiKey { .. } => "API key",
+                AuthConfig::OAuth2 { .. } => "OAuth 2",
+            };
+            (kind.to_string(), request.auth.is_none())
+        }
+        ActiveTab::Scripts => {
+            let empty = request.scripts.pre_request.trim().is_empty()
+                && request.scripts.post_response.trim().is_empty();
+            ((if empty { "–" } else { "•" }).to_string(), empty)
+        }
+        ActiveTab::Docs => {
+            let empty = request.description.trim().is_empty();
+            ((if empty { "–" } else { "•" }).to_string(), empty)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::check::Check;
+    use crate::state::request_state::KeyValuePair;
+
+    #[test]
+    fn test_tab_badge_counts_enabled_rows() {
+        let mut request = RequestState::default();
+        assert_eq!(tab_badge(&ActiveTab::Headers, &request), ("0".to_string(), true));
+
+        request.headers.push(KeyValuePair::new("Accept", "*/*"));
+        request.headers.push(KeyValuePair { enabled: false, ..KeyValuePair::new("X-Debug", "1") });
+        request.params.push(KeyValuePair::new("page", "2"));
+        request.checks.push(Check { enabled: false, ..Check::status(200) });
+        assert_eq!(tab_badge(&ActiveTab::Headers, &request), ("1".to_string(), false));
+        assert_eq!(tab_badge(&ActiveTab::Params, &request), ("1".to_string(), false));
+        assert_eq!(tab_badge(&ActiveTab::Checks, &request), ("0".to_string(), true));
+    }
+
+    #[test]
+    fn test_tab_badge_body_auth_scripts() {
+        let mut request = RequestState::default();
+        assert_eq!(tab_badge(&ActiveTab::Body, &request), ("0 B".to_string(), true));
+        assert_eq!(tab_badge(&ActiveTab::Auth, &request), ("None".to_string(), true));
+        assert_eq!(tab_badge(&ActiveTab::Scripts, &request), ("–".to_string(), true));
+
+        request.body = RequestBody::Json("x".repeat(1200));
+        request.auth = AuthConfig::Bearer { token: String::new() };
+        request.scripts.post_response = "pm.test(\"ok\", () => true);".to_string();
+        assert_eq!(tab_badge(&ActiveTab::Body, &request), ("1.2 kB".to_string(), false));
+        assert_eq!(tab_badge(&ActiveTab::Auth, &request), ("Bearer".to_string(), false));
+        assert_eq!(tab_badge(&ActiveTab::Scripts, &request), ("•".to_string(), false));
+    }
+}
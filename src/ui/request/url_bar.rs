@@ -1,40 +1,23 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
 use crate::env::interpolator::parse_vars;
-use crate::env::resolver::resolver_from_state;
+use crate::env::resolver::active_tab_resolver;
 use crate::env::resolver::VarStatus;
 use crate::state::app_state::{AppState, RequestStatus};
 use crate::state::focus::Focus;
-use crate::state::mode::Mode;
-use crate::state::request_state::HttpMethod;
-use super::super::layout::{ACCENT_BLUE, BORDER_INACTIVE, SPINNER_FRAMES};
-
-// TokyoNight colors for variable highlighting
-const ENV_VAR_RESOLVED: Color = Color::Rgb(42, 195, 222);   // #2ac3de cyan
-const ENV_VAR_UNRESOLVED: Color = Color::Rgb(247, 118, 142); // #f7768e red
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-
-fn method_color(method: &HttpMethod) -> Color {
-    match method {
-        HttpMethod::Get => Color::Rgb(115, 218, 202),
-        HttpMethod::Post => Color::Rgb(158, 206, 106),
-        HttpMethod::Put => Color::Rgb(224, 175, 104),
-        HttpMethod::Patch => Color::Rgb(187, 154, 247),
-        HttpMethod::Delete => Color::Rgb(247, 118, 142),
-        HttpMethod::Head => Color::Rgb(122, 162, 247),
-        HttpMethod::Options => Color::Rgb(65, 72, 104),
-    }
-}
+use super::super::layout::SPINNER_FRAMES;
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let focused = matches!(state.focus, Focus::UrlBar);
-    let border_color = if focused { ACCENT_BLUE } else { BORDER_INACTIVE };
+    let border_color = if focused { theme.accent } else { theme.border };
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -62,7 +45,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         .split(inner);
 
     // Method badge
-    let mc = method_color(&request.method);
+    let mc = theme.method_color(request.method.as_str());
     let method_para = Paragraph::new(Line::from(Span::styled(
         request.method.as_str(),
         Style::default().fg(mc).add_modifier(Modifier::BOLD),
@@ -71,12 +54,14 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // Separator
     frame.render_widget(
-        Paragraph::new(Span::styled("│", Style::default().fg(BORDER_INACTIVE))),
+        Paragraph::new(Span::styled("│", Style::default().fg(theme.border))),
         chunks[1],
     );
 
     // URL input area — split vertically if there's room for ghost text
     let url_area = chunks[2];
+    state.hit_areas.borrow_mut().url_input = url_area;
+    let scroll = if focused { request.url_scroll_offset as u16 } else { 0 };
     let has_vars = !parse_vars(&request.url).is_empty();
     if url_area.height >= 2 && has_vars {
         let url_chunks = Layout::default()
@@ -84,23 +69,23 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             .constraints([Constraint::Length(1), Constraint::Length(1)])
             .split(url_area);
         let url_line = build_url_line(state, focused);
-        frame.render_widget(Paragraph::new(url_line), url_chunks[0]);
+        frame.render_widget(Paragraph::new(url_line).scroll((0, scroll)), url_chunks[0]);
         // Ghost resolved text
-        let resolver = resolver_from_state(state);
+        let resolver = active_tab_resolver(state);
         let resolved = resolver.resolve_for_send(&request.url);
         let ghost_line = Line::from(vec![
-            Span::styled("→ ", Style::default().fg(TEXT_MUTED)),
-            Span::styled(resolved, Style::default().fg(TEXT_MUTED)),
+            Span::styled("→ ", Style::default().fg(theme.muted)),
+            Span::styled(resolved, Style::default().fg(theme.muted)),
         ]);
         frame.render_widget(Paragraph::new(ghost_line), url_chunks[1]);
     } else {
         let url_line = build_url_line(state, focused);
-        frame.render_widget(Paragraph::new(url_line), url_area);
+        frame.render_widget(Paragraph::new(url_line).scroll((0, scroll)), url_area);
     }
 
     // Separator
     frame.render_widget(
-        Paragraph::new(Span::styled("│", Style::default().fg(BORDER_INACTIVE))),
+        Paragraph::new(Span::styled("│", Style::default().fg(theme.border))),
         chunks[3],
     );
 
@@ -112,7 +97,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             frame.render_widget(
                 Paragraph::new(Line::from(Span::styled(
                     format!("{} ..", SPINNER_FRAMES[idx]),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.warning),
                 ))),
                 chunks[4],
             );
@@ -121,7 +106,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             frame.render_widget(
                 Paragraph::new(Line::from(Span::styled(
                     "Send ↵",
-                    Style::default().fg(Color::Rgb(158, 206, 106)),
+                    Style::default().fg(theme.success),
                 ))),
                 chunks[4],
             );
@@ -129,11 +114,43 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     }
 }
 
+/// Screen position of the URL cursor when the bar is drawn in `area`.
+pub fn cursor_position(area: Rect, state: &AppState) -> Option<Position> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let request = &state.active_tab()?.request;
+    // Method badge (9) + separator (1) precede the URL
+    let col = cursor_column(&request.url, request.url_cursor)
+        .saturating_sub(request.url_scroll_offset) as u16;
+    Some(Position { x: inner.x + 10 + col, y: inner.y })
+}
+
+/// Column of byte offset `cursor` in `url`.
+pub fn cursor_column(url: &str, cursor: usize) -> usize {
+    url[..cursor.min(url.len())].chars().count()
+}
+
+/// Scroll offset that keeps the cursor at `column` visible in a URL field
+/// `width` columns wide, moving `offset` only when the cursor has crossed
+/// an edge. The last column is left for the cursor block past the end.
+pub fn scroll_to_cursor(offset: usize, column: usize, width: usize) -> usize {
+    if width == 0 {
+        return offset;
+    }
+    if column < offset {
+        column
+    } else if column >= offset + width {
+        column + 1 - width
+    } else {
+        offset
+    }
+}
+
 fn build_url_line(state: &AppState, focused: bool) -> Line<'static> {
+    let theme = theme(state);
     let Some(tab) = state.active_tab() else {
         return Line::from(Span::styled(
             "No active tab",
-            Style::default().fg(Color::Rgb(65, 72, 104)),
+            Style::default().fg(theme.border),
         ));
     };
     let url = &tab.request.url;
@@ -142,14 +159,31 @@ fn build_url_line(state: &AppState, focused: bool) -> Line<'static> {
     if url.is_empty() {
         return Line::from(Span::styled(
             "Enter URL or paste text…",
-            Style::default().fg(Color::Rgb(65, 72, 104)),
+            Style::default().fg(theme.border),
         ));
     }
 
     let var_spans = parse_vars(url);
 
-    if matches!(state.mode, Mode::Insert) && focused {
-        // Insert mode with cursor — show cursor block, and color variables
+    // The cursor shows in Normal mode too, where w / b / e / 0 / $ move it
+    if focused {
+        // Selected text is drawn inverted, ending at the cursor block
+        if let Some(range) = tab.request.url_selection() {
+            let (cursor_char, after) = match url[range.end..].chars().next() {
+                Some(ch) => (ch.to_string(), url[range.end + ch.len_utf8()..].to_string()),
+                None => (" ".to_string(), String::new()),
+            };
+            return Line::from(vec![
+                Span::raw(url[..range.start].to_string()),
+                Span::styled(
+                    url[range.clone()].to_string(),
+                    Style::default().fg(theme.text).add_modifier(Modifier::REVERSED),
+                ),
+                Span::styled(cursor_char, Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg)),
+                Span::raw(after),
+            ]);
+        }
+        // Cursor block, and color variables
         if var_spans.is_empty() {
             // No variables: simple cursor rendering
             let before = url[..cursor].to_string();
@@ -162,7 +196,7 @@ fn build_url_line(state: &AppState, focused: bool) -> Line<'static> {
             };
             Line::from(vec![
                 Span::raw(before),
-                Span::styled(cursor_char, Style::default().bg(Color::White).fg(Color::Black)),
+                Span::styled(cursor_char, Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg)),
                 Span::raw(after),
             ])
         } else {
@@ -180,7 +214,8 @@ fn build_url_line(state: &AppState, focused: bool) -> Line<'static> {
 
 /// Build a highlighted URL line for normal mode (no cursor block).
 fn build_highlighted_url(url: &str, var_spans: &[(usize, usize, String)], state: &AppState) -> Line<'static> {
-    let resolver = resolver_from_state(state);
+    let theme = theme(state);
+    let resolver = active_tab_resolver(state);
     let mut spans = Vec::new();
     let mut last = 0;
 
@@ -190,7 +225,7 @@ fn build_highlighted_url(url: &str, var_spans: &[(usize, usize, String)], state:
         }
         let resolved = resolver.resolve(&url[*start..*end]);
         let is_resolved = resolved.spans.first().map(|s| !matches!(s.status, VarStatus::Unresolved)).unwrap_or(false);
-        let final_color = if is_resolved { ENV_VAR_RESOLVED } else { ENV_VAR_UNRESOLVED };
+        let final_color = if is_resolved { theme.var_resolved } else { theme.var_unresolved };
         spans.push(Span::styled(
             format!("{{{{{}}}}}", name),
             Style::default().fg(final_color),
@@ -211,7 +246,8 @@ fn build_highlighted_url_with_cursor(
     var_spans: &[(usize, usize, String)],
     state: &AppState,
 ) -> Line<'static> {
-    let resolver = resolver_from_state(state);
+    let theme = theme(state);
+    let resolver = active_tab_resolver(state);
     let mut spans: Vec<Span<'static>> = Vec::new();
     let mut last = 0;
     let mut cursor_placed = false;
@@ -229,7 +265,7 @@ fn build_highlighted_url_with_cursor(
                 (" ".to_string(), String::new())
             };
             if !before.is_empty() { spans.push(Span::raw(before)); }
-            spans.push(Span::styled(cur_char, Style::default().bg(Color::White).fg(Color::Black)));
+            spans.push(Span::styled(cur_char, Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg)));
             if !after.is_empty() { spans.push(Span::raw(after)); }
             *placed = true;
         } else {
@@ -249,14 +285,14 @@ fn build_highlighted_url_with_cursor(
             let resolved = resolver.resolve(&url[*start..*end]);
             resolved.spans.first().map(|s| !matches!(s.status, VarStatus::Unresolved)).unwrap_or(false)
         };
-        let final_color = if is_resolved { ENV_VAR_RESOLVED } else { ENV_VAR_UNRESOLVED };
+        let final_color = if is_resolved { theme.var_resolved } else { theme.var_unresolved };
 
         // Check if cursor is inside the variable placeholder
         if !cursor_placed && cursor >= *start && cursor < *end {
             // Place cursor block on the opening `{`
             spans.push(Span::styled(
                 format!("{{{{{}}}}}", name),
-                Style::default().fg(final_color).bg(Color::Rgb(60, 60, 80)),
+                Style::default().fg(final_color).bg(theme.surface),
             ));
             cursor_placed = true;
         } else {
@@ -277,8 +313,27 @@ fn build_highlighted_url_with_cursor(
 
     // If cursor is at the very end
     if !cursor_placed {
-        spans.push(Span::styled(" ", Style::default().bg(Color::White).fg(Color::Black)));
+        spans.push(Span::styled(" ", Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg)));
     }
 
     Line::from(spans)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_to_cursor_moves_only_past_edges() {
+        // 10 columns visible, starting at column 0
+        assert_eq!(scroll_to_cursor(0, 5, 10), 0);
+        // Past the right edge: cursor lands in the last column
+        assert_eq!(scroll_to_cursor(0, 10, 10), 1);
+        assert_eq!(scroll_to_cursor(1, 25, 10), 16);
+        // Moving back inside the window doesn't scroll
+        assert_eq!(scroll_to_cursor(16, 20, 10), 16);
+        // Past the left edge
+        assert_eq!(scroll_to_cursor(16, 3, 10), 3);
+        assert_eq!(cursor_column("é/{{x}}", "é/".len()), 2);
+    }
+}
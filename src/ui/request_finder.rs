@@ -0,0 +1,172 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::state::collection::CollectionItem;
+use crate::ui::popup::centered_rect;
+use crate::util::fuzzy::fuzzy_match;
+use crate::ui::theme::{theme, Theme};
+
+/// A request offered by the Ctrl+P finder.
+#[derive(Debug, Clone)]
+pub struct FinderEntry {
+    pub id: String,
+    pub name: String,
+    pub method: String,
+    /// "Collection / Folder / Request"
+    pub path: String,
+    /// Char indices into `path` matched by the query
+    pub matched: Vec<usize>,
+}
+
+/// Every request in the workspace matching the finder query, best match first.
+/// Ties keep sidebar order.
+pub fn finder_matches(state: &AppState) -> Vec<FinderEntry> {
+    request_matches(state, &state.request_finder.query)
+}
+
+/// Every request in the workspace matching `query`, best match first.
+pub fn request_matches(state: &AppState, query: &str) -> Vec<FinderEntry> {
+    let mut all = Vec::new();
+    for col in &state.workspace.collections {
+        collect_requests(&col.items, &col.name, &mut all);
+    }
+
+    let mut scored: Vec<(i64, FinderEntry)> = all
+        .into_iter()
+        .filter_map(|mut entry| {
+            let m = fuzzy_match(query, &entry.path)?;
+            entry.matched = m.indices;
+            Some((m.score, entry))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, e)| e).collect()
+}
+
+fn collect_requests(items: &[CollectionItem], prefix: &str, out: &mut Vec<FinderEntry>) {
+    for item in items {
+        match item {
+            CollectionItem::Folder(f) => {
+                collect_requests(&f.items, &format!("{prefix} / {}", f.name), out);
+            }
+            CollectionItem::Request(r) => out.push(FinderEntry {
+                id: r.id.clone(),
+                name: r.name.clone(),
+                method: r.method.clone(),
+                path: format!("{prefix} / {}", r.name),
+                matched: Vec::new(),
+            }),
+        }
+    }
+}
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Go to Request (Ctrl+P) ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Search row
+    let finder = &state.request_finder;
+    let search_line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(finder.query.clone(), Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(Paragraph::new(search_line), chunks[0]);
+    let col_offset = finder.query[..finder.cursor.min(finder.query.len())]
+        .chars()
+        .count() as u16;
+    frame.set_cursor_position(Position {
+        x: chunks[0].x + 2 + col_offset,
+        y: chunks[0].y,
+    });
+
+    // Results
+    let matches = finder_matches(state);
+    let list_area = chunks[1];
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No matching requests",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+
+    render_entries(frame, theme, list_area, &matches, Some(finder.selected));
+
+    // Hint bar
+    let hint = Line::from(vec![
+        Span::styled("↑↓/C-j C-k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" open  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[2],
+    );
+}
+
+/// Draw `entries` as method + highlighted path rows, keeping `selected` in view.
+pub fn render_entries(
+    frame: &mut Frame,
+    theme: &Theme,
+    list_area: Rect,
+    entries: &[FinderEntry],
+    selected: Option<usize>,
+) {
+    let visible = list_area.height as usize;
+    let offset = selected.unwrap_or(0).saturating_sub(visible.saturating_sub(1));
+    for (row, entry) in entries.iter().enumerate().skip(offset) {
+        let y = list_area.y + (row - offset) as u16;
+        if y >= list_area.y + list_area.height {
+            break;
+        }
+        let is_selected = selected == Some(row);
+        let base = if is_selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+
+        let mut spans = vec![Span::styled(
+            format!("{:<7} ", entry.method),
+            Style::default().fg(theme.method_color(&entry.method)),
+        )];
+        for (i, ch) in entry.path.chars().enumerate() {
+            let style = if entry.matched.contains(&i) { base.fg(theme.orange) } else { base };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), Rect { y, height: 1, ..list_area });
+    }
+}
@@ -1,64 +1,192 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
 };
 
-use crate::state::app_state::AppState;
+use crate::state::app_state::{AppState, RequestStatus};
+use crate::state::workspace::RequestTab;
 use crate::state::focus::Focus;
-use crate::ui::layout::ACCENT_BLUE;
+use crate::ui::layout::SPINNER_FRAMES;
+use crate::ui::theme::{theme, Theme};
 
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
+const SEPARATOR: &str = " │ ";
+const OVERFLOW_LEFT: &str = "‹ ";
+const OVERFLOW_RIGHT: &str = " ›";
 
-/// Render the open-tabs bar (1 row height) showing all open request tabs.
+/// Render the open-tabs bar (1 row height) showing the open request tabs of
+/// the pane being drawn.
+/// When the tabs don't fit, only a window around the active tab is shown
+/// with `‹`/`›` markers for the hidden ones.
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     if state.workspace.open_tabs.is_empty() {
         let hint = Paragraph::new(Line::from(Span::styled(
             "No open tabs",
-            Style::default().fg(TEXT_MUTED).add_modifier(Modifier::DIM),
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
         )));
         frame.render_widget(hint, area);
         return;
     }
 
-    let mut spans: Vec<Span<'static>> = Vec::new();
+    // A split's pane without focus draws `render_tab` and its own tabs
+    let tabs_focused = matches!(state.focus, Focus::RequestTabs) && state.render_tab.get().is_none();
+    let shown = state.render_tab.get().unwrap_or(state.workspace.active_tab_idx);
+    let pane = state.workspace.pane_tabs(shown);
+    let active = pane.iter().position(|&i| i == shown).unwrap_or(0);
+
+    let labels: Vec<Vec<Span<'static>>> = pane
+        .iter()
+        .map(|&i| (i, &state.workspace.open_tabs[i]))
+        .map(|(i, tab)| {
+            let env = tab
+                .env_override
+                .as_deref()
+                .and_then(|id| state.workspace.environments.iter().find(|e| e.id == id))
+                .map(|e| e.name.as_str());
+            tab_spans(theme, tab, env, i == shown, tabs_focused)
+        })
+        .collect();
+    let widths: Vec<usize> = labels
+        .iter()
+        .map(|spans| spans.iter().map(|s| s.width()).sum())
+        .collect();
 
-    let tabs_focused = matches!(state.focus, Focus::RequestTabs);
-
-    for (i, tab) in state.workspace.open_tabs.iter().enumerate() {
-        let is_active = i == state.workspace.active_tab_idx;
-        let method = tab.request.method.as_str();
-        let name = if tab.request.name.is_empty() {
-            "Untitled".to_string()
-        } else {
-            tab.request.name.clone()
-        };
-        let dirty = if tab.is_dirty { "*" } else { "" };
-
-        let tab_label = format!(" {} {}{} ", method, name, dirty);
-
-        let style = if is_active && tabs_focused {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::White)
-                .add_modifier(Modifier::BOLD)
-        } else if is_active {
-            Style::default()
-                .fg(ACCENT_BLUE)
-                .add_modifier(Modifier::BOLD)
-                .add_modifier(Modifier::UNDERLINED)
-        } else {
-            Style::default().fg(TEXT_PRIMARY)
-        };
-
-        if i > 0 {
-            spans.push(Span::styled(" │ ", Style::default().fg(TEXT_MUTED)));
+    let (start, end) = visible_range(&widths, active, area.width as usize);
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    if start > 0 {
+        spans.push(Span::styled(OVERFLOW_LEFT, Style::default().fg(theme.muted)));
+    }
+    for (i, label) in labels.into_iter().enumerate().take(end).skip(start) {
+        if i > start {
+            spans.push(Span::styled(SEPARATOR, Style::default().fg(theme.muted)));
         }
-        spans.push(Span::styled(tab_label, style));
+        spans.extend(label);
+    }
+    if end < widths.len() {
+        spans.push(Span::styled(OVERFLOW_RIGHT, Style::default().fg(theme.muted)));
     }
 
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
+
+/// Spans for one tab: colored method badge, name, dirty marker, the tab's own
+/// environment if it has one, and a status chip for the last response (or
+/// error / in-flight spinner).
+fn tab_spans(
+    theme: &Theme,
+    tab: &RequestTab,
+    env: Option<&str>,
+    is_active: bool,
+    tabs_focused: bool,
+) -> Vec<Span<'static>> {
+    let method = tab.request.method.as_str();
+    let name = if tab.request.name.is_empty() {
+        "Untitled".to_string()
+    } else {
+        tab.request.name.clone()
+    };
+    let dirty = if tab.is_dirty { "*" } else { "" };
+
+    let style = if is_active && tabs_focused {
+        Style::default()
+            .fg(theme.cursor_fg)
+            .bg(theme.cursor_bg)
+            .add_modifier(Modifier::BOLD)
+    } else if is_active {
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD)
+            .add_modifier(Modifier::UNDERLINED)
+    } else {
+        Style::default().fg(theme.text)
+    };
+    // Keep the badge readable on the inverted (focused) background.
+    let method_style = if is_active && tabs_focused {
+        style
+    } else {
+        style.fg(theme.method_color(method))
+    };
+
+    let mut spans = vec![
+        Span::styled(if tab.pinned { " 📌 " } else { " " }, style),
+        Span::styled(method.to_string(), method_style),
+        Span::styled(format!(" {}{}", name, dirty), style),
+    ];
+    if let Some(env) = env {
+        let env_style = if is_active && tabs_focused { style } else { style.fg(theme.info) };
+        spans.push(Span::styled(format!(" [{env}]"), env_style));
+    }
+
+    let chip = match &tab.request_status {
+        RequestStatus::Loading { spinner_tick } => {
+            let idx = (*spinner_tick as usize) % SPINNER_FRAMES.len();
+            Some(Span::styled(
+                format!(" {}", SPINNER_FRAMES[idx]),
+                Style::default().fg(theme.warning),
+            ))
+        }
+        RequestStatus::Queued => Some(Span::styled(" ⋯", Style::default().fg(theme.warning))),
+        RequestStatus::Error(_) => Some(Span::styled(" ✗", Style::default().fg(theme.error))),
+        RequestStatus::Idle => tab.response.as_ref().map(|resp| {
+            Span::styled(" ●", Style::default().fg(theme.status_color(resp.status)))
+        }),
+    };
+    spans.extend(chip);
+    spans.push(Span::styled(" ", style));
+    spans
+}
+
+/// Pick the half-open range of tabs to draw so that the active tab is always
+/// visible within `max_width` columns, accounting for separators and the
+/// overflow markers.
+fn visible_range(widths: &[usize], active: usize, max_width: usize) -> (usize, usize) {
+    let sep = SEPARATOR.chars().count();
+    let marker = OVERFLOW_LEFT.chars().count();
+    let total: usize = widths.iter().sum::<usize>() + sep * widths.len().saturating_sub(1);
+    if total <= max_width || widths.is_empty() {
+        return (0, widths.len());
+    }
+
+    let active = active.min(widths.len() - 1);
+    let fits = |start: usize, end: usize| {
+        let mut used: usize = widths[start..end].iter().sum::<usize>() + sep * (end - start - 1);
+        if start > 0 {
+            used += marker;
+        }
+        if end < widths.len() {
+            used += marker;
+        }
+        used <= max_width
+    };
+
+    let (mut start, mut end) = (active, active + 1);
+    // Grow to the left first so tabs preceding the active one stay in view,
+    // then fill any remaining room to the right.
+    while start > 0 && fits(start - 1, end) {
+        start -= 1;
+    }
+    while end < widths.len() && fits(start, end + 1) {
+        end += 1;
+    }
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_range_keeps_active_tab_in_view() {
+        let widths = [10, 10, 10, 10, 10];
+        assert_eq!(visible_range(&widths, 2, 200), (0, 5));
+        // Room for two tabs plus separator and both markers.
+        assert_eq!(visible_range(&widths, 4, 27), (3, 5));
+        assert_eq!(visible_range(&widths, 0, 27), (0, 2));
+        let (start, end) = visible_range(&widths, 3, 27);
+        assert!(start <= 3 && 3 < end);
+    }
+}
@@ -1,21 +1,42 @@
+use std::time::{Duration, Instant};
+
 use humansize::{format_size, DECIMAL};
 use ratatui::{
     Frame,
-    layout::Rect,
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::Paragraph,
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 
 use crate::state::app_state::{AppState, RequestStatus};
-use crate::state::response_state::ResponseBody;
-use crate::state::focus::Focus;
-use super::super::layout::{ACCENT_BLUE, BORDER_INACTIVE, SPINNER_FRAMES};
+use crate::http::executor::MAX_DISPLAY_BYTES;
+use crate::http::retry::retry_after;
+use crate::state::response_state::{BodyFilter, ResponseBody, ResponseState};
+use crate::state::sse::SseStream;
+use crate::state::websocket::{WsDirection, WsSession, WsStatus};
+use crate::util::sparkline::sparkline;
+use super::super::layout::SPINNER_FRAMES;
+use crate::ui::highlight::HIGHLIGHT_MAX_BYTES;
+use crate::ui::request::body_editor::render_gutter;
+use crate::ui::response::{dry_run, json_tree, sse_log, ws_log};
+use crate::ui::text_utils::{shorten_url, wrap_text, wrapped_rows};
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
-    let focused = matches!(state.focus, Focus::ResponseViewer);
-    let border_color = if focused { ACCENT_BLUE } else { BORDER_INACTIVE };
-
+    let theme = theme(state);
+    if let Some(dry) = state.active_tab().and_then(|t| t.dry_run.as_ref()) {
+        dry_run::render(frame, area, state, dry);
+        return;
+    }
+    if let Some(ws) = state.active_tab().and_then(|t| t.ws.as_ref()) {
+        ws_log::render(frame, area, state, ws);
+        return;
+    }
+    if let Some(sse) = state.active_tab().and_then(|t| t.sse.as_ref()) {
+        sse_log::render(frame, area, state, sse);
+        return;
+    }
     let request_status = state.active_tab().map(|t| &t.request_status);
     let response = state.active_tab().and_then(|t| t.response.as_ref());
 
@@ -25,20 +46,27 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             let text = Line::from(vec![
                 Span::styled(
                     format!("  {} ", SPINNER_FRAMES[idx]),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.warning),
                 ),
                 Span::styled(
                     "Sending request…",
-                    Style::default().fg(Color::Rgb(65, 72, 104)),
+                    Style::default().fg(theme.border),
                 ),
             ]);
             frame.render_widget(Paragraph::new(text), area);
         }
+        Some(RequestStatus::Queued) => {
+            let text = Line::from(Span::styled(
+                "  Queued — waiting for earlier sends",
+                Style::default().fg(theme.border),
+            ));
+            frame.render_widget(Paragraph::new(text), area);
+        }
         Some(RequestStatus::Error(msg)) => {
             let msg = msg.clone();
             let text = Line::from(Span::styled(
                 format!("  Error: {}", msg),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.error),
             ));
             frame.render_widget(Paragraph::new(text), area);
         }
@@ -47,61 +75,173 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                 None => {
                     let hint = Paragraph::new(Line::from(Span::styled(
                         "  Send a request to see the response",
-                        Style::default().fg(Color::Rgb(65, 72, 104)),
+                        Style::default().fg(theme.border),
                     )));
                     frame.render_widget(hint, area);
                 }
-                Some(resp) => {
-                    let body_text = match &resp.body {
-                        ResponseBody::Empty => {
-                            ratatui::text::Text::raw("  (empty response body)")
-                        }
-                        ResponseBody::Binary(bytes) => {
-                            ratatui::text::Text::raw(format!(
-                                "  [Binary data: {} bytes]",
-                                bytes.len()
-                            ))
-                        }
-                        ResponseBody::Text(text) => {
-                            // Use the pre-computed highlighted text; fall back to plain
-                            // text only if the cache is somehow absent (e.g. after serde
-                            // round-trip in a future history feature).
-                            resp.highlighted_body
-                                .clone()
-                                .unwrap_or_else(|| ratatui::text::Text::raw(text.clone()))
-                        }
-                    };
-
-                    let para = Paragraph::new(body_text)
-                        .scroll((resp.scroll_offset, 0))
-                        .style(Style::default().fg(if focused {
-                            Color::Reset
-                        } else {
-                            Color::Reset
-                        }));
-                    // draw focus border hint via border color on the unused style field
-                    let _ = border_color; // used for border styling in layout parent
-                    frame.render_widget(para, area);
-                }
+                Some(resp) => render_response(frame, area, state, resp),
             }
         }
     }
 }
 
+/// The body of `resp`: the filter bar and filtered result when a filter is
+/// in use, otherwise the tree view or the highlighted text.
+fn render_response(frame: &mut Frame, area: Rect, state: &AppState, resp: &ResponseState) {
+    let theme = theme(state);
+    let filter = &resp.filter;
+    let mut area = area;
+    if (filter.editing || filter.error.is_some()) && area.height > 1 {
+        render_filter_bar(frame, Rect { height: 1, ..area }, state, filter);
+        area = Rect { y: area.y + 1, height: area.height - 1, ..area };
+    }
+
+    if filter.result.is_none() && resp.tree_view && let Some(root) = &resp.tree_value {
+        json_tree::render(frame, area, state, resp, root);
+        return;
+    }
+    render_scrolled(frame, body_text(resp).style(Style::default().fg(theme.text)), area, state, resp);
+}
+
+/// The text the viewer shows for `resp` outside the tree view: the filter
+/// result, the sorted or markdown view, or the highlighted body.
+pub fn body_text(resp: &ResponseState) -> Text<'static> {
+    if let Some(result) = &resp.filter.result {
+        return result.clone();
+    }
+    match &resp.body {
+        ResponseBody::Empty => {
+            ratatui::text::Text::raw("  (empty response body)")
+        }
+        ResponseBody::Binary(bytes) => {
+            ratatui::text::Text::raw(format!(
+                "  [Binary data: {} bytes]",
+                bytes.len()
+            ))
+        }
+        ResponseBody::Text(_) if resp.sorted_view && resp.sorted_body.is_some() => {
+            resp.sorted_body.clone().unwrap_or_default()
+        }
+        ResponseBody::Text(_) if resp.markdown_view && resp.markdown_body.is_some() => {
+            resp.markdown_body.clone().unwrap_or_default()
+        }
+        ResponseBody::Text(text) => {
+            // Use the pre-computed highlighted text; fall back to plain
+            // text only if the cache is somehow absent (e.g. after serde
+            // round-trip in a future history feature).
+            resp.highlighted_body
+                .clone()
+                .unwrap_or_else(|| ratatui::text::Text::raw(text.clone()))
+        }
+    }
+}
+
+/// `text` scrolled to the response's offset: wrapped to `area` when word
+/// wrap is on (`w`), the offset then counting wrapped rows; otherwise
+/// scrolled sideways by `h_scroll`, beside line numbers when the workspace
+/// shows them. A body taller than `area` gets a scrollbar on the right.
+fn render_scrolled(frame: &mut Frame, text: Text<'_>, area: Rect, state: &AppState, resp: &ResponseState) {
+    let height = area.height as usize;
+    if state.word_wrap {
+        let mut width = area.width as usize;
+        let mut rows: usize = wrapped_rows(&text, width).iter().sum();
+        if rows > height && width > 1 {
+            // Rewrap to leave the scrollbar its column
+            width -= 1;
+            rows = wrapped_rows(&text, width).iter().sum();
+        }
+        let body_area = Rect { width: width as u16, ..area };
+        {
+            let mut hits = state.hit_areas.borrow_mut();
+            hits.response_body = body_area;
+            hits.response_body_lines = rows;
+            hits.response_body_overflow = 0;
+        }
+        // A shorter view (another filter, a new response) may need less
+        let skip = (resp.scroll_offset as usize).min(rows.saturating_sub(height));
+        render_scrollbar(frame, area, state, rows, skip);
+        let para = Paragraph::new(wrap_text(&text, width, skip, height));
+        frame.render_widget(para.style(text.style), body_area);
+        return;
+    }
+    let lines = text.lines.len();
+    let body_area = if lines > height { Rect { width: area.width.saturating_sub(1), ..area } } else { area };
+    let offset = (resp.scroll_offset as usize).min(lines.saturating_sub(height));
+    render_scrollbar(frame, area, state, lines, offset);
+    // Wrapped rows don't map to line numbers, so only unwrapped bodies get them
+    let text_area = render_gutter(frame, body_area, state, lines, offset as u16, None);
+    let overflow = text.width().saturating_sub(text_area.width as usize);
+    {
+        let mut hits = state.hit_areas.borrow_mut();
+        hits.response_body = body_area;
+        hits.response_body_lines = lines;
+        hits.response_body_overflow = overflow;
+    }
+    // A narrower view (another filter, a sorted body) may need less
+    let h_scroll = (resp.h_scroll as usize).min(overflow) as u16;
+    let para = Paragraph::new(text).scroll((offset as u16, h_scroll));
+    frame.render_widget(para, text_area);
+}
+
+/// Scrollbar down the right edge of `area` for `rows` rows scrolled by
+/// `offset`; nothing when they all fit.
+fn render_scrollbar(frame: &mut Frame, area: Rect, state: &AppState, rows: usize, offset: usize) {
+    let height = area.height as usize;
+    if rows <= height {
+        return;
+    }
+    let theme = theme(state);
+    let mut scroll = ScrollbarState::new(rows - height + 1)
+        .position(offset)
+        .viewport_content_length(height);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.border))
+        .thumb_style(Style::default().fg(theme.accent));
+    frame.render_stateful_widget(scrollbar, area, &mut scroll);
+}
+
+/// `filter › .items[] | .id` with the cursor while typing, and the reason
+/// the expression doesn't evaluate.
+fn render_filter_bar(frame: &mut Frame, area: Rect, state: &AppState, filter: &BodyFilter) {
+    let theme = theme(state);
+    let prefix = " filter › ";
+    let mut spans = vec![
+        Span::styled(prefix, Style::default().fg(theme.accent)),
+        Span::styled(filter.input.clone(), Style::default().fg(theme.text)),
+    ];
+    if let Some(err) = &filter.error {
+        spans.push(Span::styled(format!("  {err}"), Style::default().fg(theme.error)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.surface)), area);
+
+    if filter.editing {
+        let col = filter.input[..filter.cursor.min(filter.input.len())].chars().count();
+        frame.set_cursor_position(Position {
+            x: area.x + (prefix.chars().count() + col) as u16,
+            y: area.y,
+        });
+    }
+}
+
 pub fn render_meta(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    if let Some(dry) = state.active_tab().and_then(|t| t.dry_run.as_ref()) {
+        frame.render_widget(Paragraph::new(dry_run::meta(state, dry)), area);
+        return;
+    }
+    if let Some(ws) = state.active_tab().and_then(|t| t.ws.as_ref()) {
+        frame.render_widget(Paragraph::new(ws_meta(state, ws)), area);
+        return;
+    }
     let response = state.active_tab().and_then(|t| t.response.as_ref());
     let line = match response {
-        None => Line::from(Span::styled("─", Style::default().fg(BORDER_INACTIVE))),
+        None => Line::from(Span::styled("─", Style::default().fg(theme.border))),
         Some(resp) => {
-            let status_color = match resp.status {
-                200..=299 => Color::Rgb(158, 206, 106), // green
-                300..=399 => Color::Rgb(122, 162, 247), // blue
-                400..=499 => Color::Rgb(224, 175, 104), // orange/yellow
-                500..=599 => Color::Rgb(247, 118, 142), // red
-                _ => Color::White,
-            };
+            let status_color = theme.status_color(resp.status);
             let size_str = format_size(resp.size_bytes as u64, DECIMAL);
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     format!(" {} {}", resp.status, resp.status_text),
                     Style::default()
@@ -110,10 +250,218 @@ pub fn render_meta(frame: &mut Frame, area: Rect, state: &AppState) {
                 ),
                 Span::styled(
                     format!("  ·  {}ms  ·  {}", resp.timing.total_ms, size_str),
-                    Style::default().fg(Color::Rgb(65, 72, 104)),
+                    Style::default().fg(theme.border),
                 ),
-            ])
+            ];
+            if let Some(max) = resp.truncated_at {
+                spans.push(Span::styled(
+                    format!(" (truncated at {})", format_size(max as u64, DECIMAL)),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            if resp.truncated {
+                spans.push(Span::styled(
+                    format!("  ·  ⚠ showing first {}", format_size(MAX_DISPLAY_BYTES as u64, DECIMAL)),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            if !resp.filter.applied.is_empty() {
+                spans.push(Span::styled(
+                    format!("  ·  filter: {}", resp.filter.applied),
+                    Style::default().fg(theme.accent),
+                ));
+            } else if resp.tree_view {
+                spans.push(Span::styled(
+                    "  ·  tree view",
+                    Style::default().fg(theme.accent),
+                ));
+            } else if resp.sorted_view {
+                spans.push(Span::styled(
+                    "  ·  sorted view",
+                    Style::default().fg(theme.accent),
+                ));
+            } else if resp.markdown_view {
+                spans.push(Span::styled(
+                    "  ·  markdown",
+                    Style::default().fg(theme.accent),
+                ));
+            }
+            if state.word_wrap {
+                spans.push(Span::styled("  ·  wrap: on", Style::default().fg(theme.accent)));
+            } else {
+                spans.push(Span::styled("  ·  wrap: off", Style::default().fg(theme.border)));
+            }
+            if resp.highlight_job != 0 {
+                spans.push(Span::styled("  ·  highlighting…", Style::default().fg(theme.muted)));
+            } else if matches!(&resp.body, ResponseBody::Text(text) if text.len() > HIGHLIGHT_MAX_BYTES) {
+                spans.push(Span::styled("  ·  too large to highlight", Style::default().fg(theme.muted)));
+            }
+            if let Some(lang) = &resp.force_lang {
+                spans.push(Span::styled(
+                    format!("  ·  highlighted as {lang}"),
+                    Style::default().fg(theme.accent),
+                ));
+            }
+            if let Some(sse) = state.active_tab().and_then(|t| t.sse.as_ref()) {
+                spans.extend(sse_spans(state, sse));
+            }
+            spans.extend(retry_spans(state, resp));
+            spans.extend(test_summary_spans(state));
+            spans.extend(run_history_spans(state));
+            let used: usize = spans.iter().map(|s| s.width()).sum();
+            spans.extend(sent_spans(state, resp, (area.width as usize).saturating_sub(used)));
+            Line::from(spans)
         }
     };
     frame.render_widget(Paragraph::new(line), area);
 }
+
+/// Connection state of a WebSocket tab, with its message counts.
+fn ws_meta(state: &AppState, ws: &WsSession) -> Line<'static> {
+    let theme = theme(state);
+    let (label, color) = match &ws.status {
+        WsStatus::Connecting => ("● connecting…".to_string(), theme.warning),
+        WsStatus::Open => ("● open".to_string(), theme.success),
+        WsStatus::Closed { code, reason } => {
+            let mut label = "● closed".to_string();
+            if let Some(code) = code {
+                label.push_str(&format!(" ({code})"));
+            }
+            if !reason.is_empty() {
+                label.push_str(&format!(": {reason}"));
+            }
+            (label, theme.error)
+        }
+    };
+    let sent = ws.log.iter().filter(|e| e.direction == WsDirection::Sent).count();
+    let received = ws.log.len() - sent;
+    let action = if ws.is_live() { "Esc to close" } else { "Ctrl+R to reconnect" };
+    Line::from(vec![
+        Span::styled(format!(" {label}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!("  ·  {sent} sent  ·  {received} received  ·  {action}"),
+            Style::default().fg(theme.border),
+        ),
+    ])
+}
+
+/// Whether an event stream is still open, with its event count.
+fn sse_spans(state: &AppState, sse: &SseStream) -> Vec<Span<'static>> {
+    let theme = theme(state);
+    let (label, color, action) = match &sse.closed {
+        None => ("● streaming".to_string(), theme.success, "Esc to close"),
+        Some(reason) => (format!("● {reason}"), theme.error, "Ctrl+R to reconnect"),
+    };
+    vec![
+        Span::styled(format!("  ·  {label}"), Style::default().fg(color)),
+        Span::styled(
+            format!("  ·  {} events  ·  {action}", sse.log.len()),
+            Style::default().fg(theme.border),
+        ),
+    ]
+}
+
+/// The pending Retry-After re-send's countdown, or an offer to enable
+/// auto-retry when a 429/503 response carries the header.
+fn retry_spans(state: &AppState, resp: &ResponseState) -> Vec<Span<'static>> {
+    let theme = theme(state);
+    let Some(tab) = state.active_tab() else {
+        return Vec::new();
+    };
+    let text = if let Some(at) = tab.retry_at {
+        let secs = at.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64;
+        format!("retrying in {secs} s — Esc to cancel")
+    } else {
+        let max = Duration::from_secs(state.workspace.retry_max_secs);
+        match retry_after(resp.status, &resp.headers, chrono::Utc::now(), max) {
+            Some(delay) if !tab.request.auto_retry && !state.workspace.auto_retry => {
+                format!("Retry-After {} s — b to retry automatically", delay.as_secs())
+            }
+            _ => return Vec::new(),
+        }
+    };
+    vec![
+        Span::styled("  ·  ", Style::default().fg(theme.border)),
+        Span::styled(text, Style::default().fg(theme.warning)),
+    ]
+}
+
+/// "3 passed, 1 failed" for the post-response script's tests, if it ran any.
+fn test_summary_spans(state: &AppState) -> Vec<Span<'static>> {
+    let theme = theme(state);
+    let Some(result) = state.active_tab().and_then(|t| t.script_result.as_ref()) else {
+        return Vec::new();
+    };
+    if result.tests.is_empty() {
+        return Vec::new();
+    }
+    let passed = result.tests.iter().filter(|(_, ok)| *ok).count();
+    let failed = result.tests.len() - passed;
+    let mut spans = vec![
+        Span::styled("  ·  ", Style::default().fg(theme.border)),
+        Span::styled(format!("{passed} passed"), Style::default().fg(theme.success)),
+    ];
+    if failed > 0 {
+        spans.push(Span::styled(format!(", {failed} failed"), Style::default().fg(theme.error)));
+    }
+    spans
+}
+
+/// The environment the response was sent under and the resolved request,
+/// cut to fit `room` columns. The environment is flagged when it has been
+/// switched since, as the editor no longer matches what was sent.
+fn sent_spans(state: &AppState, resp: &ResponseState, room: usize) -> Vec<Span<'static>> {
+    let theme = theme(state);
+    if resp.request_url.is_empty() {
+        return Vec::new();
+    }
+    let sep = || Span::styled("  ·  ", Style::default().fg(theme.border));
+    let env = resp.environment.as_deref().unwrap_or("no env");
+    let env_span = if resp.environment.as_deref() == state.active_environment_name() {
+        Span::styled(env.to_string(), Style::default().fg(theme.purple))
+    } else {
+        Span::styled(format!("⚠ {env}"), Style::default().fg(theme.warning))
+    };
+    let method = format!("{} ", resp.request_method);
+    let room = room.saturating_sub(5 + env_span.width());
+    let mut spans = vec![sep(), env_span];
+    // Not worth showing a URL cut to a handful of chars
+    let url_room = room.saturating_sub(5 + method.len());
+    if url_room >= 12 {
+        spans.push(sep());
+        spans.push(Span::styled(method, Style::default().fg(theme.accent)));
+        spans.push(Span::styled(
+            shorten_url(&resp.request_url, url_room),
+            Style::default().fg(theme.text_dim),
+        ));
+    }
+    spans
+}
+
+/// Latency sparkline and a 2xx/error dot strip for the active collection
+/// request's recent runs (full list: `g h`).
+fn run_history_spans(state: &AppState) -> Vec<Span<'static>> {
+    let theme = theme(state);
+    let runs = state
+        .active_tab()
+        .and_then(|t| t.collection_id.as_ref())
+        .and_then(|id| state.workspace.run_history.get(id));
+    let Some(runs) = runs.filter(|runs| runs.len() > 1) else {
+        return Vec::new();
+    };
+    let latencies: Vec<u64> = runs.iter().map(|r| r.total_ms).collect();
+    let mut spans = vec![
+        Span::styled("  ·  ", Style::default().fg(theme.border)),
+        Span::styled(sparkline(&latencies), Style::default().fg(theme.accent)),
+        Span::raw(" "),
+    ];
+    spans.extend(runs.iter().map(|r| {
+        let color = if r.is_success() {
+            theme.success
+        } else {
+            theme.error
+        };
+        Span::styled("•", Style::default().fg(color))
+    }));
+    spans
+}
@@ -0,0 +1,83 @@
+// Script console: log output, environment updates and errors of the last send
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::scripting::runner::ScriptResult;
+use crate::state::app_state::AppState;
+use crate::ui::theme::{theme, Theme};
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+
+    let sections = [
+        ("Pre-request", &tab.pre_script_result),
+        ("Post-response", &tab.script_result),
+    ];
+    let mut lines = Vec::new();
+    for (title, result) in sections {
+        if let Some(result) = result {
+            push_section(&mut lines, title, result, theme);
+        }
+    }
+    if lines.is_empty() {
+        let hint = "  forge.log(…) and print(…) output from the request's scripts shows up here";
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hint, Style::default().fg(theme.muted)))),
+            area,
+        );
+        return;
+    }
+    let env_set = sections
+        .iter()
+        .any(|(_, r)| r.as_ref().is_some_and(|r| !r.env_updates.is_empty()));
+    if env_set && state.active_environment_idx().is_none() {
+        lines.push(Line::from(Span::styled(
+            "  No active environment — forge.env.set values were not saved",
+            Style::default().fg(theme.warning),
+        )));
+    }
+
+    // Keep the end of the output in view
+    let offset = lines.len().saturating_sub(area.height as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), area);
+}
+
+fn push_section(lines: &mut Vec<Line<'static>>, title: &str, result: &ScriptResult, theme: &Theme) {
+    lines.push(Line::from(Span::styled(
+        format!("  {title}"),
+        Style::default().fg(theme.purple).add_modifier(Modifier::BOLD),
+    )));
+    for log in &result.logs {
+        for text in log.lines() {
+            lines.push(Line::from(vec![
+                Span::styled("  › ", Style::default().fg(theme.border)),
+                Span::styled(text.to_string(), Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+    // Keys only: values may be secrets
+    for (key, _) in &result.env_updates {
+        lines.push(Line::from(vec![
+            Span::styled("  env ", Style::default().fg(theme.teal)),
+            Span::styled(format!("{key} updated"), Style::default().fg(theme.text_dim)),
+        ]));
+    }
+    if let Some(err) = &result.error {
+        lines.push(Line::from(Span::styled(
+            format!("  ✗ {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    if result.logs.is_empty() && result.env_updates.is_empty() && result.error.is_none() {
+        lines.push(Line::from(Span::styled("  (no output)", Style::default().fg(theme.muted))));
+    }
+    lines.push(Line::default());
+}
@@ -1 +1,116 @@
 // Response cookies viewer
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use url::Url;
+
+use crate::state::app_state::AppState;
+use crate::state::response_state::Cookie;
+use crate::ui::theme::theme;
+
+/// Longer values are cut with an ellipsis so the other columns stay visible
+const MAX_VALUE_W: usize = 32;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let Some(resp) = state.active_tab().and_then(|t| t.response.as_ref()) else {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  Send a request to see the cookies it sets",
+                Style::default().fg(theme.muted),
+            ))),
+            area,
+        );
+        return;
+    };
+    if resp.cookies.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  This response set no cookies  ·  C opens the workspace cookie jar",
+                Style::default().fg(theme.muted),
+            ))),
+            area,
+        );
+        return;
+    }
+
+    let jar = state.workspace.cookies.read();
+    let url = Url::parse(&resp.url).ok();
+    let width = |f: fn(&Cookie) -> usize, header: &str| {
+        resp.cookies.iter().map(f).max().unwrap_or(0).max(header.len())
+    };
+    let name_w = width(|c| c.name.chars().count(), "Name");
+    let value_w = width(|c| c.value.chars().count(), "Value").min(MAX_VALUE_W);
+    let domain_w = width(|c| c.domain.chars().count(), "Domain");
+    let path_w = width(|c| c.path.chars().count(), "Path");
+
+    let header = Line::from(Span::styled(
+        format!(
+            "  {:<8}{:<name_w$}  {:<value_w$}  {:<domain_w$}  {:<path_w$}  {:<17}Flags",
+            "", "Name", "Value", "Domain", "Path", "Expires"
+        ),
+        Style::default().fg(theme.muted).add_modifier(Modifier::BOLD),
+    ));
+    let mut lines = Vec::new();
+    for cookie in &resp.cookies {
+        let stored = url
+            .as_ref()
+            .and_then(|url| jar.find(url, &cookie.name, &cookie.domain, &cookie.path));
+        let (badge, color) = match stored {
+            Some(c) if c.created >= resp.sent_at => ("new     ", theme.success),
+            Some(_) => ("in jar  ", theme.info),
+            // Expired on arrival, rejected, or deleted since
+            None => ("removed ", theme.muted),
+        };
+        // Host-only cookies have no Domain attribute
+        let domain = if cookie.domain.is_empty() { "—" } else { cookie.domain.as_str() };
+        let expires = cookie.expires.map_or("session".to_string(), |at| {
+            at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()
+        });
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {badge}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:<name_w$}  ", cookie.name), Style::default().fg(theme.accent)),
+            Span::styled(
+                format!("{:<value_w$}  ", truncate(&cookie.value, value_w)),
+                Style::default().fg(theme.text),
+            ),
+            Span::styled(format!("{domain:<domain_w$}  "), Style::default().fg(theme.muted)),
+            Span::styled(format!("{:<path_w$}  ", cookie.path), Style::default().fg(theme.muted)),
+            Span::styled(format!("{expires:<17}"), Style::default().fg(theme.muted)),
+            Span::styled(flags(cookie), Style::default().fg(theme.purple)),
+        ]));
+    }
+
+    let header_area = Rect { height: 1, ..area };
+    let rows_area = Rect { y: area.y + 1, height: area.height.saturating_sub(1), ..area };
+    frame.render_widget(Paragraph::new(header), header_area);
+    frame.render_widget(Paragraph::new(lines).scroll((resp.cookies_scroll, 0)), rows_area);
+}
+
+/// `HttpOnly`, `Secure` and `SameSite` attributes, space-separated.
+fn flags(cookie: &Cookie) -> String {
+    let mut flags = Vec::new();
+    if cookie.http_only {
+        flags.push("HttpOnly".to_string());
+    }
+    if cookie.secure {
+        flags.push("Secure".to_string());
+    }
+    if let Some(same_site) = &cookie.same_site {
+        flags.push(format!("SameSite={same_site}"));
+    }
+    flags.join(" ")
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
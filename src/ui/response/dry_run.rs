@@ -0,0 +1,93 @@
+// Dry-run preview: the request a send would have made
+use humansize::{format_size, DECIMAL};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::http::dry_run::DryRun;
+use crate::state::app_state::AppState;
+use crate::ui::theme::theme;
+
+/// Body lines shown before the preview is cut off
+const BODY_PREVIEW_LINES: usize = 40;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState, dry: &DryRun) {
+    let theme = theme(state);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("  {} ", dry.method),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(dry.url.clone(), Style::default().fg(theme.text)),
+    ])];
+    lines.push(Line::from(""));
+
+    let key_w = dry.headers.iter().map(|(k, _)| k.chars().count()).max().unwrap_or(0);
+    for (key, value) in &dry.headers {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {key:<key_w$}  "), Style::default().fg(theme.purple)),
+            Span::styled(value.clone(), Style::default().fg(theme.text)),
+        ]));
+    }
+    if dry.fetches_token {
+        lines.push(Line::from(Span::styled(
+            "  Authorization: an OAuth2 token would be fetched first",
+            Style::default().fg(theme.warning),
+        )));
+    }
+    if dry.headers.is_empty() && !dry.fetches_token {
+        lines.push(Line::from(Span::styled("  No headers", Style::default().fg(theme.muted))));
+    }
+    lines.push(Line::from(""));
+
+    if dry.body.is_empty() {
+        lines.push(Line::from(Span::styled("  No body", Style::default().fg(theme.muted))));
+    } else {
+        match std::str::from_utf8(&dry.body) {
+            Ok(text) => {
+                let total = text.lines().count();
+                for line in text.lines().take(BODY_PREVIEW_LINES) {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {line}"),
+                        Style::default().fg(theme.text),
+                    )));
+                }
+                if total > BODY_PREVIEW_LINES {
+                    lines.push(Line::from(Span::styled(
+                        format!("  … {} more lines", total - BODY_PREVIEW_LINES),
+                        Style::default().fg(theme.muted),
+                    )));
+                }
+            }
+            Err(_) => lines.push(Line::from(Span::styled(
+                "  Binary body",
+                Style::default().fg(theme.muted),
+            ))),
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// The meta row over a dry-run preview.
+pub fn meta(state: &AppState, dry: &DryRun) -> Line<'static> {
+    let theme = theme(state);
+    Line::from(vec![
+        Span::styled(
+            " DRY RUN — nothing sent",
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  ·  {} headers  ·  body {}",
+                dry.headers.len(),
+                format_size(dry.body.len() as u64, DECIMAL)
+            ),
+            Style::default().fg(theme.border),
+        ),
+    ])
+}
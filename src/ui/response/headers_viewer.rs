@@ -1 +1,89 @@
 // Response headers viewer
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::ui::theme::theme;
+
+/// Widest the name column grows; longer names push their value along
+const MAX_NAME_W: usize = 32;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let Some(resp) = state.active_tab().and_then(|t| t.response.as_ref()) else {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  Send a request to see the response headers",
+                Style::default().fg(theme.muted),
+            ))),
+            area,
+        );
+        return;
+    };
+    if resp.headers.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  The response has no headers",
+                Style::default().fg(theme.muted),
+            ))),
+            area,
+        );
+        return;
+    }
+
+    let focused = state.focus == Focus::ResponseViewer;
+    let name_w = resp
+        .headers
+        .iter()
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_W);
+    // "  name  value": values wrap under their own column
+    let indent = 2 + name_w + 2;
+    let value_w = (area.width as usize).saturating_sub(indent).max(8);
+
+    let mut lines = Vec::new();
+    let mut selected_rows = 0..0;
+    for (i, (name, value)) in resp.headers.iter().enumerate() {
+        let selected = focused && i == resp.headers_cursor;
+        let bg = if selected { theme.surface } else { theme.bg };
+        let start = lines.len();
+        for (j, chunk) in wrap(value, value_w).into_iter().enumerate() {
+            let label = if j == 0 { name.as_str() } else { "" };
+            lines.push(
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {label:<name_w$}  "),
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(chunk, Style::default().fg(theme.text)),
+                ])
+                .style(Style::default().bg(bg)),
+            );
+        }
+        if i == resp.headers_cursor {
+            selected_rows = start..lines.len();
+        }
+    }
+
+    // Keep every line of the selected header on screen
+    let visible = area.height as usize;
+    let offset = selected_rows.end.saturating_sub(visible).min(selected_rows.start);
+    frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), area);
+}
+
+/// `text` cut into pieces of at most `width` characters.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width).map(|chunk| chunk.iter().collect()).collect()
+}
@@ -0,0 +1,98 @@
+// Collapsible tree view of a JSON response body
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde_json::Value;
+
+use crate::state::app_state::AppState;
+use crate::state::focus::Focus;
+use crate::state::response_state::ResponseState;
+use crate::util::json::{tree_rows, TreeRow};
+use crate::ui::theme::{theme, Theme};
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState, resp: &ResponseState, root: &Value) {
+    let theme = theme(state);
+    let focused = state.focus == Focus::ResponseViewer;
+    let rows = tree_rows(root, &resp.tree_collapsed);
+    let cursor = resp.tree_cursor.min(rows.len().saturating_sub(1));
+    let visible = area.height as usize;
+    let offset = (cursor + 1).saturating_sub(visible);
+    {
+        let mut hits = state.hit_areas.borrow_mut();
+        hits.response_body = area;
+        hits.response_body_lines = rows.len();
+    }
+
+    let lines: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, row)| {
+            let line = row_line(row, theme, area.width as usize);
+            if focused && i == cursor {
+                line.style(Style::default().bg(theme.surface))
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Indent, fold marker, key and value of one row. Long strings are cut to
+/// fit `width`; Enter shows them in full.
+fn row_line(row: &TreeRow, theme: &Theme, width: usize) -> Line<'static> {
+    let container = matches!(row.value, Value::Object(_) | Value::Array(_));
+    let marker = match (container, row.collapsed) {
+        (true, true) => "▸ ",
+        (true, false) => "▾ ",
+        (false, _) => "  ",
+    };
+    let mut used = 2 + row.depth * 2 + 2;
+    let mut spans = vec![
+        Span::raw(format!("  {}", "  ".repeat(row.depth))),
+        Span::styled(marker, Style::default().fg(theme.muted)),
+    ];
+    if let Some(label) = &row.label {
+        used += label.chars().count() + 2;
+        let key_style = if label.starts_with('[') {
+            Style::default().fg(theme.muted)
+        } else {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        };
+        spans.push(Span::styled(label.clone(), key_style));
+        spans.push(Span::styled(": ", Style::default().fg(theme.muted)));
+    }
+
+    let muted = Style::default().fg(theme.muted);
+    match row.value {
+        Value::Object(map) if row.collapsed => {
+            spans.push(Span::styled("{…}", Style::default().fg(theme.text)));
+            spans.push(Span::styled(format!(" {} keys", map.len()), muted));
+        }
+        Value::Array(items) if row.collapsed => {
+            spans.push(Span::styled("[…]", Style::default().fg(theme.text)));
+            spans.push(Span::styled(format!(" {} items", items.len()), muted));
+        }
+        Value::Object(_) => spans.push(Span::styled("{", muted)),
+        Value::Array(_) => spans.push(Span::styled("[", muted)),
+        Value::String(s) => {
+            let room = width.saturating_sub(used + 2);
+            let text = if s.chars().count() > room {
+                let cut: String = s.chars().take(room.saturating_sub(1)).collect();
+                format!("\"{cut}…\"")
+            } else {
+                format!("\"{s}\"")
+            };
+            spans.push(Span::styled(text, Style::default().fg(theme.success)));
+        }
+        Value::Number(n) => spans.push(Span::styled(n.to_string(), Style::default().fg(theme.warning))),
+        other => spans.push(Span::styled(other.to_string(), Style::default().fg(theme.purple))),
+    }
+    Line::from(spans)
+}
@@ -3,6 +3,12 @@ pub mod body_viewer;
 pub mod headers_viewer;
 pub mod cookies_viewer;
 pub mod timing_viewer;
+pub mod tests_viewer;
+pub mod console_viewer;
+pub mod json_tree;
+pub mod ws_log;
+pub mod sse_log;
+pub mod dry_run;
 
 use ratatui::{Frame, layout::Rect};
 use crate::state::app_state::AppState;
@@ -0,0 +1,57 @@
+// Server-Sent Events log: each event with its name, id and data
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::state::app_state::AppState;
+use crate::state::sse::SseStream;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState, sse: &SseStream) {
+    let theme = theme(state);
+    if sse.log.is_empty() {
+        let hint = if sse.is_live() {
+            "  Waiting for events…"
+        } else {
+            "  The stream ended before any events  ·  Ctrl+R reconnects"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hint, Style::default().fg(theme.muted)))),
+            area,
+        );
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for entry in &sse.log {
+        let mut header = vec![
+            Span::styled(
+                format!("  {} ", entry.at.format("%H:%M:%S%.3f")),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                entry.event.event.clone(),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ),
+        ];
+        if let Some(id) = &entry.event.id {
+            header.push(Span::styled(format!("  id {id}"), Style::default().fg(theme.muted)));
+        }
+        lines.push(Line::from(header));
+        for data in entry.event.data.split('\n') {
+            lines.push(Line::from(Span::styled(
+                format!("    {data}"),
+                Style::default().fg(theme.text),
+            )));
+        }
+    }
+
+    // Follow the newest event unless scrolled back
+    let bottom = lines.len().saturating_sub(area.height as usize);
+    let offset = bottom.saturating_sub(sse.scroll as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), area);
+}
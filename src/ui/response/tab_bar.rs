@@ -1,37 +1,49 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
 };
 
 use crate::state::app_state::{AppState, ResponseTab};
+use crate::ui::theme::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let tabs = [
         ("Body", ResponseTab::Body),
         ("Headers", ResponseTab::Headers),
         ("Cookies", ResponseTab::Cookies),
         ("Timing", ResponseTab::Timing),
+        ("Tests", ResponseTab::Tests),
+        ("Console", ResponseTab::Console),
     ];
 
     let response_tab = state.active_tab().map(|t| &t.response_tab);
 
     let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut hits = Vec::new();
+    let mut x = area.x;
     for (i, (name, tab)) in tabs.iter().enumerate() {
         if i > 0 {
             spans.push(Span::raw("  "));
+            x = x.saturating_add(2);
         }
         let style = if response_tab == Some(tab) {
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.info)
                 .add_modifier(Modifier::UNDERLINED)
         } else {
-            Style::default().fg(Color::Rgb(65, 72, 104))
+            Style::default().fg(theme.border)
         };
-        spans.push(Span::styled(name.to_string(), style));
+        let label = name.to_string();
+        let width = label.chars().count() as u16;
+        hits.push((Rect { x, y: area.y, width, height: 1 }.intersection(area), tab.clone()));
+        x = x.saturating_add(width);
+        spans.push(Span::styled(label, style));
     }
+    state.hit_areas.borrow_mut().response_tabs = hits;
 
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
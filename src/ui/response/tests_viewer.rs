@@ -0,0 +1,62 @@
+// Post-response script test results
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+
+    let Some(result) = &tab.script_result else {
+        let hint = if tab.request.scripts.post_response.trim().is_empty() {
+            "  No post-response script — add forge.test(…) calls in the Scripts tab"
+        } else {
+            "  Send a request to run the post-response script"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hint, Style::default().fg(theme.muted)))),
+            area,
+        );
+        return;
+    };
+
+    let passed = result.tests.iter().filter(|(_, ok)| *ok).count();
+    let failed = result.tests.len() - passed;
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("  {passed} passed"), Style::default().fg(theme.success)),
+        Span::styled("  ·  ", Style::default().fg(theme.muted)),
+        Span::styled(
+            format!("{failed} failed"),
+            Style::default().fg(if failed > 0 { theme.error } else { theme.muted }),
+        ),
+    ])];
+    if let Some(err) = &result.error {
+        lines.push(Line::from(Span::styled(
+            format!("  Script error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    lines.push(Line::from(""));
+    for (name, ok) in &result.tests {
+        let (mark, color) = if *ok { ("✓", theme.success) } else { ("✗", theme.error) };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {mark} "),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(name.clone(), Style::default().fg(theme.text)),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -1 +1,136 @@
 // Response timing breakdown viewer
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::state::app_state::AppState;
+use crate::state::response_state::RequestTiming;
+use crate::ui::theme::theme;
+
+/// Width of the phase label column ("Download  ")
+const LABEL_W: usize = 10;
+/// Width of the millisecond column right of the bars
+const MS_W: usize = 9;
+
+/// One row of the waterfall: the phase, when it started and how long it took.
+struct Phase {
+    label: &'static str,
+    start_ms: u64,
+    ms: u64,
+}
+
+/// The phases in the order they happen. Connection phases that weren't
+/// measured (0 ms) are left out; "Waiting" is the time to first byte after
+/// them.
+fn phases(timing: &RequestTiming) -> Vec<Phase> {
+    let mut out = Vec::new();
+    let mut at = 0;
+    for (label, ms) in [
+        ("DNS", timing.dns_lookup_ms),
+        ("TCP", timing.tcp_connect_ms),
+        ("TLS", timing.tls_handshake_ms),
+    ] {
+        if ms > 0 {
+            out.push(Phase { label, start_ms: at, ms });
+            at += ms;
+        }
+    }
+    out.push(Phase {
+        label: "Waiting",
+        start_ms: at,
+        ms: timing.time_to_first_byte_ms.saturating_sub(at),
+    });
+    out.push(Phase {
+        label: "Download",
+        start_ms: timing.time_to_first_byte_ms,
+        ms: timing.download_ms,
+    });
+    out
+}
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let Some(resp) = state.active_tab().and_then(|t| t.response.as_ref()) else {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  Send a request to see where its time went",
+                Style::default().fg(theme.muted),
+            ))),
+            area,
+        );
+        return;
+    };
+
+    let timing = &resp.timing;
+    let total = timing.total_ms.max(1);
+    let bar_w = (area.width as usize).saturating_sub(2 + LABEL_W + MS_W).max(1);
+    let col = |ms: u64| (ms.min(total) as usize * bar_w) / total as usize;
+
+    let phases = phases(timing);
+    // The slowest phase stands out so the bottleneck is obvious
+    let dominant = phases.iter().map(|p| p.ms).max().unwrap_or(0);
+    let row = |label: &str, start_ms: u64, ms: u64, color| {
+        let start = col(start_ms);
+        let len = if ms == 0 { 0 } else { (col(start_ms + ms) - start).max(1) };
+        let bar = if len == 0 { "▏".to_string() } else { "█".repeat(len) };
+        Line::from(vec![
+            Span::styled(format!("  {label:<LABEL_W$}"), Style::default().fg(theme.text_dim)),
+            Span::raw(" ".repeat(start)),
+            Span::styled(bar, Style::default().fg(color)),
+            Span::raw(" ".repeat(bar_w.saturating_sub(start + len.max(1)))),
+            Span::styled(format!("{ms:>w$}ms", w = MS_W - 2), Style::default().fg(theme.text)),
+        ])
+    };
+
+    let mut lines = vec![Line::default()];
+    for phase in &phases {
+        let color = if phase.ms == dominant && dominant > 0 { theme.warning } else { theme.accent };
+        lines.push(row(phase.label, phase.start_ms, phase.ms, color));
+    }
+    lines.push(Line::default());
+    let mut total_row = row("Total", 0, timing.total_ms, theme.muted);
+    total_row.spans[0].style = total_row.spans[0].style.add_modifier(Modifier::BOLD);
+    lines.push(total_row);
+
+    // What was actually sent, in full (the meta line shortens it)
+    if !resp.request_url.is_empty() {
+        let label = |text: &str| {
+            Span::styled(format!("  {text:<LABEL_W$}"), Style::default().fg(theme.text_dim))
+        };
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            label("Sent"),
+            Span::styled(format!("{} ", resp.request_method), Style::default().fg(theme.accent)),
+            Span::styled(resp.request_url.clone(), Style::default().fg(theme.text)),
+        ]));
+        if !resp.url.is_empty() && resp.url != resp.request_url {
+            lines.push(Line::from(vec![
+                label("Final"),
+                Span::styled(resp.url.clone(), Style::default().fg(theme.text)),
+            ]));
+        }
+        let env = resp.environment.as_deref().unwrap_or("none");
+        let mut env_row = vec![label("Env"), Span::styled(env.to_string(), Style::default().fg(theme.purple))];
+        if resp.environment.as_deref() != state.active_environment_name() {
+            let now = state.active_environment_name().unwrap_or("none");
+            env_row.push(Span::styled(
+                format!("  ⚠ switched to {now} since; the editor resolves differently"),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        lines.push(Line::from(env_row));
+    }
+
+    if phases.len() == 2 {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            "  DNS, TCP and TLS aren't measured separately yet; they're part of Waiting",
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -0,0 +1,56 @@
+// WebSocket message log: sent and received frames with timestamps
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::state::app_state::AppState;
+use crate::state::websocket::{WsDirection, WsSession, WsStatus};
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState, ws: &WsSession) {
+    let theme = theme(state);
+    if ws.log.is_empty() {
+        let hint = match ws.status {
+            WsStatus::Connecting => "  Connecting…",
+            WsStatus::Open => "  Connected  ·  write a message in the Body tab and press Ctrl+R to send it",
+            WsStatus::Closed { .. } => "  The connection closed before any messages  ·  Ctrl+R reconnects",
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hint, Style::default().fg(theme.muted)))),
+            area,
+        );
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for entry in &ws.log {
+        let (arrow, color) = match entry.direction {
+            WsDirection::Sent => ("→", theme.accent),
+            WsDirection::Received => ("←", theme.success),
+        };
+        let time = entry.at.format("%H:%M:%S%.3f").to_string();
+        // Continuation lines of a multi-line message line up under its text
+        for (i, text) in entry.text.split('\n').enumerate() {
+            let prefix = if i == 0 {
+                vec![
+                    Span::styled(format!("  {time} "), Style::default().fg(theme.muted)),
+                    Span::styled(format!("{arrow} "), Style::default().fg(color)),
+                ]
+            } else {
+                vec![Span::raw(" ".repeat(time.len() + 5))]
+            };
+            let mut spans = prefix;
+            spans.push(Span::styled(text.trim_end_matches('\r').to_string(), Style::default().fg(theme.text)));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    // Follow the newest message unless scrolled back
+    let bottom = lines.len().saturating_sub(area.height as usize);
+    let offset = bottom.saturating_sub(ws.scroll as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), area);
+}
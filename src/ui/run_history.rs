@@ -0,0 +1,94 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(50, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let popup = &state.run_history;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Recent runs — {} ", popup.request_name))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    // Newest first
+    let runs: Vec<_> = state
+        .workspace
+        .run_history
+        .get(&popup.request_id)
+        .map(|runs| runs.iter().rev().collect())
+        .unwrap_or_default();
+
+    let list_area = chunks[0];
+    if runs.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No runs recorded yet",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+
+    let visible = list_area.height as usize;
+    let offset = popup.selected.saturating_sub(visible.saturating_sub(1));
+    for (i, run) in runs.iter().enumerate().skip(offset) {
+        let y = list_area.y + (i - offset) as u16;
+        if y >= list_area.y + list_area.height {
+            break;
+        }
+        let base = if i == popup.selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let status_color = if run.is_success() { theme.success } else { theme.error };
+        let status = run.status.map_or("ERR".to_string(), |s| s.to_string());
+        let line = Line::from(vec![
+            Span::styled("● ", base.fg(status_color)),
+            Span::styled(
+                run.timestamp
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S  ")
+                    .to_string(),
+                base.fg(theme.muted),
+            ),
+            Span::styled(format!("{status:<4}"), base.fg(status_color)),
+            Span::styled(format!("{:>7}ms", run.total_ms), base),
+        ]);
+        frame.render_widget(Paragraph::new(line), Rect { y, height: 1, ..list_area });
+    }
+
+    let hint = Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[1],
+    );
+}
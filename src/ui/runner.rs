@@ -0,0 +1,139 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::{AppState, NodeRunStatus};
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let runner = &state.runner;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Run — {} ", runner.title))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let list_area = chunks[0];
+    if runner.rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No requests to run",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            list_area,
+        );
+    }
+
+    let name_w = runner
+        .rows
+        .iter()
+        .map(|r| r.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(4, 32);
+    let visible = list_area.height as usize;
+    let offset = runner.selected.saturating_sub(visible.saturating_sub(1));
+    for (i, row) in runner.rows.iter().enumerate().skip(offset) {
+        let y = list_area.y + (i - offset) as u16;
+        if y >= list_area.y + list_area.height {
+            break;
+        }
+        let base = if i == runner.selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let (status, status_fg, time) = match row.status {
+            NodeRunStatus::Queued => ("·".to_string(), theme.muted, String::new()),
+            NodeRunStatus::Running => ("…".to_string(), theme.warning, String::new()),
+            NodeRunStatus::Done { status, total_ms } => {
+                (status.to_string(), theme.status_color(status), format!("{total_ms}ms"))
+            }
+            NodeRunStatus::Failed => ("ERR".to_string(), theme.error, String::new()),
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<7} ", row.method), base.fg(theme.method_color(&row.method))),
+            Span::styled(format!("{:<name_w$}  ", row.name), base),
+            Span::styled(format!("{status:<4}"), base.fg(status_fg)),
+            Span::styled(format!("{time:>8}  "), base.fg(theme.muted)),
+        ];
+        match row.passed() {
+            Some(true) => spans.push(Span::styled("theme.success", base.fg(theme.success))),
+            Some(false) => {
+                spans.push(Span::styled("theme.error", base.fg(theme.error)));
+                spans.push(Span::styled(format!("  {}", row.failures.join("; ")), base.fg(theme.muted)));
+            }
+            None => {}
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), Rect { y, height: 1, ..list_area });
+    }
+
+    // Summary
+    let (passed, failed) = runner.counts();
+    let summary = if runner.finished() {
+        Line::from(vec![
+            Span::styled(format!("{passed} passed"), Style::default().fg(theme.success)),
+            Span::styled("  ·  ", Style::default().fg(theme.muted)),
+            Span::styled(
+                format!("{failed} failed"),
+                Style::default().fg(if failed > 0 { theme.error } else { theme.muted }),
+            ),
+            Span::styled(
+                format!("  ·  {} requests  ·  {}ms", runner.rows.len(), runner.total_ms()),
+                Style::default().fg(theme.muted),
+            ),
+        ])
+    } else {
+        Line::from(Span::styled(
+            format!("Running {}/{}…", passed + failed + 1, runner.rows.len()),
+            Style::default().fg(theme.warning),
+        ))
+    };
+    frame.render_widget(Paragraph::new(summary), chunks[1]);
+
+    let mut hint = vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" open  ", Style::default().fg(theme.muted)),
+        Span::styled("v", Style::default().fg(theme.text)),
+        Span::styled(" variables  ", Style::default().fg(theme.muted)),
+    ];
+    if runner.finished() {
+        hint.extend([
+            Span::styled("r", Style::default().fg(theme.text)),
+            Span::styled(" rerun  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]);
+    } else {
+        hint.extend([
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" abort", Style::default().fg(theme.muted)),
+        ]);
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(hint)).style(theme.hint_style()),
+        chunks[2],
+    );
+}
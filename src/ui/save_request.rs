@@ -0,0 +1,99 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::sidebar::{node_line, save_targets};
+use crate::ui::theme::theme;
+
+/// Width of the field label column ("Name  ")
+const LABEL_W: u16 = 6;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(50, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Save Request ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 4 {
+        return;
+    }
+
+    // Name, "Save to" label, the target tree and hints
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // The name row is active in the second step, once a target is picked
+    let save = &state.save_request;
+    let (label_fg, name_bg) = if save.naming { (theme.highlight, theme.surface) } else { (theme.muted, theme.bg) };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<w$}", "Name", w = LABEL_W as usize), Style::default().fg(label_fg)),
+            Span::styled(save.name.clone(), Style::default().fg(theme.text)),
+        ]))
+        .style(Style::default().bg(name_bg)),
+        chunks[0],
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled("Save to", Style::default().fg(theme.muted))),
+        chunks[1],
+    );
+
+    let targets = save_targets(state);
+    let visible = chunks[2].height as usize;
+    let offset = (save.selected + 1).saturating_sub(visible);
+    let width = chunks[2].width as usize;
+    let lines: Vec<Line> = targets
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, node)| node_line(theme, node, None, None, i == save.selected, false, width))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+    let hint = if save.naming {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" save  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" back", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("j/k", Style::default().fg(theme.text)),
+            Span::styled(" target  ", Style::default().fg(theme.muted)),
+            Span::styled("h/l", Style::default().fg(theme.text)),
+            Span::styled(" fold  ", Style::default().fg(theme.muted)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" next  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[3]);
+
+    if save.naming {
+        let col = save.name[..save.cursor.min(save.name.len())].chars().count() as u16;
+        frame.set_cursor_position(Position { x: chunks[0].x + LABEL_W + col, y: chunks[0].y });
+    }
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::{AppState, QueuedSend};
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let ws = &state.workspace;
+    let mode = if ws.send_queue { "on" } else { "off" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Send queue — {mode}, {} ms apart ", ws.queue_gap_ms))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let queue = &state.send_queue;
+    let mut lines = Vec::new();
+    if let Some(running) = &queue.running {
+        let mut spans = vec![Span::styled("  ▶ ", Style::default().fg(theme.warning))];
+        spans.extend(entry_spans(state, running, Style::default().fg(theme.text)));
+        lines.push(Line::from(spans));
+    }
+    for (i, entry) in queue.entries.iter().enumerate() {
+        let style = if i == queue.selected {
+            Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let mut spans = vec![Span::styled(format!("{:>3} ", i + 1), style.fg(theme.muted))];
+        spans.extend(entry_spans(state, entry, style));
+        lines.push(Line::from(spans));
+    }
+    if lines.is_empty() {
+        let text = if ws.send_queue {
+            "Nothing queued — sends wait here for their turn"
+        } else {
+            "Queue mode is off — press m to send one request at a time"
+        };
+        lines.push(Line::from(Span::styled(
+            text,
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
+        )));
+    }
+    let selected_line = queue.selected + usize::from(queue.running.is_some());
+    let visible = chunks[0].height as usize;
+    let offset = (selected_line + 1).saturating_sub(visible) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), chunks[0]);
+
+    let hint = Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" move  ", Style::default().fg(theme.muted)),
+        Span::styled("J/K", Style::default().fg(theme.text)),
+        Span::styled(" reorder  ", Style::default().fg(theme.muted)),
+        Span::styled("d", Style::default().fg(theme.text)),
+        Span::styled(" drop  ", Style::default().fg(theme.muted)),
+        Span::styled("c", Style::default().fg(theme.text)),
+        Span::styled(" cancel running  ", Style::default().fg(theme.muted)),
+        Span::styled("m", Style::default().fg(theme.text)),
+        Span::styled(" mode  ", Style::default().fg(theme.muted)),
+        Span::styled("+/-", Style::default().fg(theme.text)),
+        Span::styled(" gap  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" close", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[1],
+    );
+}
+
+/// Method, request name and URL of a queued send.
+fn entry_spans<'a>(state: &AppState, entry: &'a QueuedSend, style: Style) -> Vec<Span<'a>> {
+    let theme = theme(state);
+    let method = entry.request.method.as_str();
+    let name = if entry.request.name.is_empty() { "Untitled" } else { entry.request.name.as_str() };
+    vec![
+        Span::styled(format!("{method:<7}"), style.fg(theme.method_color(method))),
+        Span::styled(format!("{name}  "), style),
+        Span::styled(entry.request.url.as_str(), style.fg(theme.muted)),
+    ]
+}
@@ -0,0 +1,143 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::http::client::{env_var, proxy_host, ResolvedProxy};
+use crate::state::app_state::AppState;
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+/// Field labels, in `ProxySettings::field` order, with the environment
+/// variable an empty field falls back to
+const FIELDS: [(&str, Option<&str>); 5] = [
+    ("HTTP proxy", Some("HTTP_PROXY")),
+    ("HTTPS proxy", Some("HTTPS_PROXY")),
+    ("Username", None),
+    ("Password", None),
+    ("No proxy", Some("NO_PROXY")),
+];
+
+/// Columns before a field's value: its label
+const LABEL_W: usize = 15;
+
+/// The workspace settings popup: proxy fields, then the proxies requests
+/// actually use once environment variables fill the empty ones.
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup = &state.settings;
+    let proxy = &state.workspace.proxy;
+    let popup_area = centered_rect(70, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Settings — {} ", state.workspace.name))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines = vec![Line::from(Span::styled(
+        " Proxy",
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    ))];
+    let mut cursor = None;
+    for (i, (label, var)) in FIELDS.iter().enumerate() {
+        let selected = i == popup.selected;
+        let row_bg = if selected { theme.surface } else { theme.bg };
+        let mut value_style = Style::default().fg(if selected { theme.highlight } else { theme.text }).bg(row_bg);
+        if selected {
+            value_style = value_style.add_modifier(Modifier::BOLD);
+        }
+        let editing = popup.editing.as_ref().filter(|_| selected);
+        let text = editing.map_or(proxy.field(i), |(text, _)| text.as_str());
+        let value = if text.is_empty() && editing.is_none() {
+            // What the empty field falls back to
+            let fallback = match var.map(|name| (name, env_var(name))) {
+                Some(("NO_PROXY", Some(value))) => format!("{value} (from NO_PROXY)"),
+                Some((name, Some(value))) => format!("{} (from {name})", proxy_host(&value)),
+                Some((name, None)) => format!("not set, nor {name}"),
+                None => "not set".to_string(),
+            };
+            Span::styled(fallback, Style::default().fg(theme.muted).bg(row_bg))
+        } else if i == 3 {
+            Span::styled("•".repeat(text.chars().count()), value_style)
+        } else {
+            Span::styled(text.to_string(), value_style)
+        };
+        if let Some((text, at)) = editing {
+            cursor = Some((lines.len(), LABEL_W + text[..*at].chars().count()));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {label:<w$}", w = LABEL_W - 3), Style::default().fg(theme.muted).bg(row_bg)),
+            value,
+        ]));
+    }
+
+    lines.push(Line::default());
+    let resolved = ResolvedProxy::resolve(proxy, env_var);
+    let shown = |url: &Option<String>| url.as_deref().map_or_else(|| "direct".to_string(), proxy_host);
+    lines.push(Line::from(vec![
+        Span::styled(" Active  ", Style::default().fg(theme.muted)),
+        Span::styled(
+            format!(
+                "http → {} · https → {}",
+                shown(&resolved.http),
+                shown(&resolved.https)
+            ),
+            Style::default().fg(if resolved.is_active() { theme.warning } else { theme.text }),
+        ),
+    ]));
+    if resolved.is_active() && !resolved.no_proxy.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(" Bypass  ", Style::default().fg(theme.muted)),
+            Span::styled(resolved.no_proxy.clone(), Style::default().fg(theme.text)),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some((row, x)) = cursor
+        && row < chunks[0].height as usize
+    {
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + x as u16,
+            y: chunks[0].y + row as u16,
+        });
+    }
+
+    let hint = if popup.editing.is_some() {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" save  ", Style::default().fg(theme.muted)),
+            Span::styled("Tab", Style::default().fg(theme.text)),
+            Span::styled(" save, next field  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("j/k", Style::default().fg(theme.text)),
+            Span::styled(" move  ", Style::default().fg(theme.muted)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" edit  ", Style::default().fg(theme.muted)),
+            Span::styled("d", Style::default().fg(theme.text)),
+            Span::styled(" clear  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[1]);
+}
@@ -1,27 +1,27 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use crate::state::app_state::AppState;
-use crate::state::collection::CollectionItem;
+use crate::state::app_state::{AppState, NodeRunStatus};
+use crate::state::collection::{CollectionItem, Folder};
+use crate::state::config::SidebarMode;
 use crate::state::focus::Focus;
-use super::layout::{ACCENT_BLUE, BORDER_INACTIVE};
-
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
-const SURFACE: Color = Color::Rgb(36, 40, 59);
+use crate::ui::theme::{theme, Theme};
 
 // ─── Flat tree model ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
 pub enum NodeKind {
-    Collection { collapsed: bool },
+    Collection { collapsed: bool, has_default_env: bool },
     Folder { collapsed: bool },
-    Request { method: String },
+    Request { method: String, has_dependency: bool },
 }
 
 #[derive(Debug, Clone)]
@@ -32,50 +32,99 @@ pub struct SidebarNode {
     pub label: String,
 }
 
+/// A visible node as found by `walk_tree`, borrowing from the workspace.
+struct NodeRef<'a> {
+    depth: u16,
+    kind: NodeRefKind<'a>,
+    id: &'a str,
+    label: &'a str,
+}
+
+enum NodeRefKind<'a> {
+    Collection { collapsed: bool, has_default_env: bool },
+    Folder { collapsed: bool },
+    Request { method: &'a str, has_dependency: bool },
+}
+
+impl NodeRef<'_> {
+    fn to_node(&self) -> SidebarNode {
+        let kind = match self.kind {
+            NodeRefKind::Collection { collapsed, has_default_env } => {
+                NodeKind::Collection { collapsed, has_default_env }
+            }
+            NodeRefKind::Folder { collapsed } => NodeKind::Folder { collapsed },
+            NodeRefKind::Request { method, has_dependency } => {
+                NodeKind::Request { method: method.to_string(), has_dependency }
+            }
+        };
+        SidebarNode {
+            depth: self.depth,
+            kind,
+            id: self.id.to_string(),
+            label: self.label.to_string(),
+        }
+    }
+}
+
 /// Walk the workspace collections and produce a flat ordered list of visible nodes.
 /// Collapsed collections/folders hide their children.
 /// If `search_query` is non-empty, only nodes whose label contains the query are shown
 /// (search ignores collapse state — all matching items are visible).
-pub fn flatten_tree(state: &AppState) -> Vec<SidebarNode> {
-    let mut out = Vec::new();
+/// The list is kept in the sidebar state until `SidebarState::invalidate_nodes`.
+pub fn flatten_tree(state: &AppState) -> Rc<[SidebarNode]> {
+    cached_nodes(state).clone()
+}
+
+/// The `height` visible nodes starting at `offset`, and the number of
+/// visible nodes in total. The tree is only walked after it has changed,
+/// so the cost of a frame doesn't grow with its size.
+pub fn visible_window(state: &AppState, offset: usize, height: usize) -> (&[SidebarNode], usize) {
+    let nodes = cached_nodes(state);
+    let start = offset.min(nodes.len());
+    let end = offset.saturating_add(height).min(nodes.len());
+    (&nodes[start..end], nodes.len())
+}
+
+fn cached_nodes(state: &AppState) -> &Rc<[SidebarNode]> {
+    state.sidebar.nodes.get_or_init(|| {
+        let mut out = Vec::new();
+        walk_tree(state, &mut |node| out.push(node.to_node()));
+        out.into()
+    })
+}
+
+/// Call `visit` with every visible node, in display order.
+fn walk_tree<'a>(state: &'a AppState, visit: &mut dyn FnMut(NodeRef<'a>)) {
     let query = state.sidebar.search_query.to_lowercase();
     let searching = state.sidebar.search_mode && !query.is_empty();
 
     for col in &state.workspace.collections {
         let collapsed = state.sidebar.collapsed_ids.contains(&col.id);
-
-        if !searching {
-            out.push(SidebarNode {
-                depth: 0,
-                kind: NodeKind::Collection { collapsed },
-                id: col.id.clone(),
-                label: col.name.clone(),
-            });
-        }
-
         let col_match = searching && col.name.to_lowercase().contains(&query);
-        if col_match {
-            out.push(SidebarNode {
+
+        if !searching || col_match {
+            visit(NodeRef {
                 depth: 0,
-                kind: NodeKind::Collection { collapsed: false },
-                id: col.id.clone(),
-                label: col.name.clone(),
+                kind: NodeRefKind::Collection {
+                    collapsed: collapsed && !searching,
+                    has_default_env: col.default_env_id.is_some(),
+                },
+                id: &col.id,
+                label: &col.name,
             });
         }
 
         // Show children if: not searching + not collapsed, OR searching
         if !collapsed || searching {
-            push_items(&col.items, 1, &mut out, state, &query, searching);
+            walk_items(&col.items, 1, visit, state, &query, searching);
         }
     }
-
-    out
 }
 
-fn push_items(
-    items: &[CollectionItem],
+fn walk_items<'a>(
+    items: &'a [CollectionItem],
     depth: u16,
-    out: &mut Vec<SidebarNode>,
+    visit: &mut dyn FnMut(NodeRef<'a>),
     state: &AppState,
     query: &str,
     searching: bool,
@@ -87,57 +136,346 @@ fn push_items(
                 let folder_match = searching && f.name.to_lowercase().contains(query);
 
                 if !searching || folder_match {
-                    out.push(SidebarNode {
+                    visit(NodeRef {
                         depth,
-                        kind: NodeKind::Folder {
+                        kind: NodeRefKind::Folder {
                             collapsed: if searching { false } else { collapsed },
                         },
-                        id: f.id.clone(),
-                        label: f.name.clone(),
+                        id: &f.id,
+                        label: &f.name,
                     });
                 }
 
                 if !collapsed || searching {
-                    push_items(&f.items, depth + 1, out, state, query, searching);
+                    walk_items(&f.items, depth + 1, visit, state, query, searching);
                 }
             }
             CollectionItem::Request(r) => {
                 if searching && !r.name.to_lowercase().contains(query) {
                     continue;
                 }
-                out.push(SidebarNode {
+                visit(NodeRef {
                     depth,
-                    kind: NodeKind::Request {
-                        method: r.method.clone(),
+                    kind: NodeRefKind::Request {
+                        method: &r.method,
+                        has_dependency: r.depends_on.is_some(),
                     },
-                    id: r.id.clone(),
-                    label: r.name.clone(),
+                    id: &r.id,
+                    label: &r.name,
                 });
             }
         }
     }
 }
 
-fn method_badge_color(method: &str) -> Color {
+/// Collections and folders a request can be saved into (`Ctrl+S` on a
+/// scratch tab). The popup keeps its own expanded set, whatever the sidebar
+/// shows.
+pub fn save_targets(state: &AppState) -> Vec<SidebarNode> {
+    fn folders(items: &[CollectionItem], depth: u16, expanded: &HashSet<String>, out: &mut Vec<SidebarNode>) {
+        for item in items {
+            if let CollectionItem::Folder(f) = item {
+                let open = expanded.contains(&f.id);
+                out.push(SidebarNode {
+                    depth,
+                    kind: NodeKind::Folder { collapsed: !open },
+                    id: f.id.clone(),
+                    label: f.name.clone(),
+                });
+                if open {
+                    folders(&f.items, depth + 1, expanded, out);
+                }
+            }
+        }
+    }
+
+    let expanded = &state.save_request.expanded;
+    let mut out = Vec::new();
+    for col in &state.workspace.collections {
+        let open = expanded.contains(&col.id);
+        out.push(SidebarNode {
+            depth: 0,
+            kind: NodeKind::Collection { collapsed: !open, has_default_env: col.default_env_id.is_some() },
+            id: col.id.clone(),
+            label: col.name.clone(),
+        });
+        if open {
+            folders(&col.items, 1, expanded, &mut out);
+        }
+    }
+    out
+}
+
+// ─── Compact mode ────────────────────────────────────────────────────────────
+
+/// Sidebar width in compact mode.
+pub const COMPACT_WIDTH: u16 = 16;
+/// In `SidebarMode::Auto`, terminals narrower than this get the compact sidebar.
+pub const AUTO_COMPACT_BELOW: u16 = 100;
+
+/// Whether the sidebar renders in compact (badge-only) mode for a terminal
+/// `terminal_width` columns wide.
+pub fn is_compact(state: &AppState, terminal_width: u16) -> bool {
+    match state.config.ui.sidebar_mode {
+        SidebarMode::Compact => true,
+        SidebarMode::Full => false,
+        SidebarMode::Auto => terminal_width < AUTO_COMPACT_BELOW,
+    }
+}
+
+/// Columns taken by the sidebar (borders included).
+pub fn sidebar_width(state: &AppState, terminal_width: u16) -> u16 {
+    if is_compact(state, terminal_width) {
+        COMPACT_WIDTH
+    } else {
+        state.config.ui.sidebar_width
+    }
+}
+
+fn method_letter(method: &str) -> &'static str {
     match method {
-        "GET" => Color::Rgb(115, 218, 202),
-        "POST" => Color::Rgb(158, 206, 106),
-        "PUT" => Color::Rgb(224, 175, 104),
-        "PATCH" => Color::Rgb(187, 154, 247),
-        "DELETE" => Color::Rgb(247, 118, 142),
-        "HEAD" | "OPTIONS" => Color::Rgb(86, 95, 137),
-        _ => Color::White,
+        "GET" => "G",
+        "POST" => "P",
+        "PUT" => "U",
+        "PATCH" => "A",
+        "DELETE" => "D",
+        "HEAD" => "H",
+        "OPTIONS" => "O",
+        _ => "?",
+    }
+}
+
+/// Truncate `text` to at most `width` chars, ending in `…` when cut.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(width.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+// ─── Runner health ───────────────────────────────────────────────────────────
+
+/// Results of the last collection runner pass over a request, or over every
+/// request under a folder or collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Health {
+    pub passed: usize,
+    pub failed: usize,
+    /// Requests counted, including ones the pass didn't run
+    pub total: usize,
+}
+
+impl Health {
+    fn of_request(id: &str, results: &HashMap<String, bool>) -> Health {
+        match results.get(id) {
+            Some(true) => Health { passed: 1, failed: 0, total: 1 },
+            Some(false) => Health { passed: 0, failed: 1, total: 1 },
+            None => Health { passed: 0, failed: 0, total: 1 },
+        }
+    }
+}
+
+/// Tally `results` over every request under `items`, folders included.
+pub fn subtree_health(items: &[CollectionItem], results: &HashMap<String, bool>) -> Health {
+    let mut health = Health::default();
+    for item in items {
+        let part = match item {
+            CollectionItem::Folder(f) => subtree_health(&f.items, results),
+            CollectionItem::Request(r) => Health::of_request(&r.id, results),
+        };
+        health.passed += part.passed;
+        health.failed += part.failed;
+        health.total += part.total;
+    }
+    health
+}
+
+fn find_folder<'a>(items: &'a [CollectionItem], id: &str) -> Option<&'a Folder> {
+    items.iter().find_map(|item| match item {
+        CollectionItem::Folder(f) if f.id == id => Some(f),
+        CollectionItem::Folder(f) => find_folder(&f.items, id),
+        CollectionItem::Request(_) => None,
+    })
+}
+
+/// Health mark for a sidebar node; `None` until a runner pass has results.
+pub fn node_health(state: &AppState, node: &SidebarNode) -> Option<Health> {
+    let results = &state.workspace.runner_results;
+    if results.is_empty() {
+        return None;
+    }
+    let collections = &state.workspace.collections;
+    match node.kind {
+        NodeKind::Request { .. } => Some(Health::of_request(&node.id, results)),
+        NodeKind::Collection { .. } => collections
+            .iter()
+            .find(|c| c.id == node.id)
+            .map(|c| subtree_health(&c.items, results)),
+        NodeKind::Folder { .. } => collections
+            .iter()
+            .find_map(|c| find_folder(&c.items, &node.id))
+            .map(|f| subtree_health(&f.items, results)),
     }
 }
 
+/// A dot for a request (green passed, red failed, grey not run); for a
+/// folder or collection "passed/total" and a tick or cross, shortened to
+/// the glyph in compact mode.
+fn health_span(theme: &Theme, health: Health, is_request: bool, compact: bool, bg: Color) -> Span<'static> {
+    let color = if health.failed > 0 {
+        theme.error
+    } else if health.passed == health.total {
+        theme.success
+    } else {
+        theme.muted
+    };
+    let glyph = match (is_request, health.failed > 0) {
+        (true, _) => "●",
+        (false, true) => "✗",
+        (false, false) if health.passed > 0 => "✓",
+        (false, false) => "·",
+    };
+    let text = if is_request || compact {
+        format!(" {glyph}")
+    } else {
+        format!(" {}/{} {glyph}", health.passed, health.total)
+    };
+    Span::styled(text, Style::default().fg(color).bg(bg))
+}
+
 // ─── Render ──────────────────────────────────────────────────────────────────
 
+/// Inline run-in-place result after a request label, e.g. " 200 · 134ms".
+/// Compact mode shows only a colored dot.
+fn run_status_span(theme: &Theme, status: &NodeRunStatus, compact: bool, bg: Color) -> Span<'static> {
+    let (text, color) = match (status, compact) {
+        (NodeRunStatus::Queued, _) => (" ·".to_string(), theme.muted),
+        (NodeRunStatus::Running, _) => (" …".to_string(), theme.warning),
+        (NodeRunStatus::Done { status, .. }, true) => (" ●".to_string(), theme.status_color(*status)),
+        (NodeRunStatus::Done { status, total_ms }, false) => {
+            (format!(" {} · {}ms", status, total_ms), theme.status_color(*status))
+        }
+        (NodeRunStatus::Failed, true) => (" ✗".to_string(), theme.error),
+        (NodeRunStatus::Failed, false) => (" error".to_string(), theme.error),
+    };
+    Span::styled(text, Style::default().fg(color).bg(bg))
+}
+
+/// Build the row for one node. In compact mode the indent is one column per
+/// level, requests show a single-letter method badge and the label is
+/// truncated to `label_width` chars.
+pub fn node_line(
+    theme: &Theme,
+    node: &SidebarNode,
+    run: Option<&NodeRunStatus>,
+    health: Option<Health>,
+    is_cursor: bool,
+    compact: bool,
+    label_width: usize,
+) -> Line<'static> {
+    let row_bg = if is_cursor { theme.surface } else { theme.bg };
+    let indent = if compact {
+        " ".repeat(node.depth as usize)
+    } else {
+        "  ".repeat(node.depth as usize)
+    };
+    let label_style = if is_cursor {
+        Style::default().fg(theme.highlight).bg(row_bg)
+    } else {
+        Style::default().fg(theme.text).bg(row_bg)
+    };
+
+    let (prefix, badge, label_style) = match &node.kind {
+        NodeKind::Collection { collapsed, .. } => {
+            let arrow = match (*collapsed, compact) {
+                (true, true) => "▶",
+                (false, true) => "▼",
+                (true, false) => "▶ ",
+                (false, false) => "▼ ",
+            };
+            (
+                Span::styled(format!("{}{}", indent, arrow), Style::default().fg(theme.accent).bg(row_bg)),
+                None,
+                label_style.add_modifier(Modifier::BOLD),
+            )
+        }
+        NodeKind::Folder { collapsed } => {
+            let arrow = match (*collapsed, compact) {
+                (true, true) => "▶",
+                (false, true) => "▼",
+                (true, false) => "▶ ",
+                (false, false) => "▼ ",
+            };
+            (
+                Span::styled(format!("{}{}", indent, arrow), Style::default().fg(theme.muted).bg(row_bg)),
+                None,
+                label_style,
+            )
+        }
+        NodeKind::Request { method, .. } => {
+            let color = theme.method_color(method);
+            let badge = if compact {
+                method_letter(method).to_string()
+            } else {
+                format!("{:<6} ", method)
+            };
+            let pad = if compact { "" } else { "  " };
+            (
+                Span::styled(format!("{}{}", indent, pad), Style::default().bg(row_bg)),
+                Some(Span::styled(
+                    badge,
+                    Style::default().fg(color).bg(row_bg).add_modifier(Modifier::BOLD),
+                )),
+                label_style,
+            )
+        }
+    };
+
+    // Link glyph marks requests that depend on another request
+    let link = matches!(node.kind, NodeKind::Request { has_dependency: true, .. })
+        .then(|| Span::styled(" ⇠", Style::default().fg(theme.muted).bg(row_bg)));
+    // Collections that switch the environment when their requests open
+    let env = matches!(node.kind, NodeKind::Collection { has_default_env: true, .. })
+        .then(|| Span::styled(" [E]", Style::default().fg(theme.info).bg(row_bg)));
+
+    // A request's live run status takes the place of its last-pass dot
+    let is_request = matches!(node.kind, NodeKind::Request { .. });
+    let health = health
+        .filter(|_| !is_request || run.is_none())
+        .map(|h| health_span(theme, h, is_request, compact, row_bg));
+    let run = run.map(|r| run_status_span(theme, r, compact, row_bg));
+
+    let used = prefix.width()
+        + badge.as_ref().map(|b| b.width()).unwrap_or(0)
+        + link.as_ref().map(|l| l.width()).unwrap_or(0)
+        + env.as_ref().map(|e| e.width()).unwrap_or(0)
+        + health.as_ref().map(|h| h.width()).unwrap_or(0)
+        + run.as_ref().map(|r| r.width()).unwrap_or(0);
+    let label = if compact {
+        truncate(&node.label, label_width.saturating_sub(used))
+    } else {
+        node.label.clone()
+    };
+    let mut spans = vec![prefix];
+    spans.extend(badge);
+    spans.push(Span::styled(label, label_style));
+    spans.extend(link);
+    spans.extend(env);
+    spans.extend(health);
+    spans.extend(run);
+    Line::from(spans)
+}
+
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let focused = matches!(state.focus, Focus::Sidebar);
-    let border_color = if focused { ACCENT_BLUE } else { BORDER_INACTIVE };
+    let border_color = if focused { theme.accent } else { theme.border };
+    let compact = is_compact(state, frame.area().width);
 
     let block = Block::default()
-        .title(" forge ")
+        .title(if compact { " f " } else { " forge " })
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
@@ -145,11 +483,10 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(block, area);
 
     if inner.width < 3 || inner.height < 2 {
+        state.hit_areas.borrow_mut().sidebar_list = Rect::default();
         return;
     }
 
-    let nodes = flatten_tree(state);
-
     // Always reserve the last 1 row for the footer (hints or search bar)
     let (list_area, footer_area) = if inner.height < 3 {
         (inner, None)
@@ -161,90 +498,38 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         (chunks[0], Some(chunks[1]))
     };
 
+    state.hit_areas.borrow_mut().sidebar_list = list_area;
+    let scroll = state.sidebar.scroll_offset;
+    let (nodes, total) = visible_window(state, scroll, list_area.height as usize);
+
     // Empty state
-    if nodes.is_empty() && !state.sidebar.search_mode {
+    if total == 0 && !state.sidebar.search_mode {
         let hint = Paragraph::new(Line::from(Span::styled(
-            "Ctrl+n: new collection",
-            Style::default().fg(TEXT_MUTED).add_modifier(Modifier::DIM),
+            if compact { "^n: new" } else { "Ctrl+n: new collection" },
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
         )));
         frame.render_widget(hint, list_area);
-    } else if nodes.is_empty() {
+    } else if total == 0 {
         let hint = Paragraph::new(Line::from(Span::styled(
             "No results",
-            Style::default().fg(TEXT_MUTED).add_modifier(Modifier::DIM),
+            Style::default().fg(theme.muted).patch(theme.hint_style()),
         )));
         frame.render_widget(hint, list_area);
     } else {
-        let scroll = state.sidebar.scroll_offset;
-        let visible_nodes = nodes.iter().skip(scroll);
-
-        for (i, node) in visible_nodes.enumerate() {
+        for (i, node) in nodes.iter().enumerate() {
             let y = list_area.y + i as u16;
-            if y >= list_area.y + list_area.height {
-                break;
-            }
             let abs_idx = i + scroll;
             let is_cursor = abs_idx == state.sidebar.cursor;
-            let row_bg = if is_cursor { SURFACE } else { Color::Reset };
             let row_area = Rect { y, height: 1, ..list_area };
-
-            let indent = "  ".repeat(node.depth as usize);
-            let line = match &node.kind {
-                NodeKind::Collection { collapsed } => {
-                    let arrow = if *collapsed { "▶ " } else { "▼ " };
-                    let label_style = if is_cursor {
-                        Style::default()
-                            .fg(Color::White)
-                            .bg(row_bg)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(TEXT_PRIMARY).bg(row_bg).add_modifier(Modifier::BOLD)
-                    };
-                    Line::from(vec![
-                        Span::styled(
-                            format!("{}{}", indent, arrow),
-                            Style::default().fg(ACCENT_BLUE).bg(row_bg),
-                        ),
-                        Span::styled(node.label.clone(), label_style),
-                    ])
-                }
-                NodeKind::Folder { collapsed } => {
-                    let arrow = if *collapsed { "▶ " } else { "▼ " };
-                    let label_style = if is_cursor {
-                        Style::default().fg(Color::White).bg(row_bg)
-                    } else {
-                        Style::default().fg(TEXT_PRIMARY).bg(row_bg)
-                    };
-                    Line::from(vec![
-                        Span::styled(
-                            format!("{}{}", indent, arrow),
-                            Style::default().fg(TEXT_MUTED).bg(row_bg),
-                        ),
-                        Span::styled(node.label.clone(), label_style),
-                    ])
-                }
-                NodeKind::Request { method } => {
-                    let color = method_badge_color(method);
-                    let method_display = format!("{:<6} ", method);
-                    let label_style = if is_cursor {
-                        Style::default().fg(Color::White).bg(row_bg)
-                    } else {
-                        Style::default().fg(TEXT_PRIMARY).bg(row_bg)
-                    };
-                    Line::from(vec![
-                        Span::styled(
-                            format!("{}  ", indent),
-                            Style::default().bg(row_bg),
-                        ),
-                        Span::styled(
-                            method_display,
-                            Style::default().fg(color).bg(row_bg).add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(node.label.clone(), label_style),
-                    ])
-                }
-            };
-
+            let run = state.sidebar.run_status.get(&node.id);
+            let health = node_health(state, node);
+            let mut line =
+                node_line(theme, node, run, health, is_cursor, compact, list_area.width as usize);
+            // The node marked for moving stays dimmed until it is pasted
+            if state.sidebar.cut.as_deref() == Some(node.id.as_str()) {
+                line = line.patch_style(Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC));
+            }
+            flash_if_moved(state, theme, node, &mut line);
             frame.render_widget(Paragraph::new(line), row_area);
         }
     }
@@ -253,28 +538,200 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     if let Some(fa) = footer_area {
         if state.sidebar.search_mode {
             let search_line = Line::from(vec![
-                Span::styled("/ ", Style::default().fg(ACCENT_BLUE)),
+                Span::styled("/ ", Style::default().fg(theme.accent)),
                 Span::styled(
                     state.sidebar.search_query.clone(),
-                    Style::default().fg(TEXT_PRIMARY),
+                    Style::default().fg(theme.text),
                 ),
             ]);
             frame.render_widget(Paragraph::new(search_line), fa);
+        } else if state.sidebar.cut.is_some() && !compact {
+            let hints = Line::from(vec![
+                Span::styled("p", Style::default().fg(theme.accent)),
+                Span::styled(" move here  ", Style::default().fg(theme.muted)),
+                Span::styled("Esc", Style::default().fg(theme.accent)),
+                Span::styled(" cancel", Style::default().fg(theme.muted)),
+            ]);
+            frame.render_widget(
+                Paragraph::new(hints).style(theme.hint_style()),
+                fa,
+            );
+        } else if compact {
+            let hints = Line::from(vec![
+                Span::styled("/", Style::default().fg(theme.accent)),
+                Span::styled(" find ", Style::default().fg(theme.muted)),
+                Span::styled("w", Style::default().fg(theme.accent)),
+                Span::styled(" wide", Style::default().fg(theme.muted)),
+            ]);
+            frame.render_widget(
+                Paragraph::new(hints).style(theme.hint_style()),
+                fa,
+            );
+        } else if let Some(description) = focused.then(|| cursor_description(state)).flatten() {
+            // The highlighted node's notes in place of the hints
+            let mut lines = description.lines().filter(|l| !l.trim().is_empty());
+            let first = lines.next().unwrap_or_default().trim().to_string();
+            let more = if lines.next().is_some() { " …" } else { "" };
+            let line = Line::from(Span::styled(
+                format!("{first}{more}"),
+                Style::default().fg(theme.text_dim).add_modifier(Modifier::ITALIC),
+            ));
+            frame.render_widget(Paragraph::new(line), fa);
         } else {
             let hints = Line::from(vec![
-                Span::styled("^n", Style::default().fg(ACCENT_BLUE)),
-                Span::styled(" col  ", Style::default().fg(TEXT_MUTED)),
-                Span::styled("n", Style::default().fg(ACCENT_BLUE)),
-                Span::styled(" req  ", Style::default().fg(TEXT_MUTED)),
-                Span::styled("d", Style::default().fg(ACCENT_BLUE)),
-                Span::styled(" del  ", Style::default().fg(TEXT_MUTED)),
-                Span::styled("/", Style::default().fg(ACCENT_BLUE)),
-                Span::styled(" search", Style::default().fg(TEXT_MUTED)),
+                Span::styled("^n", Style::default().fg(theme.accent)),
+                Span::styled(" col  ", Style::default().fg(theme.muted)),
+                Span::styled("n", Style::default().fg(theme.accent)),
+                Span::styled(" req  ", Style::default().fg(theme.muted)),
+                Span::styled("d", Style::default().fg(theme.accent)),
+                Span::styled(" del  ", Style::default().fg(theme.muted)),
+                Span::styled("/", Style::default().fg(theme.accent)),
+                Span::styled(" search", Style::default().fg(theme.muted)),
             ]);
             frame.render_widget(
-                Paragraph::new(hints).style(Style::default().add_modifier(Modifier::DIM)),
+                Paragraph::new(hints).style(theme.hint_style()),
                 fa,
             );
         }
     }
 }
+
+/// Description of the collection, folder or request under the cursor, if
+/// it has one.
+fn cursor_description(state: &AppState) -> Option<&str> {
+    fn find<'a>(items: &'a [CollectionItem], id: &str) -> Option<&'a str> {
+        items.iter().find_map(|item| match item {
+            CollectionItem::Request(r) if r.id == id => Some(r.description.as_str()),
+            CollectionItem::Folder(f) if f.id == id => Some(f.description.as_str()),
+            CollectionItem::Folder(f) => find(&f.items, id),
+            CollectionItem::Request(_) => None,
+        })
+    }
+
+    let (nodes, _) = visible_window(state, state.sidebar.cursor, 1);
+    let id = &nodes.first()?.id;
+    state
+        .workspace
+        .collections
+        .iter()
+        .find_map(|col| if col.id == *id { Some(col.description.as_str()) } else { find(&col.items, id) })
+        .filter(|d| !d.trim().is_empty())
+}
+
+/// A node just moved with `J` / `K` flashes so the move is noticed.
+fn flash_if_moved(state: &AppState, theme: &Theme, node: &SidebarNode, line: &mut Line<'static>) {
+    if state.sidebar.moved.as_ref().is_some_and(|(id, _)| *id == node.id) {
+        for span in &mut line.spans {
+            span.style = span.style.bg(theme.border);
+        }
+    }
+}
+
+/// In compact mode, redraw the row under the cursor at its full width on top
+/// of the neighbouring panels so the selected item is always readable.
+/// Must be called after the main panels have rendered. `area` is the sidebar rect.
+pub fn render_cursor_overlay(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let screen = frame.area();
+    if !is_compact(state, screen.width) || state.focus != Focus::Sidebar {
+        return;
+    }
+    // Same geometry as `render`: borders, then footer on the last inner row
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    let list_height = if inner.height < 3 { inner.height } else { inner.height - 1 };
+    let cursor = state.sidebar.cursor;
+    let Some(row) = cursor.checked_sub(state.sidebar.scroll_offset) else {
+        return;
+    };
+    if row >= list_height as usize {
+        return;
+    }
+    let (nodes, _) = visible_window(state, cursor, 1);
+    let Some(node) = nodes.first() else {
+        return;
+    };
+
+    let run = state.sidebar.run_status.get(&node.id);
+    let mut line = node_line(theme, node, run, node_health(state, node), true, false, usize::MAX);
+    flash_if_moved(state, theme, node, &mut line);
+    let full_width = line.width() as u16 + 1;
+    if full_width <= inner.width {
+        return;
+    }
+    let overlay = Rect {
+        x: inner.x,
+        y: inner.y + row as u16,
+        width: full_width.min(screen.width.saturating_sub(inner.x)),
+        height: 1,
+    };
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(Paragraph::new(line).style(Style::default().bg(theme.surface)), overlay);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::collection::{Collection, CollectionRequest};
+
+    /// A workspace with one collection of `n` requests.
+    fn large_workspace(n: usize) -> AppState {
+        let mut state = AppState::default();
+        let mut col = Collection::new("big");
+        for i in 0..n {
+            col.items.push(CollectionItem::Request(CollectionRequest::new(format!("req {i}"))));
+        }
+        state.workspace.collections.push(col);
+        state
+    }
+
+    #[test]
+    fn test_visible_window_is_bounded_by_height() {
+        let mut state = large_workspace(5_000);
+        let (window, total) = visible_window(&state, 2_500, 30);
+        assert_eq!(total, 5_001);
+        assert_eq!(window.len(), 30);
+        assert_eq!(window[0].label, "req 2499");
+
+        let (window, _) = visible_window(&state, 4_990, 30);
+        assert_eq!(window.len(), 11);
+
+        state.sidebar.search_mode = true;
+        state.sidebar.search_query = "req 49".into();
+        state.sidebar.invalidate_nodes();
+        let (window, total) = visible_window(&state, 0, 30);
+        assert_eq!(total, 111);
+        assert_eq!(window.len(), 30);
+        assert_eq!(flatten_tree(&state).len(), total);
+    }
+
+    #[test]
+    fn test_subtree_health_counts_nested_requests() {
+        let req = |name: &str| CollectionRequest::new(name);
+        let (a, b, c, d) = (req("a"), req("b"), req("c"), req("d"));
+        let mut inner = Folder::new("inner");
+        inner.items.push(CollectionItem::Request(c.clone()));
+        let mut outer = Folder::new("outer");
+        outer.items.push(CollectionItem::Request(b.clone()));
+        outer.items.push(CollectionItem::Folder(inner));
+        let items = vec![
+            CollectionItem::Request(a.clone()),
+            CollectionItem::Folder(outer),
+            CollectionItem::Request(d.clone()),
+        ];
+
+        let mut results = HashMap::new();
+        assert_eq!(subtree_health(&items, &results), Health { passed: 0, failed: 0, total: 4 });
+        results.insert(a.id.clone(), true);
+        results.insert(b.id.clone(), true);
+        results.insert(c.id.clone(), false);
+        assert_eq!(subtree_health(&items, &results), Health { passed: 2, failed: 1, total: 4 });
+        let CollectionItem::Folder(outer) = &items[1] else { unreachable!() };
+        assert_eq!(subtree_health(&outer.items, &results), Health { passed: 1, failed: 1, total: 2 });
+        assert_eq!(subtree_health(&[], &results), Health::default());
+    }
+}
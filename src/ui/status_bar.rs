@@ -1,35 +1,105 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
 };
 
-use crate::state::app_state::AppState;
+use crate::http::client::{env_var, ResolvedProxy};
+use crate::state::app_state::{AppState, RequestStatus};
+use crate::state::config::ThemeKind;
 use crate::state::mode::Mode;
+use crate::ui::theme::{resolve, theme};
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let (mode_label, mode_color) = match state.mode {
-        Mode::Normal => ("NORMAL", Color::Rgb(122, 162, 247)),   // blue
-        Mode::Insert => ("INSERT", Color::Rgb(158, 206, 106)),   // green
-        Mode::Command => ("COMMAND", Color::Rgb(224, 175, 104)), // orange
-        Mode::Visual => ("VISUAL", Color::Rgb(187, 154, 247)),   // purple
+        Mode::Normal => ("NORMAL", theme.accent),   // blue
+        Mode::Insert => ("INSERT", theme.success),   // green
+        Mode::Command => ("COMMAND", theme.warning), // orange
+        Mode::Visual => ("VISUAL", theme.purple),   // purple
     };
 
     let mode_span = Span::styled(
         format!(" {} ", mode_label),
         Style::default()
-            .fg(Color::Black)
+            .fg(theme.bg)
             .bg(mode_color)
             .add_modifier(Modifier::BOLD),
     );
 
     let hints = Span::styled(
-        "  · ?:help · Ctrl+R:send · Ctrl+E:env · [ ]:method · Tab:focus · q:quit",
-        Style::default().fg(Color::Rgb(65, 72, 104)),
+        "  · ?:help · Ctrl+R:send · Ctrl+E:env · { }:cycle env · [ ]:method · Tab:focus · q:quit",
+        Style::default().fg(theme.border),
     );
 
-    let line = Line::from(vec![mode_span, hints]);
-    frame.render_widget(Paragraph::new(line), area);
+    let mut spans = vec![mode_span];
+    // Sends waiting their turn (Q opens the queue)
+    let queue = &state.send_queue;
+    let pending = queue.entries.len() + usize::from(queue.running.is_some());
+    if state.workspace.send_queue || pending > 0 {
+        spans.push(Span::styled(
+            format!(" ⋯ {pending} queued "),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    if let Some(proxy) = ResolvedProxy::resolve(&state.workspace.proxy, env_var).label() {
+        spans.push(Span::styled(format!(" ⇄ {proxy} "), Style::default().fg(theme.text_dim)));
+    }
+    if let Some((message, _)) = &state.toast {
+        spans.push(Span::styled(format!(" {message} "), Style::default().fg(theme.accent)));
+    }
+    spans.push(hints);
+
+    // Right-hand corner: `workspace` and an environment badge in the
+    // environment's color, so sends don't go to the wrong one, then the
+    // active theme
+    let ws = &state.workspace;
+    let mut env_spans = vec![];
+    let active = state.active_environment_idx().and_then(|i| ws.environments.get(i));
+    // While a send is out, show the environment it went with when that's
+    // no longer the active one
+    let sent = state
+        .active_tab()
+        .filter(|t| matches!(t.request_status, RequestStatus::Loading { .. }))
+        .map(|t| t.sent_environment.as_ref())
+        .filter(|sent| sent.map(|e| e.id.as_str()) != active.map(|e| e.id.as_str()));
+    let badge = match sent {
+        Some(sent) => sent.map(|e| (e.name.as_str(), theme.hex_color(&e.color))),
+        None => active.map(|e| (e.name.as_str(), theme.env_color(e))),
+    };
+    match badge {
+        Some((name, color)) => env_spans.push(Span::styled(
+            format!(" {name} "),
+            Style::default().fg(theme.bg).bg(color).add_modifier(Modifier::BOLD),
+        )),
+        None => env_spans.push(Span::styled(" (no env) ", Style::default().fg(theme.muted))),
+    }
+    if sent.is_some() {
+        let now = active.map_or("no env", |e| e.name.as_str());
+        env_spans.push(Span::styled(format!(" sending, now {now}"), Style::default().fg(theme.warning)));
+    }
+    let kind = state.config.ui.theme;
+    let theme_name = match kind {
+        ThemeKind::Auto => format!("  {} (auto) ", resolve(kind).label()),
+        kind => format!("  {} ", kind.label()),
+    };
+    let mut right_spans = vec![Span::styled(format!("{} ", ws.name), Style::default().fg(theme.text_dim))];
+    right_spans.extend(env_spans);
+    right_spans.push(Span::styled(theme_name, Style::default().fg(theme.muted)));
+    let right = Line::from(right_spans);
+
+    // The left side gets what the right leaves, padded out to meet it
+    let right_w = right.width() as u16;
+    let left_area = Rect { width: area.width.saturating_sub(right_w), ..area };
+    let left = Line::from(spans);
+    let pad = (left_area.width as usize).saturating_sub(left.width());
+    let mut left_spans = left.spans;
+    left_spans.push(Span::raw(" ".repeat(pad)));
+    frame.render_widget(Paragraph::new(Line::from(left_spans)), left_area);
+    frame.render_widget(
+        Paragraph::new(right),
+        Rect { x: left_area.x + left_area.width, width: area.width - left_area.width, ..area },
+    );
 }
@@ -0,0 +1,136 @@
+// Line wrapping and URL shortening for the read-only views
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+
+/// Wrap each line of `text` to at most `width` columns, skipping the first
+/// `skip` rows and stopping after `height` rows. Lines break after the last
+/// whitespace that fits, or mid-word when a word is wider than `width`.
+pub fn wrap_text(text: &Text<'_>, width: usize, skip: usize, height: usize) -> Text<'static> {
+    let lines: Vec<Line<'static>> = text
+        .lines
+        .iter()
+        .flat_map(|line| wrap_line(line, width))
+        .skip(skip)
+        .take(height)
+        .collect();
+    Text::from(lines)
+}
+
+/// How many rows each line of `text` takes when wrapped to `width`.
+pub fn wrapped_rows(text: &Text<'_>, width: usize) -> Vec<usize> {
+    text.lines.iter().map(|line| wrap_line(line, width).len()).collect()
+}
+
+/// The line holding wrapped row `row`, given each line's row count.
+pub fn line_at_row(rows: &[usize], row: usize) -> usize {
+    let mut seen = 0;
+    for (i, n) in rows.iter().enumerate() {
+        seen += n;
+        if row < seen {
+            return i;
+        }
+    }
+    rows.len().saturating_sub(1)
+}
+
+/// One line split into rows of at most `width` chars, keeping span styles.
+pub fn wrap_line(line: &Line<'_>, width: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    // Highlighted lines keep their trailing newline; it takes no column
+    let chars: Vec<(char, Style)> = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+        .filter(|(c, _)| !matches!(c, '\n' | '\r'))
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while chars.len() - start > width {
+        let end = (start + 1..=start + width)
+            .rev()
+            .find(|&i| chars[i - 1].0.is_whitespace())
+            .unwrap_or(start + width);
+        rows.push(row_line(&chars[start..end], line.style));
+        start = end;
+    }
+    rows.push(row_line(&chars[start..], line.style));
+    rows
+}
+
+/// Regroup styled chars into one span per run of the same style.
+fn row_line(chars: &[(char, Style)], style: Style) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for &(c, char_style) in chars {
+        match spans.last_mut() {
+            Some(span) if span.style == char_style => span.content.to_mut().push(c),
+            _ => spans.push(Span::styled(c.to_string(), char_style)),
+        }
+    }
+    Line::from(spans).style(style)
+}
+
+/// `url` without its scheme, cut to at most `width` chars by replacing the
+/// middle of the path with `…` so the host and the last segment stay
+/// readable.
+pub fn shorten_url(url: &str, width: usize) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let len = url.chars().count();
+    if len <= width {
+        return url.to_string();
+    }
+    if width < 2 {
+        return "…".chars().take(width).collect();
+    }
+    let head = (width - 1).div_ceil(2);
+    let tail = width - 1 - head;
+    let mut out: String = url.chars().take(head).collect();
+    out.push('…');
+    out.extend(url.chars().skip(len - tail));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_line() {
+        let bold = Style::default().add_modifier(ratatui::style::Modifier::BOLD);
+        let line = Line::from(vec![Span::raw("one two "), Span::styled("three\n", bold)]);
+        let rows = wrap_line(&line, 9);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].to_string(), "one two ");
+        assert_eq!(rows[1].spans, vec![Span::styled("three", bold)]);
+
+        // No whitespace to break at: cut mid-word
+        let rows = wrap_line(&Line::raw("abcdefgh"), 3);
+        let rows: Vec<String> = rows.iter().map(|r| r.to_string()).collect();
+        assert_eq!(rows, ["abc", "def", "gh"]);
+
+        // Skipping counts wrapped rows, not lines
+        let text = Text::from(vec![Line::raw("skipped"), Line::raw("aa bb cc")]);
+        let rows: Vec<String> = wrap_text(&text, 3, 2, 2).lines.iter().map(|r| r.to_string()).collect();
+        assert_eq!(rows, ["d", "aa "]);
+    }
+
+    #[test]
+    fn test_wrapped_rows_and_line_at_row() {
+        let text = Text::from(vec![Line::raw("skipped"), Line::raw(""), Line::raw("aa bb cc")]);
+        let rows = wrapped_rows(&text, 3);
+        assert_eq!(rows, [3, 1, 3]);
+        assert_eq!(line_at_row(&rows, 0), 0);
+        assert_eq!(line_at_row(&rows, 2), 0);
+        assert_eq!(line_at_row(&rows, 3), 1);
+        assert_eq!(line_at_row(&rows, 4), 2);
+        assert_eq!(line_at_row(&rows, 99), 2);
+    }
+
+    #[test]
+    fn test_shorten_url_keeps_both_ends() {
+        assert_eq!(shorten_url("https://api.test/users", 40), "api.test/users");
+        assert_eq!(shorten_url("https://api.test/v1/users/42", 13), "api.te…ers/42");
+        assert_eq!(shorten_url("https://api.test/v1/users/42", 13).chars().count(), 13);
+        assert_eq!(shorten_url("api.test", 1), "…");
+    }
+}
@@ -0,0 +1,290 @@
+// Color themes. Every UI module takes its colors from the active `Theme`
+// (`theme(state)`) instead of hardcoding them.
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, bail};
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::state::app_state::AppState;
+use crate::state::config::ThemeKind;
+use crate::state::environment::{parse_hex_color, Environment};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Popup and panel background.
+    pub bg: Color,
+    /// Selected row background.
+    pub surface: Color,
+    /// Unfocused borders and separators.
+    pub border: Color,
+    /// Focused borders, keys in hint bars, active tabs.
+    pub accent: Color,
+    pub text: Color,
+    /// Secondary text, e.g. unselected editor cells.
+    pub text_dim: Color,
+    /// Placeholders, hint descriptions, metadata.
+    pub muted: Color,
+    /// Emphasised text on `surface`.
+    pub highlight: Color,
+    /// Block cursor and the focused open tab (fg on bg).
+    pub cursor_fg: Color,
+    pub cursor_bg: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub info: Color,
+    pub purple: Color,
+    pub teal: Color,
+    pub orange: Color,
+    /// `{{variable}}` placeholders that resolve / don't resolve.
+    pub var_resolved: Color,
+    pub var_unresolved: Color,
+    /// Whether hint bars are drawn with the DIM modifier.
+    pub dim_hints: bool,
+}
+
+pub const TOKYO_NIGHT: Theme = Theme {
+    bg: Color::Rgb(26, 27, 38),
+    surface: Color::Rgb(36, 40, 59),
+    border: Color::Rgb(65, 72, 104),
+    accent: Color::Rgb(122, 162, 247),
+    text: Color::Rgb(192, 202, 245),
+    text_dim: Color::Rgb(169, 177, 214),
+    muted: Color::Rgb(86, 95, 137),
+    highlight: Color::White,
+    cursor_fg: Color::Black,
+    cursor_bg: Color::White,
+    success: Color::Rgb(158, 206, 106),
+    warning: Color::Rgb(224, 175, 104),
+    error: Color::Rgb(247, 118, 142),
+    info: Color::Rgb(42, 195, 222),
+    purple: Color::Rgb(187, 154, 247),
+    teal: Color::Rgb(115, 218, 202),
+    orange: Color::Rgb(255, 158, 100),
+    var_resolved: Color::Rgb(42, 195, 222),
+    var_unresolved: Color::Rgb(247, 118, 142),
+    dim_hints: true,
+};
+
+/// Tokyo Night Day.
+pub const LIGHT: Theme = Theme {
+    bg: Color::Rgb(225, 226, 231),
+    surface: Color::Rgb(196, 200, 218),
+    border: Color::Rgb(150, 156, 182),
+    accent: Color::Rgb(46, 126, 233),
+    text: Color::Rgb(52, 59, 88),
+    text_dim: Color::Rgb(70, 78, 110),
+    muted: Color::Rgb(98, 105, 140),
+    highlight: Color::Rgb(15, 15, 20),
+    cursor_fg: Color::Rgb(225, 226, 231),
+    cursor_bg: Color::Rgb(52, 59, 88),
+    success: Color::Rgb(72, 102, 40),
+    warning: Color::Rgb(140, 96, 30),
+    error: Color::Rgb(200, 30, 80),
+    info: Color::Rgb(0, 105, 140),
+    purple: Color::Rgb(130, 70, 220),
+    teal: Color::Rgb(15, 120, 100),
+    orange: Color::Rgb(177, 92, 0),
+    var_resolved: Color::Rgb(0, 105, 140),
+    var_unresolved: Color::Rgb(200, 30, 80),
+    dim_hints: false,
+};
+
+/// Pure black background, bright text and no dimmed hints.
+pub const HIGH_CONTRAST: Theme = Theme {
+    bg: Color::Black,
+    surface: Color::Rgb(0, 55, 110),
+    border: Color::Rgb(190, 190, 190),
+    accent: Color::Rgb(90, 200, 255),
+    text: Color::White,
+    text_dim: Color::Rgb(235, 235, 235),
+    muted: Color::Rgb(210, 210, 210),
+    highlight: Color::White,
+    cursor_fg: Color::Black,
+    cursor_bg: Color::Rgb(255, 230, 0),
+    success: Color::Rgb(90, 255, 120),
+    warning: Color::Rgb(255, 220, 0),
+    error: Color::Rgb(255, 100, 100),
+    info: Color::Rgb(0, 255, 255),
+    purple: Color::Rgb(255, 140, 255),
+    teal: Color::Rgb(0, 255, 200),
+    orange: Color::Rgb(255, 170, 60),
+    var_resolved: Color::Rgb(0, 255, 255),
+    var_unresolved: Color::Rgb(255, 100, 100),
+    dim_hints: false,
+};
+
+impl Theme {
+    /// The palette for `kind`. `Custom` is the loaded `theme.toml`, or Tokyo
+    /// Night when there is none.
+    pub fn of(kind: ThemeKind, custom: Option<&Theme>) -> &Theme {
+        match resolve(kind) {
+            ThemeKind::Auto | ThemeKind::TokyoNight => &TOKYO_NIGHT,
+            ThemeKind::Light => &LIGHT,
+            ThemeKind::HighContrast => &HIGH_CONTRAST,
+            ThemeKind::Custom => custom.unwrap_or(&TOKYO_NIGHT),
+        }
+    }
+
+    /// Parse a `theme.toml`. `base` names the built-in palette the file starts
+    /// from (Tokyo Night by default); every other key is a field of `Theme`
+    /// set to `"#rrggbb"`, a color name or a 256-color index.
+    pub fn from_toml(content: &str) -> anyhow::Result<Theme> {
+        let mut table: toml::Table = content.parse()?;
+        let base = match table.remove("base") {
+            Some(value) => value.try_into::<ThemeKind>()?,
+            None => ThemeKind::TokyoNight,
+        };
+        if base == ThemeKind::Custom {
+            bail!("base must be a built-in theme");
+        }
+        let mut theme = *Theme::of(base, None);
+        for (key, value) in table {
+            if key == "dim_hints" {
+                theme.dim_hints = value.as_bool().ok_or_else(|| anyhow!("dim_hints must be true or false"))?;
+                continue;
+            }
+            let Some(slot) = theme.color_mut(&key) else {
+                bail!("unknown color `{key}`");
+            };
+            let color = match &value {
+                toml::Value::String(text) => text.parse().ok(),
+                toml::Value::Integer(index) => u8::try_from(*index).ok().map(Color::Indexed),
+                _ => None,
+            };
+            *slot = color.ok_or_else(|| anyhow!("`{key}` is not a color: {value}"))?;
+        }
+        Ok(theme)
+    }
+
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "bg" => &mut self.bg,
+            "surface" => &mut self.surface,
+            "border" => &mut self.border,
+            "accent" => &mut self.accent,
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "muted" => &mut self.muted,
+            "highlight" => &mut self.highlight,
+            "cursor_fg" => &mut self.cursor_fg,
+            "cursor_bg" => &mut self.cursor_bg,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "info" => &mut self.info,
+            "purple" => &mut self.purple,
+            "teal" => &mut self.teal,
+            "orange" => &mut self.orange,
+            "var_resolved" => &mut self.var_resolved,
+            "var_unresolved" => &mut self.var_unresolved,
+            _ => return None,
+        })
+    }
+
+    /// An environment's own color, or the text color if it isn't `#rrggbb`.
+    pub fn env_color(&self, env: &Environment) -> Color {
+        env.rgb().map_or(self.text, |(r, g, b)| Color::Rgb(r, g, b))
+    }
+
+    /// A `#rrggbb` color, or the text color if it isn't one.
+    pub fn hex_color(&self, hex: &str) -> Color {
+        parse_hex_color(hex).map_or(self.text, |(r, g, b)| Color::Rgb(r, g, b))
+    }
+
+    /// Badge color for an HTTP method.
+    pub fn method_color(&self, method: &str) -> Color {
+        match method {
+            "GET" => self.teal,
+            "POST" => self.success,
+            "PUT" => self.warning,
+            "PATCH" => self.purple,
+            "DELETE" => self.error,
+            "HEAD" => self.accent,
+            "OPTIONS" => self.muted,
+            _ => self.text,
+        }
+    }
+
+    /// Color for an HTTP status code class.
+    pub fn status_color(&self, status: u16) -> Color {
+        match status {
+            200..=299 => self.success,
+            300..=399 => self.accent,
+            400..=499 => self.warning,
+            500..=599 => self.error,
+            _ => self.text,
+        }
+    }
+
+    /// Base style for key-hint bars.
+    pub fn hint_style(&self) -> Style {
+        if self.dim_hints {
+            Style::default().add_modifier(Modifier::DIM)
+        } else {
+            Style::default()
+        }
+    }
+}
+
+/// The theme selected in the user config.
+pub fn theme(state: &AppState) -> &Theme {
+    Theme::of(state.config.ui.theme, state.custom_theme.as_ref())
+}
+
+/// The built-in theme `Auto` stands for: the light variant on a light
+/// terminal background, Tokyo Night otherwise. Other kinds are themselves.
+pub fn resolve(kind: ThemeKind) -> ThemeKind {
+    static LIGHT_TERMINAL: OnceLock<bool> = OnceLock::new();
+    match kind {
+        ThemeKind::Auto => {
+            let light = *LIGHT_TERMINAL.get_or_init(|| {
+                std::env::var("COLORFGBG").ok().and_then(|v| light_background(&v)).unwrap_or(false)
+            });
+            if light { ThemeKind::Light } else { ThemeKind::TokyoNight }
+        }
+        kind => kind,
+    }
+}
+
+/// Whether `$COLORFGBG` ("15;0", or "0;default;15" in rxvt) names a light
+/// background color; `None` when it can't be read.
+fn light_background(colorfgbg: &str) -> Option<bool> {
+    let bg: u8 = colorfgbg.rsplit(';').next()?.trim().parse().ok()?;
+    // The ANSI colors 7 and 9-15 are the light ones; 8 is bright black
+    Some(bg == 7 || bg > 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_from_toml() {
+        let theme = Theme::from_toml(
+            "base = \"light\"\naccent = \"#ff9e64\"\nerror = \"red\"\nborder = 240\ndim_hints = true\n",
+        )
+        .unwrap();
+        assert_eq!(theme.accent, Color::Rgb(255, 158, 100));
+        assert_eq!(theme.error, Color::Red);
+        assert_eq!(theme.border, Color::Indexed(240));
+        assert!(theme.dim_hints);
+        // Unset colors come from the base palette
+        assert_eq!(theme.bg, LIGHT.bg);
+        assert_eq!(Theme::from_toml("").unwrap(), TOKYO_NIGHT);
+
+        assert!(Theme::from_toml("accent = \"not-a-color\"").is_err());
+        assert!(Theme::from_toml("accnet = \"#ffffff\"").is_err());
+        assert!(Theme::from_toml("base = \"custom\"").is_err());
+    }
+
+    #[test]
+    fn test_light_background_from_colorfgbg() {
+        assert_eq!(light_background("15;0"), Some(false));
+        assert_eq!(light_background("0;15"), Some(true));
+        assert_eq!(light_background("0;default;7"), Some(true));
+        assert_eq!(light_background("7;8"), Some(false));
+        assert_eq!(light_background("default;default"), None);
+        assert_eq!(light_background(""), None);
+    }
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::state::config::ThemeKind;
+use crate::ui::highlight::{default_syntax_theme, syntax_themes};
+use crate::ui::theme::{theme, Theme};
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let picker = &state.theme_picker;
+    // Sized to the longer list: borders, heading, one row per theme, blank
+    // line and hints
+    let rows = ThemeKind::ALL.len().max(syntax_themes().count() + 1) as u16;
+    let width = 68.min(area.width);
+    let height = (rows + 5).min(area.height);
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Theme ")
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(31), Constraint::Min(1)])
+        .split(chunks[0]);
+    // Rows of the column the keys move in stand out
+    let row_style = |selected: bool, active: bool| match (selected, active) {
+        (true, true) => Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD),
+        (true, false) => Style::default().fg(theme.text).bg(theme.surface),
+        _ => Style::default().fg(theme.text),
+    };
+    let heading = |text: &'static str, active: bool| {
+        let color = if active { theme.accent } else { theme.muted };
+        Line::from(Span::styled(text, Style::default().fg(color).add_modifier(Modifier::BOLD)))
+    };
+
+    let mut ui_lines = vec![heading(" Interface", !picker.syntax_column)];
+    ui_lines.extend(ThemeKind::ALL.iter().enumerate().map(|(i, kind)| {
+        let style = row_style(i == picker.selected, !picker.syntax_column);
+        let mut spans = vec![Span::styled(format!(" {:<18}", kind.label()), style)];
+        spans.extend(swatch(Theme::of(*kind, state.custom_theme.as_ref())));
+        Line::from(spans)
+    }));
+    frame.render_widget(Paragraph::new(ui_lines), columns[0]);
+
+    let matching = format!("Match UI ({})", default_syntax_theme(state.config.ui.theme));
+    let mut syntax_lines = vec![heading(" Syntax", picker.syntax_column)];
+    syntax_lines.extend(
+        std::iter::once(matching).chain(syntax_themes().map(str::to_string)).enumerate().map(|(i, name)| {
+            let style = row_style(i == picker.syntax_selected, picker.syntax_column);
+            Line::from(Span::styled(format!(" {name}"), style))
+        }),
+    );
+    frame.render_widget(Paragraph::new(syntax_lines), columns[1]);
+
+    let hint = Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.text)),
+        Span::styled(" preview  ", Style::default().fg(theme.muted)),
+        Span::styled("Tab", Style::default().fg(theme.text)),
+        Span::styled(" column  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.text)),
+        Span::styled(" save  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.text)),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(hint).style(theme.hint_style()),
+        chunks[1],
+    );
+}
+
+/// A few blocks in the palette's key colors, drawn on its own background.
+fn swatch(palette: &Theme) -> Vec<Span<'static>> {
+    [palette.accent, palette.success, palette.warning, palette.error, palette.purple]
+        .into_iter()
+        .map(|color| Span::styled("██", Style::default().fg(color).bg(palette.bg)))
+        .collect()
+}
@@ -0,0 +1,138 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::state::app_state::AppState;
+use crate::state::environment::{EnvVariable, VarOrigin, VarType};
+use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+
+/// Variables of the active environment set by a script, capture or token
+/// this session, in environment order, with where each value came from.
+pub fn session_variables(state: &AppState) -> Vec<(&EnvVariable, &VarOrigin)> {
+    let ws = &state.workspace;
+    let Some(env) = state.active_environment_idx().and_then(|i| ws.environments.get(i)) else {
+        return Vec::new();
+    };
+    env.variables
+        .iter()
+        .filter_map(|var| state.var_origins.get(&var.key).map(|origin| (var, origin)))
+        .collect()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
+    let popup_area = centered_rect(80, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let env_name = state.active_environment_name().unwrap_or("no environment");
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Variables — {env_name} "))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let vars = session_variables(state);
+    if vars.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No variables set yet — values written by scripts, captures and OAuth tokens show up here",
+                Style::default().fg(theme.muted).patch(theme.hint_style()),
+            ))),
+            chunks[0],
+        );
+    }
+
+    let popup = &state.variables;
+    let key_w = vars.iter().map(|(v, _)| v.key.chars().count()).max().unwrap_or(0).max(4);
+    let mut cursor = None;
+    let lines: Vec<Line> = vars
+        .iter()
+        .enumerate()
+        .map(|(i, (var, origin))| {
+            let selected = i == popup.selected;
+            let base = if selected {
+                Style::default().fg(theme.highlight).bg(theme.surface).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let source = match (origin.request.as_str(), origin.via) {
+                ("", via) => via.to_string(),
+                (request, via) => format!("{request} · {via}"),
+            };
+            let value = match &popup.editing {
+                Some((text, at)) if selected => {
+                    let col = text[..*at].chars().count();
+                    cursor = Some((i, col));
+                    text.clone()
+                }
+                _ if var.var_type == VarType::Secret && !popup.show_secrets => "••••••••".to_string(),
+                _ => var.value.clone(),
+            };
+            Line::from(vec![
+                Span::styled(format!("  {}  ", origin.at.format("%H:%M:%S")), base.fg(theme.muted)),
+                Span::styled(format!("{:<key_w$}  ", var.key), base.fg(theme.accent)),
+                Span::styled(format!("{source}  "), base.fg(theme.purple)),
+                Span::styled(value, base),
+            ])
+        })
+        .collect();
+    let offset = (popup.selected + 1).saturating_sub(chunks[0].height as usize);
+    frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[0]);
+
+    // The value column starts after the time, key and source columns
+    if let Some((row, col)) = cursor
+        && let Some((_, origin)) = vars.get(row)
+    {
+        let source_w = match origin.request.as_str() {
+            "" => origin.via.chars().count(),
+            request => request.chars().count() + 3 + origin.via.chars().count(),
+        };
+        let x = 2 + 8 + 2 + key_w + 2 + source_w + 2 + col;
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + x as u16,
+            y: chunks[0].y + (row - offset) as u16,
+        });
+    }
+
+    let hint = if popup.editing.is_some() {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" save  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("j/k", Style::default().fg(theme.text)),
+            Span::styled(" move  ", Style::default().fg(theme.muted)),
+            Span::styled("e", Style::default().fg(theme.text)),
+            Span::styled(" edit  ", Style::default().fg(theme.muted)),
+            Span::styled("d", Style::default().fg(theme.text)),
+            Span::styled(" delete  ", Style::default().fg(theme.muted)),
+            Span::styled("s", Style::default().fg(theme.text)),
+            Span::styled(
+                if popup.show_secrets { " hide secrets  " } else { " show secrets  " },
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme.hint_style()), chunks[1]);
+}
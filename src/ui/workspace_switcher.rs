@@ -1,29 +1,50 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Position, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use chrono::Utc;
+
 use crate::state::app_state::AppState;
-use crate::ui::layout::ACCENT_BLUE;
 use crate::ui::popup::centered_rect;
+use crate::ui::theme::theme;
+use crate::util::time::time_ago;
 
-const TEXT_MUTED: Color = Color::Rgb(86, 95, 137);
-const TEXT_PRIMARY: Color = Color::Rgb(192, 202, 245);
-const SURFACE: Color = Color::Rgb(36, 40, 59);
-const BG: Color = Color::Rgb(26, 27, 38);
+/// Workspaces matching the search, most recently opened first (never-opened
+/// ones last, by name), or by name when sorting alphabetically.
+pub fn switcher_workspaces(state: &AppState) -> Vec<&str> {
+    let filter = state.ws_switcher.search.to_lowercase();
+    let mut names: Vec<&str> = state
+        .all_workspaces
+        .iter()
+        .filter(|w| filter.is_empty() || w.to_lowercase().contains(&filter))
+        .map(|w| w.as_str())
+        .collect();
+    if !state.ws_switcher.alphabetical {
+        let opened = &state.workspace_last_opened;
+        // Stable: names stay alphabetical within ties
+        names.sort_by_key(|name| std::cmp::Reverse(opened.get(*name)));
+    }
+    names
+}
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = theme(state);
     let popup_area = centered_rect(50, 40, area);
     frame.render_widget(Clear, popup_area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(ACCENT_BLUE))
-        .title(" Workspaces (Ctrl+W) ")
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme.accent))
+        .title(if state.ws_switcher.alphabetical {
+            " Workspaces (Ctrl+W)  ·  by name "
+        } else {
+            " Workspaces (Ctrl+W)  ·  recent first "
+        })
+        .style(Style::default().bg(theme.bg));
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
@@ -41,27 +62,28 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         .split(inner);
 
     // Search / naming row
-    if state.ws_switcher.naming {
+    if state.ws_switcher.naming && state.ws_switcher.renaming.is_none() {
         let new_name = &state.ws_switcher.new_name;
+        let label = if state.ws_switcher.duplicating.is_some() { "Copy as: " } else { "Name: " };
         let name_line = Line::from(vec![
-            Span::styled("Name: ", Style::default().fg(TEXT_MUTED)),
-            Span::styled(new_name.clone(), Style::default().fg(TEXT_PRIMARY)),
+            Span::styled(label, Style::default().fg(theme.muted)),
+            Span::styled(new_name.clone(), Style::default().fg(theme.text)),
         ]);
         frame.render_widget(Paragraph::new(name_line), chunks[0]);
         let col_offset = new_name[..state.ws_switcher.new_name_cursor.min(new_name.len())]
             .chars()
             .count() as u16;
         frame.set_cursor_position(Position {
-            x: chunks[0].x + 6 + col_offset,
+            x: chunks[0].x + label.len() as u16 + col_offset,
             y: chunks[0].y,
         });
     } else {
         let search = &state.ws_switcher.search;
         let search_line = if search.is_empty() {
-            Line::from(Span::styled("Search…", Style::default().fg(TEXT_MUTED)))
+            Line::from(Span::styled("Search…", Style::default().fg(theme.muted)))
         } else {
             Line::from(vec![
-                Span::styled("/ ", Style::default().fg(ACCENT_BLUE)),
+                Span::styled("/ ", Style::default().fg(theme.accent)),
                 Span::raw(search.clone()),
             ])
         };
@@ -69,13 +91,8 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     }
 
     // Workspace list (filtered)
-    let filter = state.ws_switcher.search.to_lowercase();
-    let filtered: Vec<&str> = state
-        .all_workspaces
-        .iter()
-        .filter(|w| filter.is_empty() || w.to_lowercase().contains(&filter))
-        .map(|w| w.as_str())
-        .collect();
+    let filtered = switcher_workspaces(state);
+    let now = Utc::now();
 
     let list_area = chunks[1];
     for (row, &name) in filtered.iter().enumerate() {
@@ -87,46 +104,106 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         let is_selected = row == state.ws_switcher.selected;
         let marker = if is_active { "● " } else { "○ " };
         let marker_color = if is_active {
-            Color::Rgb(158, 206, 106)
+            theme.success
         } else {
-            TEXT_MUTED
+            theme.muted
         };
         let name_style = if is_selected {
             Style::default()
-                .fg(Color::White)
-                .bg(SURFACE)
+                .fg(theme.highlight)
+                .bg(theme.surface)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(TEXT_PRIMARY)
+            Style::default().fg(theme.text)
         };
         let row_area = Rect { y, height: 1, ..list_area };
-        let line = Line::from(vec![
+        // The workspace being renamed is edited in place
+        if state.ws_switcher.naming && state.ws_switcher.renaming.as_deref() == Some(name) {
+            let new_name = &state.ws_switcher.new_name;
+            let line = Line::from(vec![
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::styled(
+                    new_name.clone(),
+                    Style::default().fg(theme.text).bg(theme.surface).add_modifier(Modifier::UNDERLINED),
+                ),
+            ]);
+            frame.render_widget(Paragraph::new(line), row_area);
+            let col_offset = new_name[..state.ws_switcher.new_name_cursor.min(new_name.len())]
+                .chars()
+                .count() as u16;
+            frame.set_cursor_position(Position {
+                x: row_area.x + marker.chars().count() as u16 + col_offset,
+                y,
+            });
+            continue;
+        }
+        let mut spans = vec![
             Span::styled(marker, Style::default().fg(marker_color)),
             Span::styled(name, name_style),
-        ]);
+        ];
+        let mut info = Vec::new();
+        if let Some(&at) = state.workspace_last_opened.get(name) {
+            info.push(time_ago(at, now));
+        }
+        if let Some(summary) = state.workspace_summaries.get(name) {
+            info.push(plural(summary.collections, "collection"));
+            info.push(plural(summary.requests, "request"));
+        }
+        if !info.is_empty() {
+            spans.push(Span::styled(
+                format!("  {}", info.join(" · ")),
+                Style::default().fg(theme.muted).add_modifier(Modifier::DIM),
+            ));
+        }
+        let line = Line::from(spans);
         frame.render_widget(Paragraph::new(line), row_area);
     }
 
     // Hint bar
     let hint = if state.ws_switcher.naming {
         Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" confirm  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" cancel", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(
+                if state.ws_switcher.renaming.is_some() {
+                    " rename  "
+                } else if state.ws_switcher.duplicating.is_some() {
+                    " duplicate  "
+                } else {
+                    " confirm  "
+                },
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ])
     } else {
         Line::from(vec![
-            Span::styled("Enter", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" switch  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Alt+n", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" new  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("Esc", Style::default().fg(TEXT_PRIMARY)),
-            Span::styled(" close", Style::default().fg(TEXT_MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.text)),
+            Span::styled(" switch  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+n", Style::default().fg(theme.text)),
+            Span::styled(" new  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+r", Style::default().fg(theme.text)),
+            Span::styled(" rename  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+c", Style::default().fg(theme.text)),
+            Span::styled(" duplicate  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+d", Style::default().fg(theme.text)),
+            Span::styled(" delete  ", Style::default().fg(theme.muted)),
+            Span::styled("Alt+s", Style::default().fg(theme.text)),
+            Span::styled(" sort  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.text)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
         ])
     };
     frame.render_widget(
-        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        Paragraph::new(hint).style(theme.hint_style()),
         chunks[2],
     );
 }
+
+fn plural(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
@@ -0,0 +1,164 @@
+// Subsequence fuzzy matcher used by the request finder
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_PREFIX: i64 = 24;
+const BONUS_BOUNDARY: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 12;
+const PENALTY_GAP: i64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Char indices into the candidate that matched the query, ascending.
+    pub indices: Vec<usize>,
+}
+
+/// Case-insensitive subsequence match of `query` against `text`.
+///
+/// Every query char must appear in `text` in order. Among all such alignments
+/// the best-scoring one is chosen: matches at the start of the text or at a
+/// word boundary (after a space, `/`, `-`, `_`, `.` or at a lower→upper case
+/// change) and runs of consecutive matches score higher; gaps cost a little.
+/// An empty query matches everything with score 0.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, indices: Vec::new() });
+    }
+    let original: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = original
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let (n, m) = (query.len(), lower.len());
+    if n > m {
+        return None;
+    }
+
+    let bonus: Vec<i64> = (0..m)
+        .map(|j| {
+            if j == 0 {
+                BONUS_PREFIX
+            } else if is_boundary(original[j - 1], original[j]) {
+                BONUS_BOUNDARY
+            } else {
+                0
+            }
+        })
+        .collect();
+
+    // best[i][j]: best score with query[..=i] matched and query[i] at text[j].
+    // from[i][j]: position of query[i - 1] in that alignment.
+    let mut best = vec![vec![None::<i64>; m]; n];
+    let mut from = vec![vec![0usize; m]; n];
+
+    for j in 0..m {
+        if lower[j] == query[0] {
+            best[0][j] = Some(SCORE_MATCH + bonus[j]);
+        }
+    }
+    for i in 1..n {
+        // Running max over k < j of best[i-1][k] + k * PENALTY_GAP, so the gap
+        // penalty (j - k - 1) can be applied in O(1).
+        let mut running: Option<(i64, usize)> = None;
+        for j in 0..m {
+            if j > 0
+                && let Some(prev) = best[i - 1][j - 1]
+            {
+                let v = prev + (j as i64 - 1) * PENALTY_GAP;
+                if running.is_none_or(|(r, _)| v > r) {
+                    running = Some((v, j - 1));
+                }
+            }
+            if lower[j] != query[i] {
+                continue;
+            }
+            let Some((r, k)) = running else {
+                continue;
+            };
+            let mut score = r - (j as i64 - 1) * PENALTY_GAP;
+            let mut src = k;
+            // A consecutive match beats any gapped one from the same row
+            if let Some(prev) = best[i - 1][j - 1] {
+                let consecutive = prev + BONUS_CONSECUTIVE;
+                if consecutive >= score {
+                    score = consecutive;
+                    src = j - 1;
+                }
+            }
+            best[i][j] = Some(score + SCORE_MATCH + bonus[j]);
+            from[i][j] = src;
+        }
+    }
+
+    let (end, score) = (0..m)
+        .filter_map(|j| best[n - 1][j].map(|s| (j, s)))
+        .max_by_key(|&(j, s)| (s, std::cmp::Reverse(j)))?;
+
+    let mut indices = vec![end; n];
+    for i in (1..n).rev() {
+        indices[i - 1] = from[i][indices[i]];
+    }
+    Some(FuzzyMatch { score, indices })
+}
+
+fn is_boundary(prev: char, cur: char) -> bool {
+    matches!(prev, ' ' | '/' | '-' | '_' | '.' | ':')
+        || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(q: &str, t: &str) -> i64 {
+        fuzzy_match(q, t).map(|m| m.score).unwrap_or(i64::MIN)
+    }
+
+    #[test]
+    fn test_subsequence_required() {
+        assert!(fuzzy_match("crf", "Create refund").is_some());
+        assert!(fuzzy_match("fcr", "Create refund").is_none());
+        assert!(fuzzy_match("toolong", "tool").is_none());
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert_eq!(
+            fuzzy_match("", "anything"),
+            Some(FuzzyMatch { score: 0, indices: Vec::new() })
+        );
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert!(fuzzy_match("PAY", "payments").is_some());
+        assert!(fuzzy_match("pay", "PAYMENTS").is_some());
+    }
+
+    #[test]
+    fn test_prefix_beats_middle() {
+        assert!(score("pay", "Payments / List") > score("pay", "Repay / List"));
+    }
+
+    #[test]
+    fn test_word_boundary_beats_inner_match() {
+        // "cr" at the start of "Create" vs buried inside "Secret"
+        assert!(score("cr", "Users / Create user") > score("cr", "Users / Secret"));
+        // camelCase humps count as boundaries
+        assert!(score("gu", "getUser") > score("gu", "debugging"));
+    }
+
+    #[test]
+    fn test_indices_prefer_boundaries() {
+        let m = fuzzy_match("cr", "Payments / Refunds / Create refund").unwrap();
+        assert_eq!(m.indices, vec![21, 22]);
+        let m = fuzzy_match("prc", "Payments / Refunds / Create refund").unwrap();
+        assert_eq!(m.indices, vec![0, 11, 21]);
+    }
+
+    #[test]
+    fn test_consecutive_beats_scattered() {
+        assert!(score("user", "Get user") > score("user", "button sizer"));
+    }
+}
@@ -0,0 +1,130 @@
+// JSON display helpers for the response viewer
+
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+/// Recursively sort object keys. Array order and non-object values are kept
+/// as they are.
+pub fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Pretty-printed `text` with keys sorted recursively, or `None` if it isn't
+/// valid JSON.
+pub fn sorted_pretty(text: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(text).ok()?;
+    serde_json::to_string_pretty(&sort_keys(value)).ok()
+}
+
+/// JSONPath of `key` under `parent`: `$.key`, or `$["odd key"]` when the
+/// key isn't a plain identifier.
+pub fn child_path(parent: &str, key: &str) -> String {
+    let plain = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{parent}.{key}")
+    } else {
+        format!("{parent}[{}]", Value::String(key.to_string()))
+    }
+}
+
+/// One line of the response tree view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeRow<'a> {
+    /// JSONPath of the node; also its key in the collapsed set
+    pub path: String,
+    pub depth: usize,
+    /// Object key or `[i]` array index; `None` for the root
+    pub label: Option<String>,
+    pub value: &'a Value,
+    pub collapsed: bool,
+}
+
+/// Lines of the tree view of `root` in document order. Children of nodes
+/// whose path is in `collapsed` are left out.
+pub fn tree_rows<'a>(root: &'a Value, collapsed: &HashSet<String>) -> Vec<TreeRow<'a>> {
+    let mut rows = Vec::new();
+    push_tree_rows(&mut rows, root, "$".to_string(), None, 0, collapsed);
+    rows
+}
+
+fn push_tree_rows<'a>(
+    rows: &mut Vec<TreeRow<'a>>,
+    value: &'a Value,
+    path: String,
+    label: Option<String>,
+    depth: usize,
+    collapsed: &HashSet<String>,
+) {
+    let is_collapsed = collapsed.contains(&path);
+    rows.push(TreeRow { path: path.clone(), depth, label, value, collapsed: is_collapsed });
+    if is_collapsed {
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = child_path(&path, key);
+                push_tree_rows(rows, child, child_path, Some(key.clone()), depth + 1, collapsed);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                let label = format!("[{i}]");
+                push_tree_rows(rows, child, format!("{path}{label}"), Some(label), depth + 1, collapsed);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_keys_nested_and_arrays() {
+        let value: Value =
+            serde_json::from_str(r#"{"b":{"z":1,"a":[{"y":2,"x":1},3]},"a":null}"#).unwrap();
+        let sorted = serde_json::to_string(&sort_keys(value)).unwrap();
+        assert_eq!(sorted, r#"{"a":null,"b":{"a":[{"x":1,"y":2},3],"z":1}}"#);
+    }
+
+    #[test]
+    fn test_tree_rows_skip_collapsed_children() {
+        let value: Value = serde_json::from_str(r#"{"user":{"id":1},"tags":["a","b"],"odd key":null}"#).unwrap();
+        let paths = |collapsed: &HashSet<String>| -> Vec<String> {
+            tree_rows(&value, collapsed).into_iter().map(|r| r.path).collect()
+        };
+        assert_eq!(
+            paths(&HashSet::new()),
+            vec!["$", "$.user", "$.user.id", "$.tags", "$.tags[0]", "$.tags[1]", r#"$["odd key"]"#]
+        );
+        let collapsed = HashSet::from(["$.tags".to_string()]);
+        assert_eq!(paths(&collapsed), vec!["$", "$.user", "$.user.id", "$.tags", r#"$["odd key"]"#]);
+        assert!(tree_rows(&value, &collapsed)[3].collapsed);
+    }
+
+    #[test]
+    fn test_sorted_pretty_tolerates_duplicate_keys() {
+        // The last duplicate wins, as when parsing normally
+        let pretty = sorted_pretty(r#"{"b":1,"a":2,"b":3}"#).unwrap();
+        assert_eq!(pretty, "{\n  \"a\": 2,\n  \"b\": 3\n}");
+        assert!(sorted_pretty("not json").is_none());
+    }
+}
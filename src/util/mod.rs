@@ -0,0 +1,7 @@
+pub mod fuzzy;
+pub mod redact;
+pub mod sparkline;
+pub mod json;
+pub mod xml;
+pub mod text;
+pub mod time;
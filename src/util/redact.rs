@@ -0,0 +1,95 @@
+// Redaction rules shared by everything that writes request data to disk
+// (audit log, state dumps). Secrets must never leave memory.
+use crate::state::environment::{Environment, VarType};
+use crate::state::request_state::AuthConfig;
+
+pub const REDACTED: &str = "[redacted]";
+
+/// Secret values in play for a request: `Secret` variables of the active
+/// environment plus credentials from the request's auth config.
+pub fn secret_values(env: Option<&Environment>, auth: &AuthConfig) -> Vec<String> {
+    let mut out: Vec<String> = env
+        .map(|env| {
+            env.variables
+                .iter()
+                .filter(|v| v.var_type == VarType::Secret)
+                .map(|v| v.value.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    match auth {
+        AuthConfig::None => {}
+        AuthConfig::Bearer { token } => out.push(token.clone()),
+        AuthConfig::Basic { password, .. } => out.push(password.clone()),
+        AuthConfig::ApiKey { value, .. } => out.push(value.clone()),
+        AuthConfig::OAuth2 { client_secret, refresh_token, token, .. } => {
+            out.push(client_secret.clone());
+            out.push(refresh_token.clone());
+            out.extend(token.as_ref().map(|t| t.access_token.clone()));
+        }
+    }
+    out.retain(|s| !s.is_empty());
+    // Longest first so a secret containing another is replaced whole
+    out.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    out.dedup();
+    out
+}
+
+/// Replace every occurrence of any of `secrets` in `text` with [`REDACTED`].
+pub fn redact_secrets(text: &str, secrets: &[String]) -> String {
+    let mut out = text.to_string();
+    for secret in secrets.iter().filter(|s| !s.is_empty()) {
+        out = out.replace(secret.as_str(), REDACTED);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::environment::EnvVariable;
+
+    fn env_with(vars: &[(&str, &str, VarType)]) -> Environment {
+        Environment {
+            variables: vars
+                .iter()
+                .map(|(k, v, t)| EnvVariable {
+                    key: k.to_string(),
+                    value: v.to_string(),
+                    var_type: t.clone(),
+                    ..EnvVariable::default()
+                })
+                .collect(),
+            ..Environment::default()
+        }
+    }
+
+    #[test]
+    fn test_secret_values_only_secrets_and_auth() {
+        let env = env_with(&[
+            ("host", "api.test", VarType::Text),
+            ("token", "s3cr3t", VarType::Secret),
+            ("empty", "", VarType::Secret),
+        ]);
+        let auth = AuthConfig::Basic { username: "ada".into(), password: "hunter2".into() };
+        assert_eq!(secret_values(Some(&env), &auth), vec!["hunter2", "s3cr3t"]);
+        assert!(secret_values(None, &AuthConfig::None).is_empty());
+    }
+
+    #[test]
+    fn test_redact_replaces_every_occurrence() {
+        let secrets = vec!["abc123".to_string()];
+        assert_eq!(
+            redact_secrets("/users/abc123/keys/abc123", &secrets),
+            "/users/[redacted]/keys/[redacted]"
+        );
+        assert_eq!(redact_secrets("/users/me", &secrets), "/users/me");
+    }
+
+    #[test]
+    fn test_redact_longest_secret_first() {
+        let env = env_with(&[("a", "key", VarType::Secret), ("b", "key-long", VarType::Secret)]);
+        let secrets = secret_values(Some(&env), &AuthConfig::None);
+        assert_eq!(redact_secrets("/x/key-long", &secrets), "/x/[redacted]");
+    }
+}
@@ -0,0 +1,33 @@
+// Single-line block-character sparklines for the response meta line
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One bar per value, scaled between the smallest and largest value.
+/// All-equal values render as the lowest bar.
+pub fn sparkline(values: &[u64]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = max - min;
+    values
+        .iter()
+        .map(|&v| {
+            let idx = ((v - min) * (BARS.len() as u64 - 1))
+                .checked_div(range)
+                .unwrap_or(0);
+            BARS[idx as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_scales_to_range() {
+        assert_eq!(sparkline(&[10, 80, 45]), "▁█▄");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
@@ -0,0 +1,140 @@
+// Word and line boundaries shared by the text inputs (URL, body, headers,
+// auth fields, environment editor)
+use std::ops::Range;
+
+/// Characters that split words in a URL besides whitespace.
+const URL_SEPARATORS: &[char] = &['/', '.', '?', '&', '='];
+
+fn is_url_separator(c: char) -> bool {
+    c.is_whitespace() || URL_SEPARATORS.contains(&c)
+}
+
+/// Byte offset of the start of the next word after `pos`, or the end of
+/// `text`. Words are separated by whitespace.
+pub fn word_next(text: &str, pos: usize) -> usize {
+    word_next_by(text, pos, char::is_whitespace)
+}
+
+/// Byte offset of the start of the word before `pos`, or 0.
+pub fn word_prev(text: &str, pos: usize) -> usize {
+    word_prev_by(text, pos, char::is_whitespace)
+}
+
+/// Byte offset of the last char of the word ending after `pos` (vim's
+/// `e`), or `pos` when no word follows.
+pub fn word_end(text: &str, pos: usize) -> usize {
+    word_end_by(text, pos, char::is_whitespace)
+}
+
+/// `word_next` that also stops at `/`, `.`, `?`, `&` and `=`.
+pub fn url_word_next(text: &str, pos: usize) -> usize {
+    word_next_by(text, pos, is_url_separator)
+}
+
+/// `word_prev` that also stops at `/`, `.`, `?`, `&` and `=`.
+pub fn url_word_prev(text: &str, pos: usize) -> usize {
+    word_prev_by(text, pos, is_url_separator)
+}
+
+/// `word_end` that also stops at `/`, `.`, `?`, `&` and `=`.
+pub fn url_word_end(text: &str, pos: usize) -> usize {
+    word_end_by(text, pos, is_url_separator)
+}
+
+fn word_next_by(text: &str, pos: usize, is_sep: fn(char) -> bool) -> usize {
+    let pos = pos.min(text.len());
+    let mut chars = text[pos..].char_indices().peekable();
+    // Rest of the current word, then the separators after it
+    while chars.next_if(|&(_, c)| !is_sep(c)).is_some() {}
+    while chars.next_if(|&(_, c)| is_sep(c)).is_some() {}
+    chars.peek().map_or(text.len(), |&(i, _)| pos + i)
+}
+
+fn word_prev_by(text: &str, pos: usize, is_sep: fn(char) -> bool) -> usize {
+    let pos = pos.min(text.len());
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    // Separators before the cursor, then back to the start of that word
+    while chars.next_if(|&(_, c)| is_sep(c)).is_some() {}
+    let mut start = chars.peek().map_or(0, |&(i, _)| i);
+    while let Some((i, _)) = chars.next_if(|&(_, c)| !is_sep(c)) {
+        start = i;
+    }
+    start
+}
+
+fn word_end_by(text: &str, pos: usize, is_sep: fn(char) -> bool) -> usize {
+    let pos = pos.min(text.len());
+    // Always move at least one char, then past separators to the word's end
+    let mut chars = text[pos..].char_indices().skip(1).peekable();
+    while chars.next_if(|&(_, c)| is_sep(c)).is_some() {}
+    let mut end = pos;
+    while let Some((i, _)) = chars.next_if(|&(_, c)| !is_sep(c)) {
+        end = pos + i;
+    }
+    end
+}
+
+/// Byte offset of the start of the line holding `pos`.
+pub fn line_start(text: &str, pos: usize) -> usize {
+    let pos = pos.min(text.len());
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Byte offset of the end of the line holding `pos`, before its line break.
+pub fn line_end(text: &str, pos: usize) -> usize {
+    let pos = pos.min(text.len());
+    text[pos..].find('\n').map_or(text.len(), |i| pos + i)
+}
+
+/// The line holding `pos` with one line break, the one after it or, on the
+/// last line, the one before: what deleting the whole line (`dd`) removes.
+pub fn line_with_break(text: &str, pos: usize) -> Range<usize> {
+    let (start, end) = (line_start(text, pos), line_end(text, pos));
+    if end < text.len() {
+        start..end + 1
+    } else {
+        start.saturating_sub(1)..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_movement() {
+        let url = "https://api.test/users?id=42&q=é x";
+        assert_eq!(url_word_next(url, 0), 8); // "api"
+        assert_eq!(url_word_next(url, 8), 12); // "test"
+        assert_eq!(url_word_next(url, 12), 17); // "users"
+        assert_eq!(url_word_next(url, 17), 23); // "id"
+        assert_eq!(url_word_prev(url, 23), 17);
+        assert_eq!(url_word_prev(url, 19), 17);
+        assert_eq!(url_word_prev(url, 17), 12);
+        assert_eq!(url_word_prev(url, 8), 0);
+        assert_eq!(url_word_next(url, url.len()), url.len());
+
+        let body = "{\"a\": 1,\n  \"b\": \"é\"}";
+        assert_eq!(word_next(body, 0), 6);
+        assert_eq!(word_next(body, 6), 11);
+        assert_eq!(word_prev(body, 11), 6);
+        assert_eq!(word_prev(body, body.len()), 16);
+        assert_eq!(word_prev(body, 0), 0);
+    }
+
+    #[test]
+    fn test_word_end_and_lines() {
+        let url = "https://api.test/users";
+        assert_eq!(url_word_end(url, 8), 10); // "api" ends on `i`
+        assert_eq!(url_word_end(url, 10), 15); // on to the end of "test"
+        assert_eq!(url_word_end(url, url.len() - 1), url.len() - 1);
+        assert_eq!(word_end("ab  cd", 0), 1);
+        assert_eq!(word_end("ab  cd", 1), 5);
+
+        let text = "ab\ncd\nef";
+        assert_eq!((line_start(text, 4), line_end(text, 4)), (3, 5));
+        assert_eq!(line_with_break(text, 4), 3..6);
+        assert_eq!(line_with_break(text, 7), 5..8);
+        assert_eq!(line_with_break("ab", 1), 0..2);
+    }
+}
@@ -0,0 +1,50 @@
+// Human-friendly relative times ("3 hours ago")
+use chrono::{DateTime, Utc};
+
+/// How long before `now` `at` was, in the largest whole unit.
+pub fn time_ago(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - at).num_seconds();
+    if secs < 60 {
+        return "just now".to_string();
+    }
+    format!("{} ago", span(secs))
+}
+
+/// `secs` in its largest whole unit: "45 seconds", "1 hour", "3 days".
+pub fn span(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (n, unit) = match secs {
+        0..60 => (secs, "second"),
+        60..3_600 => (secs / 60, "minute"),
+        3_600..86_400 => (secs / 3_600, "hour"),
+        86_400..2_592_000 => (secs / 86_400, "day"),
+        2_592_000..31_536_000 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    if n == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{n} {unit}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_time_ago_units() {
+        let now = Utc::now();
+        assert_eq!(time_ago(now - Duration::seconds(20), now), "just now");
+        assert_eq!(time_ago(now - Duration::minutes(1), now), "1 minute ago");
+        assert_eq!(time_ago(now - Duration::hours(5), now), "5 hours ago");
+        assert_eq!(time_ago(now - Duration::days(2), now), "2 days ago");
+        assert_eq!(time_ago(now - Duration::days(65), now), "2 months ago");
+        assert_eq!(time_ago(now - Duration::days(800), now), "2 years ago");
+        // Clock skew doesn't produce negative times
+        assert_eq!(time_ago(now + Duration::minutes(3), now), "just now");
+        assert_eq!(span(45), "45 seconds");
+    }
+}
@@ -0,0 +1,49 @@
+// XML display helpers for the response viewer
+
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+
+/// `text` re-indented by two spaces per level, or `None` if it isn't
+/// well-formed XML.
+pub fn pretty(text: &str) -> Option<String> {
+    let mut reader = Reader::from_str(text);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let mut depth = 0usize;
+    let mut elements = 0usize;
+    loop {
+        let event = reader.read_event().ok()?;
+        match &event {
+            Event::Start(_) => {
+                depth += 1;
+                elements += 1;
+            }
+            Event::End(_) => depth = depth.checked_sub(1)?,
+            Event::Empty(_) => elements += 1,
+            Event::Eof => break,
+            _ => {}
+        }
+        writer.write_event(event).ok()?;
+    }
+    if depth != 0 || elements == 0 {
+        return None;
+    }
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_indents_nested_elements() {
+        let xml = r#"<?xml version="1.0"?><rss><channel><title>News</title><item/></channel></rss>"#;
+        assert_eq!(
+            pretty(xml).unwrap(),
+            "<?xml version=\"1.0\"?>\n<rss>\n  <channel>\n    <title>News</title>\n    <item/>\n  </channel>\n</rss>"
+        );
+        assert!(pretty("<a><b></a>").is_none());
+        assert!(pretty("<a>").is_none());
+        assert!(pretty("plain text").is_none());
+    }
+}